@@ -0,0 +1,69 @@
+//! Compiles `src/cities.json` into static tables, so the city database needs
+//! no parsing at startup and lookups are binary searches over `&'static str`.
+
+use serde::Deserialize;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
+#[derive(Deserialize)]
+struct City {
+    name: String,
+    code: String,
+    timezone: String,
+    country: String,
+    coordinates: [f64; 2],
+    aliases: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct Cities {
+    cities: Vec<City>,
+    major_cities: Vec<String>,
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/cities.json");
+
+    let json = fs::read_to_string("src/cities.json").expect("Failed to read cities.json");
+    let data: Cities = serde_json::from_str(&json).expect("Failed to parse cities.json");
+
+    let mut out = String::from("pub static CITIES: &[CityData] = &[\n");
+    for city in &data.cities {
+        writeln!(
+            out,
+            "    CityData {{ name: {:?}, code: {:?}, timezone: {:?}, country: {:?}, \
+             coordinates: [{:?}, {:?}], aliases: &{:?} }},",
+            city.name,
+            city.code,
+            city.timezone,
+            city.country,
+            city.coordinates[0],
+            city.coordinates[1],
+            city.aliases,
+        )
+        .unwrap();
+    }
+    out.push_str("];\n\n");
+
+    writeln!(
+        out,
+        "pub static MAJOR_CITIES: &[&str] = &{:?};\n",
+        data.major_cities
+    )
+    .unwrap();
+
+    // Indices sorted by key; the sort is stable so cities sharing a name keep
+    // their file order
+    let index = |name: &str, key: fn(&City) -> String, out: &mut String| {
+        let mut order: Vec<usize> = (0..data.cities.len()).collect();
+        order.sort_by_key(|&i| key(&data.cities[i]));
+        writeln!(out, "static {name}: &[u16] = &{order:?};").unwrap();
+    };
+    index("BY_NAME", |c| c.name.to_ascii_lowercase(), &mut out);
+    index("BY_CODE", |c| c.code.clone(), &mut out);
+
+    let path = Path::new(&env::var("OUT_DIR").unwrap()).join("cities.rs");
+    fs::write(path, out).expect("Failed to write city tables");
+}
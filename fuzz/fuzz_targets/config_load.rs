@@ -0,0 +1,32 @@
+//! Config files as the board loads them: layered over the defaults as the
+//! user's or the org's file, checked by `alltz config check`, and written
+//! back as the user's part. Only the parsing and merging is exercised; a
+//! config also names files and programs, which building a board would read
+//! and run.
+
+#![no_main]
+
+use alltz::config::{check_config, AppConfig, ConfigLayers};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|content: &str| {
+    let _ = check_config(content);
+    let _ = alltz::blocked::parse(content);
+    let Some(table) = AppConfig::parse_table(content) else {
+        return;
+    };
+    for layers in [
+        ConfigLayers {
+            user: Some(table.clone()),
+            ..ConfigLayers::default()
+        },
+        ConfigLayers {
+            org: Some(table),
+            ..ConfigLayers::default()
+        },
+    ] {
+        let config = layers.resolve();
+        let _ = config.user_table(layers.user.as_ref(), layers.org.clone());
+        let _ = layers.explain("zones");
+    }
+});
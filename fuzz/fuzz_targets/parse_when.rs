@@ -0,0 +1,24 @@
+//! Reminder times and clock times as typed or pasted: `alltz remind --at`,
+//! the goto prompt and quiz answers
+
+#![no_main]
+
+use alltz::time::{parse_clock_time, TimeZone};
+use alltz::{quiz, remind};
+use chrono::{TimeZone as _, Utc};
+use libfuzzer_sys::fuzz_target;
+use std::collections::BTreeMap;
+
+fuzz_target!(|input: &str| {
+    let now = Utc.with_ymd_and_hms(2024, 3, 30, 12, 0, 0).unwrap();
+    let zones = [TimeZone::with_source_city(
+        chrono_tz::Asia::Tokyo,
+        "TYO".to_string(),
+        Some("Maria".to_string()),
+        Some("Tokyo".to_string()),
+    )];
+    let learned = BTreeMap::from([("CST".to_string(), "Asia/Shanghai".to_string())]);
+    let _ = remind::parse_when(input, now, &zones, &learned);
+    let _ = parse_clock_time(input);
+    let _ = quiz::parse_answer(input);
+});
@@ -0,0 +1,26 @@
+//! Text piped through `alltz pipe`, where any ISO 8601 timestamp found is
+//! converted and everything else must pass through untouched
+
+#![no_main]
+
+use alltz::pipe::{convert, PipeOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    let noting = PipeOptions {
+        to: chrono_tz::Asia::Tokyo,
+        from: None,
+        replace: false,
+    };
+    // Without an assumed zone, nothing without an offset changes
+    let noted = convert(text, &noting);
+    assert!(noted.len() >= text.len());
+
+    let replacing = PipeOptions {
+        from: Some(chrono_tz::Europe::London),
+        replace: true,
+        ..noting
+    };
+    let _ = convert(text, &replacing);
+    let _ = alltz::org::parse(text);
+});
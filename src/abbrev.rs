@@ -0,0 +1,169 @@
+use crate::cities::{CityData, CITIES, MAJOR_CITIES};
+use crate::platform::Platform;
+use chrono::{DateTime, Datelike, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How many times in a row an abbreviation has to be resolved to the same
+/// zone before that's remembered
+pub const LEARN_AFTER: u32 = 3;
+
+/// The abbreviations a zone's clocks show over `now`'s year, e.g. CST and
+/// CDT for Chicago. Zones that only show an offset (`+05`) have none.
+pub fn abbreviations(tz: Tz, now: DateTime<Utc>) -> Vec<String> {
+    let mut found: Vec<String> = [1, 7]
+        .iter()
+        .filter_map(|&month| {
+            Utc.with_ymd_and_hms(now.year(), month, 15, 12, 0, 0)
+                .single()
+        })
+        .map(|at| at.with_timezone(&tz).format("%Z").to_string())
+        .filter(|abbreviation| abbreviation.starts_with(|c: char| c.is_ascii_alphabetic()))
+        .collect();
+    found.dedup();
+    found
+}
+
+/// One city for each zone that shows `abbreviation` during `now`'s year, a
+/// major city where there is one, in the order the cities are listed
+pub fn cities_for(abbreviation: &str, now: DateTime<Utc>) -> Vec<&'static CityData> {
+    let mut found: Vec<&'static CityData> = Vec::new();
+    for city in CITIES {
+        let Ok(tz) = city.timezone.parse::<Tz>() else {
+            continue;
+        };
+        if !abbreviations(tz, now)
+            .iter()
+            .any(|a| a.eq_ignore_ascii_case(abbreviation))
+        {
+            continue;
+        }
+        match found.iter().position(|c| c.timezone == city.timezone) {
+            Some(index) if !MAJOR_CITIES.contains(&found[index].name) => {
+                if MAJOR_CITIES.contains(&city.name) {
+                    found[index] = city;
+                }
+            }
+            Some(_) => {}
+            None => found.push(city),
+        }
+    }
+    found
+}
+
+/// The zone an abbreviation stands for: the learned or configured one, or
+/// the only zone that uses it. Ambiguous abbreviations without a
+/// preference stand for nothing.
+pub fn resolve(
+    abbreviation: &str,
+    learned: &BTreeMap<String, String>,
+    now: DateTime<Utc>,
+) -> Option<Tz> {
+    if let Some(zone) = learned
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(abbreviation))
+        .and_then(|(_, zone)| zone.parse().ok())
+    {
+        return Some(zone);
+    }
+    match cities_for(abbreviation, now).as_slice() {
+        [only] => only.timezone.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Resolutions counted towards learning an abbreviation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Pick {
+    pub zone: String,
+    pub count: u32,
+}
+
+/// Counts towards learning, kept next to the config. Only learned mappings
+/// go in the config itself.
+pub fn picks_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("abbreviations.json"))
+}
+
+/// Reads the counts, treating a missing or unreadable file as empty
+pub fn load_picks(path: &Path) -> BTreeMap<String, Pick> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_picks(path: &Path, picks: &BTreeMap<String, Pick>) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(picks)?)
+}
+
+/// Counts resolving `abbreviation` to `zone`; picking another zone starts
+/// the count again. Returns whether that makes it a learned preference.
+pub fn record_pick(picks: &mut BTreeMap<String, Pick>, abbreviation: &str, zone: Tz) -> bool {
+    let pick = picks
+        .entry(abbreviation.to_uppercase())
+        .or_insert_with(|| Pick {
+            zone: zone.name().to_string(),
+            count: 0,
+        });
+    if pick.zone != zone.name() {
+        pick.zone = zone.name().to_string();
+        pick.count = 0;
+    }
+    pick.count += 1;
+    pick.count == LEARN_AFTER
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_abbreviations() {
+        let now = Utc.with_ymd_and_hms(2024, 10, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            abbreviations(chrono_tz::America::Chicago, now),
+            ["CST", "CDT"]
+        );
+        assert_eq!(abbreviations(chrono_tz::Asia::Shanghai, now), ["CST"]);
+        assert!(abbreviations(chrono_tz::Asia::Dubai, now).is_empty());
+
+        let cities: Vec<&str> = cities_for("cst", now)
+            .iter()
+            .map(|city| city.name)
+            .collect();
+        assert!(cities.contains(&"Chicago"));
+        assert!(cities.contains(&"Shanghai"));
+
+        let mut learned = BTreeMap::new();
+        assert_eq!(resolve("CST", &learned, now), None);
+        assert_eq!(resolve("JST", &learned, now), Some(chrono_tz::Asia::Tokyo));
+        learned.insert("CST".to_string(), "Asia/Shanghai".to_string());
+        assert_eq!(
+            resolve("cst", &learned, now),
+            Some(chrono_tz::Asia::Shanghai)
+        );
+    }
+
+    #[test]
+    fn test_record_pick() {
+        let mut picks = BTreeMap::new();
+        assert!(!record_pick(&mut picks, "cst", chrono_tz::Asia::Shanghai));
+        assert!(!record_pick(&mut picks, "CST", chrono_tz::Asia::Shanghai));
+        // Changing your mind starts over
+        assert!(!record_pick(&mut picks, "CST", chrono_tz::America::Chicago));
+        assert_eq!(picks["CST"].count, 1);
+        assert!(!record_pick(&mut picks, "CST", chrono_tz::Asia::Shanghai));
+        assert!(!record_pick(&mut picks, "CST", chrono_tz::Asia::Shanghai));
+        assert!(record_pick(&mut picks, "CST", chrono_tz::Asia::Shanghai));
+    }
+}
@@ -5,10 +5,35 @@ use ratatui::{
     widgets::{Block, Borders, Cell, Clear, Paragraph, Row, Table, TableState, Wrap},
     Frame,
 };
-
-use crate::config::{AppConfig, ColorTheme, TimeDisplayConfig};
-use crate::time::{TimeZone, TimeZoneManager};
-use crate::ui::TimelineWidget;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+use crate::abbrev;
+use crate::command::{self, Command};
+use crate::config::{
+    find_palette, AppConfig, BackgroundMode, BlockedDates, CalendarConfig, CardTemplate,
+    ColorTheme, EtiquetteConfig, GlyphMode, HandoffConfig, HourBoundary, LayoutConfig, Marker,
+    Milestone, NotifyBackend, NotifyConfig, NowLineStyle, Palette, PresenceConfig, SyncConfig,
+    TasksConfig, TimeDisplayConfig, UserTheme, ZoneConfigCompat, ZoneOrder, MAX_DST_COUNTDOWN_DAYS,
+};
+use crate::handoff;
+use crate::health;
+use crate::history::{self, HistoryEvent};
+use crate::ics;
+use crate::notify::{self, Alarm, AlarmKind};
+use crate::org;
+use crate::plan;
+use crate::remind::{self, Reminder};
+use crate::session::{self, Snapshot, UndoStack};
+#[cfg(feature = "sync")]
+use crate::sync::{BoardSync, SyncTask};
+use crate::tasks;
+use crate::terminal::{Background, Capabilities, ColorSupport, GraphicsProtocol};
+use crate::time::{next_work_end, next_work_start, TimeZone, TimeZoneManager};
+use crate::ui::{
+    render_rows, ClockChanges, DayLayoutCache, Ghost, Glyphs, QrWidget, TimelineWidget,
+};
+use crate::update::{self, UpdateCheck};
 
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum TimeFormat {
@@ -30,25 +55,241 @@ pub enum Direction {
     Right,
 }
 
+/// Jumps longer than this are animated so the board doesn't teleport
+const ANIMATION_THRESHOLD_MINUTES: i64 = 3 * 60;
+/// Number of frames a scrub animation takes to reach its target
+const ANIMATION_FRAMES: u32 = 8;
+/// How long decorations stay off after a frame runs over budget
+const LEAN_COOLDOWN: std::time::Duration = std::time::Duration::from_secs(3);
+
+/// An in-progress glide of the timeline position towards a jump target
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScrubAnimation {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub frame: u32,
+}
+
+impl ScrubAnimation {
+    /// Position after the current frame, eased out so motion slows near the target
+    fn position(&self) -> DateTime<Utc> {
+        let t = (self.frame as f64 / ANIMATION_FRAMES as f64).min(1.0);
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let total_ms = self.to.signed_duration_since(self.from).num_milliseconds();
+        self.from + chrono::Duration::milliseconds((total_ms as f64 * eased) as i64)
+    }
+
+    fn is_finished(&self) -> bool {
+        self.frame >= ANIMATION_FRAMES
+    }
+}
+
+/// How long the footer confirms a copied time card
+const COPIED_NOTICE: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How long the footer shows a reminder that has fired
+const REMINDER_NOTICE: std::time::Duration = std::time::Duration::from_secs(5 * 60);
+
+/// How many jumped-from positions are kept for going back
+const JUMP_HISTORY: usize = 50;
+
+/// Scrub positions jumped away from, walked like browser history. `index`
+/// is the entry being viewed, or the end when at a position not yet saved.
+/// It's kept in the session file, so `--resume` can still go back.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct JumpList {
+    entries: Vec<DateTime<Utc>>,
+    index: usize,
+}
+
+impl JumpList {
+    /// Saves `from` before a jump, dropping any positions ahead of it
+    fn record(&mut self, from: DateTime<Utc>) {
+        self.entries.truncate(self.index);
+        if self.entries.last() != Some(&from) {
+            self.entries.push(from);
+        }
+        if self.entries.len() > JUMP_HISTORY {
+            self.entries.remove(0);
+        }
+        self.index = self.entries.len();
+    }
+
+    /// The position before the one viewed, saving `current` first when it
+    /// isn't in the list so forward can return to it
+    fn back(&mut self, current: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        if self.index == self.entries.len() {
+            if self.entries.last() == Some(&current) {
+                self.index -= 1;
+            } else {
+                self.entries.push(current);
+            }
+        }
+        self.index = self.index.checked_sub(1)?;
+        Some(self.entries[self.index])
+    }
+
+    fn forward(&mut self) -> Option<DateTime<Utc>> {
+        let target = *self.entries.get(self.index + 1)?;
+        self.index += 1;
+        Some(target)
+    }
+
+    /// The list as a session file had it, kept in bounds whatever was read
+    fn resumed(mut self) -> Self {
+        let excess = self.entries.len().saturating_sub(JUMP_HISTORY);
+        self.entries.drain(..excess);
+        self.index = self.index.saturating_sub(excess).min(self.entries.len());
+        self
+    }
+}
+
+/// Milestones picked to slip together. The shift is applied to the board's
+/// milestones straight away as a preview, and undone if the plan is cancelled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MilestonePlan {
+    pub cursor: usize,
+    pub selected: Vec<bool>,
+    pub shift: chrono::Duration,
+    pub original: Vec<Milestone>,
+    pub before: Snapshot, // The board before the plan, for undo if it is kept
+}
+
+impl MilestonePlan {
+    fn new(milestones: &[Milestone], before: Snapshot) -> Self {
+        Self {
+            cursor: 0,
+            selected: vec![false; milestones.len()],
+            shift: chrono::Duration::zero(),
+            original: milestones.to_vec(),
+            before,
+        }
+    }
+
+    /// The milestones with the shift applied to the selected ones
+    fn preview(&self) -> Vec<Milestone> {
+        self.original
+            .iter()
+            .zip(&self.selected)
+            .map(|(milestone, &selected)| Milestone {
+                time: milestone.time
+                    + if selected {
+                        self.shift
+                    } else {
+                        chrono::Duration::zero()
+                    },
+                ..milestone.clone()
+            })
+            .collect()
+    }
+}
+
+/// A zone's work and awake hours being edited, applied to the zone as they
+/// change and put back if the edit is cancelled
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoursEditor {
+    pub boundary: usize,
+    pub zone: usize,
+    pub original: Option<TimeDisplayConfig>, // The zone's own hours, if it had any
+    pub before: Snapshot,                    // The board before the edit, for undo if it is kept
+}
+
+/// A shift as "+2d 3h", "−45m" or "±0"
+fn format_shift(shift: chrono::Duration) -> String {
+    let minutes = shift.num_minutes();
+    if minutes == 0 {
+        return "±0".to_string();
+    }
+    let sign = if minutes < 0 { '−' } else { '+' };
+    let minutes = minutes.unsigned_abs();
+    let parts: Vec<String> = [
+        (minutes / 1440, 'd'),
+        (minutes / 60 % 24, 'h'),
+        (minutes % 60, 'm'),
+    ]
+    .iter()
+    .filter(|(amount, _)| *amount > 0)
+    .map(|(amount, unit)| format!("{amount}{unit}"))
+    .collect();
+    format!("{sign}{}", parts.join(" "))
+}
+
+/// What a command run with `--open` hands to the board it opens
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Preload {
+    /// Cities the command was about, shown for this session only
+    pub zones: Vec<String>,
+    /// The instant the command was about, scrubbed to
+    pub at: Option<DateTime<Utc>>,
+    /// Open the overlap matrix
+    pub matrix: bool,
+}
+
+/// Accent colors `A` cycles marked zones through, before going back to none
+const ACCENTS: [&str; 6] = ["red", "green", "yellow", "blue", "magenta", "cyan"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MacroAction {
+    Record,
+    Replay,
+}
+
 #[derive(Debug, Clone)]
 pub enum Message {
     // Time navigation
     Tick,
+    AnimationFrame,
     ScrubTimeline(Direction),
     ScrubTimelineWithShift(Direction),
     ResetToNow,
     FineAdjust(i32), // minutes
+    JumpDays(i64),
+    ShiftMilestones(i64), // days
+
+    // Slipping chosen milestones
+    StartMilestonePlan,
+    NavigateMilestonePlan(Direction),
+    ToggleMilestoneSelection,
+    SelectMilestoneGroup,
+    AdjustMilestonePlan(i64), // minutes
+    ConfirmMilestonePlan,
+    CancelMilestonePlan,
 
     // Zone navigation
     NavigateZone(Direction),
 
+    // Split view
+    ToggleSplitView,
+    CycleSplitLayout,
+    SwitchPane,
+    CopyTimeCard,
+    NavigateShareMenu(Direction),
+    SelectShareOption(usize),
+    CancelShareMenu,
+    JumpBack,
+    JumpForward,
+
     // Display options
     ToggleTimeFormat,
     ToggleTimezoneDisplayMode,
     ToggleDate,
     ToggleSunTimes,
+    ToggleFocusMode,
+    ToggleFocusRuler,
+    ToggleAlignMidnight,
+    TogglePrivacyMode,
     ToggleHelp,
+    DismissClockWarnings,
+    ToggleQrCode,
+    ToggleOffsetMatrix,
     CycleColorTheme,
+    CycleZoneOrder,
+
+    // Work hours editor
+    StartHoursEditor,
+    NavigateHoursEditor(Direction),
+    ConfirmHoursEditor,
+    CancelHoursEditor,
 
     // Zone management
     StartAddZone,
@@ -59,22 +300,93 @@ pub enum Message {
     CancelAddZone,
     RemoveCurrentZone,
 
+    // Acting on several zones at once
+    ToggleMark,
+    ClearMarks,
+    CycleAccent,
+    StartAssignGroup,
+    UpdateGroupInput(String),
+    ConfirmAssignGroup,
+    CancelAssignGroup,
+
+    // Archiving zones, and bringing them back
+    ArchiveZone,
+    OpenArchive,
+    NavigateArchive(Direction),
+    RestoreArchived(usize),
+    CancelArchive,
+
     // Zone renaming
+    StartGotoTime,
+    UpdateGotoInput(String),
+    ConfirmGotoTime,
+    CancelGotoTime,
     StartRenameZone,
     UpdateRenameInput(String),
     ConfirmRename,
     CancelRename,
     ClearCustomName,
 
+    // Keyboard macros
+    AwaitMacroRegister(MacroAction),
+    StartMacroRecording(char),
+    StopMacroRecording,
+
+    // Taking back changes
+    Undo,
+    Redo,
+
     // App lifecycle
     Quit,
 }
 
+impl Message {
+    /// Whether the message can change the board as undo keeps it, so only
+    /// these take snapshots; ticks, scrubbing and typing don't
+    fn changes_board(&self) -> bool {
+        matches!(
+            self,
+            Message::ShiftMilestones(_)
+                | Message::ToggleSplitView
+                | Message::CycleSplitLayout
+                | Message::ToggleTimeFormat
+                | Message::ToggleTimezoneDisplayMode
+                | Message::ToggleDate
+                | Message::ToggleSunTimes
+                | Message::ToggleFocusMode
+                | Message::ToggleFocusRuler
+                | Message::ToggleAlignMidnight
+                | Message::TogglePrivacyMode
+                | Message::CycleColorTheme
+                | Message::CycleZoneOrder
+                | Message::SelectSearchResult(_)
+                | Message::ConfirmAddZone
+                | Message::RemoveCurrentZone
+                | Message::ToggleMark
+                | Message::ClearMarks
+                | Message::CycleAccent
+                | Message::ConfirmAssignGroup
+                | Message::ArchiveZone
+                | Message::RestoreArchived(_)
+                | Message::ConfirmRename
+                | Message::ClearCustomName
+        )
+    }
+}
+
 #[derive(Debug)]
 pub struct App {
     // Time management
     pub current_time: DateTime<Utc>,
     pub timeline_position: DateTime<Utc>,
+    pub scrub_animation: Option<ScrubAnimation>,
+    pub jumps: JumpList,
+    pub reduced_motion: bool,
+    // Over slow links: no animation, images or per-second redraws
+    pub low_bandwidth: bool,
+
+    // Changes stay in memory: nothing is saved or logged (the demo board)
+    pub ephemeral: bool,
 
     // Zone management
     pub timezone_manager: TimeZoneManager,
@@ -85,15 +397,147 @@ pub struct App {
     pub timezone_display_mode: TimezoneDisplayMode,
     pub time_config: TimeDisplayConfig,
     pub color_theme: ColorTheme,
+    pub user_theme: Option<String>,
+    pub themes: Vec<UserTheme>,
+    pub background: BackgroundMode,
+    pub terminal_background: Option<Background>,
+    pub color_support: ColorSupport, // Palette colors are brought down to what the terminal shows
+    pub zone_order: ZoneOrder,
     pub show_help: bool,
+    pub show_qr: bool,
+    pub show_matrix: bool,
     pub adding_zone: bool,
     pub add_zone_input: String,
     pub zone_search_results: Vec<String>,
     pub selected_search_result: usize,
+    /// What's typed after `:`, a time to move the scrubber to in the
+    /// selected zone
+    pub goto_time: Option<String>,
+    /// Cities marked for a bulk action; bulk actions use the selected zone
+    /// when none are
+    pub marked_zones: Vec<String>,
+    /// What's typed after `g`, the layout to add the marked zones to
+    pub group_input: Option<String>,
     pub renaming_zone: bool,
     pub rename_zone_input: String,
     pub show_date: bool,
     pub show_sun_times: bool,
+    pub focus_mode: bool,
+    pub focus_ruler: bool,
+    pub offset_minutes: bool,
+    pub align_to: Option<String>,
+    pub dst_countdown_days: u32,
+    pub glyph_mode: GlyphMode,
+    pub glyphs: Glyphs,
+    pub marker_priority: Vec<Marker>,
+    pub now_line: NowLineStyle,
+    pub etiquette: EtiquetteConfig,
+    pub fiscal_year_start: Option<u32>,
+    pub milestones: Vec<Milestone>,
+    pub imminent_minutes: u32,
+    pub pulse_imminent: bool,
+    pub blocked: Vec<BlockedDates>,
+    pub calendars: Vec<CalendarConfig>,
+    /// Blocked dates and calendar out-of-office days together, as drawn
+    pub away: Vec<BlockedDates>,
+    pub tasks: Option<TasksConfig>,
+    /// Due dates from the task lists, marked on the home zone's bar
+    pub due_tasks: Vec<Milestone>,
+    pub tasks_tz: Option<chrono_tz::Tz>,
+    pub handoff: Option<HandoffConfig>,
+    pub notify: Option<NotifyConfig>,
+    /// Zones ambiguous abbreviations stand for in searches, by abbreviation
+    pub abbreviations: BTreeMap<String, String>,
+    pub milestone_plan: Option<MilestonePlan>,
+    pub hours_editor: Option<HoursEditor>,
+
+    // Graphical clock beside the local time box, on kitty/sixel terminals.
+    // The area is recorded while rendering so the image can be drawn after.
+    pub clock_graphics: bool,
+    pub privacy_mode: bool,
+    pub graphics: Option<GraphicsProtocol>,
+    pub clock_area: std::cell::Cell<Option<Rect>>,
+
+    // Where each zone's days fall in the visible window, shared by all rows;
+    // the split pane is narrower, so it keeps its own
+    pub day_layouts: DayLayoutCache,
+    pub split_day_layouts: DayLayoutCache,
+    // Each zone's next clock change, for the countdown in zone titles
+    pub clock_changes: ClockChanges,
+
+    // Frames slower than the budget turn decorations off for a while
+    pub frame_budget_ms: u64,
+    pub lean_until: Option<std::time::Instant>,
+    pub card_templates: Vec<CardTemplate>,
+    /// The highlighted layout while choosing how to copy the time card
+    pub share_menu: Option<usize>,
+    /// Zones taken off the board with their labels and accents, to restore
+    pub archived: Vec<ZoneConfigCompat>,
+    /// The highlighted zone while choosing one to restore
+    pub archive_picker: Option<usize>,
+    /// Text for the event loop to put on the clipboard
+    pub clipboard: Option<String>,
+    /// Until when the footer says the time card was copied
+    pub copied_until: Option<std::time::Instant>,
+    /// The reminders that last fired, and until when the footer shows them
+    pub fired_reminders: Option<(Vec<Reminder>, std::time::Instant)>,
+    /// The reminders file as last read
+    reminders: remind::Cache,
+    /// Whether the event loop should ring the terminal bell
+    pub bell: bool,
+
+    // Split view: a second zone group shown beside the main board,
+    // sharing the timeline position but with its own selection
+    pub layouts: Vec<LayoutConfig>,
+    pub board_name: Option<String>,
+    pub split_view: bool,
+    pub split_layout_index: usize,
+    pub split_timezone_manager: TimeZoneManager,
+    pub split_selected_zone_index: usize,
+    pub split_pane_focused: bool,
+
+    // Mini mode: number of compact rows to show, with no other chrome
+    pub mini_rows: Option<usize>,
+
+    // Keyboard macros, stored as encoded key names per register
+    pub macros: BTreeMap<String, Vec<String>>,
+    pub recording_macro: Option<(char, Vec<String>)>,
+    pub pending_macro_register: Option<MacroAction>,
+
+    // Keys the config binds to other commands, and the keymap they make
+    pub keys: BTreeMap<String, String>,
+    pub keymap: BTreeMap<String, Command>,
+
+    // Shared team board, and the last sync error to show in the footer
+    pub sync: Option<SyncConfig>,
+    pub sync_error: Option<String>,
+    #[cfg(feature = "sync")]
+    pub sync_task: Option<SyncTask>,
+    // Who this board is on the shared board, when positions were last
+    // exchanged, and where teammates are scrubbed to
+    pub presence: Option<PresenceConfig>,
+    pub presence_shared: Option<std::time::Instant>,
+    pub ghosts: Vec<Ghost>,
+
+    // The zone this machine should keep, a time server to check the clock
+    // against, and what the startup check found wrong until dismissed
+    pub home: Option<String>,
+    pub ntp_server: Option<String>,
+    pub clock_warnings: Vec<String>,
+    pub clock_task: Option<health::ServerCheck>,
+
+    // Opt-in check for a newer release, and the footer notice it leads to
+    pub update_check: bool,
+    pub update_task: Option<UpdateCheck>,
+    pub update_notice: Option<String>,
+
+    // Config keys forced by ALLTZ_* env vars, kept out of the saved file
+    pub env_keys: Vec<String>,
+    // Cities added by `--open` for this session only, never saved
+    pub temporary_zones: Vec<String>,
+
+    // Boards from before each change, for undo and redo
+    pub undo: UndoStack,
 
     // App state
     pub should_quit: bool,
@@ -105,21 +549,110 @@ impl Default for App {
         Self {
             current_time: now,
             timeline_position: now,
+            scrub_animation: None,
+            jumps: JumpList::default(),
+            reduced_motion: false,
+            low_bandwidth: false,
+            ephemeral: false,
             timezone_manager: TimeZoneManager::with_default_zones(),
             selected_zone_index: 0,
             display_format: TimeFormat::TwentyFourHour,
             timezone_display_mode: TimezoneDisplayMode::Short,
             time_config: TimeDisplayConfig::default(),
             color_theme: ColorTheme::default(),
+            user_theme: None,
+            themes: Vec::new(),
+            background: BackgroundMode::default(),
+            terminal_background: None,
+            color_support: ColorSupport::TrueColor,
+            zone_order: ZoneOrder::default(),
             show_help: false,
+            show_qr: false,
+            show_matrix: false,
             adding_zone: false,
             add_zone_input: String::new(),
             zone_search_results: Vec::new(),
             selected_search_result: 0,
+            goto_time: None,
+            marked_zones: Vec::new(),
+            group_input: None,
             renaming_zone: false,
             rename_zone_input: String::new(),
             show_date: false,
             show_sun_times: true,
+            focus_mode: false,
+            focus_ruler: false,
+            offset_minutes: false,
+            align_to: None,
+            dst_countdown_days: 0,
+            glyph_mode: GlyphMode::default(),
+            glyphs: Glyphs::for_mode(GlyphMode::default(), true),
+            marker_priority: Marker::DEFAULT_PRIORITY.to_vec(),
+            now_line: NowLineStyle::default(),
+            etiquette: EtiquetteConfig::default(),
+            fiscal_year_start: None,
+            milestones: Vec::new(),
+            imminent_minutes: 15,
+            pulse_imminent: false,
+            blocked: Vec::new(),
+            calendars: Vec::new(),
+            away: Vec::new(),
+            tasks: None,
+            due_tasks: Vec::new(),
+            tasks_tz: None,
+            handoff: None,
+            notify: None,
+            abbreviations: BTreeMap::new(),
+            milestone_plan: None,
+            hours_editor: None,
+            clock_graphics: false,
+            privacy_mode: false,
+            graphics: None,
+            clock_area: std::cell::Cell::new(None),
+            day_layouts: Default::default(),
+            split_day_layouts: Default::default(),
+            clock_changes: Default::default(),
+            frame_budget_ms: 50,
+            lean_until: None,
+            card_templates: Vec::new(),
+            share_menu: None,
+            archived: Vec::new(),
+            archive_picker: None,
+            clipboard: None,
+            copied_until: None,
+            fired_reminders: None,
+            reminders: remind::Cache::default(),
+            bell: false,
+            layouts: Vec::new(),
+            board_name: None,
+            split_view: false,
+            split_layout_index: 0,
+            split_timezone_manager: TimeZoneManager::new(),
+            split_selected_zone_index: 0,
+            split_pane_focused: false,
+            mini_rows: None,
+            macros: BTreeMap::new(),
+            recording_macro: None,
+            pending_macro_register: None,
+            keys: BTreeMap::new(),
+            keymap: command::keymap(&BTreeMap::new()),
+            sync: None,
+            sync_error: None,
+            presence: None,
+            presence_shared: None,
+            ghosts: Vec::new(),
+            home: None,
+            ntp_server: None,
+            clock_warnings: Vec::new(),
+            update_check: false,
+            update_task: None,
+            clock_task: None,
+            update_notice: None,
+            #[cfg(feature = "sync")]
+            sync_task: None,
+            env_keys: Vec::new(),
+            temporary_zones: Vec::new(),
+            undo: UndoStack::default(),
             should_quit: false,
         }
     }
@@ -133,12 +666,12 @@ impl App {
         if let Some(config_path) = AppConfig::config_path() {
             if !config_path.exists() {
                 // Only create if we successfully loaded default config
-                if config.save().is_err() {
+                if let Err(e) = config.save() {
                     // Don't fail if we can't save config, just continue with defaults
-                    eprintln!(
-                        "Warning: Could not create default config file at {}",
+                    crate::log::write(&format!(
+                        "Could not create default config file at {}: {e}",
                         config_path.display()
-                    );
+                    ));
                 }
             }
         }
@@ -148,16 +681,31 @@ impl App {
         app
     }
 
-    pub fn from_config(config: AppConfig) -> Self {
+    /// Builds a zone manager from a list of zone configs, keeping custom labels
+    fn manager_from_zone_configs(zones: &[ZoneConfigCompat]) -> TimeZoneManager {
         let mut timezone_manager = TimeZoneManager::new();
-
-        // Load timezones from config with custom labels
-        for zone_config in &config.zones {
-            timezone_manager.add_timezone_with_label(
+        for zone_config in zones {
+            let added = timezone_manager.add_timezone_with_label(
                 zone_config.city_name(),
                 zone_config.custom_label().map(|s| s.to_string()),
             );
+            // Zones are kept in offset order, so look for where it went
+            let index = TimeZoneManager::find_city(zone_config.city_name()).and_then(|city| {
+                timezone_manager
+                    .zones()
+                    .iter()
+                    .position(|zone| zone.display.code == city.code)
+            });
+            if let (true, Some(index)) = (added, index) {
+                timezone_manager.apply(index, zone_config);
+            }
         }
+        timezone_manager
+    }
+
+    pub fn from_config(config: AppConfig) -> Self {
+        // Load timezones from config with custom labels
+        let mut timezone_manager = Self::manager_from_zone_configs(&config.zones);
 
         // If no zones were loaded, use defaults
         if timezone_manager.zones().is_empty() {
@@ -169,701 +717,2722 @@ impl App {
             .selected_zone_index
             .min(timezone_manager.zone_count().saturating_sub(1));
 
-        Self {
+        let mut app = Self {
             current_time: now,
             timeline_position: now,
+            scrub_animation: None,
+            jumps: JumpList::default(),
+            reduced_motion: config.reduced_motion,
+            low_bandwidth: config.low_bandwidth,
+            ephemeral: false,
             timezone_manager,
             selected_zone_index,
             display_format: config.display_format,
             timezone_display_mode: config.timezone_display_mode,
             time_config: config.time_config,
             color_theme: config.color_theme,
+            user_theme: config.user_theme,
+            themes: config.themes,
+            background: config.background,
+            terminal_background: None,
+            color_support: ColorSupport::TrueColor,
+            zone_order: config.zone_order,
             show_help: false,
+            show_qr: false,
+            show_matrix: false,
             adding_zone: false,
             add_zone_input: String::new(),
             zone_search_results: Vec::new(),
             selected_search_result: 0,
+            goto_time: None,
+            marked_zones: Vec::new(),
+            group_input: None,
             renaming_zone: false,
             rename_zone_input: String::new(),
             show_date: config.show_date,
             show_sun_times: config.show_sun_times,
+            focus_mode: config.focus_mode,
+            focus_ruler: config.focus_ruler,
+            offset_minutes: config.offset_minutes,
+            align_to: config.align_to,
+            dst_countdown_days: config.dst_countdown_days,
+            glyph_mode: config.glyphs,
+            glyphs: Glyphs::for_mode(config.glyphs, true),
+            marker_priority: config.marker_priority,
+            now_line: config.now_line,
+            etiquette: config.etiquette,
+            fiscal_year_start: config.fiscal_year_start.filter(|m| (1..=12).contains(m)),
+            milestones: config.milestones,
+            imminent_minutes: config.imminent_minutes,
+            pulse_imminent: config.pulse_imminent,
+            away: config.blocked.clone(),
+            blocked: config.blocked,
+            calendars: config.calendars,
+            due_tasks: Vec::new(),
+            tasks_tz: None,
+            tasks: config.tasks,
+            handoff: config.handoff,
+            notify: config.notify,
+            abbreviations: config.abbreviations,
+            milestone_plan: None,
+            hours_editor: None,
+            clock_graphics: config.clock_graphics,
+            privacy_mode: config.privacy_mode,
+            graphics: None,
+            clock_area: std::cell::Cell::new(None),
+            day_layouts: Default::default(),
+            split_day_layouts: Default::default(),
+            clock_changes: Default::default(),
+            frame_budget_ms: config.frame_budget_ms,
+            lean_until: None,
+            card_templates: config.card_templates,
+            share_menu: None,
+            archived: config.archived,
+            archive_picker: None,
+            clipboard: None,
+            copied_until: None,
+            fired_reminders: None,
+            reminders: remind::Cache::default(),
+            bell: false,
+            layouts: config.layouts,
+            board_name: config.board_name,
+            split_view: false,
+            split_layout_index: 0,
+            split_timezone_manager: TimeZoneManager::new(),
+            split_selected_zone_index: 0,
+            split_pane_focused: false,
+            mini_rows: None,
+            macros: config.macros,
+            recording_macro: None,
+            pending_macro_register: None,
+            keymap: command::keymap(&config.keys),
+            keys: config.keys,
+            sync: config.sync,
+            sync_error: None,
+            presence: config.presence,
+            presence_shared: None,
+            ghosts: Vec::new(),
+            home: config.home,
+            ntp_server: config.ntp_server,
+            clock_warnings: Vec::new(),
+            update_check: config.update_check,
+            update_task: None,
+            clock_task: None,
+            update_notice: None,
+            #[cfg(feature = "sync")]
+            sync_task: None,
+            env_keys: config.env_keys,
+            temporary_zones: Vec::new(),
+            undo: UndoStack::default(),
             should_quit: false,
-        }
+        };
+        app.apply_zone_order();
+        app
+    }
+
+    /// Adjusts rendering to what the terminal was found to support
+    pub fn apply_capabilities(&mut self, capabilities: &Capabilities) {
+        self.glyphs = Glyphs::for_mode(self.glyph_mode, capabilities.unicode_glyphs());
+        self.graphics = capabilities
+            .graphics
+            .filter(|_| self.clock_graphics && cfg!(feature = "clock-graphics"))
+            // Images are large, and sixel ones need full-screen redraws
+            .filter(|_| !self.low_bandwidth);
+        self.terminal_background = capabilities.background;
+        self.color_support = capabilities.color;
     }
 
+    /// Where the graphical clock goes and the local time it should show
+    #[cfg(feature = "clock-graphics")]
+    pub fn clock_image(&self) -> Option<(Rect, u32, u32)> {
+        let area = self.clock_area.get()?;
+        let local_time = self.timeline_position.with_timezone(&Local);
+        Some((area, local_time.hour(), local_time.minute()))
+    }
+
+    /// How a board zone is written to the config
     pub fn to_config(&self) -> AppConfig {
         AppConfig {
             zones: self
                 .timezone_manager
                 .zones()
                 .iter()
-                .map(|zone| {
-                    // Use the source_city if available, otherwise find the original search name
-                    let city_name = if let Some(source_city) = &zone.source_city {
-                        source_city.clone()
-                    } else {
-                        let available = TimeZoneManager::get_all_available_timezones();
-                        available
+                .filter(|zone| {
+                    zone.display.label.is_some()
+                        || !self
+                            .temporary_zones
                             .iter()
-                            .find(|(tz, _, _, _, _)| *tz == zone.tz)
-                            .map(|(_, search_name, _, _, _)| search_name.clone())
-                            .unwrap_or_else(|| zone.tz.to_string())
-                    };
-
-                    // Save as full ZoneConfig if custom label is present, otherwise as simple string
-                    match &zone.custom_label {
-                        Some(label) => {
-                            crate::config::ZoneConfigCompat::Full(crate::config::ZoneConfig {
-                                city_name,
-                                custom_label: Some(label.clone()),
-                            })
-                        }
-                        None => crate::config::ZoneConfigCompat::Simple(city_name),
-                    }
+                            .any(|city| zone.get_city_name().eq_ignore_ascii_case(city))
                 })
+                .map(ZoneConfigCompat::from)
+                .collect(),
+            selected_zone_index: self.selected_zone_index,
+            display_format: self.display_format.clone(),
+            timezone_display_mode: self.timezone_display_mode.clone(),
+            time_config: self.time_config.clone(),
+            color_theme: self.color_theme,
+            user_theme: self.user_theme.clone(),
+            themes: self.themes.clone(),
+            background: self.background,
+            zone_order: self.zone_order,
+            show_date: self.show_date,
+            show_sun_times: self.show_sun_times,
+            focus_mode: self.focus_mode,
+            focus_ruler: self.focus_ruler,
+            offset_minutes: self.offset_minutes,
+            align_to: self.align_to.clone(),
+            dst_countdown_days: self.dst_countdown_days,
+            glyphs: self.glyph_mode,
+            marker_priority: self.marker_priority.clone(),
+            now_line: self.now_line.clone(),
+            etiquette: self.etiquette.clone(),
+            clock_graphics: self.clock_graphics,
+            privacy_mode: self.privacy_mode,
+            update_check: self.update_check,
+            reduced_motion: self.reduced_motion,
+            low_bandwidth: self.low_bandwidth,
+            frame_budget_ms: self.frame_budget_ms,
+            layouts: self.layouts.clone(),
+            board_name: self.board_name.clone(),
+            archived: self.archived.clone(),
+            macros: self.macros.clone(),
+            keys: self.keys.clone(),
+            sync: self.sync.clone(),
+            presence: self.presence.clone(),
+            home: self.home.clone(),
+            ntp_server: self.ntp_server.clone(),
+            fiscal_year_start: self.fiscal_year_start,
+            milestones: self.milestones.clone(),
+            imminent_minutes: self.imminent_minutes,
+            pulse_imminent: self.pulse_imminent,
+            card_templates: self.card_templates.clone(),
+            blocked: self.blocked.clone(),
+            calendars: self.calendars.clone(),
+            tasks: self.tasks.clone(),
+            handoff: self.handoff.clone(),
+            notify: self.notify.clone(),
+            abbreviations: self.abbreviations.clone(),
+            env_keys: self.env_keys.clone(),
+        }
+    }
+
+    /// The board as undo and the session file keep it
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            zones: self
+                .timezone_manager
+                .zones()
+                .iter()
+                .map(ZoneConfigCompat::from)
                 .collect(),
             selected_zone_index: self.selected_zone_index,
+            marked_zones: self.marked_zones.clone(),
             display_format: self.display_format.clone(),
             timezone_display_mode: self.timezone_display_mode.clone(),
             time_config: self.time_config.clone(),
             color_theme: self.color_theme,
+            zone_order: self.zone_order,
             show_date: self.show_date,
             show_sun_times: self.show_sun_times,
+            focus_mode: self.focus_mode,
+            focus_ruler: self.focus_ruler,
+            align_to: self.align_to.clone(),
+            privacy_mode: self.privacy_mode,
+            layouts: self.layouts.clone(),
+            archived: self.archived.clone(),
+            milestones: self.milestones.clone(),
+            split_view: self.split_view,
+            split_layout_index: self.split_layout_index,
+            timeline_position: None,
+            jumps: None,
+        }
+    }
+
+    /// The snapshot the session file keeps, with where the scrubber is and
+    /// the positions jumped from
+    pub fn session_snapshot(&self) -> Snapshot {
+        Snapshot {
+            timeline_position: Some(self.timeline_position),
+            jumps: Some(self.jumps.clone()),
+            ..self.snapshot()
+        }
+    }
+
+    /// Puts the board back as a snapshot had it, without saving it, as
+    /// `alltz --resume` does
+    pub fn restore(&mut self, snapshot: Snapshot) {
+        self.timezone_manager = Self::manager_from_zone_configs(&snapshot.zones);
+        if self.timezone_manager.zones().is_empty() {
+            self.timezone_manager = TimeZoneManager::with_default_zones();
+        }
+        self.selected_zone_index = snapshot
+            .selected_zone_index
+            .min(self.timezone_manager.zone_count().saturating_sub(1));
+        self.marked_zones = snapshot.marked_zones;
+        self.display_format = snapshot.display_format;
+        self.timezone_display_mode = snapshot.timezone_display_mode;
+        self.time_config = snapshot.time_config;
+        self.color_theme = snapshot.color_theme;
+        self.zone_order = snapshot.zone_order;
+        self.show_date = snapshot.show_date;
+        self.show_sun_times = snapshot.show_sun_times;
+        self.focus_mode = snapshot.focus_mode;
+        self.focus_ruler = snapshot.focus_ruler;
+        self.align_to = snapshot.align_to;
+        self.privacy_mode = snapshot.privacy_mode;
+        self.layouts = snapshot.layouts;
+        self.archived = snapshot.archived;
+        self.milestones = snapshot.milestones;
+        self.split_layout_index = snapshot
+            .split_layout_index
+            .min(self.layouts.len().saturating_sub(1));
+        self.split_view = snapshot.split_view && !self.layouts.is_empty();
+        if !self.split_view {
+            self.split_pane_focused = false;
+        }
+        if let Some(position) = snapshot.timeline_position {
+            self.timeline_position = position;
+        }
+        if let Some(jumps) = snapshot.jumps {
+            self.jumps = jumps.resumed();
+        }
+        if self.split_view {
+            self.load_split_layout();
+        } else {
+            self.apply_zone_order();
+        }
+    }
+
+    /// Writes the board, and where the scrubber is, for `alltz --resume`
+    fn save_session(&self) {
+        if self.ephemeral {
+            return;
+        }
+        let Some(path) = session::session_path() else {
+            return;
+        };
+        if let Err(e) = session::save(&path, &self.session_snapshot()) {
+            crate::log::write(&format!("Couldn't save the session: {e}"));
         }
     }
 
     pub fn save_config(&self) {
+        if self.ephemeral {
+            return;
+        }
         let config = self.to_config();
         if let Err(e) = config.save() {
-            // In a real app, you might want to show an error message to the user
-            eprintln!("Failed to save config: {e}");
+            crate::log::write(&format!("Failed to save config: {e}"));
         }
     }
 
-    fn select_local_timezone(&mut self) {
-        let local_time = self.current_time.with_timezone(&Local);
-        let local_offset_seconds = local_time.offset().fix().local_minus_utc();
-        let local_offset_hours = local_offset_seconds / 3600;
+    /// Adds a zone by name, logging it to the history when it's new
+    fn add_zone(&mut self, name: &str) -> bool {
+        let added = self.timezone_manager.add_timezone_by_name(name);
+        if let Some(zone) = self.timezone_manager.zones().last().filter(|_| added) {
+            self.record_history(HistoryEvent::ZoneAdded {
+                city: zone.get_city_name(),
+            });
+        }
+        added
+    }
 
-        // Find the timezone that matches our local offset
-        for (index, zone) in self.timezone_manager.zones().iter().enumerate() {
-            let zone_offset_hours = zone.utc_offset_hours();
-            if zone_offset_hours == local_offset_hours {
-                self.selected_zone_index = index;
-                break;
+    /// Sets the board up the way a command run with `--open` left it. Zones
+    /// not already on the board are added for this session only, and the
+    /// last one is selected.
+    pub fn preload(&mut self, preload: Preload) {
+        for city in &preload.zones {
+            let on_board = self
+                .timezone_manager
+                .zones()
+                .iter()
+                .any(|zone| zone.answers_to(city));
+            if !on_board && self.timezone_manager.add_timezone_by_name(city) {
+                self.temporary_zones.push(city.clone());
             }
         }
+        self.apply_zone_order();
+        if let Some(index) = preload.zones.last().and_then(|city| {
+            self.timezone_manager
+                .zones()
+                .iter()
+                .position(|zone| zone.answers_to(city))
+        }) {
+            self.selected_zone_index = index;
+        }
+        if let Some(at) = preload.at {
+            self.timeline_position = at;
+        }
+        self.show_matrix |= preload.matrix;
     }
 
-    pub fn update(&mut self, msg: Message) -> Option<Message> {
-        match msg {
-            Message::Tick => {
-                self.current_time = Utc::now();
-                None
-            }
+    /// Indexes of the zones in the pane with focus a bulk action applies
+    /// to: the marked ones, or the selected zone when none are marked
+    fn bulk_targets(&self) -> Vec<usize> {
+        let (manager, selected) = self.focused_pane();
+        let zones = manager.zones();
+        if self.marked_zones.is_empty() {
+            return (selected < zones.len())
+                .then_some(selected)
+                .into_iter()
+                .collect();
+        }
+        (0..zones.len())
+            .filter(|&index| self.is_marked(&zones[index]))
+            .collect()
+    }
 
-            Message::ScrubTimeline(direction) => {
-                // Round to the next/previous hour boundary
-                let rounded_time = match direction {
-                    Direction::Left => {
-                        // Go to the start of the current hour, or previous hour if already at start
-                        if self.timeline_position.minute() == 0
-                            && self.timeline_position.second() == 0
-                            && self.timeline_position.nanosecond() == 0
-                        {
-                            // Already at hour boundary, go to previous hour
-                            self.timeline_position - chrono::Duration::hours(1)
-                        } else {
-                            // Go to start of current hour
-                            self.timeline_position
-                                .with_minute(0)
-                                .unwrap_or(self.timeline_position)
-                                .with_second(0)
-                                .unwrap_or(self.timeline_position)
-                                .with_nanosecond(0)
-                                .unwrap_or(self.timeline_position)
-                        }
-                    }
-                    Direction::Right => {
-                        // Go to the start of the next hour
-                        self.timeline_position
-                            .with_minute(0)
-                            .unwrap_or(self.timeline_position)
-                            .with_second(0)
-                            .unwrap_or(self.timeline_position)
-                            .with_nanosecond(0)
-                            .unwrap_or(self.timeline_position)
-                            + chrono::Duration::hours(1)
-                    }
-                    _ => self.timeline_position,
-                };
-                self.timeline_position = rounded_time;
-                None
-            }
+    fn is_marked(&self, zone: &TimeZone) -> bool {
+        self.marked_zones.contains(&zone.get_city_name())
+    }
 
-            Message::ScrubTimelineWithShift(direction) => {
-                // Fine scrub by 1 minute when shift is held
-                let delta = match direction {
-                    Direction::Left => chrono::Duration::minutes(-1),
-                    Direction::Right => chrono::Duration::minutes(1),
-                    _ => chrono::Duration::zero(),
-                };
-                self.timeline_position += delta;
-                None
+    /// Adds the bulk action's zones to the layout called `name`, creating it
+    /// if there isn't one. Zones already in it are left as they are.
+    fn assign_group(&mut self, name: &str) {
+        let entries: Vec<ZoneConfigCompat> = self
+            .bulk_targets()
+            .into_iter()
+            .map(|index| ZoneConfigCompat::from(&self.focused_pane().0.zones()[index]))
+            .collect();
+        let index = match self
+            .layouts
+            .iter()
+            .position(|layout| layout.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => index,
+            None => {
+                self.layouts.push(LayoutConfig {
+                    name: name.to_string(),
+                    zones: Vec::new(),
+                    theme: None,
+                });
+                self.layouts.len() - 1
             }
-
-            Message::ResetToNow => {
-                self.timeline_position = self.current_time;
-                None
+        };
+        let layout = &mut self.layouts[index];
+        for entry in entries {
+            if !layout
+                .zones
+                .iter()
+                .any(|zone| zone.city_name() == entry.city_name())
+            {
+                layout.zones.push(entry);
             }
+        }
+        if self.split_view && self.split_layout_index == index {
+            self.load_split_layout();
+        }
+    }
 
-            Message::FineAdjust(minutes) => {
-                let delta = chrono::Duration::minutes(minutes as i64);
-                self.timeline_position += delta;
-                None
-            }
+    /// Puts an archived zone back on the board with its label and accent, and
+    /// selects it. A zone that's been added again since gets them back.
+    fn restore_zone(&mut self, entry: &ZoneConfigCompat) {
+        let position = |manager: &TimeZoneManager| {
+            manager
+                .zones()
+                .iter()
+                .position(|zone| zone.get_city_name() == entry.city_name())
+        };
+        if position(&self.timezone_manager).is_none() {
+            self.timezone_manager
+                .add_timezone_by_name(entry.city_name());
+        }
+        // Zones are kept in offset order, so look for where it went
+        let Some(index) = position(&self.timezone_manager) else {
+            return;
+        };
+        self.timezone_manager.apply(index, entry);
+        self.selected_zone_index = index;
+    }
 
-            Message::NavigateZone(direction) => {
-                let zone_count = self.timezone_manager.zone_count();
-                if zone_count > 0 {
-                    let old_index = self.selected_zone_index;
-                    match direction {
-                        Direction::Up => {
-                            if self.selected_zone_index > 0 {
-                                self.selected_zone_index -= 1;
-                            }
-                        }
-                        Direction::Down => {
-                            if self.selected_zone_index < zone_count - 1 {
-                                self.selected_zone_index += 1;
-                            }
-                        }
-                        _ => {}
-                    }
-                    // Save config if selection changed
-                    if old_index != self.selected_zone_index {
-                        self.save_config();
-                    }
-                }
-                None
-            }
+    /// The zones in the pane with focus, and the index of its selected one
+    fn focused_pane(&self) -> (&TimeZoneManager, usize) {
+        if self.split_view && self.split_pane_focused {
+            (&self.split_timezone_manager, self.split_selected_zone_index)
+        } else {
+            (&self.timezone_manager, self.selected_zone_index)
+        }
+    }
 
-            Message::ToggleTimeFormat => {
-                self.display_format = match self.display_format {
-                    TimeFormat::TwentyFourHour => TimeFormat::TwelveHour,
-                    TimeFormat::TwelveHour => TimeFormat::TwentyFourHour,
-                };
-                self.save_config();
-                None
-            }
+    fn focused_pane_mut(&mut self) -> (&mut TimeZoneManager, &mut usize) {
+        if self.split_view && self.split_pane_focused {
+            (
+                &mut self.split_timezone_manager,
+                &mut self.split_selected_zone_index,
+            )
+        } else {
+            (&mut self.timezone_manager, &mut self.selected_zone_index)
+        }
+    }
 
-            Message::ToggleTimezoneDisplayMode => {
-                self.timezone_display_mode = match self.timezone_display_mode {
-                    TimezoneDisplayMode::Short => TimezoneDisplayMode::Full,
-                    TimezoneDisplayMode::Full => TimezoneDisplayMode::Short,
-                };
-                self.save_config();
-                None
-            }
-
-            Message::ToggleDate => {
-                self.show_date = !self.show_date;
-                self.save_config();
-                None
-            }
+    /// The selected zone in the pane with focus
+    fn focused_zone(&self) -> Option<&TimeZone> {
+        let (manager, index) = self.focused_pane();
+        manager.zones().get(index)
+    }
 
-            Message::ToggleSunTimes => {
-                self.show_sun_times = !self.show_sun_times;
-                self.save_config();
-                None
-            }
+    /// Saves the pane with focus, to the layout shown for the split pane or
+    /// else the board
+    fn save_focused(&mut self) {
+        if self.split_view && self.split_pane_focused {
+            self.save_split_layout();
+        } else {
+            self.save_board();
+        }
+    }
 
-            Message::CycleColorTheme => {
-                self.color_theme = self.color_theme.next();
-                self.save_config();
-                None
+    /// Adds a zone to the pane with focus and saves it, to the layout shown
+    /// for the split pane or else the board
+    fn add_to_focused(&mut self, name: &str) {
+        if self.split_view && self.split_pane_focused {
+            if self.split_timezone_manager.add_timezone_by_name(name) {
+                self.save_split_layout();
             }
-
-            Message::ToggleHelp => {
-                self.show_help = !self.show_help;
-                None
+        } else if self.add_zone(name) {
+            // Update selected index if needed
+            if self.selected_zone_index >= self.timezone_manager.zone_count() {
+                self.selected_zone_index = self.timezone_manager.zone_count().saturating_sub(1);
             }
+            self.save_board();
+        }
+    }
 
-            Message::StartAddZone => {
-                // Clear other modal states
-                self.renaming_zone = false;
-                self.rename_zone_input.clear();
+    /// Labels the selected zone in the pane with focus and saves it, to the
+    /// layout shown for the split pane or else the board
+    fn relabel_focused(&mut self, custom_label: Option<String>) {
+        if self.split_view && self.split_pane_focused {
+            self.split_timezone_manager
+                .update_zone_label(self.split_selected_zone_index, custom_label);
+            self.save_split_layout();
+        } else if self.focused_zone().is_some() {
+            self.set_selected_label(custom_label);
+            self.save_board();
+        }
+    }
 
-                self.adding_zone = true;
-                self.add_zone_input.clear();
-                self.zone_search_results.clear();
-                self.selected_search_result = 0;
-                None
-            }
+    /// Writes the split pane's zones back to the layout it shows
+    fn save_split_layout(&mut self) {
+        if let Some(layout) = self.layouts.get_mut(self.split_layout_index) {
+            layout.zones = self
+                .split_timezone_manager
+                .zones()
+                .iter()
+                .map(ZoneConfigCompat::from)
+                .collect();
+            self.save_config();
+        }
+    }
 
-            Message::UpdateAddZoneInput(input) => {
-                self.add_zone_input = input.clone();
-                self.zone_search_results = crate::time::TimeZoneManager::search_timezones(&input);
-                self.selected_search_result = 0; // Reset selection when search changes
-                None
-            }
+    /// Sets or clears the selected zone's label, logging the change
+    fn set_selected_label(&mut self, custom_label: Option<String>) {
+        let Some(zone) = self.timezone_manager.zones().get(self.selected_zone_index) else {
+            return;
+        };
+        let event = HistoryEvent::LabelChanged {
+            city: zone.get_city_name(),
+            from: zone.display.label.clone(),
+            to: custom_label.clone(),
+        };
+        let changed = zone.display.label != custom_label;
+        self.timezone_manager
+            .update_zone_label(self.selected_zone_index, custom_label);
+        if changed {
+            self.record_history(event);
+        }
+    }
 
-            Message::NavigateSearchResults(direction) => {
-                if !self.zone_search_results.is_empty() {
-                    match direction {
-                        Direction::Up => {
-                            if self.selected_search_result > 0 {
-                                self.selected_search_result -= 1;
-                            }
-                        }
-                        Direction::Down => {
-                            if self.selected_search_result < self.zone_search_results.len() - 1 {
-                                self.selected_search_result += 1;
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-                None
-            }
+    /// Saves a change to the zone roster and shares it with the team board
+    fn save_board(&mut self) {
+        self.save_config();
+        self.sync_board();
+    }
 
-            Message::SelectSearchResult(index) => {
-                if let Some(zone_name) = self.zone_search_results.get(index) {
-                    let success = self.timezone_manager.add_timezone_by_name(zone_name);
-
-                    if success {
-                        // Update selected index if needed
-                        if self.selected_zone_index >= self.timezone_manager.zone_count() {
-                            self.selected_zone_index =
-                                self.timezone_manager.zone_count().saturating_sub(1);
-                        }
-                        self.save_config();
-                    }
-                }
-                self.adding_zone = false;
-                self.add_zone_input.clear();
-                self.zone_search_results.clear();
-                None
-            }
+    /// Records local board changes and starts a background exchange with
+    /// the shared team board, if sync is set up
+    #[cfg(feature = "sync")]
+    pub fn sync_board(&mut self) {
+        let Some(board_sync) = self.sync.as_ref().and_then(BoardSync::from_config) else {
+            return;
+        };
+        let zones = self.to_config().zones;
+        if let Err(e) = board_sync.record(&zones) {
+            self.sync_error = Some(e.to_string());
+        }
+        match &mut self.sync_task {
+            Some(task) => task.rerun = true,
+            None => self.sync_task = Some(board_sync.spawn(zones)),
+        }
+    }
 
-            Message::ConfirmAddZone => {
-                if !self.zone_search_results.is_empty() {
-                    // Use the currently selected search result
-                    if let Some(zone_name) =
-                        self.zone_search_results.get(self.selected_search_result)
-                    {
-                        let success = self.timezone_manager.add_timezone_by_name(zone_name);
+    /// Applies the result of a finished background sync to the board
+    #[cfg(feature = "sync")]
+    pub fn poll_sync(&mut self) {
+        let Some(result) = self.sync_task.as_ref().and_then(SyncTask::try_result) else {
+            return;
+        };
+        let rerun = self.sync_task.take().is_some_and(|task| task.rerun);
+        let Some(board_sync) = self.sync.as_ref().and_then(BoardSync::from_config) else {
+            return;
+        };
 
-                        if success {
-                            // Update selected index if needed
-                            if self.selected_zone_index >= self.timezone_manager.zone_count() {
-                                self.selected_zone_index =
-                                    self.timezone_manager.zone_count().saturating_sub(1);
-                            }
-                            self.save_config();
-                        }
-                    }
-                } else if !self.add_zone_input.is_empty() {
-                    // Try to add the exact input if no search results
-                    let success = self
-                        .timezone_manager
-                        .add_timezone_by_name(&self.add_zone_input);
-
-                    if success {
-                        // Update selected index if needed
-                        if self.selected_zone_index >= self.timezone_manager.zone_count() {
-                            self.selected_zone_index =
-                                self.timezone_manager.zone_count().saturating_sub(1);
-                        }
-                        self.save_config();
-                    }
+        let local = self.to_config().zones;
+        match result.and_then(|board| board_sync.apply(&board)) {
+            Ok(board) => {
+                self.sync_error = None;
+                if let Some(presence) = &self.presence {
+                    self.ghosts = board
+                        .others(&presence.name, Utc::now())
+                        .into_iter()
+                        .map(|cursor| Ghost {
+                            name: cursor.name.clone(),
+                            position: cursor.position,
+                        })
+                        .collect();
                 }
-                self.adding_zone = false;
-                self.add_zone_input.clear();
-                self.zone_search_results.clear();
-                self.selected_search_result = 0;
-                None
-            }
-
-            Message::CancelAddZone => {
-                self.adding_zone = false;
-                self.add_zone_input.clear();
-                self.zone_search_results.clear();
-                self.selected_search_result = 0;
-                None
-            }
-
-            Message::RemoveCurrentZone => {
-                if self.timezone_manager.zone_count() > 1 {
-                    // Keep at least one zone
-                    self.timezone_manager.remove_zone(self.selected_zone_index);
-
-                    // Adjust selected index if needed
-                    if self.selected_zone_index >= self.timezone_manager.zone_count() {
-                        self.selected_zone_index =
-                            self.timezone_manager.zone_count().saturating_sub(1);
-                    }
+                let zones = board.zones();
+                if zones != local {
+                    self.timezone_manager = Self::manager_from_zone_configs(&zones);
+                    self.selected_zone_index = self
+                        .selected_zone_index
+                        .min(self.timezone_manager.zone_count().saturating_sub(1));
                     self.save_config();
                 }
-                None
             }
+            Err(e) => self.sync_error = Some(e.to_string()),
+        }
 
-            Message::StartRenameZone => {
-                if self.timezone_manager.zone_count() > 0 {
-                    // Clear other modal states
-                    self.adding_zone = false;
-                    self.add_zone_input.clear();
-                    self.zone_search_results.clear();
+        // Changes made while the exchange ran still need pushing
+        if rerun {
+            self.sync_board();
+        }
+    }
 
-                    self.renaming_zone = true;
-                    // Pre-fill with current custom label or empty
-                    self.rename_zone_input = self.timezone_manager.zones()
-                        [self.selected_zone_index]
-                        .custom_label
-                        .clone()
-                        .unwrap_or_default();
-                }
-                None
-            }
+    /// Shares where this board is scrubbed to, and fetches where teammates
+    /// are, every `every_seconds` while presence is set up
+    #[cfg(feature = "sync")]
+    pub fn share_cursor(&mut self) {
+        let Some(presence) = &self.presence else {
+            return;
+        };
+        let every = std::time::Duration::from_secs(presence.every_seconds);
+        if self.sync_task.is_some() || self.presence_shared.is_some_and(|at| at.elapsed() < every) {
+            return;
+        }
+        let Some(board_sync) = self
+            .sync
+            .as_ref()
+            .and_then(BoardSync::from_config)
+            .filter(BoardSync::joined)
+        else {
+            return;
+        };
+        self.presence_shared = Some(std::time::Instant::now());
+        self.sync_task =
+            Some(board_sync.spawn_cursor(presence.name.clone(), self.timeline_position));
+    }
 
-            Message::UpdateRenameInput(input) => {
-                self.rename_zone_input = input;
-                None
-            }
+    #[cfg(not(feature = "sync"))]
+    pub fn sync_board(&mut self) {}
 
-            Message::ConfirmRename => {
-                if self.timezone_manager.zone_count() > 0 {
-                    let custom_label = if self.rename_zone_input.trim().is_empty() {
-                        None
-                    } else {
-                        Some(self.rename_zone_input.trim().to_string())
-                    };
-                    self.timezone_manager
-                        .update_zone_label(self.selected_zone_index, custom_label);
-                    self.save_config();
-                }
-                self.renaming_zone = false;
-                self.rename_zone_input.clear();
-                None
-            }
+    #[cfg(not(feature = "sync"))]
+    pub fn share_cursor(&mut self) {}
 
-            Message::CancelRename => {
-                self.renaming_zone = false;
-                self.rename_zone_input.clear();
-                None
+    #[cfg(not(feature = "sync"))]
+    pub fn poll_sync(&mut self) {}
+
+    /// Fires the reminders in the file at `path` that are due, taking them
+    /// out of it. The file is read again whenever it changes, so reminders
+    /// added with `alltz remind` while the board is open are picked up.
+    pub fn fire_reminders(&mut self, path: &std::path::Path) {
+        let due = match self.reminders.take_due(path, self.current_time) {
+            Ok(due) => due,
+            Err(e) => {
+                crate::log::write(&format!("Failed to take due reminders: {e}"));
+                return;
             }
+        };
+        if due.is_empty() {
+            return;
+        }
+        for reminder in &due {
+            self.raise(Alarm {
+                kind: AlarmKind::Reminder,
+                title: "alltz reminder".to_string(),
+                text: format!("{} ({})", reminder.text, reminder.local_time()),
+                at: reminder.at,
+            });
+        }
+        self.fired_reminders = Some((due, std::time::Instant::now() + REMINDER_NOTICE));
+    }
 
-            Message::ClearCustomName => {
-                if self.timezone_manager.zone_count() > 0 {
-                    self.timezone_manager
-                        .update_zone_label(self.selected_zone_index, None);
-                    self.save_config();
-                }
-                None
+    /// Raises an alarm for each milestone whose time came after `since`
+    pub fn fire_milestones(&mut self, since: DateTime<Utc>) {
+        let arrived: Vec<Alarm> = self
+            .milestones
+            .iter()
+            .filter(|milestone| since < milestone.time && milestone.time <= self.current_time)
+            .map(|milestone| Alarm {
+                kind: AlarmKind::Milestone,
+                title: "alltz milestone".to_string(),
+                text: milestone.name.clone(),
+                at: milestone.time,
+            })
+            .collect();
+        for alarm in arrived {
+            self.raise(alarm);
+        }
+    }
+
+    /// Sends an alarm to the backends configured for its kind. The bell is
+    /// left for the event loop to ring between frames, so it can't land in
+    /// the middle of one.
+    fn raise(&mut self, alarm: Alarm) {
+        let config = self.notify.clone().unwrap_or_default();
+        for &backend in notify::backends(&config, alarm.kind) {
+            if backend == NotifyBackend::Bell {
+                self.bell = true;
+                continue;
             }
+            let Some(notifier) = notify::notifier(backend, &config) else {
+                crate::log::write(&format!(
+                    "Alarm not sent to {backend:?}: its URL or command is not set"
+                ));
+                continue;
+            };
+            notify::send(notifier, alarm.clone());
+        }
+    }
 
-            Message::Quit => {
-                self.should_quit = true;
-                None
+    /// Cities matching a zone search. When the input is a zone abbreviation
+    /// like CST, one city for each zone it could mean comes first, led by
+    /// the zone it's known to mean.
+    fn search_zones(&self, input: &str) -> Vec<String> {
+        let input = input.trim();
+        let mut cities = abbrev::cities_for(input, self.current_time);
+        if let Some(preferred) = abbrev::resolve(input, &self.abbreviations, self.current_time) {
+            cities.sort_by_key(|city| city.timezone != preferred.name());
+        }
+        let mut results: Vec<String> = cities
+            .iter()
+            .map(|city| format!("{}, {}", city.name, city.country))
+            .collect();
+        for result in TimeZoneManager::search_timezones(input) {
+            if !results.contains(&result) {
+                results.push(result);
             }
         }
+        results.truncate(8);
+        results
     }
 
-    pub fn view(&self, f: &mut Frame) {
-        let chunks = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Length(3), // Header
-                Constraint::Length(4), // Current time display (taller)
-                Constraint::Min(1),    // Main content
-                Constraint::Length(2), // Legend
-                Constraint::Length(3), // Footer
-            ])
-            .split(f.area());
+    /// Counts picking `city` from a search for an abbreviation
+    fn note_abbreviation_pick(&mut self, city: &str) {
+        if let Some(path) = abbrev::picks_path().filter(|_| !self.ephemeral) {
+            let abbreviation = self.add_zone_input.trim().to_string();
+            self.learn_abbreviation(&path, &abbreviation, city);
+        }
+    }
 
-        self.render_header(f, chunks[0]);
-        self.render_current_time_display(f, chunks[1]);
-        self.render_zones(f, chunks[2]);
-        self.render_legend(f, chunks[3]);
-        self.render_footer(f, chunks[4]);
+    /// Counts resolving `abbreviation` to `city`'s zone in the picks file at
+    /// `path`, and once it's been picked enough times in a row remembers
+    /// that zone as what the abbreviation means
+    pub fn learn_abbreviation(&mut self, path: &std::path::Path, abbreviation: &str, city: &str) {
+        if abbrev::cities_for(abbreviation, self.current_time).is_empty() {
+            return;
+        }
+        let Some(zone) = TimeZoneManager::find_city(city).and_then(|c| c.timezone.parse().ok())
+        else {
+            return;
+        };
+        let mut picks = abbrev::load_picks(path);
+        if abbrev::record_pick(&mut picks, abbreviation, zone) {
+            self.abbreviations
+                .insert(abbreviation.to_uppercase(), zone.name().to_string());
+            self.save_config();
+        }
+        if let Err(e) = abbrev::save_picks(path, &picks) {
+            crate::log::write(&format!("Failed to save abbreviation picks: {e}"));
+        }
+    }
 
-        // Render modals on top if needed
-        if self.show_help {
-            self.render_help_modal(f);
-        } else if self.adding_zone {
-            self.render_add_zone_modal(f);
-        } else if self.renaming_zone {
-            self.render_rename_zone_modal(f);
+    fn record_history(&self, event: HistoryEvent) {
+        if self.ephemeral {
+            return;
+        }
+        if let Err(e) = history::record(event) {
+            crate::log::write(&format!("Failed to write history: {e}"));
         }
     }
 
-    fn render_header(&self, f: &mut Frame, area: Rect) {
-        let local_time = self.current_time.with_timezone(&Local);
+    /// Appends a key to the macro being recorded, if any
+    pub fn record_macro_key(&mut self, key: String) {
+        if let Some((_, keys)) = &mut self.recording_macro {
+            keys.push(key);
+        }
+    }
 
-        // Use chrono-tz names for better abbreviations, fall back to %Z
-        let tz_name = self.get_local_timezone_name();
-        let local_time_str = match self.display_format {
-            TimeFormat::TwentyFourHour => format!("{} {}", local_time.format("%H:%M:%S"), tz_name),
-            TimeFormat::TwelveHour => format!("{} {}", local_time.format("%I:%M:%S %p"), tz_name),
-        };
+    pub fn is_animating(&self) -> bool {
+        self.scrub_animation.is_some()
+    }
 
-        let timeline_time_str = match self.display_format {
-            TimeFormat::TwentyFourHour => self.timeline_position.format("%H:%M UTC").to_string(),
-            TimeFormat::TwelveHour => self.timeline_position.format("%I:%M %p UTC").to_string(),
-        };
+    /// How soon a clock change must be to count down to in zone titles, at
+    /// most a year ahead
+    fn dst_countdown_window(&self) -> Option<chrono::Duration> {
+        (self.dst_countdown_days > 0).then(|| {
+            chrono::Duration::days(self.dst_countdown_days.min(MAX_DST_COUNTDOWN_DAYS) as i64)
+        })
+    }
 
-        // Create inner area for content
-        let inner = area.inner(ratatui::layout::Margin {
-            horizontal: 1,
-            vertical: 1,
-        });
+    /// How soon a milestone must be to stand out, if at all
+    fn imminent_window(&self) -> Option<chrono::Duration> {
+        (self.imminent_minutes > 0).then(|| chrono::Duration::minutes(self.imminent_minutes as i64))
+    }
 
-        let chunks = Layout::default()
-            .direction(LayoutDirection::Horizontal)
-            .constraints([
-                Constraint::Percentage(33), // App name (left)
-                Constraint::Percentage(34), // Local time (center)
-                Constraint::Percentage(33), // Timeline (right)
-            ])
-            .split(inner);
+    /// Each of the next seven days' work-hour overlap between `zone` and
+    /// the local zone, as a fraction of a full working day, with the zone's
+    /// own or seasonal hours where it has them. Days the zone's people are
+    /// away have none.
+    fn overlap_week(&self, zone: &TimeZone) -> [f32; 7] {
+        let today = self.current_time.with_timezone(&Local).date_naive();
+        let hours = (
+            self.time_config.work_hours_start,
+            self.time_config.work_hours_end,
+        );
+        let work_day = chrono::Duration::hours(crate::time::work_length(hours).max(1) as i64);
+        std::array::from_fn(|day| {
+            let date = today + chrono::Duration::days(day as i64);
+            if self.away.iter().any(|entry| entry.covers(zone, date)) {
+                return 0.0;
+            }
+            let zone_hours = zone.hours_on(&self.time_config, date);
+            let zone_hours = (zone_hours.work_hours_start, zone_hours.work_hours_end);
+            let overlap = crate::time::work_overlap(&Local, zone.tz, date, hours, zone_hours);
+            overlap.num_minutes() as f32 / work_day.num_minutes() as f32
+        })
+    }
 
-        // Left: App name
-        let app_name = Paragraph::new(format!("alltz v{}", env!("CARGO_PKG_VERSION")))
-            .alignment(Alignment::Left);
-        f.render_widget(app_name, chunks[0]);
+    /// Whether imminent milestones pulse: only when asked for, and never
+    /// with reduced motion or over slow links
+    fn pulses(&self) -> bool {
+        self.pulse_imminent && !self.reduced_motion && !self.low_bandwidth
+    }
 
-        // Center: Local time
-        let local_display =
-            Paragraph::new(format!("Local: {local_time_str}")).alignment(Alignment::Center);
-        f.render_widget(local_display, chunks[1]);
+    /// This machine's zone: the configured home, or else the zone the
+    /// system is set to. Where neither is known, the first board zone
+    /// keeping the same time stands in.
+    fn home_tz(&self) -> Option<chrono_tz::Tz> {
+        let zones = self.timezone_manager.zones();
+        let named = self
+            .home
+            .as_deref()
+            .and_then(|name| crate::time::resolve_zone(name, zones))
+            .or_else(|| {
+                crate::platform::Platform::current()
+                    .system_zone()?
+                    .parse()
+                    .ok()
+            });
+        named.or_else(|| {
+            let local = self.current_time.with_timezone(&Local).offset().fix();
+            zones
+                .iter()
+                .find(|zone| {
+                    zone.offset_minutes_at(self.current_time) * 60 == local.local_minus_utc()
+                })
+                .map(|zone| zone.tz)
+        })
+    }
 
-        // Right: Timeline time
-        let timeline_display =
-            Paragraph::new(format!("Timeline: {timeline_time_str}")).alignment(Alignment::Right);
-        f.render_widget(timeline_display, chunks[2]);
+    /// Adds the out-of-office days in the configured calendars to the days
+    /// away. Left out of `from_config` so commands that don't need them
+    /// don't pay for reading the files.
+    pub fn read_calendars(&mut self) {
+        self.away.extend(ics::calendar_blocked(&self.calendars));
+    }
 
-        let border = Block::default().borders(Borders::ALL);
-        f.render_widget(border, area);
+    /// Reads due dates from the configured task lists, which can mean
+    /// running taskwarrior, for the home zone's bar
+    pub fn load_tasks(&mut self) {
+        let Some(config) = &self.tasks else {
+            return;
+        };
+        self.due_tasks = tasks::load(config);
+        self.tasks_tz = self.home_tz();
     }
 
-    fn get_local_timezone_name(&self) -> String {
-        // Try to get a better timezone name from our configured zones
-        let local_time = self.current_time.with_timezone(&Local);
-        let local_offset_hours = local_time.offset().fix().local_minus_utc() / 3600;
+    /// Checks this machine's zone and clock against the configured home
+    /// zone, the `TZ` variable and when alltz's own files were last written,
+    /// keeping what's wrong for the banner. The time server, if there is
+    /// one, is asked in the background.
+    pub fn check_clock(&mut self) {
+        let now = Utc::now();
+        let local_offset = now.with_timezone(&Local).offset().fix().local_minus_utc() / 60;
+        let home = self.home.as_deref().and_then(|name| {
+            crate::time::resolve_zone(name, self.timezone_manager.zones()).map(|tz| (name, tz))
+        });
+        let modified: Vec<_> = [
+            AppConfig::config_path(),
+            history::history_path(),
+            remind::reminders_path(),
+            std::env::current_exe().ok(),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|path| {
+            let at = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((path, DateTime::<Utc>::from(at)))
+        })
+        .collect();
+        let warnings = health::check(
+            now,
+            local_offset,
+            home,
+            crate::platform::env_var("TZ").as_deref(),
+            crate::platform::Platform::current()
+                .system_zone()
+                .as_deref(),
+            &modified,
+        );
+        self.clock_warnings = warnings.iter().map(ToString::to_string).collect();
+        self.clock_task = self.ntp_server.as_deref().map(health::ServerCheck::spawn);
+    }
 
-        // Look for a matching timezone in our list to get a better abbreviation
-        for zone in self.timezone_manager.zones() {
-            if zone.utc_offset_hours() == local_offset_hours {
-                return zone.get_timezone_abbreviation();
+    /// Takes the answer of a finished background time server check, adding
+    /// a warning if the clock is off
+    pub fn poll_clock(&mut self) {
+        let Some(task) = &self.clock_task else {
+            return;
+        };
+        let Some(result) = task.try_result() else {
+            return;
+        };
+        let server = task.server.clone();
+        self.clock_task = None;
+        match result {
+            Ok(offset) if offset.abs() > health::TOLERANCE => {
+                let warning = health::ClockWarning::ServerOffset { server, offset };
+                self.clock_warnings.push(warning.to_string());
             }
+            Ok(_) => {}
+            Err(e) => crate::log::write(&format!("Failed to reach {server}: {e}")),
         }
+    }
 
-        // Fallback: use system timezone name
-        let tz_str = local_time.format("%Z").to_string();
-        if tz_str.starts_with('+') || tz_str.starts_with('-') {
-            // If it's still showing offset, try a different approach
-            format!(
-                "UTC{}",
-                if local_offset_hours >= 0 {
-                    format!("+{local_offset_hours}")
-                } else {
-                    local_offset_hours.to_string()
-                }
-            )
-        } else {
-            tz_str
+    /// Looks for a newer release if the check is turned on, trusting the
+    /// last answer for a day and otherwise asking in the background
+    pub fn check_for_update(&mut self) {
+        if !self.update_check || self.ephemeral {
+            return;
+        }
+        match update::state_path().and_then(|path| update::load(&path)) {
+            Some(last) if Utc::now() - last.at < update::CHECK_EVERY => {
+                self.update_notice = update::notice(&last.latest);
+            }
+            _ => self.update_task = Some(UpdateCheck::spawn()),
         }
     }
 
-    fn render_zones(&self, f: &mut Frame, area: Rect) {
-        let zones = self.timezone_manager.zones();
-
-        if zones.is_empty() {
-            let empty_msg = Paragraph::new("No timezones configured")
-                .block(Block::default().borders(Borders::ALL).title("Timezones"));
-            f.render_widget(empty_msg, area);
+    /// Takes the answer of a finished background update check
+    pub fn poll_update(&mut self) {
+        let Some(result) = self.update_task.as_ref().and_then(UpdateCheck::try_result) else {
             return;
+        };
+        self.update_task = None;
+        match result {
+            Ok(latest) => {
+                self.update_notice = update::notice(&latest);
+                let last = update::LastCheck {
+                    at: Utc::now(),
+                    latest,
+                };
+                if let Some(Err(e)) = update::state_path().map(|path| update::save(&path, &last)) {
+                    crate::log::write(&format!("Failed to save update check: {e}"));
+                }
+            }
+            Err(e) => crate::log::write(&format!("Update check failed: {e}")),
         }
+    }
 
-        let zone_constraints = zones
+    /// The zones as they stand at the scrub line, with anyone who has moved
+    /// by then drawn in their new city
+    fn zones_at_scrub<'z>(&self, zones: &'z [TimeZone]) -> Vec<Cow<'z, TimeZone>> {
+        zones
             .iter()
-            .map(|_| Constraint::Length(4))
-            .collect::<Vec<_>>();
+            .map(|zone| zone.as_of(self.timeline_position))
+            .collect()
+    }
 
-        let zone_chunks = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints(zone_constraints)
-            .split(area);
+    /// The zone whose midnight starts the window, if the board is aligned
+    fn aligned_tz(&self) -> Option<chrono_tz::Tz> {
+        let name = self.align_to.as_deref()?;
+        crate::time::resolve_zone(name, self.timezone_manager.zones())
+    }
 
-        for (i, zone) in zones.iter().enumerate() {
-            if i < zone_chunks.len() {
-                self.render_zone(f, zone_chunks[i], zone, i == self.selected_zone_index);
-            }
-        }
+    /// The local date at the scrub line, which zone dates are compared to
+    fn home_date(&self) -> chrono::NaiveDate {
+        self.timeline_position.with_timezone(&Local).date_naive()
     }
 
-    fn render_zone(&self, f: &mut Frame, area: Rect, zone: &TimeZone, is_selected: bool) {
-        let timeline_widget = TimelineWidget::new(
-            self.timeline_position,
-            self.current_time,
-            zone,
-            is_selected,
-            self.display_format.clone(),
-            self.timezone_display_mode.clone(),
-            &self.time_config,
-            self.color_theme,
-            self.show_date,
-            true, // DST indicators always on
-            self.show_sun_times,
-        );
+    /// Whether timelines draw sun times, DST markers and date labels
+    pub fn decorations(&self) -> bool {
+        self.lean_until
+            .is_none_or(|until| std::time::Instant::now() >= until)
+    }
 
-        f.render_widget(timeline_widget, area);
+    /// Notes how long a frame took to draw. Past the budget, decorations stay
+    /// off until frames have been fast for a few seconds.
+    pub fn record_frame_time(&mut self, elapsed: std::time::Duration) {
+        // Over a slow link the frame time is the link's, and switching
+        // decorations would only repaint every row
+        if self.low_bandwidth
+            || self.frame_budget_ms == 0
+            || elapsed.as_millis() <= u128::from(self.frame_budget_ms)
+        {
+            return;
+        }
+        if self.decorations() {
+            crate::log::write(&format!(
+                "frame took {}ms (budget {}ms), drawing without decorations",
+                elapsed.as_millis(),
+                self.frame_budget_ms
+            ));
+        }
+        self.lean_until = Some(std::time::Instant::now() + LEAN_COOLDOWN);
     }
 
-    fn render_current_time_display(&self, f: &mut Frame, area: Rect) {
-        // Show what the scrubbed timeline position is in the user's local timezone
-        let local_time = self.timeline_position.with_timezone(&chrono::Local);
+    /// Moves the timeline to `target`, remembering where it was for `Ctrl-o`
+    fn jump_to(&mut self, target: DateTime<Utc>) {
+        let from = self
+            .scrub_animation
+            .as_ref()
+            .map_or(self.timeline_position, |animation| animation.to);
+        if from != target {
+            self.jumps.record(from);
+        }
+        self.glide_to(target);
+    }
 
-        // Format as two lines: local timezone info with UTC offset on first line, time with day on second
-        let local_offset_seconds = local_time.offset().fix().local_minus_utc();
-        let local_offset_hours = local_offset_seconds / 3600;
-        let utc_offset_str = if local_offset_hours >= 0 {
-            format!("UTC+{local_offset_hours}")
+    /// Moves the timeline to `target`, animating long jumps unless reduced motion is on
+    fn glide_to(&mut self, target: DateTime<Utc>) {
+        let distance = target.signed_duration_since(self.timeline_position);
+        if self.reduced_motion
+            || self.low_bandwidth
+            || distance.num_minutes().abs() < ANIMATION_THRESHOLD_MINUTES
+        {
+            self.timeline_position = target;
+            self.scrub_animation = None;
         } else {
-            format!("UTC{local_offset_hours}")
-        };
-        let timezone_line = format!("{} ({})", self.get_local_timezone_name(), utc_offset_str);
+            self.scrub_animation = Some(ScrubAnimation {
+                from: self.timeline_position,
+                to: target,
+                frame: 0,
+            });
+        }
+    }
 
-        let time_line = match self.display_format {
-            TimeFormat::TwentyFourHour => local_time.format("%H:%M %a").to_string(),
-            TimeFormat::TwelveHour => local_time.format("%I:%M %p %a").to_string(),
-        };
+    /// Snaps any running animation to its target so new input starts from there
+    fn finish_animation(&mut self) {
+        if let Some(animation) = self.scrub_animation.take() {
+            self.timeline_position = animation.to;
+        }
+    }
 
-        let display_text = format!("{timezone_line}\n{time_line}");
+    /// Whether to draw the light variant of the theme
+    fn light_background(&self) -> bool {
+        match self.background {
+            BackgroundMode::Auto => self.terminal_background == Some(Background::Light),
+            BackgroundMode::Dark => false,
+            BackgroundMode::Light => true,
+        }
+    }
 
-        // Calculate the width needed for the longest line plus borders and padding
-        let max_line_width = timezone_line.len().max(time_line.len());
-        let box_width = (max_line_width as u16 + 4).min(area.width); // +4 for borders and padding
-        let center_x = (area.width.saturating_sub(box_width)) / 2;
+    /// The palette of the current theme, a user theme if one is chosen and
+    /// still defined, in colors the terminal can show
+    pub fn palette(&self) -> Palette {
+        let light = self.light_background();
+        self.user_theme
+            .as_deref()
+            .and_then(|name| self.themes.iter().find(|theme| theme.name == name))
+            .map(|theme| theme.palette(light))
+            .unwrap_or_else(|| self.color_theme.palette_for(light))
+            .fit(self.color_support)
+    }
 
-        // Create a centered, narrow area for the time box
-        let time_area = Rect {
-            x: area.x + center_x,
-            y: area.y,
-            width: box_width,
-            height: area.height,
-        };
+    /// The palette for a layout's pane: its own theme if it names one
+    fn layout_palette(&self, layout: Option<&LayoutConfig>) -> Palette {
+        layout
+            .and_then(|layout| layout.theme.as_deref())
+            .and_then(|name| find_palette(name, &self.themes, self.light_background()))
+            .map(|palette| palette.fit(self.color_support))
+            .unwrap_or_else(|| self.palette())
+    }
 
-        // Center the time display in a bordered box
-        let time_display = Paragraph::new(display_text)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Cyan))
-            .alignment(Alignment::Center)
-            .block(Block::default().borders(Borders::ALL).title("Local"));
+    /// Switches to a built-in or user theme by name, returning false if
+    /// there's no such theme
+    pub fn select_theme(&mut self, name: &str) -> bool {
+        if let Some(theme) = self
+            .themes
+            .iter()
+            .find(|t| t.name.eq_ignore_ascii_case(name))
+        {
+            self.color_theme = theme.inherits;
+            self.user_theme = Some(theme.name.clone());
+        } else if let Some(theme) = ColorTheme::from_name(name) {
+            self.color_theme = theme;
+            self.user_theme = None;
+        } else {
+            return false;
+        }
+        true
+    }
 
-        f.render_widget(time_display, time_area);
+    /// Steps through the built-in themes, then the user's own. A user theme
+    /// also sets `color_theme` to the theme it inherits, so older versions
+    /// reading the config still get close colors.
+    fn cycle_theme(&mut self) {
+        let current = self
+            .user_theme
+            .as_deref()
+            .and_then(|name| self.themes.iter().position(|theme| theme.name == name));
+        let next_user = match current {
+            Some(index) => index + 1,
+            None if self.color_theme.next() == ColorTheme::default() => 0,
+            None => {
+                self.color_theme = self.color_theme.next();
+                self.user_theme = None;
+                return;
+            }
+        };
+        match self.themes.get(next_user) {
+            Some(theme) => {
+                self.color_theme = theme.inherits;
+                self.user_theme = Some(theme.name.clone());
+            }
+            None => {
+                self.color_theme = ColorTheme::default();
+                self.user_theme = None;
+            }
+        }
     }
 
-    fn render_legend(&self, f: &mut Frame, area: Rect) {
-        use ratatui::text::{Line, Span};
+    /// The chain's next handoff for the footer: who hands to whom, how soon,
+    /// and the receiving team's local time. Short overlaps, now or after an
+    /// upcoming DST change, get a warning.
+    fn handoff_status(&self) -> Option<String> {
+        let config = self.handoff.as_ref()?;
+        let teams = handoff::teams(config, self.timezone_manager.zones());
+        let hours = (
+            self.time_config.work_hours_start,
+            self.time_config.work_hours_end,
+        );
+        let next = handoff::upcoming(&teams, self.current_time, hours)
+            .into_iter()
+            .next()?;
+        let local_time = match self.display_format {
+            TimeFormat::TwentyFourHour => next.receiver_time.format("%H:%M"),
+            TimeFormat::TwelveHour => next.receiver_time.format("%I:%M %p"),
+        };
+        let mut status = format!(
+            "{} → {} in {} ({local_time} there)",
+            next.from,
+            next.to,
+            handoff::format_duration(next.time - self.current_time)
+        );
 
-        // Create legend showing what the different timeline colors/characters mean
-        let night_char = self
-            .time_config
-            .get_activity_char(crate::config::TimeActivity::Night);
-        let awake_char = self
-            .time_config
-            .get_activity_char(crate::config::TimeActivity::Awake);
-        let work_char = self
-            .time_config
-            .get_activity_char(crate::config::TimeActivity::Work);
-
-        let night_color = self.color_theme.get_night_color();
-        let awake_color = self.color_theme.get_awake_color();
-        let work_color = self.color_theme.get_work_color();
+        let minimum = chrono::Duration::minutes(config.min_overlap_minutes as i64);
+        let today = self.current_time.date_naive();
+        if next.overlap < minimum {
+            status += &format!(" ⚠ {} overlap", handoff::format_duration(next.overlap));
+        } else if let Some(warning) = handoff::warnings(&teams, today, hours, minimum).first() {
+            status += &format!(
+                " ⚠ {} → {} overlap {} from {}",
+                warning.from,
+                warning.to,
+                handoff::format_duration(warning.overlap),
+                warning.date.format("%-d %b")
+            );
+        }
+        Some(status)
+    }
 
-        let legend_line = Line::from(vec![
-            Span::styled(format!("{night_char} "), Style::default().fg(night_color)),
-            Span::raw("Night  "),
-            Span::styled(format!("{awake_char} "), Style::default().fg(awake_color)),
-            Span::raw("Awake  "),
-            Span::styled(format!("{work_char} "), Style::default().fg(work_color)),
-            Span::raw("Work  "),
-            Span::styled("┊ ", Style::default().fg(night_color)),
-            Span::raw("Midnight  "),
-            Span::styled("│ ", Style::default().fg(Color::Red)),
-            Span::raw("Now  "),
-            Span::styled(
-                "┃ ",
-                Style::default().fg(self.color_theme.get_timeline_position_color()),
+    /// Puts both panes' zones in `zone_order` as of now, keeping the same
+    /// zones selected
+    fn apply_zone_order(&mut self) {
+        let (order, now) = (self.zone_order, self.current_time);
+        let hours = (
+            self.time_config.work_hours_start,
+            self.time_config.work_hours_end,
+        );
+        for (manager, selected) in [
+            (&mut self.timezone_manager, &mut self.selected_zone_index),
+            (
+                &mut self.split_timezone_manager,
+                &mut self.split_selected_zone_index,
             ),
-            Span::raw("Timeline"),
-        ]);
-
-        let legend = Paragraph::new(legend_line)
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
+        ] {
+            // Zones that never work in the next week go last
+            *selected = manager.sort_by_key(*selected, |zone| match order {
+                ZoneOrder::Offset => (None, 0),
+                // Whole hours alone would mix +5:30 in with +5
+                ZoneOrder::OffsetGroups => (None, zone.offset_minutes_at(now)),
+                ZoneOrder::WorkStartsNext => (
+                    Some(next_work_start(zone.tz, now, hours).unwrap_or(DateTime::<Utc>::MAX_UTC)),
+                    0,
+                ),
+                ZoneOrder::WorkEndsNext => (
+                    Some(next_work_end(zone.tz, now, hours).unwrap_or(DateTime::<Utc>::MAX_UTC)),
+                    0,
+                ),
+            });
+        }
+    }
 
-        f.render_widget(legend, area);
+    /// Loads the layout at `split_layout_index` into the split pane
+    fn load_split_layout(&mut self) {
+        if let Some(layout) = self.layouts.get(self.split_layout_index) {
+            self.split_timezone_manager = Self::manager_from_zone_configs(&layout.zones);
+            self.split_selected_zone_index = 0;
+            self.apply_zone_order();
+        }
     }
 
-    fn render_footer(&self, f: &mut Frame, area: Rect) {
-        let footer_text = "?: help │ a: add │ q: quit";
+    fn select_local_timezone(&mut self) {
+        let local_time = self.current_time.with_timezone(&Local);
+        let local_offset_minutes = local_time.offset().fix().local_minus_utc() / 60;
 
-        let footer = Paragraph::new(footer_text)
-            .style(Style::default().fg(Color::DarkGray))
-            .alignment(Alignment::Center);
+        // Find the timezone that matches our local offset, to the minute so
+        // +5:45 isn't taken for +5:30
+        for (index, zone) in self.timezone_manager.zones().iter().enumerate() {
+            if zone.offset_minutes_at(self.current_time) == local_offset_minutes {
+                self.selected_zone_index = index;
+                break;
+            }
+        }
+    }
 
-        f.render_widget(footer, area);
+    /// Handles a message, keeping the board from before it for undo when it
+    /// changes anything undo covers
+    pub fn update(&mut self, msg: Message) -> Option<Message> {
+        let before = msg.changes_board().then(|| self.snapshot());
+        let next = self.handle(msg);
+        if let Some(before) = before.filter(|before| *before != self.snapshot()) {
+            self.undo.record(before);
+        }
+        next
     }
 
-    fn render_help_modal(&self, f: &mut Frame) {
-        let area = f.area();
+    fn handle(&mut self, msg: Message) -> Option<Message> {
+        if !matches!(msg, Message::Tick | Message::AnimationFrame) {
+            self.finish_animation();
+        }
 
-        // Calculate modal size to fit content
-        let modal_width = area.width * 2 / 3; // Same as add city modal
-                                              // Calculate height based on content: title + max column content + footer + borders
-        let max_content_lines = 17; // Longest column has about 17 lines
-        let modal_height = (2 + max_content_lines + 1 + 4).min(area.height.saturating_sub(2)); // title + content + footer + borders + margin
+        match msg {
+            Message::Tick => {
+                let since = std::mem::replace(&mut self.current_time, Utc::now());
+                self.fire_milestones(since);
+                if let Some(path) = remind::reminders_path().filter(|_| !self.ephemeral) {
+                    self.fire_reminders(&path);
+                }
+                if self.zone_order != ZoneOrder::Offset {
+                    self.apply_zone_order();
+                }
+                None
+            }
 
-        let popup_area = Rect {
-            x: (area.width.saturating_sub(modal_width)) / 2,
-            y: (area.height.saturating_sub(modal_height)) / 2,
-            width: modal_width,
-            height: modal_height,
-        };
+            Message::AnimationFrame => {
+                if let Some(animation) = &mut self.scrub_animation {
+                    animation.frame += 1;
+                    self.timeline_position = animation.position();
+                    if animation.is_finished() {
+                        self.scrub_animation = None;
+                    }
+                }
+                None
+            }
 
-        // Clear the background
-        f.render_widget(Clear, popup_area);
+            Message::ScrubTimeline(direction) => {
+                // Round to the next/previous hour boundary
+                let rounded_time = match direction {
+                    Direction::Left => {
+                        // Go to the start of the current hour, or previous hour if already at start
+                        if self.timeline_position.minute() == 0
+                            && self.timeline_position.second() == 0
+                            && self.timeline_position.nanosecond() == 0
+                        {
+                            // Already at hour boundary, go to previous hour
+                            self.timeline_position - chrono::Duration::hours(1)
+                        } else {
+                            // Go to start of current hour
+                            self.timeline_position
+                                .with_minute(0)
+                                .unwrap_or(self.timeline_position)
+                                .with_second(0)
+                                .unwrap_or(self.timeline_position)
+                                .with_nanosecond(0)
+                                .unwrap_or(self.timeline_position)
+                        }
+                    }
+                    Direction::Right => {
+                        // Go to the start of the next hour
+                        self.timeline_position
+                            .with_minute(0)
+                            .unwrap_or(self.timeline_position)
+                            .with_second(0)
+                            .unwrap_or(self.timeline_position)
+                            .with_nanosecond(0)
+                            .unwrap_or(self.timeline_position)
+                            + chrono::Duration::hours(1)
+                    }
+                    _ => self.timeline_position,
+                };
+                self.timeline_position = rounded_time;
+                None
+            }
 
-        // Split into sections using layout
-        let inner = popup_area.inner(ratatui::layout::Margin {
-            horizontal: 1,
-            vertical: 1,
-        });
-        let chunks = Layout::default()
-            .direction(LayoutDirection::Vertical)
-            .constraints([
-                Constraint::Length(2), // Title
-                Constraint::Min(1),    // Content
-                Constraint::Length(1), // Footer
-            ])
-            .split(inner);
+            Message::ScrubTimelineWithShift(direction) => {
+                // Fine scrub by 1 minute when shift is held
+                let delta = match direction {
+                    Direction::Left => chrono::Duration::minutes(-1),
+                    Direction::Right => chrono::Duration::minutes(1),
+                    _ => chrono::Duration::zero(),
+                };
+                self.timeline_position += delta;
+                None
+            }
 
-        // Render title
-        let title = Paragraph::new("🕐 HELP & KEYBOARD SHORTCUTS")
-            .style(
-                Style::default()
-                    .fg(self.color_theme.get_selected_border_color())
-                    .add_modifier(Modifier::BOLD),
-            )
-            .alignment(Alignment::Center);
-        f.render_widget(title, chunks[0]);
+            Message::ResetToNow => {
+                self.jump_to(self.current_time);
+                None
+            }
 
-        // Split content into two columns for better space usage
-        let content_chunks = Layout::default()
-            .direction(LayoutDirection::Horizontal)
-            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-            .split(chunks[1]);
+            Message::JumpDays(days) => {
+                self.jump_to(self.timeline_position + chrono::Duration::days(days));
+                None
+            }
+
+            Message::ShiftMilestones(days) => {
+                for milestone in &mut self.milestones {
+                    milestone.time += chrono::Duration::days(days);
+                }
+                if !self.milestones.is_empty() {
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::StartMilestonePlan => {
+                if !self.milestones.is_empty() {
+                    let before = self.snapshot();
+                    self.milestone_plan = Some(MilestonePlan::new(&self.milestones, before));
+                }
+                None
+            }
+
+            Message::NavigateMilestonePlan(direction) => {
+                if let Some(plan) = &mut self.milestone_plan {
+                    let last = plan.original.len().saturating_sub(1);
+                    plan.cursor = match direction {
+                        Direction::Up => plan.cursor.saturating_sub(1),
+                        Direction::Down => (plan.cursor + 1).min(last),
+                        _ => plan.cursor,
+                    };
+                }
+                None
+            }
+
+            Message::ToggleMilestoneSelection => {
+                if let Some(plan) = &mut self.milestone_plan {
+                    if let Some(selected) = plan.selected.get_mut(plan.cursor) {
+                        *selected = !*selected;
+                    }
+                    self.milestones = plan.preview();
+                }
+                None
+            }
+
+            Message::SelectMilestoneGroup => {
+                // Everything sharing the cursor's group, or everything when it has none
+                if let Some(plan) = &mut self.milestone_plan {
+                    let group = plan.original.get(plan.cursor).and_then(|m| m.group.clone());
+                    for (milestone, selected) in plan.original.iter().zip(&mut plan.selected) {
+                        if group.is_none() || milestone.group == group {
+                            *selected = true;
+                        }
+                    }
+                    self.milestones = plan.preview();
+                }
+                None
+            }
+
+            Message::AdjustMilestonePlan(minutes) => {
+                if let Some(plan) = &mut self.milestone_plan {
+                    plan.shift += chrono::Duration::minutes(minutes);
+                    self.milestones = plan.preview();
+                }
+                None
+            }
+
+            Message::ConfirmMilestonePlan => {
+                if let Some(plan) = self.milestone_plan.take() {
+                    // The previews were never undo steps; the plan is one
+                    if plan.preview() != plan.original {
+                        self.undo.record(plan.before);
+                        self.save_config();
+                    }
+                }
+                None
+            }
+
+            Message::CancelMilestonePlan => {
+                if let Some(plan) = self.milestone_plan.take() {
+                    self.milestones = plan.original;
+                }
+                None
+            }
+
+            Message::FineAdjust(minutes) => {
+                let delta = chrono::Duration::minutes(minutes as i64);
+                self.timeline_position += delta;
+                None
+            }
+
+            Message::NavigateZone(direction) if self.split_view && self.split_pane_focused => {
+                let zone_count = self.split_timezone_manager.zone_count();
+                match direction {
+                    Direction::Up if self.split_selected_zone_index > 0 => {
+                        self.split_selected_zone_index -= 1;
+                    }
+                    Direction::Down if self.split_selected_zone_index + 1 < zone_count => {
+                        self.split_selected_zone_index += 1;
+                    }
+                    _ => {}
+                }
+                None
+            }
+
+            Message::NavigateZone(direction) => {
+                let zone_count = self.timezone_manager.zone_count();
+                if zone_count > 0 {
+                    let old_index = self.selected_zone_index;
+                    match direction {
+                        Direction::Up if self.selected_zone_index > 0 => {
+                            self.selected_zone_index -= 1;
+                        }
+                        Direction::Down if self.selected_zone_index < zone_count - 1 => {
+                            self.selected_zone_index += 1;
+                        }
+                        _ => {}
+                    }
+                    // Save config if selection changed
+                    if old_index != self.selected_zone_index {
+                        self.save_config();
+                    }
+                }
+                None
+            }
+
+            Message::ToggleSplitView => {
+                // Split view needs at least one layout configured to show beside the board
+                if self.split_view {
+                    self.split_view = false;
+                    self.split_pane_focused = false;
+                } else if !self.layouts.is_empty() {
+                    self.split_layout_index = self.split_layout_index.min(self.layouts.len() - 1);
+                    self.load_split_layout();
+                    self.split_view = true;
+                }
+                None
+            }
+
+            Message::CycleSplitLayout => {
+                if self.split_view && !self.layouts.is_empty() {
+                    self.split_layout_index = (self.split_layout_index + 1) % self.layouts.len();
+                    self.load_split_layout();
+                }
+                None
+            }
+
+            Message::CopyTimeCard => {
+                if self.card_templates.is_empty() {
+                    return self.handle(Message::SelectShareOption(0));
+                }
+                self.share_menu = Some(0);
+                None
+            }
+
+            Message::NavigateShareMenu(direction) => {
+                if let Some(cursor) = &mut self.share_menu {
+                    *cursor = match direction {
+                        Direction::Up => cursor.saturating_sub(1),
+                        Direction::Down => (*cursor + 1).min(self.card_templates.len()),
+                        _ => *cursor,
+                    };
+                }
+                None
+            }
+
+            Message::SelectShareOption(option) => {
+                self.share_menu = None;
+                if option <= self.card_templates.len() {
+                    let template = option.checked_sub(1).map(|i| &self.card_templates[i]);
+                    self.clipboard = Some(self.time_card(template));
+                    self.copied_until = Some(std::time::Instant::now() + COPIED_NOTICE);
+                }
+                None
+            }
+
+            Message::CancelShareMenu => {
+                self.share_menu = None;
+                None
+            }
+
+            Message::JumpBack => {
+                self.finish_animation();
+                if let Some(target) = self.jumps.back(self.timeline_position) {
+                    self.glide_to(target);
+                }
+                None
+            }
+
+            Message::JumpForward => {
+                self.finish_animation();
+                if let Some(target) = self.jumps.forward() {
+                    self.glide_to(target);
+                }
+                None
+            }
+
+            Message::SwitchPane => {
+                if self.split_view {
+                    self.split_pane_focused = !self.split_pane_focused;
+                    // Marks belong to the pane they were made in
+                    self.marked_zones.clear();
+                }
+                None
+            }
+
+            Message::ToggleTimeFormat => {
+                self.display_format = match self.display_format {
+                    TimeFormat::TwentyFourHour => TimeFormat::TwelveHour,
+                    TimeFormat::TwelveHour => TimeFormat::TwentyFourHour,
+                };
+                self.save_config();
+                None
+            }
+
+            Message::ToggleTimezoneDisplayMode => {
+                self.timezone_display_mode = match self.timezone_display_mode {
+                    TimezoneDisplayMode::Short => TimezoneDisplayMode::Full,
+                    TimezoneDisplayMode::Full => TimezoneDisplayMode::Short,
+                };
+                self.save_config();
+                None
+            }
+
+            Message::ToggleDate => {
+                self.show_date = !self.show_date;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleSunTimes => {
+                self.show_sun_times = !self.show_sun_times;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleFocusMode => {
+                self.focus_mode = !self.focus_mode;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleFocusRuler => {
+                self.focus_ruler = !self.focus_ruler;
+                self.save_config();
+                None
+            }
+
+            Message::ToggleAlignMidnight => {
+                self.align_to = match self.align_to {
+                    Some(_) => None,
+                    None => self.focused_zone().map(|zone| zone.tz.name().to_string()),
+                };
+                self.save_config();
+                None
+            }
+
+            Message::TogglePrivacyMode => {
+                self.privacy_mode = !self.privacy_mode;
+                self.save_config();
+                None
+            }
+
+            Message::CycleZoneOrder => {
+                self.zone_order = self.zone_order.next();
+                self.apply_zone_order();
+                self.save_config();
+                None
+            }
+
+            Message::CycleColorTheme => {
+                self.cycle_theme();
+                self.save_config();
+                None
+            }
+
+            Message::DismissClockWarnings => {
+                self.clock_warnings.clear();
+                None
+            }
+
+            Message::ToggleHelp => {
+                self.show_help = !self.show_help;
+                None
+            }
+
+            Message::ToggleQrCode => {
+                self.show_qr = !self.show_qr;
+                None
+            }
+
+            Message::ToggleOffsetMatrix => {
+                self.show_matrix = !self.show_matrix;
+                None
+            }
+
+            Message::StartHoursEditor => {
+                let (_, index) = self.focused_pane();
+                if let Some(zone) = self.focused_zone() {
+                    self.hours_editor = Some(HoursEditor {
+                        boundary: 1,
+                        zone: index,
+                        original: zone.schedule.hours.clone(),
+                        before: self.snapshot(),
+                    });
+                }
+                None
+            }
+
+            Message::NavigateHoursEditor(direction) => {
+                if let Some(editor) = &mut self.hours_editor {
+                    let boundary = HourBoundary::ALL[editor.boundary];
+                    match direction {
+                        Direction::Up => editor.boundary = editor.boundary.saturating_sub(1),
+                        Direction::Down => {
+                            editor.boundary = (editor.boundary + 1).min(HourBoundary::ALL.len() - 1)
+                        }
+                        Direction::Left | Direction::Right => {
+                            let index = editor.zone;
+                            let hours = self.focused_pane().0.zones().get(index).map(|zone| {
+                                let mut hours = zone.hours(&self.time_config).clone();
+                                let step = if direction == Direction::Left { -1 } else { 1 };
+                                hours.nudge(boundary, step);
+                                hours
+                            });
+                            if let Some(hours) = hours {
+                                self.focused_pane_mut()
+                                    .0
+                                    .update_zone_hours(index, Some(hours));
+                            }
+                        }
+                    }
+                }
+                None
+            }
+
+            Message::ConfirmHoursEditor => {
+                if let Some(editor) = self.hours_editor.take() {
+                    let zones = self.focused_pane().0.zones();
+                    if zones.get(editor.zone).map(|zone| &zone.schedule.hours)
+                        != Some(&editor.original)
+                    {
+                        self.undo.record(editor.before);
+                        self.save_focused();
+                    }
+                }
+                None
+            }
+
+            Message::CancelHoursEditor => {
+                if let Some(editor) = self.hours_editor.take() {
+                    self.focused_pane_mut()
+                        .0
+                        .update_zone_hours(editor.zone, editor.original);
+                }
+                None
+            }
+
+            Message::StartAddZone => {
+                // Clear other modal states
+                self.renaming_zone = false;
+                self.rename_zone_input.clear();
+
+                self.adding_zone = true;
+                self.add_zone_input.clear();
+                self.zone_search_results.clear();
+                self.selected_search_result = 0;
+                None
+            }
+
+            Message::UpdateAddZoneInput(input) => {
+                self.add_zone_input = input.clone();
+                self.zone_search_results = self.search_zones(&input);
+                self.selected_search_result = 0; // Reset selection when search changes
+                None
+            }
+
+            Message::NavigateSearchResults(direction) => {
+                if !self.zone_search_results.is_empty() {
+                    match direction {
+                        Direction::Up if self.selected_search_result > 0 => {
+                            self.selected_search_result -= 1;
+                        }
+                        Direction::Down
+                            if self.selected_search_result < self.zone_search_results.len() - 1 =>
+                        {
+                            self.selected_search_result += 1;
+                        }
+                        _ => {}
+                    }
+                }
+                None
+            }
+
+            Message::SelectSearchResult(index) => {
+                if let Some(zone_name) = self.zone_search_results.get(index).cloned() {
+                    self.note_abbreviation_pick(&zone_name);
+                    self.add_to_focused(&zone_name);
+                }
+                self.adding_zone = false;
+                self.add_zone_input.clear();
+                self.zone_search_results.clear();
+                None
+            }
+
+            Message::ConfirmAddZone => {
+                if !self.zone_search_results.is_empty() {
+                    // Use the currently selected search result
+                    if let Some(zone_name) = self
+                        .zone_search_results
+                        .get(self.selected_search_result)
+                        .cloned()
+                    {
+                        self.note_abbreviation_pick(&zone_name);
+                        self.add_to_focused(&zone_name);
+                    }
+                } else if !self.add_zone_input.is_empty() {
+                    // Try to add the exact input if no search results
+                    self.add_to_focused(&self.add_zone_input.clone());
+                }
+                self.adding_zone = false;
+                self.add_zone_input.clear();
+                self.zone_search_results.clear();
+                self.selected_search_result = 0;
+                None
+            }
+
+            Message::CancelAddZone => {
+                self.adding_zone = false;
+                self.add_zone_input.clear();
+                self.zone_search_results.clear();
+                self.selected_search_result = 0;
+                None
+            }
+
+            Message::RemoveCurrentZone => {
+                let mut targets = self.bulk_targets();
+                // Keep at least one zone
+                targets.truncate(self.focused_pane().0.zone_count().saturating_sub(1));
+                for &index in targets.iter().rev() {
+                    if let Some(zone) = self.focused_pane_mut().0.remove_zone(index) {
+                        self.record_history(HistoryEvent::ZoneRemoved {
+                            city: zone.get_city_name(),
+                            label: zone.display.label,
+                        });
+                    }
+                }
+                if !targets.is_empty() {
+                    self.marked_zones.clear();
+                    // Adjust selected index if needed
+                    let (manager, selected) = self.focused_pane_mut();
+                    *selected = (*selected).min(manager.zone_count().saturating_sub(1));
+                    self.save_focused();
+                }
+                None
+            }
+
+            Message::ToggleMark => {
+                if let Some(zone) = self.focused_zone() {
+                    let city = zone.get_city_name();
+                    match self.marked_zones.iter().position(|marked| *marked == city) {
+                        Some(index) => {
+                            self.marked_zones.remove(index);
+                        }
+                        None => self.marked_zones.push(city),
+                    }
+                }
+                None
+            }
+
+            Message::ClearMarks => {
+                self.marked_zones.clear();
+                None
+            }
+
+            Message::CycleAccent => {
+                let targets = self.bulk_targets();
+                let zones = self.focused_pane().0.zones();
+                // Every zone moves on from the first one's color, so they match
+                let current = targets
+                    .first()
+                    .and_then(|&index| zones[index].display.accent.as_deref());
+                let next = match current.and_then(|color| ACCENTS.iter().position(|a| *a == color))
+                {
+                    Some(index) => ACCENTS.get(index + 1).map(|color| color.to_string()),
+                    None if current.is_some() => None,
+                    None => Some(ACCENTS[0].to_string()),
+                };
+                let manager = self.focused_pane_mut().0;
+                for &index in &targets {
+                    manager.update_zone_accent(index, next.clone());
+                }
+                if !targets.is_empty() {
+                    self.save_focused();
+                }
+                None
+            }
+
+            Message::StartAssignGroup => {
+                if self.focused_zone().is_some() {
+                    self.group_input = Some(String::new());
+                }
+                None
+            }
+
+            Message::UpdateGroupInput(input) => {
+                self.group_input = Some(input);
+                None
+            }
+
+            Message::ConfirmAssignGroup => {
+                let name = self.group_input.take().unwrap_or_default();
+                let name = name.trim();
+                if !name.is_empty() {
+                    self.assign_group(name);
+                    self.marked_zones.clear();
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::CancelAssignGroup => {
+                self.group_input = None;
+                None
+            }
+
+            Message::ArchiveZone => {
+                let mut targets = self.bulk_targets();
+                // Keep at least one zone
+                targets.truncate(self.focused_pane().0.zone_count().saturating_sub(1));
+                for &index in targets.iter().rev() {
+                    if let Some(zone) = self.focused_pane_mut().0.remove_zone(index) {
+                        let city = zone.get_city_name();
+                        let entry = ZoneConfigCompat::from(&zone);
+                        self.archived.retain(|e| e.city_name() != entry.city_name());
+                        self.archived.push(entry);
+                        self.record_history(HistoryEvent::ZoneArchived { city });
+                    }
+                }
+                if !targets.is_empty() {
+                    self.marked_zones.clear();
+                    let (manager, selected) = self.focused_pane_mut();
+                    *selected = (*selected).min(manager.zone_count().saturating_sub(1));
+                    self.save_focused();
+                }
+                None
+            }
+
+            Message::OpenArchive => {
+                if !self.archived.is_empty() {
+                    self.archive_picker = Some(0);
+                }
+                None
+            }
+
+            Message::NavigateArchive(direction) => {
+                if let Some(cursor) = &mut self.archive_picker {
+                    *cursor = match direction {
+                        Direction::Up => cursor.saturating_sub(1),
+                        Direction::Down => (*cursor + 1).min(self.archived.len().saturating_sub(1)),
+                        _ => *cursor,
+                    };
+                }
+                None
+            }
+
+            Message::RestoreArchived(index) => {
+                self.archive_picker = None;
+                if index < self.archived.len() {
+                    let entry = self.archived.remove(index);
+                    self.restore_zone(&entry);
+                    self.record_history(HistoryEvent::ZoneRestored {
+                        city: entry.city_name().to_string(),
+                    });
+                    self.save_board();
+                }
+                None
+            }
+
+            Message::CancelArchive => {
+                self.archive_picker = None;
+                None
+            }
+
+            Message::StartGotoTime => {
+                if self.focused_zone().is_some() {
+                    self.goto_time = Some(String::new());
+                }
+                None
+            }
+
+            Message::UpdateGotoInput(input) => {
+                self.goto_time = Some(input);
+                None
+            }
+
+            Message::ConfirmGotoTime => {
+                let target = self
+                    .goto_time
+                    .as_deref()
+                    .and_then(|input| self.goto_target(input));
+                // A time that can't be read stays open for correcting
+                if let Some(target) = target {
+                    self.goto_time = None;
+                    self.jump_to(target);
+                }
+                None
+            }
+
+            Message::CancelGotoTime => {
+                self.goto_time = None;
+                None
+            }
+
+            Message::StartRenameZone => {
+                if let Some(zone) = self.focused_zone() {
+                    // Pre-fill with current custom label or empty
+                    self.rename_zone_input = zone.display.label.clone().unwrap_or_default();
+
+                    // Clear other modal states
+                    self.adding_zone = false;
+                    self.add_zone_input.clear();
+                    self.zone_search_results.clear();
+
+                    self.renaming_zone = true;
+                }
+                None
+            }
+
+            Message::UpdateRenameInput(input) => {
+                self.rename_zone_input = input;
+                None
+            }
+
+            Message::ConfirmRename => {
+                let custom_label = if self.rename_zone_input.trim().is_empty() {
+                    None
+                } else {
+                    Some(self.rename_zone_input.trim().to_string())
+                };
+                self.relabel_focused(custom_label);
+                self.renaming_zone = false;
+                self.rename_zone_input.clear();
+                None
+            }
+
+            Message::CancelRename => {
+                self.renaming_zone = false;
+                self.rename_zone_input.clear();
+                None
+            }
+
+            Message::ClearCustomName => {
+                self.relabel_focused(None);
+                None
+            }
+
+            Message::AwaitMacroRegister(action) => {
+                self.pending_macro_register = Some(action);
+                None
+            }
+
+            Message::StartMacroRecording(register) => {
+                self.recording_macro = Some((register, Vec::new()));
+                None
+            }
+
+            Message::StopMacroRecording => {
+                if let Some((register, keys)) = self.recording_macro.take() {
+                    if keys.is_empty() {
+                        self.macros.remove(&register.to_string());
+                    } else {
+                        self.macros.insert(register.to_string(), keys);
+                    }
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::Undo => {
+                if let Some(previous) = self.undo.undo(self.snapshot()) {
+                    self.restore(previous);
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::Redo => {
+                if let Some(next) = self.undo.redo(self.snapshot()) {
+                    self.restore(next);
+                    self.save_config();
+                }
+                None
+            }
+
+            Message::Quit => {
+                self.save_session();
+                self.should_quit = true;
+                None
+            }
+        }
+    }
+
+    pub fn view(&self, f: &mut Frame) {
+        self.clock_area.set(None);
+        let area = self.render_clock_warnings(f, f.area());
+        if let Some(rows) = self.mini_rows {
+            self.render_mini(f, area, rows);
+        } else if self.focus_mode {
+            // Only the bars and times: no header, legend or footer hints
+            self.render_zones(f, area);
+        } else {
+            let chunks = Layout::default()
+                .direction(LayoutDirection::Vertical)
+                .constraints([
+                    Constraint::Length(3), // Header
+                    Constraint::Length(4), // Current time display (taller)
+                    Constraint::Min(1),    // Main content
+                    Constraint::Length(2), // Legend
+                    Constraint::Length(3), // Footer
+                ])
+                .split(area);
+
+            self.render_header(f, chunks[0]);
+            self.render_current_time_display(f, chunks[1]);
+            self.render_zones(f, chunks[2]);
+            self.render_legend(f, chunks[3]);
+            self.render_footer(f, chunks[4]);
+        }
+
+        // Render modals on top if needed, hiding the clock image they'd
+        // overlap. The mini view has them too, as the keys still open them.
+        if self.show_help
+            || self.show_qr
+            || self.show_matrix
+            || self.adding_zone
+            || self.renaming_zone
+            || self.goto_time.is_some()
+            || self.group_input.is_some()
+            || self.share_menu.is_some()
+            || self.archive_picker.is_some()
+            || self.milestone_plan.is_some()
+            || self.hours_editor.is_some()
+        {
+            self.clock_area.set(None);
+        }
+        if self.show_help {
+            self.render_help_modal(f);
+        } else if self.show_qr {
+            self.render_qr_modal(f);
+        } else if self.show_matrix {
+            self.render_matrix_modal(f);
+        } else if self.adding_zone {
+            self.render_add_zone_modal(f);
+        } else if self.renaming_zone {
+            self.render_rename_zone_modal(f);
+        } else if let Some(input) = &self.goto_time {
+            self.render_goto_modal(f, input);
+        } else if let Some(input) = &self.group_input {
+            self.render_group_modal(f, input);
+        } else if let Some(cursor) = self.share_menu {
+            self.render_share_menu(f, cursor);
+        } else if let Some(cursor) = self.archive_picker {
+            self.render_archive_picker(f, cursor);
+        } else if let Some(plan) = &self.milestone_plan {
+            self.render_milestone_plan(f, plan);
+        } else if let Some(editor) = &self.hours_editor {
+            self.render_hours_editor(f, editor);
+        }
+    }
+
+    /// Renders the startup clock warnings across the top, one per line, and
+    /// returns the area left below them. Focus mode keeps them too: a board
+    /// read off a wrong clock is worse than none.
+    fn render_clock_warnings(&self, f: &mut Frame, area: Rect) -> Rect {
+        if self.clock_warnings.is_empty() {
+            return area;
+        }
+        let [banner, rest] = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(self.clock_warnings.len() as u16 + 1),
+                Constraint::Min(0),
+            ])
+            .areas(area);
+        let style = Style::default().fg(Color::Black).bg(Color::Yellow);
+        let text: String = self
+            .clock_warnings
+            .iter()
+            .map(|warning| format!(" ! {warning}\n"))
+            .chain([" w: dismiss".to_string()])
+            .collect();
+        f.render_widget(Paragraph::new(text).style(style), banner);
+        rest
+    }
+
+    /// Renders up to `rows` zones as single-line rows, scrolled to keep the
+    /// selected zone visible. Sized for tiny floating terminals (e.g. 40x6).
+    fn render_mini(&self, f: &mut Frame, area: Rect, rows: usize) {
+        let zones = self.timezone_manager.zones();
+        let visible = rows.min(area.height as usize).min(zones.len());
+        if visible == 0 {
+            return;
+        }
+
+        let first = (self.selected_zone_index + 1).saturating_sub(visible);
+        let shown = self.zones_at_scrub(zones);
+        let mut rows = Vec::with_capacity(visible);
+        for (row, (i, zone)) in shown
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+            .enumerate()
+        {
+            let row_area = Rect {
+                x: area.x,
+                y: area.y + row as u16,
+                width: area.width,
+                height: 1,
+            };
+            let widget = TimelineWidget::new(
+                self.timeline_position,
+                self.current_time,
+                zone,
+                i == self.selected_zone_index,
+                self.display_format.clone(),
+                self.timezone_display_mode.clone(),
+                &self.time_config,
+                self.palette(),
+                false,
+                false,
+                false,
+            )
+            .compact(true)
+            .marked(self.is_marked(&zones[i]))
+            .overlap_week(self.overlap_week(zone))
+            .align_to(self.aligned_tz())
+            .glyphs(self.glyphs)
+            .layout_cache(&self.day_layouts)
+            .private(self.privacy_mode)
+            .decorations(self.decorations())
+            .marker_priority(&self.marker_priority)
+            .now_line(&self.now_line)
+            .home_date(self.home_date())
+            .fiscal_year_start(self.fiscal_year_start)
+            .milestones(&self.milestones)
+            .ghosts(&self.ghosts)
+            .tasks(if self.tasks_tz == Some(zone.tz) {
+                &self.due_tasks
+            } else {
+                &[]
+            })
+            .imminent(self.imminent_window(), self.pulses())
+            .blocked(&self.away);
+            rows.push((row_area, widget));
+        }
+        render_rows(f.buffer_mut(), rows);
+    }
+
+    fn render_header(&self, f: &mut Frame, area: Rect) {
+        let local_time = self.current_time.with_timezone(&Local);
+
+        // Use chrono-tz names for better abbreviations, fall back to %Z
+        let tz_name = self.get_local_timezone_name();
+        // Without seconds the header only changes once a minute
+        let local_time_str = match (&self.display_format, self.low_bandwidth) {
+            (TimeFormat::TwentyFourHour, false) => {
+                format!("{} {}", local_time.format("%H:%M:%S"), tz_name)
+            }
+            (TimeFormat::TwentyFourHour, true) => {
+                format!("{} {}", local_time.format("%H:%M"), tz_name)
+            }
+            (TimeFormat::TwelveHour, false) => {
+                format!("{} {}", local_time.format("%I:%M:%S %p"), tz_name)
+            }
+            (TimeFormat::TwelveHour, true) => {
+                format!("{} {}", local_time.format("%I:%M %p"), tz_name)
+            }
+        };
+
+        let timeline_time_str = match self.display_format {
+            TimeFormat::TwentyFourHour => self.timeline_position.format("%H:%M UTC").to_string(),
+            TimeFormat::TwelveHour => self.timeline_position.format("%I:%M %p UTC").to_string(),
+        };
+
+        // Create inner area for content
+        let inner = area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([
+                Constraint::Percentage(33), // App name (left)
+                Constraint::Percentage(34), // Local time (center)
+                Constraint::Percentage(33), // Timeline (right)
+            ])
+            .split(inner);
+
+        // Left: App name
+        let app_name = Paragraph::new(format!("alltz v{}", env!("CARGO_PKG_VERSION")))
+            .alignment(Alignment::Left);
+        f.render_widget(app_name, chunks[0]);
+
+        // Center: Local time
+        let local_display =
+            Paragraph::new(format!("Local: {local_time_str}")).alignment(Alignment::Center);
+        f.render_widget(local_display, chunks[1]);
+
+        // Right: Timeline time
+        let timeline_display =
+            Paragraph::new(format!("Timeline: {timeline_time_str}")).alignment(Alignment::Right);
+        f.render_widget(timeline_display, chunks[2]);
+
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border);
+        f.render_widget(border, area);
+    }
+
+    fn get_local_timezone_name(&self) -> String {
+        // Try to get a better timezone name from our configured zones
+        let local_time = self.current_time.with_timezone(&Local);
+        let local_offset_minutes = local_time.offset().fix().local_minus_utc() / 60;
+
+        // Look for a matching timezone in our list to get a better abbreviation
+        for zone in self.timezone_manager.zones() {
+            if zone.offset_minutes_at(self.current_time) == local_offset_minutes {
+                return zone.get_timezone_abbreviation();
+            }
+        }
+
+        // Fallback: use system timezone name
+        let tz_str = local_time.format("%Z").to_string();
+        if tz_str.starts_with('+') || tz_str.starts_with('-') {
+            // If it's still showing offset, try a different approach
+            crate::time::utc_offset_label(local_offset_minutes, self.offset_minutes)
+        } else {
+            tz_str
+        }
+    }
+
+    fn render_zones(&self, f: &mut Frame, area: Rect) {
+        if self.split_view {
+            let panes = Layout::default()
+                .direction(LayoutDirection::Horizontal)
+                .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+                .split(area);
+
+            // Layout names like customer lists stay hidden in privacy mode
+            let split_layout = self.layouts.get(self.split_layout_index);
+            let split_name = split_layout
+                .filter(|_| !self.privacy_mode)
+                .map(|layout| layout.name.as_str())
+                .unwrap_or("Layout");
+            let board_name = self
+                .board_name
+                .as_deref()
+                .filter(|_| !self.privacy_mode)
+                .unwrap_or("Board");
+
+            self.render_pane(
+                f,
+                panes[0],
+                board_name,
+                &self.timezone_manager,
+                (!self.split_pane_focused).then_some(self.selected_zone_index),
+                self.palette(),
+            );
+            self.render_pane(
+                f,
+                panes[1],
+                split_name,
+                &self.split_timezone_manager,
+                self.split_pane_focused
+                    .then_some(self.split_selected_zone_index),
+                self.layout_palette(split_layout),
+            );
+            return;
+        }
+
+        self.render_zone_list(
+            f,
+            area,
+            &self.timezone_manager,
+            Some(self.selected_zone_index),
+            self.palette(),
+        );
+    }
+
+    /// Renders one side of the split view: a title line and its zone list.
+    /// Only the focused pane has a selection to highlight.
+    fn render_pane(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        name: &str,
+        manager: &TimeZoneManager,
+        selected_index: Option<usize>,
+        palette: Palette,
+    ) {
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(1)])
+            .split(area);
+
+        let title_style = if selected_index.is_some() {
+            Style::default()
+                .fg(palette.selected_border)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::DarkGray)
+        };
+        let title = Paragraph::new(name.to_string())
+            .style(title_style)
+            .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        self.render_zone_list(f, chunks[1], manager, selected_index, palette);
+    }
+
+    fn render_zone_list(
+        &self,
+        f: &mut Frame,
+        area: Rect,
+        manager: &TimeZoneManager,
+        selected_index: Option<usize>,
+        palette: Palette,
+    ) {
+        let zones = manager.zones();
+
+        if zones.is_empty() {
+            let empty_msg = Paragraph::new("No timezones configured").block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_set(self.glyphs.border)
+                    .title("Timezones"),
+            );
+            f.render_widget(empty_msg, area);
+            return;
+        }
+
+        // Borderless rows in focus mode only need the bar and time lines;
+        // the selected row grows a line for its ruler
+        let zone_height = if self.focus_mode { 2 } else { 4 };
+        let shown = self.zones_at_scrub(zones);
+        // Grouped offsets start each new offset with a separator line
+        let offsets: Vec<i32> = shown
+            .iter()
+            .map(|zone| zone.offset_minutes_at(self.current_time))
+            .collect();
+        let separated = |i: usize| {
+            self.zone_order == ZoneOrder::OffsetGroups && (i == 0 || offsets[i] != offsets[i - 1])
+        };
+        let mut zone_constraints = Vec::new();
+        for i in 0..zones.len() {
+            if separated(i) {
+                zone_constraints.push(Constraint::Length(1));
+            }
+            let ruler = self.focus_ruler && selected_index == Some(i);
+            zone_constraints.push(Constraint::Length(zone_height + ruler as u16));
+        }
+
+        let mut zone_chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints(zone_constraints)
+            .split(area)
+            .to_vec()
+            .into_iter();
+
+        let main = std::ptr::eq(manager, &self.timezone_manager);
+        let layouts = if main {
+            &self.day_layouts
+        } else {
+            &self.split_day_layouts
+        };
+        let mut rows = Vec::with_capacity(zones.len());
+        for (i, zone) in zones.iter().enumerate() {
+            if separated(i) {
+                let Some(line) = zone_chunks.next() else {
+                    break;
+                };
+                self.render_offset_separator(f, line, offsets[i]);
+            }
+            let Some(row) = zone_chunks.next() else {
+                break;
+            };
+            // Marks are for the zones in the pane with focus only
+            let focused = main != (self.split_view && self.split_pane_focused);
+            let marked = focused && self.is_marked(zone);
+            rows.push((
+                row,
+                self.zone_widget(&shown[i], selected_index == Some(i), palette)
+                    .marked(marked)
+                    .layout_cache(layouts),
+            ));
+        }
+        render_rows(f.buffer_mut(), rows);
+    }
+
+    /// A rule naming the offset of the zones below it, e.g. "── UTC+5:30 ──"
+    fn render_offset_separator(&self, f: &mut Frame, area: Rect, offset_minutes: i32) {
+        let label = match offset_minutes {
+            0 => " UTC ".to_string(),
+            minutes => format!(" UTC{} ", crate::matrix::format_offset(minutes)),
+        };
+        let rule = self.glyphs.rule.to_string();
+        let line = format!(
+            "{}{label}{}",
+            rule.repeat(2),
+            rule.repeat(area.width as usize)
+        );
+        f.render_widget(
+            Paragraph::new(line).style(Style::default().fg(Color::DarkGray)),
+            area,
+        );
+    }
+
+    fn zone_widget<'a>(
+        &'a self,
+        zone: &'a TimeZone,
+        is_selected: bool,
+        palette: Palette,
+    ) -> TimelineWidget<'a> {
+        TimelineWidget::new(
+            self.timeline_position,
+            self.current_time,
+            zone,
+            is_selected,
+            self.display_format.clone(),
+            self.timezone_display_mode.clone(),
+            &self.time_config,
+            palette,
+            self.show_date,
+            true, // DST indicators always on
+            self.show_sun_times,
+        )
+        .borderless(self.focus_mode)
+        .ruler(self.focus_ruler && is_selected)
+        .offset_minutes(self.offset_minutes)
+        .align_to(self.aligned_tz())
+        .glyphs(self.glyphs)
+        .private(self.privacy_mode)
+        .decorations(self.decorations())
+        .marker_priority(&self.marker_priority)
+        .now_line(&self.now_line)
+        .home_date(self.home_date())
+        .fiscal_year_start(self.fiscal_year_start)
+        .milestones(&self.milestones)
+        .ghosts(&self.ghosts)
+        .tasks(if self.tasks_tz == Some(zone.tz) {
+            &self.due_tasks
+        } else {
+            &[]
+        })
+        .imminent(self.imminent_window(), self.pulses())
+        .dst_countdown(self.dst_countdown_window())
+        .clock_changes(&self.clock_changes)
+        .blocked(&self.away)
+    }
+
+    fn render_current_time_display(&self, f: &mut Frame, area: Rect) {
+        // Show what the scrubbed timeline position is in the user's local timezone
+        let local_time = self.timeline_position.with_timezone(&chrono::Local);
+
+        // Format as two lines: local timezone info with UTC offset on first line, time with day on second
+        let utc_offset_str = crate::time::utc_offset_label(
+            local_time.offset().fix().local_minus_utc() / 60,
+            self.offset_minutes,
+        );
+        let timezone_line = format!("{} ({})", self.get_local_timezone_name(), utc_offset_str);
+
+        let time_line = match self.display_format {
+            TimeFormat::TwentyFourHour => local_time.format("%H:%M %a").to_string(),
+            TimeFormat::TwelveHour => local_time.format("%I:%M %p %a").to_string(),
+        };
+
+        let display_text = format!("{timezone_line}\n{time_line}");
+
+        // Calculate the width needed for the longest line plus borders and padding
+        let max_line_width = timezone_line.len().max(time_line.len());
+        let box_width = (max_line_width as u16 + 4).min(area.width); // +4 for borders and padding
+        let center_x = (area.width.saturating_sub(box_width)) / 2;
+
+        // Create a centered, narrow area for the time box
+        let time_area = Rect {
+            x: area.x + center_x,
+            y: area.y,
+            width: box_width,
+            height: area.height,
+        };
+
+        // Center the time display in a bordered box
+        let time_display = Paragraph::new(display_text)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Cyan))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_set(self.glyphs.border)
+                    .title("Local"),
+            );
+
+        f.render_widget(time_display, time_area);
+
+        // Leave blank cells left of the box for the clock image, about square
+        // given cells are twice as tall as they are wide
+        let clock_width = area.height * 2;
+        if self.graphics.is_some() && time_area.x > area.x + clock_width {
+            self.clock_area.set(Some(Rect {
+                x: time_area.x - clock_width - 1,
+                y: area.y,
+                width: clock_width,
+                height: area.height,
+            }));
+        }
+    }
+
+    fn render_legend(&self, f: &mut Frame, area: Rect) {
+        use ratatui::text::{Line, Span};
+
+        // Create legend showing what the different timeline colors/characters mean
+        let glyphs = self.glyphs;
+        let night_char = glyphs.night;
+        let awake_char = glyphs.awake;
+        let work_char = glyphs.work;
+
+        let night_color = self.palette().night;
+        let awake_color = self.palette().awake;
+        let work_color = self.palette().work;
+
+        let legend_line = Line::from(vec![
+            Span::styled(format!("{night_char} "), Style::default().fg(night_color)),
+            Span::raw("Night  "),
+            Span::styled(format!("{awake_char} "), Style::default().fg(awake_color)),
+            Span::raw("Awake  "),
+            Span::styled(format!("{work_char} "), Style::default().fg(work_color)),
+            Span::raw("Work  "),
+            Span::styled(
+                format!("{} ", glyphs.midnight),
+                Style::default().fg(self.palette().midnight),
+            ),
+            Span::raw("Midnight  "),
+            Span::styled(
+                format!("{} ", self.now_line.glyph.unwrap_or(glyphs.now)),
+                Style::default().fg(self.now_line.color(self.palette())),
+            ),
+            Span::raw("Now  "),
+            Span::styled(
+                format!("{} ", glyphs.scrub),
+                Style::default().fg(self.palette().timeline_position),
+            ),
+            Span::raw("Timeline"),
+        ]);
+
+        let legend = Paragraph::new(legend_line)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+
+        f.render_widget(legend, area);
+    }
+
+    /// What the footer says about reminders that fired in the last few
+    /// minutes, e.g. "reminder: ping Maria (Sun 31 Mar 09:30 CEST)". Privacy
+    /// mode leaves out what they say.
+    fn reminder_notice(&self) -> Option<String> {
+        let (reminders, until) = self.fired_reminders.as_ref()?;
+        if std::time::Instant::now() >= *until {
+            return None;
+        }
+        let texts: Vec<String> = reminders
+            .iter()
+            .map(|reminder| {
+                if self.privacy_mode {
+                    reminder.local_time()
+                } else {
+                    format!("{} ({})", reminder.text, reminder.local_time())
+                }
+            })
+            .collect();
+        Some(format!("reminder: {}", texts.join(", ")))
+    }
+
+    /// What the footer says when the scrubbed time is early or late for
+    /// someone, e.g. "this is 06:30 for Bob, consider 1h 30m later". Only
+    /// shown once the timeline is scrubbed away from now.
+    fn etiquette_notice(&self) -> Option<String> {
+        if (self.timeline_position - self.current_time).abs() < chrono::Duration::minutes(15) {
+            return None;
+        }
+        let zones = self.timezone_manager.zones();
+        let hint = plan::etiquette_hint(zones, self.timeline_position, &self.etiquette)?;
+        let zone = &zones[hint.zone];
+        let name = if self.privacy_mode {
+            zone.display.code.as_str()
+        } else {
+            zone.effective_display_name()
+        };
+        let local_time = match self.display_format {
+            TimeFormat::TwentyFourHour => hint.local_time.format("%H:%M"),
+            TimeFormat::TwelveHour => hint.local_time.format("%I:%M %p"),
+        };
+        let direction = if hint.shift > chrono::Duration::zero() {
+            "later"
+        } else {
+            "earlier"
+        };
+        Some(format!(
+            "this is {local_time} for {name}, consider {} {direction}",
+            handoff::format_duration(hint.shift.abs())
+        ))
+    }
+
+    fn render_footer(&self, f: &mut Frame, area: Rect) {
+        let Glyphs {
+            recording,
+            separator,
+            ..
+        } = self.glyphs;
+        let footer_text = match (
+            &self.recording_macro,
+            self.pending_macro_register,
+            self.reminder_notice(),
+        ) {
+            (Some((register, _)), _, _) => {
+                format!("{recording} recording @{register} {separator} Q: stop")
+            }
+            (None, Some(MacroAction::Record), _) => "Q: press a register to record".to_string(),
+            (None, Some(MacroAction::Replay), _) => "@: press a register to replay".to_string(),
+            (None, None, Some(notice)) => {
+                format!("{notice} {separator} ?: help")
+            }
+            (None, None, None) if !self.marked_zones.is_empty() => format!(
+                "{} marked {separator} r: remove {separator} X: archive {separator} g: group {separator} A: accent {separator} Esc: clear",
+                self.marked_zones.len()
+            ),
+            (None, None, None)
+                if self
+                    .copied_until
+                    .is_some_and(|until| std::time::Instant::now() < until) =>
+            {
+                format!("time card copied {separator} ?: help")
+            }
+            (None, None, None) if self.privacy_mode => {
+                format!("private {separator} ?: help {separator} p: show labels")
+            }
+            (None, None, None) => match (
+                &self.sync_error,
+                self.etiquette_notice(),
+                self.handoff_status(),
+                &self.update_notice,
+            ) {
+                (Some(error), _, _, _) => {
+                    format!("sync failed: {error} {separator} ?: help {separator} q: quit")
+                }
+                (None, Some(hint), _, _) => format!("{hint} {separator} t: now"),
+                (None, None, Some(handoff), _) => format!("{handoff} {separator} ?: help"),
+                (None, None, None, Some(notice)) => format!("{notice} {separator} ?: help"),
+                (None, None, None, None) if self.zone_order != ZoneOrder::Offset => format!(
+                    "sorted by {} {separator} o: change {separator} ?: help",
+                    self.zone_order.label()
+                ),
+                (None, None, None, None) => {
+                    format!("?: help {separator} a: add {separator} q: quit")
+                }
+            },
+        };
+
+        let footer = Paragraph::new(footer_text)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+
+        f.render_widget(footer, area);
+    }
+
+    fn render_help_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        // Calculate modal size to fit content
+        let modal_width = area.width * 2 / 3; // Same as add city modal
+                                              // Calculate height based on content: title + max column content + footer + borders
+        let max_content_lines = 22; // Longest column has about 22 lines
+        let modal_height = (2 + max_content_lines + 1 + 4).min(area.height.saturating_sub(2)); // title + content + footer + borders + margin
+
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        // Split into sections using layout
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(2), // Title
+                Constraint::Min(1),    // Content
+                Constraint::Length(1), // Footer
+            ])
+            .split(inner);
+
+        // Render title
+        let title = Paragraph::new(format!(
+            "{}HELP & KEYBOARD SHORTCUTS",
+            self.glyphs.help_icon
+        ))
+        .style(
+            Style::default()
+                .fg(self.palette().selected_border)
+                .add_modifier(Modifier::BOLD),
+        )
+        .alignment(Alignment::Center);
+        f.render_widget(title, chunks[0]);
+
+        // Split content into two columns for better space usage
+        let content_chunks = Layout::default()
+            .direction(LayoutDirection::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(chunks[1]);
 
         // Left column content
         let left_sections = [
@@ -874,7 +3443,13 @@ impl App {
                     "Shift + h/l    Fine scrub (1 minute)",
                     "[ or ]         Adjust by ±15 minutes",
                     "{ or }         Adjust by ±1 hour",
+                    "< or >         Jump by ±1 day",
+                    "( or )         Slip milestones by ±1 day",
                     "t              Reset to current time",
+                    ":              Go to a time in the selected zone",
+                    "Ctrl-o / Ctrl-i Back / forward through jumps",
+                    "y              Copy every zone's time (pick a layout)",
+                    "x              Offsets between all zones",
                 ],
             ),
             (
@@ -882,6 +3457,9 @@ impl App {
                 vec![
                     "j/↓ or k/↑     Navigate between zones",
                     "               Selected has colored border",
+                    "v              Toggle split view",
+                    "V / Tab        Next layout / switch pane",
+                    "o              Sort by offset / offset groups / work start / end",
                 ],
             ),
             (
@@ -891,11 +3469,29 @@ impl App {
                     "n              Toggle short/full names",
                     "d              Toggle date display",
                     "s              Toggle sunrise/sunset times",
+                    "z              Toggle focus mode",
+                    "f              Toggle hour ruler on selected zone",
+                    "D              Start at selected zone's midnight",
+                    "p              Toggle privacy mode",
                     "c              Cycle color themes",
                 ],
             ),
         ];
 
+        // Indicator lines are drawn with whichever glyphs the terminal supports
+        let g = self.glyphs;
+        let indicators = [
+            format!("{}              Red line: Current time", g.now),
+            format!("{}              Colored line: Timeline position", g.scrub),
+            format!("{}              DST spring forward", g.spring_forward),
+            format!("{}              DST fall back", g.fall_back),
+            format!("{}              Milestone", g.milestone),
+            format!(
+                "{} {} {}          Night, Awake, Work hours",
+                g.night, g.awake, g.work
+            ),
+        ];
+
         // Right column content
         let right_sections = [
             (
@@ -906,21 +3502,25 @@ impl App {
                     "e              Rename selected timezone",
                     "E              Clear custom name",
                     "1-8            Quick-select search results",
+                    "Space / Esc    Mark zone for r, g, A / clear marks",
+                    "g              Add marked zones to a layout",
+                    "A              Cycle marked zones' accent color",
+                    "X / U          Archive zone / restore archived",
+                    "u / Ctrl-r     Undo / redo a change to the board",
                 ],
             ),
             (
                 "INDICATORS",
-                vec![
-                    "│              Red line: Current time",
-                    "┃              Colored line: Timeline position",
-                    "⇈              DST spring forward",
-                    "⇊              DST fall back",
-                    "░ ▒ ▓          Night, Awake, Work hours",
-                ],
+                indicators.iter().map(String::as_str).collect(),
             ),
             (
                 "CONTROLS",
                 vec![
+                    "W              Edit work hours",
+                    "M              Slip chosen milestones",
+                    "R              QR code of the scrubbed time",
+                    "Q{a-z} ... Q   Record macro",
+                    "@{a-z}         Replay macro",
                     "?              Show/hide help",
                     "q              Quit",
                     "Esc            Cancel operation",
@@ -946,60 +3546,231 @@ impl App {
             .wrap(Wrap { trim: true });
         f.render_widget(left_content, content_chunks[0]);
 
-        // Render right column
-        let right_text = right_sections
-            .iter()
-            .map(|(section, items)| {
-                let mut section_text = format!("\n{section}\n");
-                for item in items {
-                    section_text.push_str(&format!("  {item}\n"));
-                }
-                section_text
-            })
-            .collect::<Vec<_>>()
-            .join("");
+        // Render right column
+        let right_text = right_sections
+            .iter()
+            .map(|(section, items)| {
+                let mut section_text = format!("\n{section}\n");
+                for item in items {
+                    section_text.push_str(&format!("  {item}\n"));
+                }
+                section_text
+            })
+            .collect::<Vec<_>>()
+            .join("");
+
+        let right_content = Paragraph::new(right_text)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true });
+        f.render_widget(right_content, content_chunks[1]);
+
+        // Render footer
+        let footer = Paragraph::new("Press any key to close")
+            .style(Style::default().fg(Color::Gray))
+            .alignment(Alignment::Center);
+        f.render_widget(footer, chunks[2]);
+
+        // Render border around the entire modal
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .border_style(Style::default().fg(self.palette().selected_border))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_add_zone_modal(&self, f: &mut Frame) {
+        let area = f.area();
+
+        // Calculate height more generously to ensure examples are shown
+        let results_height = if !self.zone_search_results.is_empty() {
+            self.zone_search_results.len() as u16 + 2 // +2 for header and spacing
+        } else {
+            8 // Space for examples and help text
+        };
+
+        let modal_height = (3 + results_height + 3 + 2).min(area.height.saturating_sub(4)); // header + content + controls + borders
+
+        let popup_area = Rect {
+            x: area.width / 6,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: area.width * 2 / 3,
+            height: modal_height,
+        };
+
+        // Clear the background
+        f.render_widget(Clear, popup_area);
+
+        // Split the modal into sections
+        let inner = popup_area.inner(ratatui::layout::Margin {
+            horizontal: 1,
+            vertical: 1,
+        });
+        let chunks = Layout::default()
+            .direction(LayoutDirection::Vertical)
+            .constraints([
+                Constraint::Length(3), // Header and input
+                Constraint::Min(1),    // Results table
+                Constraint::Length(3), // Help text
+            ])
+            .split(inner);
+
+        // Render header and input
+        let header_text = format!("Search: {}", self.add_zone_input);
+        let header = Paragraph::new(header_text)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
+        f.render_widget(header, chunks[0]);
+
+        // Render results table or help text
+        if !self.zone_search_results.is_empty() {
+            self.render_search_results_table(f, chunks[1]);
+        } else {
+            self.render_search_help(f, chunks[1]);
+        }
+
+        // Render controls help
+        let controls = if !self.zone_search_results.is_empty() {
+            "↑↓: Navigate | Enter: Add selected | 1-8: Quick select | Esc: Cancel"
+        } else {
+            "Type to search cities, countries, or abbreviations | Esc: Cancel"
+        };
+
+        let controls_paragraph = Paragraph::new(controls)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[2]);
+
+        // Render the modal border
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Add Timezone ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Green)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Green))
+            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    fn render_search_results_table(&self, f: &mut Frame, area: Rect) {
+        let mut rows = Vec::new();
+
+        for (i, result) in self.zone_search_results.iter().enumerate() {
+            if let Some((city_country, time_str, offset, code)) =
+                self.get_search_result_parts(result)
+            {
+                rows.push(Row::new(vec![
+                    Cell::from(format!("{}", i + 1)),
+                    Cell::from(city_country),
+                    Cell::from(time_str),
+                    Cell::from(offset),
+                    Cell::from(code),
+                ]));
+            } else {
+                rows.push(Row::new(vec![
+                    Cell::from(format!("{}", i + 1)),
+                    Cell::from(result.clone()),
+                    Cell::from(""),
+                    Cell::from(""),
+                    Cell::from(""),
+                ]));
+            }
+        }
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),  // #
+                Constraint::Min(20),    // City, Country
+                Constraint::Length(10), // Time
+                Constraint::Length(8),  // Offset
+                Constraint::Length(6),  // Code
+            ],
+        )
+        .header(Row::new(vec![
+            Cell::from("#").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("City, Country")
+                .style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("Time").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("Offset")
+                .style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
+            Cell::from("Code").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
+        ]))
+        .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
+        .row_highlight_style(
+            ratatui::style::Style::default()
+                .fg(self.palette().work)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("> ")
+        .column_spacing(1);
+
+        // Create table state with selection
+        let mut table_state = TableState::default();
+        table_state.select(Some(self.selected_search_result));
 
-        let right_content = Paragraph::new(right_text)
-            .style(Style::default().fg(Color::White))
-            .wrap(Wrap { trim: true });
-        f.render_widget(right_content, content_chunks[1]);
+        f.render_stateful_widget(table, area, &mut table_state);
+    }
 
-        // Render footer
-        let footer = Paragraph::new("Press any key to close")
-            .style(Style::default().fg(Color::Gray))
-            .alignment(Alignment::Center);
-        f.render_widget(footer, chunks[2]);
+    fn render_search_help(&self, f: &mut Frame, area: Rect) {
+        let help_text = if !self.add_zone_input.is_empty() {
+            vec![
+                "No matching timezones found.",
+                "",
+                "Try searching for:",
+                "  • City names: Tokyo, New York, London",
+                "  • Countries: Japan, USA, Germany",
+                "  • Abbreviations: NYC, SF, LA",
+                "  • Zone abbreviations: CST, JST, CET",
+                "  • Regions: Bay Area, Silicon Valley",
+            ]
+        } else {
+            vec![
+                "Search for cities, countries, or abbreviations:",
+                "",
+                "Examples:",
+                "  Tokyo, London, Mumbai",
+                "  NYC, SF, LA, DC",
+                "  Japan, USA, Germany",
+                "  Bay Area, Silicon Valley",
+            ]
+        };
 
-        // Render border around the entire modal
-        let border = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(self.color_theme.get_selected_border_color()))
-            .style(Style::default().bg(Color::Black));
-        f.render_widget(border, popup_area);
+        let help_paragraph = Paragraph::new(help_text.join("\n"))
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .alignment(Alignment::Left);
+        f.render_widget(help_paragraph, area);
     }
 
-    fn render_add_zone_modal(&self, f: &mut Frame) {
+    fn render_rename_zone_modal(&self, f: &mut Frame) {
         let area = f.area();
 
-        // Calculate height more generously to ensure examples are shown
-        let results_height = if !self.zone_search_results.is_empty() {
-            self.zone_search_results.len() as u16 + 2 // +2 for header and spacing
-        } else {
-            8 // Space for examples and help text
-        };
-
-        let modal_height = (3 + results_height + 3 + 2).min(area.height.saturating_sub(4)); // header + content + controls + borders
+        let modal_height = 9; // Smaller modal for rename
+        let modal_width = area.width.saturating_sub(area.width / 3).min(60);
 
         let popup_area = Rect {
-            x: area.width / 6,
+            x: (area.width.saturating_sub(modal_width)) / 2,
             y: (area.height.saturating_sub(modal_height)) / 2,
-            width: area.width * 2 / 3,
+            width: modal_width,
             height: modal_height,
         };
 
         // Clear the background
         f.render_widget(Clear, popup_area);
 
+        // Get current zone info, from whichever pane has focus
+        let Some(current_zone) = self.focused_zone() else {
+            return;
+        };
+        let city_name = crate::time::TimeZoneManager::get_all_available_timezones()
+            .into_iter()
+            .find(|(tz, _, _, _, _)| *tz == current_zone.tz)
+            .map(|(_, name, _, _, _)| name)
+            .unwrap_or_else(|| current_zone.tz.to_string());
+
         // Split the modal into sections
         let inner = popup_area.inner(ratatui::layout::Margin {
             horizontal: 1,
@@ -1008,219 +3779,582 @@ impl App {
         let chunks = Layout::default()
             .direction(LayoutDirection::Vertical)
             .constraints([
-                Constraint::Length(3), // Header and input
-                Constraint::Min(1),    // Results table
-                Constraint::Length(3), // Help text
+                Constraint::Length(2), // Zone info
+                Constraint::Length(2), // Input field
+                Constraint::Length(1), // Spacer
+                Constraint::Length(2), // Controls help
             ])
             .split(inner);
 
-        // Render header and input
-        let header_text = format!("Search: {}", self.add_zone_input);
-        let header = Paragraph::new(header_text)
+        // Render zone info
+        let zone_info = format!("Renaming: {city_name}");
+        let zone_paragraph = Paragraph::new(zone_info)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Gray));
+        f.render_widget(zone_paragraph, chunks[0]);
+
+        // Render input field
+        let input_text = format!("Custom name: {}", self.rename_zone_input);
+        let input_paragraph = Paragraph::new(input_text)
             .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
-        f.render_widget(header, chunks[0]);
+        f.render_widget(input_paragraph, chunks[1]);
 
-        // Render results table or help text
-        if !self.zone_search_results.is_empty() {
-            self.render_search_results_table(f, chunks[1]);
+        // Render controls help
+        let controls = "Enter: Save | Esc: Cancel | Empty to remove custom name";
+        let controls_paragraph = Paragraph::new(controls)
+            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .alignment(Alignment::Center);
+        f.render_widget(controls_paragraph, chunks[3]);
+
+        // Render the modal border
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Rename Timezone ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+        f.render_widget(border, popup_area);
+    }
+
+    /// The time card layouts to copy with, each with a preview of its first
+    /// line for the current board
+    fn render_share_menu(&self, f: &mut Frame, cursor: usize) {
+        use ratatui::text::Line;
+
+        let area = f.area();
+        let options: Vec<(String, String)> = std::iter::once(None)
+            .chain(self.card_templates.iter().map(Some))
+            .map(|template| {
+                let name = template.map_or("Table", |template| template.name.as_str());
+                let preview = self.time_card(template);
+                let first = preview.lines().next().unwrap_or_default().to_string();
+                (name.to_string(), first)
+            })
+            .collect();
+
+        let modal_width = area.width.saturating_sub(area.width / 3).min(70);
+        let modal_height = (options.len() as u16 + 4).min(area.height);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let mut lines: Vec<Line> = options
+            .iter()
+            .enumerate()
+            .map(|(i, (name, preview))| {
+                let style = if i == cursor {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(self.palette().selected_border)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::styled(format!("{} {name:<12} {preview}", i + 1), style)
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "1-9 / Enter: Copy | Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        ));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Copy Time Card ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+
+    fn render_archive_picker(&self, f: &mut Frame, cursor: usize) {
+        use ratatui::text::Line;
+
+        let area = f.area();
+        let modal_width = area.width.saturating_sub(area.width / 3).min(50);
+        let modal_height = (self.archived.len() as u16 + 4).min(area.height);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let mut lines: Vec<Line> = self
+            .archived
+            .iter()
+            .enumerate()
+            .map(|(i, entry)| {
+                let style = if i == cursor {
+                    Style::default()
+                        .fg(Color::Black)
+                        .bg(self.palette().selected_border)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                let name = match entry.custom_label().filter(|_| !self.privacy_mode) {
+                    Some(label) => format!("{label} ({})", entry.city_name()),
+                    None => entry.city_name().to_string(),
+                };
+                Line::styled(format!("{} {name}", i + 1), style)
+            })
+            .collect();
+        lines.push(Line::from(""));
+        lines.push(Line::styled(
+            "1-9 / Enter: Restore | Esc: Cancel",
+            Style::default().fg(Color::DarkGray),
+        ));
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Archived Zones ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        f.render_widget(Paragraph::new(lines).block(block), popup_area);
+    }
+
+    /// Where a time typed after `:` points in the selected zone: the next
+    /// time its clocks read a clock time, or the moment of a pasted org-mode
+    /// timestamp
+    fn goto_target(&self, input: &str) -> Option<DateTime<Utc>> {
+        let zone = self.focused_zone()?;
+        match org::parse(input) {
+            Some(local) => local
+                .and_local_timezone(zone.tz)
+                .earliest()
+                .map(|local| local.with_timezone(&Utc)),
+            None => crate::time::next_local_time(
+                zone.tz,
+                self.current_time,
+                crate::time::parse_clock_time(input)?,
+            ),
+        }
+    }
+
+    fn render_goto_modal(&self, f: &mut Frame, input: &str) {
+        use ratatui::text::{Line, Span};
+
+        let area = f.area();
+        let modal_width = area.width.saturating_sub(area.width / 3).min(76);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(6)) / 2,
+            width: modal_width,
+            height: 6,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let Some(zone) = self.focused_zone() else {
+            return;
+        };
+        let valid = self.goto_target(input).is_some();
+        let input_style = ratatui::style::Style::default().fg(if valid || input.is_empty() {
+            ratatui::style::Color::White
         } else {
-            self.render_search_help(f, chunks[1]);
+            ratatui::style::Color::Red
+        });
+        let lines = vec![
+            Line::from(Span::styled(
+                format!("Time in {}: {input}", zone.effective_display_name()),
+                input_style,
+            )),
+            Line::from(""),
+            Line::from(Span::styled(
+                "e.g. 14:30, 2:30pm or <2024-06-01 Sat 15:00> | Enter: Go | Esc: Cancel",
+                ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
+            )),
+        ];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Go to Time ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .alignment(Alignment::Center),
+            popup_area,
+        );
+    }
+
+    fn render_group_modal(&self, f: &mut Frame, input: &str) {
+        use ratatui::text::{Line, Span};
+
+        let area = f.area();
+        let modal_width = area.width.saturating_sub(area.width / 3).min(60);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(6)) / 2,
+            width: modal_width,
+            height: 6,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let count = self.bulk_targets().len();
+        let zones = if count == 1 { "zone" } else { "zones" };
+        let existing = self
+            .layouts
+            .iter()
+            .any(|layout| layout.name.eq_ignore_ascii_case(input.trim()));
+        let hint = if existing || input.trim().is_empty() {
+            "Enter: Add | Esc: Cancel"
+        } else {
+            "New layout | Enter: Create | Esc: Cancel"
+        };
+        let lines = vec![
+            Line::from(format!("Add {count} {zones} to layout: {input}")),
+            Line::from(""),
+            Line::from(Span::styled(
+                hint,
+                ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray),
+            )),
+        ];
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Group Zones ")
+            .title_style(
+                ratatui::style::Style::default()
+                    .fg(ratatui::style::Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
+            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+        f.render_widget(
+            Paragraph::new(lines)
+                .block(block)
+                .alignment(Alignment::Center),
+            popup_area,
+        );
+    }
+
+    /// Every zone's local time at the scrubbed instant, as plain text laid
+    /// out by `template`, or as an aligned table without one
+    pub fn time_card(&self, template: Option<&CardTemplate>) -> String {
+        let zones = self.timezone_manager.zones();
+        match template {
+            Some(template) => crate::card::render_template(
+                template,
+                zones,
+                self.timeline_position,
+                &self.display_format,
+                self.privacy_mode,
+            ),
+            None => crate::card::time_card(
+                zones,
+                self.timeline_position,
+                &self.display_format,
+                self.privacy_mode,
+            ),
+        }
+    }
+
+    /// The scrubbed instant in the selected zone, as an ISO timestamp and the
+    /// zone's name, for sharing. Custom labels are left out in privacy mode.
+    pub fn share_text(&self) -> Option<String> {
+        let zone = self.focused_zone()?;
+        let time = zone
+            .convert_time(self.timeline_position)
+            .to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+        let mut text = format!("{time} {}", zone.get_city_name());
+        if let Some(label) = zone.display.label.as_ref().filter(|_| !self.privacy_mode) {
+            text.push_str(&format!(" ({label})"));
         }
+        Some(text)
+    }
+
+    /// How far ahead each zone's clocks are of each other's at the scrubbed
+    /// instant, with the pairs DST changes within the week
+    fn render_matrix_modal(&self, f: &mut Frame) {
+        let area = f.area();
+        let text = crate::matrix::render(self.timezone_manager.zones(), self.timeline_position);
+        let text_width = text.lines().map(|line| line.chars().count()).max();
+        let modal_width = (text_width.unwrap_or_default() as u16 + 4).min(area.width);
+        let modal_height = (text.lines().count() as u16 + 2).min(area.height);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+        f.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(" Offsets: how far ahead each column is of each row ")
+            .title_style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        let matrix = Paragraph::new(text)
+            .style(Style::default().fg(Color::White))
+            .block(block.padding(ratatui::widgets::Padding::horizontal(1)));
+        f.render_widget(matrix, popup_area);
+    }
 
-        // Render controls help
-        let controls = if !self.zone_search_results.is_empty() {
-            "↑↓: Navigate | Enter: Add selected | 1-8: Quick select | Esc: Cancel"
-        } else {
-            "Type to search cities, countries, or abbreviations | Esc: Cancel"
+    /// Shows the shared instant as a QR code, for phones in the room to scan
+    fn render_qr_modal(&self, f: &mut Frame) {
+        let area = f.area();
+        let Some(text) = self.share_text() else {
+            return;
         };
+        let qr = QrWidget::new(&text, self.glyphs.half_block);
+        let (qr_width, qr_height) = qr.as_ref().map_or((0, 0), QrWidget::size);
 
-        let controls_paragraph = Paragraph::new(controls)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
-            .alignment(Alignment::Center);
-        f.render_widget(controls_paragraph, chunks[2]);
+        // Room for the code, the text under it and the border
+        let modal_width = (qr_width.max(text.chars().count() as u16) + 4).min(area.width);
+        let modal_height = (qr_height + 4).min(area.height);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: (area.height.saturating_sub(modal_height)) / 2,
+            width: modal_width,
+            height: modal_height,
+        };
+        f.render_widget(Clear, popup_area);
 
-        // Render the modal border
         let border = Block::default()
             .borders(Borders::ALL)
-            .title(" Add Timezone ")
+            .border_set(self.glyphs.border)
+            .title(" Scan to share this time ")
             .title_style(
-                ratatui::style::Style::default()
-                    .fg(ratatui::style::Color::Green)
+                Style::default()
+                    .fg(Color::Blue)
                     .add_modifier(Modifier::BOLD),
             )
-            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Green))
-            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        let inner = border.inner(popup_area);
         f.render_widget(border, popup_area);
-    }
-
-    fn render_search_results_table(&self, f: &mut Frame, area: Rect) {
-        let mut rows = Vec::new();
 
-        for (i, result) in self.zone_search_results.iter().enumerate() {
-            if let Some((city_country, time_str, offset, code)) =
-                self.get_search_result_parts(result)
-            {
-                rows.push(Row::new(vec![
-                    Cell::from(format!("{}", i + 1)),
-                    Cell::from(city_country),
-                    Cell::from(time_str),
-                    Cell::from(offset),
-                    Cell::from(code),
-                ]));
-            } else {
-                rows.push(Row::new(vec![
-                    Cell::from(format!("{}", i + 1)),
-                    Cell::from(result.clone()),
-                    Cell::from(""),
-                    Cell::from(""),
-                    Cell::from(""),
-                ]));
+        let fits = qr_width <= inner.width && qr_height + 2 <= inner.height;
+        match qr.filter(|_| fits) {
+            Some(qr) => {
+                let code_area = Rect {
+                    x: inner.x + (inner.width - qr_width) / 2,
+                    y: inner.y,
+                    width: qr_width,
+                    height: qr_height,
+                };
+                f.render_widget(qr, code_area);
+                let caption_area = Rect {
+                    y: inner.y + qr_height + 1,
+                    height: 1,
+                    ..inner
+                };
+                f.render_widget(
+                    Paragraph::new(text)
+                        .style(Style::default().fg(Color::White))
+                        .alignment(Alignment::Center),
+                    caption_area,
+                );
+            }
+            None => {
+                f.render_widget(
+                    Paragraph::new(format!(
+                        "{text}\n\nEnlarge the terminal to show the QR code"
+                    ))
+                    .style(Style::default().fg(Color::White))
+                    .alignment(Alignment::Center)
+                    .wrap(Wrap { trim: true }),
+                    inner,
+                );
             }
         }
+    }
 
-        let table = Table::new(
-            rows,
-            [
-                Constraint::Length(3),  // #
-                Constraint::Min(20),    // City, Country
-                Constraint::Length(10), // Time
-                Constraint::Length(8),  // Offset
-                Constraint::Length(6),  // Code
-            ],
-        )
-        .header(Row::new(vec![
-            Cell::from("#").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("City, Country")
-                .style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("Time").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("Offset")
-                .style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
-            Cell::from("Code").style(ratatui::style::Style::default().add_modifier(Modifier::BOLD)),
-        ]))
-        .style(ratatui::style::Style::default().fg(ratatui::style::Color::White))
-        .row_highlight_style(
-            ratatui::style::Style::default()
-                .fg(self.color_theme.get_work_color())
-                .add_modifier(Modifier::BOLD),
-        )
-        .highlight_symbol("> ")
-        .column_spacing(1);
+    /// Lists the zone's hour boundaries being edited with a 24-hour preview
+    /// of the shading. Sits at the bottom so the board above updates in view.
+    fn render_hours_editor(&self, f: &mut Frame, editor: &HoursEditor) {
+        use ratatui::text::{Line, Span};
 
-        // Create table state with selection
-        let mut table_state = TableState::default();
-        table_state.select(Some(self.selected_search_result));
+        let Some(zone) = self.focused_pane().0.zones().get(editor.zone) else {
+            return;
+        };
+        let hours = zone.hours(&self.time_config);
+        let area = f.area();
+        let modal_height = (HourBoundary::ALL.len() as u16 + 6).min(area.height);
+        let modal_width = area.width.saturating_sub(4).min(56);
+        let popup_area = Rect {
+            x: (area.width.saturating_sub(modal_width)) / 2,
+            y: area.height.saturating_sub(modal_height),
+            width: modal_width,
+            height: modal_height,
+        };
+        f.render_widget(Clear, popup_area);
 
-        f.render_stateful_widget(table, area, &mut table_state);
-    }
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(format!(" Work Hours: {} ", zone.effective_display_name()))
+            .title_style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        let inner = border.inner(popup_area);
+        f.render_widget(border, popup_area);
 
-    fn render_search_help(&self, f: &mut Frame, area: Rect) {
-        let help_text = if !self.add_zone_input.is_empty() {
-            vec![
-                "No matching timezones found.",
-                "",
-                "Try searching for:",
-                "  • City names: Tokyo, New York, London",
-                "  • Countries: Japan, USA, Germany",
-                "  • Abbreviations: NYC, SF, LA",
-                "  • Regions: Bay Area, Silicon Valley",
-            ]
-        } else {
-            vec![
-                "Search for cities, countries, or abbreviations:",
-                "",
-                "Examples:",
-                "  Tokyo, London, Mumbai",
-                "  NYC, SF, LA, DC",
-                "  Japan, USA, Germany",
-                "  Bay Area, Silicon Valley",
-            ]
-        };
+        let mut lines: Vec<Line> = HourBoundary::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, boundary)| {
+                let hour = hours.get(*boundary);
+                let text = match self.display_format {
+                    TimeFormat::TwentyFourHour => format!("{hour:02}:00"),
+                    TimeFormat::TwelveHour => {
+                        let suffix = if hour % 24 < 12 { "AM" } else { "PM" };
+                        format!("{}:00 {suffix}", (hour + 11) % 12 + 1)
+                    }
+                };
+                let style = if i == editor.boundary {
+                    Style::default()
+                        .fg(self.palette().selected_border)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Line::styled(format!("{:<12} {text}", boundary.label()), style)
+            })
+            .collect();
 
-        let help_paragraph = Paragraph::new(help_text.join("\n"))
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
-            .alignment(Alignment::Left);
-        f.render_widget(help_paragraph, area);
+        // One cell per hour, shaded like the bars
+        let day: Vec<Span> = (0..24)
+            .map(|hour| {
+                let activity = hours.get_time_activity(hour);
+                let color = hours.get_activity_color(activity, self.palette());
+                Span::styled(
+                    self.glyphs.activity(activity).to_string(),
+                    Style::default().fg(color),
+                )
+            })
+            .collect();
+        lines.push(Line::default());
+        lines.push(Line::from(day));
+        lines.push(Line::styled(
+            "↑↓: choose | ←→: ±1 hour | Enter: save | Esc: cancel",
+            Style::default().fg(Color::DarkGray),
+        ));
+        f.render_widget(Paragraph::new(lines), inner);
     }
 
-    fn render_rename_zone_modal(&self, f: &mut Frame) {
+    /// Lists milestones with their current and planned times. Sits at the
+    /// bottom of the screen so the previewed markers on the bars stay visible.
+    fn render_milestone_plan(&self, f: &mut Frame, plan: &MilestonePlan) {
         let area = f.area();
-
-        let modal_height = 9; // Smaller modal for rename
-        let modal_width = area.width.saturating_sub(area.width / 3).min(60);
-
+        let modal_height = (plan.original.len() as u16 + 5).min(area.height);
+        let modal_width = area.width.saturating_sub(4).min(76);
         let popup_area = Rect {
             x: (area.width.saturating_sub(modal_width)) / 2,
-            y: (area.height.saturating_sub(modal_height)) / 2,
+            y: area.height.saturating_sub(modal_height),
             width: modal_width,
             height: modal_height,
         };
-
-        // Clear the background
         f.render_widget(Clear, popup_area);
 
-        // Get current zone info
-        let current_zone = &self.timezone_manager.zones()[self.selected_zone_index];
-        let city_name = self
-            .timezone_manager
-            .zones()
+        let format = match self.display_format {
+            TimeFormat::TwentyFourHour => "%a %d %b %H:%M",
+            TimeFormat::TwelveHour => "%a %d %b %I:%M %p",
+        };
+        let local = |time: DateTime<Utc>| time.with_timezone(&Local).format(format).to_string();
+
+        let rows: Vec<Row> = plan
+            .original
             .iter()
+            .zip(&plan.selected)
             .enumerate()
-            .find(|(idx, zone)| *idx == self.selected_zone_index && zone.tz == current_zone.tz)
-            .and_then(|(_, zone)| {
-                let available = crate::time::TimeZoneManager::get_all_available_timezones();
-                available
-                    .iter()
-                    .find(|(tz, _, _, _, _)| *tz == zone.tz)
-                    .map(|(_, name, _, _, _)| name.clone())
+            .map(|(i, (milestone, &selected))| {
+                let planned = if selected && !plan.shift.is_zero() {
+                    format!("→ {}", local(milestone.time + plan.shift))
+                } else {
+                    String::new()
+                };
+                let style = if i == plan.cursor {
+                    Style::default()
+                        .fg(self.palette().selected_border)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::White)
+                };
+                Row::new(vec![
+                    Cell::from(if selected { "[x]" } else { "[ ]" }),
+                    Cell::from(milestone.name.clone()),
+                    Cell::from(milestone.group.clone().unwrap_or_default()),
+                    Cell::from(local(milestone.time)),
+                    Cell::from(planned),
+                ])
+                .style(style)
             })
-            .unwrap_or_else(|| current_zone.tz.to_string());
-
-        // Split the modal into sections
-        let inner = popup_area.inner(ratatui::layout::Margin {
-            horizontal: 1,
-            vertical: 1,
-        });
+            .collect();
+        let border = Block::default()
+            .borders(Borders::ALL)
+            .border_set(self.glyphs.border)
+            .title(format!(" Slip milestones {} ", format_shift(plan.shift)))
+            .title_style(
+                Style::default()
+                    .fg(Color::Blue)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .border_style(Style::default().fg(Color::Blue))
+            .style(Style::default().bg(Color::Black));
+        let inner = border.inner(popup_area);
+        f.render_widget(border, popup_area);
         let chunks = Layout::default()
             .direction(LayoutDirection::Vertical)
             .constraints([
-                Constraint::Length(2), // Zone info
-                Constraint::Length(2), // Input field
+                Constraint::Min(1),    // Milestones
                 Constraint::Length(1), // Spacer
-                Constraint::Length(2), // Controls help
+                Constraint::Length(1), // Controls help
             ])
             .split(inner);
 
-        // Render zone info
-        let zone_info = format!("Renaming: {city_name}");
-        let zone_paragraph = Paragraph::new(zone_info)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::Gray));
-        f.render_widget(zone_paragraph, chunks[0]);
-
-        // Render input field
-        let input_text = format!("Custom name: {}", self.rename_zone_input);
-        let input_paragraph = Paragraph::new(input_text)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::White));
-        f.render_widget(input_paragraph, chunks[1]);
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(3),
+                Constraint::Min(10),
+                Constraint::Length(8),
+                Constraint::Length(19),
+                Constraint::Length(21),
+            ],
+        );
+        f.render_widget(table, chunks[0]);
 
-        // Render controls help
-        let controls = "Enter: Save | Esc: Cancel | Empty to remove custom name";
+        let controls =
+            "Space: pick | g: group | ( ) ±1 day | { } ±1 hour | Enter: apply | Esc: cancel";
         let controls_paragraph = Paragraph::new(controls)
-            .style(ratatui::style::Style::default().fg(ratatui::style::Color::DarkGray))
+            .style(Style::default().fg(Color::DarkGray))
             .alignment(Alignment::Center);
-        f.render_widget(controls_paragraph, chunks[3]);
-
-        // Render the modal border
-        let border = Block::default()
-            .borders(Borders::ALL)
-            .title(" Rename Timezone ")
-            .title_style(
-                ratatui::style::Style::default()
-                    .fg(ratatui::style::Color::Blue)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .border_style(ratatui::style::Style::default().fg(ratatui::style::Color::Blue))
-            .style(ratatui::style::Style::default().bg(ratatui::style::Color::Black));
-        f.render_widget(border, popup_area);
+        f.render_widget(controls_paragraph, chunks[2]);
     }
 
     fn get_search_result_parts(&self, city_name: &str) -> Option<(String, String, String, String)> {
@@ -1292,7 +4426,10 @@ mod tests {
 
     #[test]
     fn test_cycle_color_theme() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         let initial_theme = app.color_theme;
 
         app.update(Message::CycleColorTheme);
@@ -1308,9 +4445,162 @@ mod tests {
         assert_eq!(app.color_theme, initial_theme); // Should be back to the original after 6 cycles total
     }
 
+    #[test]
+    fn test_zone_order() {
+        let now = DateTime::parse_from_rfc3339("2024-01-17T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let zones =
+            ["London", "Tokyo", "New York"].map(|city| ZoneConfigCompat::Simple(city.to_string()));
+        let mut app = App {
+            ephemeral: true,
+            current_time: now,
+            timezone_manager: App::manager_from_zone_configs(&zones),
+            selected_zone_index: 1,
+            ..App::default()
+        };
+        let cities = |app: &App| -> Vec<String> {
+            app.timezone_manager
+                .zones()
+                .iter()
+                .map(|zone| zone.get_city_name())
+                .collect()
+        };
+        assert_eq!(cities(&app), ["New York", "London", "Tokyo"]);
+
+        app.update(Message::CycleZoneOrder);
+        assert_eq!(app.zone_order, ZoneOrder::OffsetGroups);
+        assert_eq!(cities(&app), ["New York", "London", "Tokyo"]);
+
+        // Wednesday noon UTC: New York starts first, then Tokyo overnight
+        app.update(Message::CycleZoneOrder);
+        assert_eq!(cities(&app), ["New York", "Tokyo", "London"]);
+        assert_eq!(app.selected_zone_index, 2);
+
+        // London, already at work, finishes first
+        app.update(Message::CycleZoneOrder);
+        assert_eq!(cities(&app), ["London", "New York", "Tokyo"]);
+        assert_eq!(app.selected_zone_index, 0);
+
+        app.update(Message::CycleZoneOrder);
+        assert_eq!(app.zone_order, ZoneOrder::Offset);
+        assert_eq!(cities(&app), ["New York", "London", "Tokyo"]);
+    }
+
+    #[test]
+    fn test_offset_groups() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let zones = ["Kathmandu", "Kolkata", "Karachi", "Islamabad"]
+            .map(|city| ZoneConfigCompat::Simple(city.to_string()));
+        let mut app = App {
+            ephemeral: true,
+            timezone_manager: App::manager_from_zone_configs(&zones),
+            zone_order: ZoneOrder::OffsetGroups,
+            ..App::default()
+        };
+        app.apply_zone_order();
+        let cities: Vec<String> = app
+            .timezone_manager
+            .zones()
+            .iter()
+            .map(|zone| zone.get_city_name())
+            .collect();
+        assert_eq!(cities[2..], ["Kolkata", "Kathmandu"]);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        terminal.draw(|f| app.view(f)).unwrap();
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        // Karachi and Islamabad share one separator
+        assert_eq!(text.matches("── UTC+5 ─").count(), 1);
+        assert!(text.contains("── UTC+5:30 ─"));
+        assert!(text.contains("── UTC+5:45 ─"));
+    }
+
+    #[test]
+    fn test_handoff_status() {
+        let now = DateTime::parse_from_rfc3339("2025-03-05T05:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut app = App {
+            ephemeral: true,
+            current_time: now,
+            handoff: Some(HandoffConfig {
+                chain: vec!["Sydney".to_string(), "London".to_string()],
+                min_overlap_minutes: 0,
+            }),
+            ..App::default()
+        };
+        assert_eq!(
+            app.handoff_status().as_deref(),
+            Some("Sydney → London in 2h (07:00 there)")
+        );
+
+        // Sydney's evening and London's morning never meet
+        app.handoff.as_mut().unwrap().min_overlap_minutes = 30;
+        assert_eq!(
+            app.handoff_status().as_deref(),
+            Some("Sydney → London in 2h (07:00 there) ⚠ 0m overlap")
+        );
+    }
+
+    #[test]
+    fn test_user_themes() {
+        let dusk = UserTheme {
+            name: "Dusk".to_string(),
+            inherits: ColorTheme::Sunset,
+            work: Some("#ff8800".to_string()),
+            ..UserTheme::default()
+        };
+        let mut app = App {
+            ephemeral: true,
+            color_theme: ColorTheme::Monochrome,
+            themes: vec![dusk.clone()],
+            ..App::default()
+        };
+
+        // User themes come after the built-in ones, then back to the start
+        app.update(Message::CycleColorTheme);
+        assert_eq!(app.user_theme.as_deref(), Some("Dusk"));
+        assert_eq!(app.color_theme, ColorTheme::Sunset);
+        assert_eq!(app.palette(), dusk.palette(false));
+        assert_eq!(app.palette().work, Color::Rgb(0xff, 0x88, 0x00));
+        app.update(Message::CycleColorTheme);
+        assert_eq!(app.user_theme, None);
+        assert_eq!(app.color_theme, ColorTheme::Default);
+
+        // A layout's own theme only colors its pane
+        let layout = LayoutConfig {
+            name: "Customers".to_string(),
+            zones: Vec::new(),
+            theme: Some("dusk".to_string()),
+        };
+        assert_eq!(app.layout_palette(Some(&layout)), dusk.palette(false));
+        assert_eq!(app.layout_palette(None), ColorTheme::Default.palette());
+
+        assert!(app.select_theme("ocean"));
+        assert!(!app.select_theme("sepia"));
+        assert_eq!(app.color_theme, ColorTheme::Ocean);
+
+        // Light terminals get the light variant unless overridden
+        app.terminal_background = Some(Background::Light);
+        assert_eq!(app.palette(), ColorTheme::Ocean.palette().light());
+        app.background = BackgroundMode::Dark;
+        assert_eq!(app.palette(), ColorTheme::Ocean.palette());
+    }
+
     #[test]
     fn test_quit_message() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         assert!(!app.should_quit);
 
         app.update(Message::Quit);
@@ -1319,7 +4609,10 @@ mod tests {
 
     #[test]
     fn test_toggle_time_format() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         assert_eq!(app.display_format, TimeFormat::TwentyFourHour);
 
         app.update(Message::ToggleTimeFormat);
@@ -1331,7 +4624,10 @@ mod tests {
 
     #[test]
     fn test_start_rename_zone() {
-        let mut app = App::default(); // Use default instead of new() to avoid loading config
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        }; // Use default instead of new() to avoid loading config
         assert!(!app.renaming_zone);
         assert!(app.rename_zone_input.is_empty());
 
@@ -1339,15 +4635,31 @@ mod tests {
         assert!(app.renaming_zone);
         // Should be pre-filled with current custom label (from default zones)
         let current_label = app.timezone_manager.zones()[app.selected_zone_index]
-            .custom_label
+            .display
+            .label
             .clone()
             .unwrap_or_default();
         assert_eq!(app.rename_zone_input, current_label);
     }
 
+    #[test]
+    fn test_update_check_round_trips() {
+        let config = AppConfig {
+            update_check: true,
+            ..AppConfig::default()
+        };
+        let app = App::from_config(config);
+        assert!(app.update_check);
+        assert!(app.to_config().update_check);
+        assert!(!App::from_config(AppConfig::default()).update_check);
+    }
+
     #[test]
     fn test_update_rename_input() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         app.renaming_zone = true;
 
         app.update(Message::UpdateRenameInput("Alice".to_string()));
@@ -1361,13 +4673,18 @@ mod tests {
 
     #[test]
     fn test_confirm_rename() {
-        let mut app = App::default(); // Use default to avoid loading config
-        app.renaming_zone = true;
-        app.rename_zone_input = "Bob (Sales)".to_string();
+        // Use default to avoid loading config
+        let mut app = App {
+            renaming_zone: true,
+            rename_zone_input: "Bob (Sales)".to_string(),
+            ephemeral: true,
+            ..Default::default()
+        };
 
         // Store initial state (may or may not have custom label)
         let _initial_label = app.timezone_manager.zones()[app.selected_zone_index]
-            .custom_label
+            .display
+            .label
             .clone();
 
         app.update(Message::ConfirmRename);
@@ -1375,7 +4692,8 @@ mod tests {
         // Should have the new custom label
         assert_eq!(
             app.timezone_manager.zones()[app.selected_zone_index]
-                .custom_label
+                .display
+                .label
                 .as_deref(),
             Some("Bob (Sales)")
         );
@@ -1385,7 +4703,10 @@ mod tests {
 
     #[test]
     fn test_confirm_rename_empty_input() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         app.renaming_zone = true;
         app.rename_zone_input = "  ".to_string(); // Whitespace only
 
@@ -1393,7 +4714,9 @@ mod tests {
 
         // Empty/whitespace input should clear custom label
         assert_eq!(
-            app.timezone_manager.zones()[app.selected_zone_index].custom_label,
+            app.timezone_manager.zones()[app.selected_zone_index]
+                .display
+                .label,
             None
         );
         assert!(!app.renaming_zone);
@@ -1402,7 +4725,10 @@ mod tests {
 
     #[test]
     fn test_modal_state_exclusivity() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
 
         // Add a timezone first so we can rename it
         app.update(Message::StartAddZone);
@@ -1430,7 +4756,10 @@ mod tests {
 
     #[test]
     fn test_cancel_rename() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         app.renaming_zone = true;
         app.rename_zone_input = "Some input".to_string();
 
@@ -1442,14 +4771,18 @@ mod tests {
 
     #[test]
     fn test_clear_custom_name() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
 
         // First set a custom label
         app.timezone_manager
             .update_zone_label(app.selected_zone_index, Some("Test Label".to_string()));
         assert_eq!(
             app.timezone_manager.zones()[app.selected_zone_index]
-                .custom_label
+                .display
+                .label
                 .as_deref(),
             Some("Test Label")
         );
@@ -1457,14 +4790,19 @@ mod tests {
         // Clear it
         app.update(Message::ClearCustomName);
         assert_eq!(
-            app.timezone_manager.zones()[app.selected_zone_index].custom_label,
+            app.timezone_manager.zones()[app.selected_zone_index]
+                .display
+                .label,
             None
         );
     }
 
     #[test]
     fn test_config_with_custom_labels() {
-        let mut app = App::default(); // Use default to avoid loading config
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        }; // Use default to avoid loading config
 
         // Clear any existing labels first
         for i in 0..app.timezone_manager.zone_count() {
@@ -1494,7 +4832,10 @@ mod tests {
 
     #[test]
     fn test_timeline_scrubbing() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
         let initial_time = app.timeline_position;
 
         // Scrub right - should round to next hour
@@ -1507,6 +4848,108 @@ mod tests {
         assert!(app.timeline_position < after_right);
     }
 
+    #[test]
+    fn test_long_jump_is_animated() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        let start = app.timeline_position;
+        let target = start + chrono::Duration::days(1);
+
+        app.update(Message::JumpDays(1));
+        assert!(app.is_animating());
+        assert_eq!(app.timeline_position, start);
+
+        let mut last = start;
+        for _ in 0..ANIMATION_FRAMES {
+            app.update(Message::AnimationFrame);
+            assert!(app.timeline_position >= last);
+            last = app.timeline_position;
+        }
+        assert!(!app.is_animating());
+        assert_eq!(app.timeline_position, target);
+    }
+
+    #[test]
+    fn test_reduced_motion_jumps_immediately() {
+        let mut app = App {
+            reduced_motion: true,
+            ephemeral: true,
+            ..Default::default()
+        };
+        let start = app.timeline_position;
+
+        app.update(Message::JumpDays(-1));
+        assert!(!app.is_animating());
+        assert_eq!(app.timeline_position, start - chrono::Duration::days(1));
+    }
+
+    #[test]
+    fn test_low_bandwidth_mode() {
+        use crate::platform::Platform;
+        use crate::terminal::Capabilities;
+
+        let mut app = App {
+            low_bandwidth: true,
+            clock_graphics: true,
+            ephemeral: true,
+            ..Default::default()
+        };
+        app.apply_capabilities(&Capabilities::from_env(Platform::Unix, |name| {
+            (name == "TERM").then(|| "xterm-kitty".to_string())
+        }));
+        assert_eq!(app.graphics, None);
+
+        app.update(Message::JumpDays(1));
+        assert!(!app.is_animating());
+
+        app.record_frame_time(std::time::Duration::from_secs(1));
+        assert!(app.decorations());
+    }
+
+    #[test]
+    fn test_input_during_animation_starts_from_target() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        let start = app.timeline_position;
+
+        app.update(Message::JumpDays(1));
+        app.update(Message::AnimationFrame);
+        app.update(Message::FineAdjust(15));
+
+        assert!(!app.is_animating());
+        assert_eq!(
+            app.timeline_position,
+            start + chrono::Duration::days(1) + chrono::Duration::minutes(15)
+        );
+    }
+
+    #[test]
+    fn test_macro_recording() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+
+        app.update(Message::StartMacroRecording('w'));
+        app.record_macro_key(">".to_string());
+        app.record_macro_key("l".to_string());
+        app.update(Message::StopMacroRecording);
+
+        assert!(app.recording_macro.is_none());
+        assert_eq!(app.macros["w"], vec![">", "l"]);
+        assert_eq!(app.to_config().macros["w"].len(), 2);
+
+        // Keys are ignored when not recording, and an empty recording clears the register
+        app.record_macro_key("x".to_string());
+        app.update(Message::StartMacroRecording('w'));
+        app.update(Message::StopMacroRecording);
+        assert!(!app.macros.contains_key("w"));
+    }
+
     #[test]
     fn test_local_timezone_selection() {
         let app = App::new();
@@ -1517,13 +4960,13 @@ mod tests {
 
         // The selected zone should have an offset that matches local time
         let local_time = app.current_time.with_timezone(&Local);
-        let local_offset_hours = local_time.offset().fix().local_minus_utc() / 3600;
+        let local_offset_minutes = local_time.offset().fix().local_minus_utc() / 60;
 
         let selected_zone = &zones[app.selected_zone_index];
-        let selected_offset_hours = selected_zone.utc_offset_hours();
+        let selected_offset_minutes = selected_zone.offset_minutes_at(app.current_time);
 
-        // They should match (allowing for DST differences)
-        assert_eq!(selected_offset_hours, local_offset_hours);
+        // To the minute, so half-hour zones aren't mistaken for their neighbours
+        assert_eq!(selected_offset_minutes, local_offset_minutes);
     }
 
     #[test]
@@ -1541,6 +4984,7 @@ mod tests {
             color_theme: crate::config::ColorTheme::default(),
             show_date: false,
             show_sun_times: true,
+            ..AppConfig::default()
         };
 
         // Create app from config
@@ -1575,7 +5019,10 @@ mod tests {
     #[test]
     fn test_manchester_with_default_london() {
         // Start with default app (which includes London)
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
 
         // Verify London is in defaults
         let initial_count = app.timezone_manager.zone_count();
@@ -1598,7 +5045,7 @@ mod tests {
 
         // Verify both cities are present
         let zones = reloaded_app.timezone_manager.zones();
-        let display_names: Vec<&str> = zones.iter().map(|z| z.display_name.as_str()).collect();
+        let display_names: Vec<&str> = zones.iter().map(|z| z.display.code.as_str()).collect();
         let source_cities: Vec<String> =
             zones.iter().filter_map(|z| z.source_city.clone()).collect();
 
@@ -1619,9 +5066,856 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_mini_mode_renders_compact_rows() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App {
+            mini_rows: Some(3),
+            ephemeral: true,
+            ..Default::default()
+        };
+        app.selected_zone_index = app.timezone_manager.zone_count() - 1;
+
+        let mut terminal = Terminal::new(TestBackend::new(40, 6)).unwrap();
+        terminal.draw(|f| app.view(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+
+        let row_text = |y: u16| -> String {
+            (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol().to_string())
+                .collect()
+        };
+
+        // Three rows are drawn, ending with the selected (last) zone
+        let last_zone = app.timezone_manager.zones().last().unwrap();
+        assert!(row_text(2).starts_with(last_zone.effective_display_name()));
+        assert!(row_text(3).trim().is_empty());
+        // No header chrome in mini mode
+        assert!(!row_text(0).contains("alltz"));
+
+        // Modals still show, since their keys still work
+        app.goto_time = Some(String::new());
+        terminal.draw(|f| app.view(f)).unwrap();
+        let buffer = terminal.backend().buffer();
+        let screen: String = buffer.content().iter().map(|cell| cell.symbol()).collect();
+        assert!(screen.contains("Go to Time"));
+    }
+
+    #[test]
+    fn test_fire_reminders() {
+        let path =
+            std::env::temp_dir().join(format!("alltz-reminders-{}.json", std::process::id()));
+        let at = |text: &str| {
+            DateTime::parse_from_rfc3339(text)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let reminder = |text: &str, when: &str| Reminder {
+            text: text.to_string(),
+            at: at(when),
+            zone: Some("Europe/Madrid".to_string()),
+        };
+        remind::save(
+            &path,
+            &[
+                reminder("ping Maria", "2024-03-31T07:30:00Z"),
+                reminder("standup", "2024-03-31T08:00:00Z"),
+            ],
+        )
+        .unwrap();
+
+        let mut app = App {
+            ephemeral: true,
+            current_time: at("2024-03-31T07:30:00Z"),
+            ..App::default()
+        };
+        app.fire_reminders(&path);
+        let remaining = remind::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(app.bell);
+        assert_eq!(
+            app.reminder_notice().as_deref(),
+            Some("reminder: ping Maria (Sun 31 Mar 09:30 CEST)")
+        );
+        assert_eq!(remaining, [reminder("standup", "2024-03-31T08:00:00Z")]);
+
+        app.privacy_mode = true;
+        assert_eq!(
+            app.reminder_notice().as_deref(),
+            Some("reminder: Sun 31 Mar 09:30 CEST")
+        );
+    }
+
+    #[test]
+    fn test_fire_milestones() {
+        let at = |time: &str| {
+            chrono::DateTime::parse_from_rfc3339(time)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let milestone = |name: &str, time: &str| Milestone {
+            name: name.to_string(),
+            time: at(time),
+            group: None,
+        };
+        let mut app = App {
+            ephemeral: true,
+            current_time: at("2024-03-31T07:31:00Z"),
+            milestones: vec![
+                milestone("code freeze", "2024-03-31T07:30:00Z"),
+                milestone("release", "2024-04-02T09:00:00Z"),
+            ],
+            ..App::default()
+        };
+
+        // Milestones are quiet unless sent somewhere
+        app.fire_milestones(at("2024-03-31T07:29:00Z"));
+        assert!(!app.bell);
+
+        app.notify = Some(NotifyConfig {
+            milestone: vec![NotifyBackend::Bell],
+            ..NotifyConfig::default()
+        });
+        app.fire_milestones(at("2024-03-31T07:30:00Z"));
+        assert!(!app.bell);
+        app.fire_milestones(at("2024-03-31T07:29:00Z"));
+        assert!(app.bell);
+    }
+
+    #[test]
+    fn test_share_menu() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        // With no templates of the user's own, the table is copied straight away
+        app.update(Message::CopyTimeCard);
+        assert_eq!(app.share_menu, None);
+        let table = app.clipboard.take().unwrap();
+        assert_eq!(table.lines().count(), app.timezone_manager.zone_count());
+
+        app.card_templates = vec![CardTemplate {
+            name: "one-liner".to_string(),
+            zone: "{city} {time}".to_string(),
+            separator: " | ".to_string(),
+        }];
+        app.update(Message::CopyTimeCard);
+        assert_eq!(app.share_menu, Some(0));
+        app.update(Message::NavigateShareMenu(Direction::Down));
+        app.update(Message::NavigateShareMenu(Direction::Down));
+        assert_eq!(app.share_menu, Some(1));
+        app.update(Message::SelectShareOption(1));
+        assert_eq!(app.share_menu, None);
+        let one_liner = app.clipboard.take().unwrap();
+        assert_eq!(one_liner.lines().count(), 1);
+        assert!(one_liner.contains(" | "));
+
+        app.update(Message::CopyTimeCard);
+        app.update(Message::CancelShareMenu);
+        assert_eq!(app.clipboard, None);
+    }
+
+    #[test]
+    fn test_jump_history() {
+        let mut app = App {
+            ephemeral: true,
+            reduced_motion: true,
+            ..App::default()
+        };
+        let start = app.timeline_position;
+        app.update(Message::JumpDays(1));
+        let tomorrow = app.timeline_position;
+        app.update(Message::JumpDays(1));
+        let later = app.timeline_position;
+
+        app.update(Message::JumpBack);
+        assert_eq!(app.timeline_position, tomorrow);
+        app.update(Message::JumpBack);
+        assert_eq!(app.timeline_position, start);
+        app.update(Message::JumpBack);
+        assert_eq!(app.timeline_position, start);
+        app.update(Message::JumpForward);
+        app.update(Message::JumpForward);
+        assert_eq!(app.timeline_position, later);
+        app.update(Message::JumpForward);
+        assert_eq!(app.timeline_position, later);
+
+        // A new jump from the middle drops the positions ahead
+        app.update(Message::JumpBack);
+        app.update(Message::JumpDays(-1));
+        assert_eq!(app.timeline_position, start);
+        app.update(Message::JumpForward);
+        assert_eq!(app.timeline_position, start);
+        app.update(Message::JumpBack);
+        assert_eq!(app.timeline_position, tomorrow);
+    }
+
+    #[test]
+    fn test_learn_abbreviation() {
+        let path =
+            std::env::temp_dir().join(format!("alltz-abbreviations-{}.json", std::process::id()));
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        let zone_of = |result: &str| TimeZoneManager::find_city(result).unwrap().timezone;
+        let leading = |app: &App| zone_of(&app.search_zones("cst")[0]);
+        let results = app.search_zones("CST");
+        assert!(results.iter().any(|result| result.starts_with("Chicago")));
+        assert!(results
+            .iter()
+            .any(|result| zone_of(result) == "Asia/Shanghai"));
+
+        let shanghai = "Shanghai, China";
+        app.learn_abbreviation(&path, "cst", shanghai);
+        app.learn_abbreviation(&path, "cst", shanghai);
+        assert!(app.abbreviations.is_empty());
+        // City names aren't abbreviations, so picks from them don't count
+        app.learn_abbreviation(&path, "Shang", shanghai);
+        app.learn_abbreviation(&path, "CST", shanghai);
+        assert_eq!(app.abbreviations["CST"], "Asia/Shanghai");
+        assert_eq!(leading(&app), "Asia/Shanghai");
+        std::fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_bulk_actions() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.timezone_manager = TimeZoneManager::new();
+        // Kept in offset order, whatever the date
+        for city in ["Tokyo", "Lagos", "Kolkata", "Denver"] {
+            app.timezone_manager.add_timezone_by_name(city);
+        }
+        let accents = |app: &App| -> Vec<Option<String>> {
+            app.timezone_manager
+                .zones()
+                .iter()
+                .map(|zone| zone.display.accent.clone())
+                .collect()
+        };
+
+        // Without marks, bulk actions apply to the selected zone
+        app.selected_zone_index = 3;
+        app.update(Message::CycleAccent);
+        assert_eq!(accents(&app)[3].as_deref(), Some("red"));
+
+        app.selected_zone_index = 0;
+        app.update(Message::ToggleMark);
+        app.selected_zone_index = 2;
+        app.update(Message::ToggleMark);
+        app.update(Message::CycleAccent);
+        app.update(Message::CycleAccent);
+        let green = Some("green".to_string());
+        assert_eq!(
+            accents(&app),
+            [green.clone(), None, green.clone(), Some("red".to_string())]
+        );
+        // Accents are saved with the zone
+        let saved = App::from_config(app.to_config());
+        assert_eq!(accents(&saved), accents(&app));
+
+        app.update(Message::StartAssignGroup);
+        app.update(Message::UpdateGroupInput("Team".to_string()));
+        app.update(Message::ConfirmAssignGroup);
+        assert_eq!(app.group_input, None);
+        assert!(app.marked_zones.is_empty());
+        let team = &app.layouts[0];
+        assert_eq!(team.name, "Team");
+        let cities: Vec<&str> = team.zones.iter().map(|zone| zone.city_name()).collect();
+        assert_eq!(cities, ["Denver", "Kolkata"]);
+        assert_eq!(team.zones[0].accent(), Some("green"));
+
+        app.update(Message::ToggleMark);
+        app.selected_zone_index = 1;
+        app.update(Message::ToggleMark);
+        app.update(Message::RemoveCurrentZone);
+        let left: Vec<String> = app
+            .timezone_manager
+            .zones()
+            .iter()
+            .map(|zone| zone.get_city_name())
+            .collect();
+        assert_eq!(left, ["Denver", "Tokyo"]);
+        assert!(app.marked_zones.is_empty());
+    }
+
+    #[test]
+    fn test_zone_hours() {
+        let night_shift = TimeDisplayConfig {
+            work_hours_start: 20,
+            work_hours_end: 24,
+            awake_hours_start: 14,
+            awake_hours_end: 24,
+        };
+        let config = AppConfig {
+            zones: vec![
+                ZoneConfigCompat::Simple("Tokyo".to_string()),
+                ZoneConfigCompat::Simple("Denver".to_string())
+                    .with_hours(Some(night_shift.clone())),
+            ],
+            ..AppConfig::default()
+        };
+        let app = App::from_config(config);
+        // Denver sorts first, so its hours had to follow it there
+        let zones = app.timezone_manager.zones();
+        assert_eq!(zones[0].get_city_name(), "Denver");
+        assert_eq!(zones[0].hours(&app.time_config), &night_shift);
+        assert_eq!(zones[1].hours(&app.time_config), &app.time_config);
+
+        let saved = app.to_config();
+        assert_eq!(saved.zones[0].hours(), Some(&night_shift));
+        assert_eq!(
+            saved.zones[1],
+            ZoneConfigCompat::Simple("Tokyo".to_string())
+        );
+    }
+
+    #[test]
+    fn test_etiquette_notice() {
+        let zones = [ZoneConfigCompat::new(
+            "New York".to_string(),
+            Some("Bob".to_string()),
+        )];
+        let now = DateTime::parse_from_rfc3339("2024-01-15T15:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut app = App {
+            ephemeral: true,
+            current_time: now,
+            timeline_position: now - chrono::Duration::hours(4) + chrono::Duration::minutes(30),
+            timezone_manager: App::manager_from_zone_configs(&zones),
+            ..App::default()
+        };
+        assert_eq!(
+            app.etiquette_notice().as_deref(),
+            Some("this is 06:30 for Bob, consider 1h 30m later")
+        );
+        app.privacy_mode = true;
+        assert!(app.etiquette_notice().unwrap().contains("for NYC,"));
+        // Not while the timeline is at now, whatever the hour there
+        app.timeline_position = now - chrono::Duration::hours(10);
+        app.current_time = app.timeline_position;
+        assert_eq!(app.etiquette_notice(), None);
+    }
+
+    #[test]
+    fn test_undo_redo() {
+        let zones =
+            ["London", "Tokyo", "New York"].map(|city| ZoneConfigCompat::Simple(city.to_string()));
+        let mut app = App {
+            ephemeral: true,
+            timezone_manager: App::manager_from_zone_configs(&zones),
+            show_date: false,
+            ..App::default()
+        };
+        assert!(!app.undo.can_undo());
+
+        app.update(Message::RemoveCurrentZone);
+        app.update(Message::ToggleDate);
+        // Scrubbing and ticking aren't changes to take back
+        app.update(Message::ScrubTimeline(Direction::Right));
+        app.update(Message::Tick);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        assert!(app.show_date);
+
+        app.update(Message::Undo);
+        assert!(!app.show_date);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        app.update(Message::Undo);
+        assert_eq!(app.timezone_manager.zone_count(), 3);
+        assert!(!app.undo.can_undo());
+        // Nothing left to undo leaves the board alone
+        app.update(Message::Undo);
+        assert_eq!(app.timezone_manager.zone_count(), 3);
+
+        app.update(Message::Redo);
+        assert_eq!(app.timezone_manager.zone_count(), 2);
+        assert!(!app.show_date);
+        assert!(app.undo.can_redo());
+
+        // A new change drops what could be redone
+        app.update(Message::ToggleSunTimes);
+        assert!(!app.undo.can_redo());
+
+        // The session file round-trips a snapshot, position and jumps and all
+        app.reduced_motion = true;
+        app.update(Message::JumpDays(1));
+        let snapshot = app.session_snapshot();
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let loaded: Snapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, snapshot);
+        let mut resumed = App {
+            ephemeral: true,
+            reduced_motion: true,
+            ..App::default()
+        };
+        resumed.restore(loaded);
+        assert_eq!(resumed.timezone_manager.zone_count(), 2);
+        assert_eq!(resumed.timeline_position, app.timeline_position);
+        assert_eq!(resumed.show_sun_times, app.show_sun_times);
+        assert_eq!(resumed.jumps, app.jumps);
+        resumed.update(Message::JumpBack);
+        assert_eq!(
+            resumed.timeline_position,
+            app.timeline_position - chrono::Duration::days(1)
+        );
+    }
+
+    #[test]
+    fn test_archive_zone() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.timezone_manager = TimeZoneManager::new();
+        app.timezone_manager.add_timezone_by_name("Denver");
+        app.timezone_manager
+            .add_timezone_with_label("Tokyo", Some("Maria".to_string()));
+        app.timezone_manager
+            .update_zone_accent(1, Some("magenta".to_string()));
+        app.selected_zone_index = 1;
+
+        app.update(Message::ArchiveZone);
+        assert_eq!(app.timezone_manager.zone_count(), 1);
+        assert_eq!(app.selected_zone_index, 0);
+        let saved = app.to_config();
+        assert_eq!(saved.archived.len(), 1);
+        assert_eq!(saved.archived[0].custom_label(), Some("Maria"));
+        assert_eq!(saved.archived[0].accent(), Some("magenta"));
+        // The last zone stays on the board
+        app.update(Message::ArchiveZone);
+        assert_eq!(app.timezone_manager.zone_count(), 1);
+
+        app.update(Message::OpenArchive);
+        assert_eq!(app.archive_picker, Some(0));
+        app.update(Message::RestoreArchived(0));
+        assert_eq!(app.archive_picker, None);
+        assert!(app.archived.is_empty());
+        let zone = &app.timezone_manager.zones()[app.selected_zone_index];
+        assert_eq!(zone.get_city_name(), "Tokyo");
+        assert_eq!(zone.display.label.as_deref(), Some("Maria"));
+        assert_eq!(zone.display.accent.as_deref(), Some("magenta"));
+
+        // Nothing to pick from
+        app.update(Message::OpenArchive);
+        assert_eq!(app.archive_picker, None);
+    }
+
+    #[test]
+    fn test_preload() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.timezone_manager = TimeZoneManager::new();
+        app.timezone_manager.add_timezone_by_name("London");
+        app.timezone_manager.add_timezone_by_name("Tokyo");
+        let at = DateTime::parse_from_rfc3339("2024-06-20T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        app.preload(Preload {
+            zones: vec!["Lagos".to_string(), "london".to_string()],
+            at: Some(at),
+            matrix: true,
+        });
+        assert_eq!(app.timezone_manager.zones().len(), 3);
+        assert_eq!(
+            app.timezone_manager.zones()[app.selected_zone_index].get_city_name(),
+            "London"
+        );
+        assert_eq!(app.timeline_position, at);
+        assert!(app.show_matrix);
+        // Only the zone it added is temporary, and that isn't saved
+        assert_eq!(app.temporary_zones, ["Lagos"]);
+        let saved: Vec<String> = app
+            .to_config()
+            .zones
+            .iter()
+            .map(|zone| zone.city_name().to_string())
+            .collect();
+        assert_eq!(saved, ["London", "Tokyo"]);
+    }
+
+    #[test]
+    fn test_goto_time() {
+        let mut app = App {
+            ephemeral: true,
+            reduced_motion: true,
+            ..App::default()
+        };
+        app.timezone_manager = TimeZoneManager::new();
+        app.timezone_manager
+            .add_zone(TimeZone::from_tz(chrono_tz::Asia::Tokyo));
+        app.selected_zone_index = 0;
+        app.current_time = DateTime::parse_from_rfc3339("2024-06-20T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        app.update(Message::StartGotoTime);
+        for input in ["1", "14", "14:", "14:3", "14:30"] {
+            app.update(Message::UpdateGotoInput(input.to_string()));
+        }
+        app.update(Message::ConfirmGotoTime);
+        assert_eq!(app.goto_time, None);
+        // 19:00 in Tokyo, so their 14:30 is tomorrow
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2024-06-21T05:30:00+00:00"
+        );
+
+        // Times that can't be read leave the prompt open
+        app.update(Message::StartGotoTime);
+        app.update(Message::UpdateGotoInput("teatime".to_string()));
+        app.update(Message::ConfirmGotoTime);
+        assert_eq!(app.goto_time.as_deref(), Some("teatime"));
+        app.update(Message::CancelGotoTime);
+        assert_eq!(app.goto_time, None);
+
+        // A pasted org-mode timestamp is read on the zone's clocks
+        app.update(Message::StartGotoTime);
+        app.update(Message::UpdateGotoInput(
+            "<2024-06-01 Sat 15:00>".to_string(),
+        ));
+        app.update(Message::ConfirmGotoTime);
+        assert_eq!(
+            app.timeline_position.to_rfc3339(),
+            "2024-06-01T06:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_focus_mode_hides_chrome() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App {
+            ephemeral: true,
+
+            ..App::default()
+        };
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        let screen_text = |terminal: &Terminal<TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            buffer.content().iter().map(|cell| cell.symbol()).collect()
+        };
+
+        terminal.draw(|f| app.view(f)).unwrap();
+        assert!(screen_text(&terminal).contains("?: help"));
+
+        app.focus_mode = true;
+        terminal.draw(|f| app.view(f)).unwrap();
+        let text = screen_text(&terminal);
+        assert!(!text.contains("?: help"));
+        assert!(!text.contains("alltz v"));
+        assert!(!text.contains('┌'));
+    }
+
+    #[test]
+    fn test_privacy_mode_hides_labels() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut app = App {
+            ephemeral: true,
+
+            ..App::default()
+        };
+        app.update(Message::StartRenameZone);
+        app.update(Message::UpdateRenameInput("Alice".to_string()));
+        app.update(Message::ConfirmRename);
+
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+        let screen_text = |terminal: &Terminal<TestBackend>| -> String {
+            let buffer = terminal.backend().buffer();
+            buffer.content().iter().map(|cell| cell.symbol()).collect()
+        };
+
+        terminal.draw(|f| app.view(f)).unwrap();
+        assert!(screen_text(&terminal).contains("Alice"));
+
+        app.update(Message::TogglePrivacyMode);
+        terminal.draw(|f| app.view(f)).unwrap();
+        let text = screen_text(&terminal);
+        assert!(!text.contains("Alice"));
+        assert!(text.contains(&app.timezone_manager.zones()[0].display.code));
+    }
+
+    #[test]
+    fn test_slow_frames_drop_decorations() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.record_frame_time(std::time::Duration::from_millis(10));
+        assert!(app.decorations());
+
+        app.record_frame_time(std::time::Duration::from_millis(200));
+        assert!(!app.decorations());
+
+        // A zero budget never drops them
+        let mut app = App {
+            frame_budget_ms: 0,
+            ..App::default()
+        };
+        app.record_frame_time(std::time::Duration::from_secs(1));
+        assert!(app.decorations());
+    }
+
+    #[test]
+    fn test_split_view_requires_layout() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.update(Message::ToggleSplitView);
+        assert!(!app.split_view);
+    }
+
+    #[test]
+    fn test_split_view_independent_selection() {
+        let config = AppConfig {
+            layouts: vec![
+                crate::config::LayoutConfig {
+                    name: "Customers".to_string(),
+                    zones: vec![
+                        ZoneConfigCompat::Simple("Tokyo".to_string()),
+                        ZoneConfigCompat::Simple("Sydney".to_string()),
+                    ],
+                    theme: None,
+                },
+                crate::config::LayoutConfig {
+                    name: "Partners".to_string(),
+                    zones: vec![ZoneConfigCompat::Simple("Berlin".to_string())],
+                    theme: None,
+                },
+            ],
+            ..AppConfig::default()
+        };
+        let mut app = App::from_config(config);
+        app.ephemeral = true;
+        let main_selection = app.selected_zone_index;
+        let main_zones = app.timezone_manager.zone_count();
+
+        app.update(Message::ToggleSplitView);
+        assert!(app.split_view);
+        assert_eq!(app.split_timezone_manager.zone_count(), 2);
+
+        // Navigation follows the focused pane only
+        app.update(Message::SwitchPane);
+        app.update(Message::NavigateZone(Direction::Down));
+        assert_eq!(app.split_selected_zone_index, 1);
+        assert_eq!(app.selected_zone_index, main_selection);
+
+        // Zone edits act on the focused pane, and keep to its layout
+        app.update(Message::StartAddZone);
+        app.update(Message::UpdateAddZoneInput("Reykjavik".to_string()));
+        app.update(Message::ConfirmAddZone);
+        assert_eq!(app.split_timezone_manager.zone_count(), 3);
+        assert_eq!(app.layouts[0].zones.len(), 3);
+        app.update(Message::RemoveCurrentZone);
+        app.update(Message::StartRenameZone);
+        app.update(Message::UpdateRenameInput("Acme".to_string()));
+        app.update(Message::ConfirmRename);
+        let renamed = &app.layouts[0].zones[app.split_selected_zone_index];
+        assert_eq!(renamed.custom_label(), Some("Acme"));
+        app.update(Message::RemoveCurrentZone);
+        assert_eq!(app.split_timezone_manager.zone_count(), 1);
+        assert_eq!(app.layouts[0].zones.len(), 1);
+        assert_eq!(app.split_selected_zone_index, 0);
+        assert_eq!(app.timezone_manager.zone_count(), main_zones);
+
+        // So do marks, accents, hours, alignment and go-to
+        let main_board = app.snapshot().zones;
+        let saved = |app: &App| {
+            let zones = app.split_timezone_manager.zones();
+            app.layouts[0].zones == zones.iter().map(ZoneConfigCompat::from).collect::<Vec<_>>()
+        };
+        let split_zone = app.split_timezone_manager.zones()[0].clone();
+        app.update(Message::ToggleMark);
+        assert_eq!(app.marked_zones, vec![split_zone.get_city_name()]);
+        app.update(Message::CycleAccent);
+        let accent = app.split_timezone_manager.zones()[0]
+            .display
+            .accent
+            .as_deref();
+        assert_eq!(accent, Some(ACCENTS[0]));
+        app.update(Message::ClearMarks);
+        app.update(Message::StartHoursEditor);
+        app.update(Message::NavigateHoursEditor(Direction::Right));
+        app.update(Message::ConfirmHoursEditor);
+        assert!(app.split_timezone_manager.zones()[0]
+            .schedule
+            .hours
+            .is_some());
+        assert!(saved(&app));
+        app.update(Message::ToggleAlignMidnight);
+        assert_eq!(app.align_to.as_deref(), Some(split_zone.tz.name()));
+        app.update(Message::ToggleAlignMidnight);
+        let target = app.goto_target("14:30").unwrap();
+        assert_eq!(target.with_timezone(&split_zone.tz).hour(), 14);
+        assert_eq!(app.snapshot().zones, main_board);
+
+        // Archiving takes the zone out of the layout, not the board
+        app.update(Message::StartAddZone);
+        app.update(Message::UpdateAddZoneInput("Sydney".to_string()));
+        app.update(Message::ConfirmAddZone);
+        assert_eq!(app.split_timezone_manager.zone_count(), 2);
+        let archived = ZoneConfigCompat::from(app.focused_zone().unwrap());
+        app.update(Message::ArchiveZone);
+        assert_eq!(app.split_timezone_manager.zone_count(), 1);
+        assert!(saved(&app));
+        assert!(app
+            .archived
+            .iter()
+            .any(|entry| entry.city_name() == archived.city_name()));
+        assert_eq!(app.snapshot().zones, main_board);
+
+        // Scrubbing is shared by both panes
+        let before = app.timeline_position;
+        app.update(Message::FineAdjust(60));
+        assert_eq!(app.timeline_position - before, chrono::Duration::hours(1));
+
+        // Cycling layouts reloads the split pane and resets its selection
+        app.update(Message::CycleSplitLayout);
+        assert_eq!(app.split_layout_index, 1);
+        assert_eq!(app.split_timezone_manager.zone_count(), 1);
+        assert_eq!(app.split_selected_zone_index, 0);
+
+        app.update(Message::ToggleSplitView);
+        assert!(!app.split_view);
+        assert!(!app.split_pane_focused);
+
+        // Layouts round-trip through the saved config
+        assert_eq!(app.to_config().layouts.len(), 2);
+    }
+
+    #[test]
+    fn test_milestones_slip_together() {
+        let mut config = AppConfig::default();
+        let ga = chrono::NaiveDate::from_ymd_opt(2024, 6, 20).unwrap();
+        crate::templates::Template::ReleaseTrain.apply(&mut config, ga);
+        let mut app = App::from_config(config);
+        app.ephemeral = true;
+        let before: Vec<_> = app.milestones.iter().map(|m| m.time).collect();
+
+        app.update(Message::ShiftMilestones(2));
+        app.update(Message::ShiftMilestones(-1));
+        for (milestone, time) in app.milestones.iter().zip(before) {
+            assert_eq!(milestone.time - time, chrono::Duration::days(1));
+        }
+        assert_eq!(app.to_config().milestones, app.milestones);
+    }
+
+    #[test]
+    fn test_milestone_plan() {
+        let mut config = AppConfig::default();
+        let ga = chrono::NaiveDate::from_ymd_opt(2024, 6, 20).unwrap();
+        crate::templates::Template::ReleaseTrain.apply(&mut config, ga);
+        let mut app = App::from_config(config);
+        app.ephemeral = true;
+        let before = app.milestones.clone();
+
+        // Pick canary and GA, then preview slipping them by two days
+        app.update(Message::StartMilestonePlan);
+        app.update(Message::NavigateMilestonePlan(Direction::Down));
+        app.update(Message::ToggleMilestoneSelection);
+        app.update(Message::NavigateMilestonePlan(Direction::Down));
+        app.update(Message::ToggleMilestoneSelection);
+        app.update(Message::AdjustMilestonePlan(3 * 24 * 60));
+        app.update(Message::AdjustMilestonePlan(-24 * 60));
+        let slipped: Vec<_> = app
+            .milestones
+            .iter()
+            .zip(&before)
+            .map(|(after, before)| (after.time - before.time).num_days())
+            .collect();
+        assert_eq!(slipped, [0, 2, 2]);
+
+        // Cancelling puts everything back, leaving nothing to undo
+        app.update(Message::CancelMilestonePlan);
+        assert_eq!(app.milestones, before);
+        app.update(Message::Undo);
+        assert_eq!(app.milestones, before);
+
+        // The group shortcut picks the whole release
+        app.update(Message::StartMilestonePlan);
+        app.update(Message::SelectMilestoneGroup);
+        app.update(Message::AdjustMilestonePlan(60));
+        app.update(Message::ConfirmMilestonePlan);
+        assert!(app.milestone_plan.is_none());
+        assert!(app
+            .milestones
+            .iter()
+            .zip(&before)
+            .all(|(after, before)| after.time - before.time == chrono::Duration::hours(1)));
+        // A kept plan is taken back in one step
+        app.update(Message::Undo);
+        assert_eq!(app.milestones, before);
+        assert_eq!(
+            format_shift(chrono::Duration::minutes(-(2 * 1440 + 45))),
+            "−2d 45m"
+        );
+    }
+
+    #[test]
+    fn test_share_text() {
+        let config = AppConfig {
+            zones: vec![ZoneConfigCompat::new(
+                "Tokyo".to_string(),
+                Some("Customers".to_string()),
+            )],
+            ..AppConfig::default()
+        };
+        let mut app = App::from_config(config);
+        app.timeline_position = DateTime::parse_from_rfc3339("2024-06-20T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            app.share_text().unwrap(),
+            "2024-06-21T01:00:00+09:00 Tokyo (Customers)"
+        );
+
+        app.privacy_mode = true;
+        assert_eq!(app.share_text().unwrap(), "2024-06-21T01:00:00+09:00 Tokyo");
+    }
+
+    #[test]
+    fn test_hours_editor() {
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+
+        app.selected_zone_index = 1;
+        let hours = |app: &App| app.timezone_manager.zones()[1].schedule.hours.clone();
+
+        // Starts on the work start of the selected zone, moved an hour earlier
+        app.update(Message::StartHoursEditor);
+        app.update(Message::NavigateHoursEditor(Direction::Left));
+        assert_eq!(hours(&app).unwrap().work_hours_start, 7);
+        app.update(Message::CancelHoursEditor);
+        assert_eq!(hours(&app), None);
+
+        app.update(Message::StartHoursEditor);
+        app.update(Message::NavigateHoursEditor(Direction::Down));
+        app.update(Message::NavigateHoursEditor(Direction::Right));
+        app.update(Message::ConfirmHoursEditor);
+        assert!(app.hours_editor.is_none());
+        assert_eq!(app.to_config().zones[1].hours().unwrap().work_hours_end, 19);
+        // The board's own hours stay as they were
+        assert_eq!(app.time_config, TimeDisplayConfig::default());
+    }
+
     #[test]
     fn test_search_navigation() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
 
         // Start adding zone
         app.update(Message::StartAddZone);
@@ -1676,7 +5970,10 @@ mod tests {
 
     #[test]
     fn test_search_navigation_detailed() {
-        let mut app = App::new();
+        let mut app = App {
+            ephemeral: true,
+            ..App::new()
+        };
 
         // Start adding zone
         app.update(Message::StartAddZone);
@@ -1708,4 +6005,40 @@ mod tests {
         assert!(app.adding_zone);
         assert!(!app.zone_search_results.is_empty());
     }
+
+    #[test]
+    #[cfg(feature = "clock-graphics")]
+    fn test_clock_graphics_area() {
+        use crate::platform::Platform;
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let kitty = Capabilities::from_env(Platform::Unix, |name| {
+            (name == "TERM").then(|| "xterm-kitty".to_string())
+        });
+        let mut terminal = Terminal::new(TestBackend::new(100, 40)).unwrap();
+
+        // Off unless enabled in config, even on a capable terminal
+        let mut app = App {
+            ephemeral: true,
+            ..App::default()
+        };
+        app.apply_capabilities(&kitty);
+        terminal.draw(|f| app.view(f)).unwrap();
+        assert_eq!(app.clock_image(), None);
+
+        app.clock_graphics = true;
+        app.apply_capabilities(&kitty);
+        terminal.draw(|f| app.view(f)).unwrap();
+        let (area, _, _) = app.clock_image().unwrap();
+        assert_eq!((area.y, area.width, area.height), (3, 8, 4));
+
+        // The area is left blank for the image
+        let buffer = terminal.backend().buffer();
+        assert!((area.x..area.right()).all(|x| buffer[(x, area.y + 1)].symbol() == " "));
+
+        // Modals hide the clock
+        app.show_help = true;
+        terminal.draw(|f| app.view(f)).unwrap();
+        assert_eq!(app.clock_image(), None);
+    }
 }
@@ -0,0 +1,134 @@
+use crate::config::BlockedDates;
+use chrono::NaiveDate;
+use std::fmt;
+
+/// A line of a blocked dates file that couldn't be read
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+/// Reads a blocked dates file, one entry per line:
+///
+/// ```text
+/// # who: day or first..last   note
+/// Platform: 2025-07-14..2025-07-25  summer holiday
+/// Sydney: 2025-12-25
+/// ```
+///
+/// Blank lines and lines starting with `#` are skipped. Lines that can't be
+/// read are returned alongside the entries that could.
+pub fn parse(content: &str) -> (Vec<BlockedDates>, Vec<LineError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        match parse_line(line) {
+            Ok(entry) => entries.push(entry),
+            Err(message) => errors.push(LineError {
+                line: index + 1,
+                message,
+            }),
+        }
+    }
+    (entries, errors)
+}
+
+fn parse_line(line: &str) -> Result<BlockedDates, String> {
+    let (who, rest) = line
+        .split_once(':')
+        .ok_or_else(|| "expected `who: dates`".to_string())?;
+    let who = who.trim();
+    if who.is_empty() {
+        return Err("missing who the dates are for".to_string());
+    }
+    let rest = rest.trim_start();
+    let (dates, note) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let (from, to) = dates.split_once("..").unwrap_or((dates, dates));
+    let date = |text: &str| {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d")
+            .map_err(|_| format!("`{text}` is not a YYYY-MM-DD date"))
+    };
+    let (from, to) = (date(from)?, date(to)?);
+    if to < from {
+        return Err(format!("{to} is before {from}"));
+    }
+    let note = note.trim();
+    Ok(BlockedDates {
+        who: who.to_string(),
+        from,
+        to,
+        note: (!note.is_empty()).then(|| note.to_string()),
+    })
+}
+
+/// Adds the entries not already in `existing`, returning how many were new,
+/// so importing the same file twice changes nothing
+pub fn merge(existing: &mut Vec<BlockedDates>, imported: Vec<BlockedDates>) -> usize {
+    let before = existing.len();
+    for entry in imported {
+        if !existing.contains(&entry) {
+            existing.push(entry);
+        }
+    }
+    existing.len() - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::TimeZone;
+
+    #[test]
+    fn test_parse_blocked() {
+        let content = "\
+# Team holidays
+Platform: 2025-07-14..2025-07-25  summer holiday
+
+Sydney: 2025-12-25
+Tokyo 2025-05-05
+London: 2025-08-32
+Berlin: 2025-03-10..2025-03-01
+";
+        let (entries, errors) = parse(content);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].who, "Platform");
+        assert_eq!(entries[0].note.as_deref(), Some("summer holiday"));
+        assert_eq!(entries[1].from, entries[1].to);
+        assert_eq!(entries[1].note, None);
+
+        let lines: Vec<usize> = errors.iter().map(|e| e.line).collect();
+        assert_eq!(lines, [5, 6, 7]);
+        assert_eq!(
+            errors[1].to_string(),
+            "line 6: `2025-08-32` is not a YYYY-MM-DD date"
+        );
+
+        // Entries match zones by label or city, on the days they cover
+        let zone = TimeZone::with_custom_label(
+            chrono_tz::Asia::Kolkata,
+            "Mumbai".to_string(),
+            Some("Platform".to_string()),
+        );
+        let day = |d| NaiveDate::from_ymd_opt(2025, 7, d).unwrap();
+        assert!(entries[0].covers(&zone, day(14)));
+        assert!(entries[0].covers(&zone, day(25)));
+        assert!(!entries[0].covers(&zone, day(26)));
+        assert!(!entries[1].covers(&zone, day(14)));
+
+        let mut existing = vec![entries[0].clone()];
+        assert_eq!(merge(&mut existing, entries.clone()), 1);
+        assert_eq!(merge(&mut existing, entries), 0);
+        assert_eq!(existing.len(), 2);
+    }
+}
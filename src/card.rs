@@ -0,0 +1,154 @@
+use crate::app::TimeFormat;
+use crate::config::CardTemplate;
+use crate::time::TimeZone;
+use chrono::{DateTime, Utc};
+
+/// The name a zone goes by on a card: its label, or its city in `private`
+/// mode or when it has none
+fn card_name(zone: &TimeZone, private: bool) -> String {
+    match &zone.display.label {
+        Some(label) if !private => label.clone(),
+        _ => zone.get_city_name(),
+    }
+}
+
+fn time_format(format: &TimeFormat) -> &'static str {
+    match format {
+        TimeFormat::TwentyFourHour => "%H:%M",
+        TimeFormat::TwelveHour => "%-I:%M %p",
+    }
+}
+
+/// Each zone's local date and time at `at`, one aligned line per zone, for
+/// pasting into chat
+pub fn time_card(
+    zones: &[TimeZone],
+    at: DateTime<Utc>,
+    format: &TimeFormat,
+    private: bool,
+) -> String {
+    let names: Vec<String> = zones.iter().map(|zone| card_name(zone, private)).collect();
+    let width = names.iter().map(|name| name.chars().count()).max();
+
+    let mut card = String::new();
+    for (zone, name) in zones.iter().zip(&names) {
+        let local = zone.convert_time(at);
+        card.push_str(&format!(
+            "{name:<width$}  {} {} {}\n",
+            local.format("%a %-d %b"),
+            local.format(time_format(format)),
+            zone.get_timezone_abbreviation(),
+            width = width.unwrap_or_default()
+        ));
+    }
+    card
+}
+
+/// The time card laid out by a user template. Unknown placeholders are left
+/// as typed, so a mistake shows up in the pasted text.
+pub fn render_template(
+    template: &CardTemplate,
+    zones: &[TimeZone],
+    at: DateTime<Utc>,
+    format: &TimeFormat,
+    private: bool,
+) -> String {
+    let entries: Vec<String> = zones
+        .iter()
+        .map(|zone| {
+            let local = zone.convert_time(at);
+            let values = [
+                ("name", card_name(zone, private)),
+                ("city", zone.get_city_name()),
+                ("time", local.format(time_format(format)).to_string()),
+                ("date", local.format("%a %-d %b").to_string()),
+                ("abbr", zone.get_timezone_abbreviation()),
+                ("offset", local.format("UTC%:z").to_string()),
+                ("org", crate::org::format(&local)),
+            ];
+            fill(&template.zone, &values)
+        })
+        .collect();
+    let mut card = entries.join(&template.separator);
+    if template.separator.contains('\n') {
+        card.push('\n');
+    }
+    card
+}
+
+/// Replaces each `{key}` in `text` with its value
+fn fill(text: &str, values: &[(&str, String)]) -> String {
+    let mut filled = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        filled.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let value = placeholder.find('}').and_then(|end| {
+            let key = &placeholder[1..end];
+            let value = values.iter().find(|(name, _)| *name == key)?;
+            Some((&value.1, end + 1))
+        });
+        match value {
+            Some((value, len)) => {
+                filled.push_str(value);
+                rest = &placeholder[len..];
+            }
+            None => {
+                filled.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_time_card() {
+        let zones = [
+            TimeZone::with_custom_label(
+                chrono_tz::America::Los_Angeles,
+                "LAX".to_string(),
+                Some("Ana".to_string()),
+            ),
+            TimeZone::from_tz(chrono_tz::Asia::Tokyo),
+        ];
+        let at = DateTime::parse_from_rfc3339("2024-06-20T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let card = time_card(&zones, at, &TimeFormat::TwentyFourHour, false);
+        let lines: Vec<&str> = card.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("Ana    Thu 20 Jun 09:00"), "{card}");
+        assert!(lines[1].ends_with("Fri 21 Jun 01:00 JST"), "{card}");
+
+        let private = time_card(&zones, at, &TimeFormat::TwelveHour, true);
+        assert!(!private.contains("Ana"));
+        assert!(private.contains("9:00 AM"));
+
+        let one_liner = CardTemplate {
+            name: "one-liner".to_string(),
+            zone: "{name} {time} ({offset}) {org} {unknown}".to_string(),
+            separator: " / ".to_string(),
+        };
+        assert_eq!(
+            render_template(&one_liner, &zones, at, &TimeFormat::TwentyFourHour, false),
+            "Ana 09:00 (UTC-07:00) <2024-06-20 Thu 09:00> {unknown} / \
+             Tokyo 01:00 (UTC+09:00) <2024-06-21 Fri 01:00> {unknown}"
+        );
+        let bullets = CardTemplate {
+            name: "bullets".to_string(),
+            zone: "- {city}: {date}, {time}".to_string(),
+            separator: "\n".to_string(),
+        };
+        assert_eq!(
+            render_template(&bullets, &zones, at, &TimeFormat::TwelveHour, true),
+            "- Los Angeles: Thu 20 Jun, 9:00 AM\n- Tokyo: Fri 21 Jun, 1:00 AM\n"
+        );
+    }
+}
@@ -0,0 +1,66 @@
+//! The bundled city database, compiled into static tables by `build.rs`
+
+use std::cmp::Ordering;
+
+#[derive(Debug)]
+pub struct CityData {
+    pub name: &'static str,
+    pub code: &'static str,
+    pub timezone: &'static str,
+    pub country: &'static str,
+    pub coordinates: [f64; 2],
+    pub aliases: &'static [&'static str],
+}
+
+include!(concat!(env!("OUT_DIR"), "/cities.rs"));
+
+/// Compares two strings ignoring ASCII case, without allocating
+fn cmp_ignore_ascii_case(a: &str, b: &str) -> Ordering {
+    a.bytes()
+        .map(|byte| byte.to_ascii_lowercase())
+        .cmp(b.bytes().map(|byte| byte.to_ascii_lowercase()))
+}
+
+/// The entries of a sorted index whose key compares equal to the target
+fn lookup(
+    index: &'static [u16],
+    compare: impl Fn(&'static CityData) -> Ordering,
+) -> impl Iterator<Item = &'static CityData> {
+    let start = index.partition_point(|&i| compare(&CITIES[i as usize]) == Ordering::Less);
+    index[start..]
+        .iter()
+        .map(|&i| &CITIES[i as usize])
+        .take_while(move |city| compare(city) == Ordering::Equal)
+}
+
+/// Cities with this name, ignoring ASCII case, in database order
+pub fn by_name(name: &str) -> impl Iterator<Item = &'static CityData> + '_ {
+    lookup(BY_NAME, move |city| cmp_ignore_ascii_case(city.name, name))
+}
+
+/// The first city with this airport code
+pub fn by_code(code: &str) -> Option<&'static CityData> {
+    lookup(BY_CODE, |city| city.code.cmp(code)).next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexes_find_every_city() {
+        for city in CITIES {
+            let first = CITIES
+                .iter()
+                .find(|c| c.name.eq_ignore_ascii_case(city.name));
+            assert!(std::ptr::eq(
+                by_name(city.name).next().unwrap(),
+                first.unwrap()
+            ));
+            assert_eq!(by_code(city.code).unwrap().code, city.code);
+        }
+        assert_eq!(by_name("lONDON").count(), 2);
+        assert!(by_name("Atlantis").next().is_none());
+        assert!(by_code("XXX").is_none());
+    }
+}
@@ -0,0 +1,439 @@
+use crate::app::{Direction, Message};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Something the board can be asked to do outside a dialog, by name. Keys,
+/// the config's `keys` table and recorded macros all come down to one of
+/// these, which becomes a [`Message`] for [`App::update`](crate::app::App::update).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Quit,
+    Help,
+    JumpBack,
+    JumpForward,
+    AddZone,
+    RemoveZone,
+    RenameZone,
+    ClearLabel,
+    ToggleTimeFormat,
+    ToggleZoneNames,
+    ToggleDate,
+    ToggleSunTimes,
+    ToggleFocusMode,
+    ToggleFocusRuler,
+    ToggleAlignMidnight,
+    TogglePrivacy,
+    CycleZoneOrder,
+    CycleTheme,
+    Now,
+    GotoTime,
+    CopyTimeCard,
+    OffsetMatrix,
+    ScrubLeft,
+    ScrubRight,
+    ScrubLeftFast,
+    ScrubRightFast,
+    NextZone,
+    PreviousZone,
+    ToggleSplitView,
+    CycleSplitLayout,
+    SwitchPane,
+    Earlier15m,
+    Later15m,
+    Earlier1h,
+    Later1h,
+    PreviousDay,
+    NextDay,
+    MilestonesEarlier,
+    MilestonesLater,
+    PlanMilestones,
+    EditHours,
+    QrCode,
+    ToggleMark,
+    ClearMarks,
+    AssignGroup,
+    CycleAccent,
+    ArchiveZone,
+    OpenArchive,
+    DismissClockWarnings,
+    Undo,
+    Redo,
+}
+
+impl Command {
+    pub const ALL: [Command; 51] = [
+        Command::Quit,
+        Command::Help,
+        Command::JumpBack,
+        Command::JumpForward,
+        Command::AddZone,
+        Command::RemoveZone,
+        Command::RenameZone,
+        Command::ClearLabel,
+        Command::ToggleTimeFormat,
+        Command::ToggleZoneNames,
+        Command::ToggleDate,
+        Command::ToggleSunTimes,
+        Command::ToggleFocusMode,
+        Command::ToggleFocusRuler,
+        Command::ToggleAlignMidnight,
+        Command::TogglePrivacy,
+        Command::CycleZoneOrder,
+        Command::CycleTheme,
+        Command::Now,
+        Command::GotoTime,
+        Command::CopyTimeCard,
+        Command::OffsetMatrix,
+        Command::ScrubLeft,
+        Command::ScrubRight,
+        Command::ScrubLeftFast,
+        Command::ScrubRightFast,
+        Command::NextZone,
+        Command::PreviousZone,
+        Command::ToggleSplitView,
+        Command::CycleSplitLayout,
+        Command::SwitchPane,
+        Command::Earlier15m,
+        Command::Later15m,
+        Command::Earlier1h,
+        Command::Later1h,
+        Command::PreviousDay,
+        Command::NextDay,
+        Command::MilestonesEarlier,
+        Command::MilestonesLater,
+        Command::PlanMilestones,
+        Command::EditHours,
+        Command::QrCode,
+        Command::ToggleMark,
+        Command::ClearMarks,
+        Command::AssignGroup,
+        Command::CycleAccent,
+        Command::ArchiveZone,
+        Command::OpenArchive,
+        Command::DismissClockWarnings,
+        Command::Undo,
+        Command::Redo,
+    ];
+
+    /// The name the config and scripts use, e.g. `scrub-left`
+    pub fn name(self) -> &'static str {
+        match self {
+            Command::Quit => "quit",
+            Command::Help => "help",
+            Command::JumpBack => "jump-back",
+            Command::JumpForward => "jump-forward",
+            Command::AddZone => "add-zone",
+            Command::RemoveZone => "remove-zone",
+            Command::RenameZone => "rename-zone",
+            Command::ClearLabel => "clear-label",
+            Command::ToggleTimeFormat => "toggle-time-format",
+            Command::ToggleZoneNames => "toggle-zone-names",
+            Command::ToggleDate => "toggle-date",
+            Command::ToggleSunTimes => "toggle-sun-times",
+            Command::ToggleFocusMode => "toggle-focus-mode",
+            Command::ToggleFocusRuler => "toggle-focus-ruler",
+            Command::ToggleAlignMidnight => "toggle-align-midnight",
+            Command::TogglePrivacy => "toggle-privacy",
+            Command::CycleZoneOrder => "cycle-zone-order",
+            Command::CycleTheme => "cycle-theme",
+            Command::Now => "now",
+            Command::GotoTime => "goto-time",
+            Command::CopyTimeCard => "copy-time-card",
+            Command::OffsetMatrix => "offset-matrix",
+            Command::ScrubLeft => "scrub-left",
+            Command::ScrubRight => "scrub-right",
+            Command::ScrubLeftFast => "scrub-left-fast",
+            Command::ScrubRightFast => "scrub-right-fast",
+            Command::NextZone => "next-zone",
+            Command::PreviousZone => "previous-zone",
+            Command::ToggleSplitView => "toggle-split-view",
+            Command::CycleSplitLayout => "cycle-split-layout",
+            Command::SwitchPane => "switch-pane",
+            Command::Earlier15m => "earlier-15m",
+            Command::Later15m => "later-15m",
+            Command::Earlier1h => "earlier-1h",
+            Command::Later1h => "later-1h",
+            Command::PreviousDay => "previous-day",
+            Command::NextDay => "next-day",
+            Command::MilestonesEarlier => "milestones-earlier",
+            Command::MilestonesLater => "milestones-later",
+            Command::PlanMilestones => "plan-milestones",
+            Command::EditHours => "edit-hours",
+            Command::QrCode => "qr-code",
+            Command::ToggleMark => "toggle-mark",
+            Command::ClearMarks => "clear-marks",
+            Command::AssignGroup => "assign-group",
+            Command::CycleAccent => "cycle-accent",
+            Command::ArchiveZone => "archive-zone",
+            Command::OpenArchive => "open-archive",
+            Command::DismissClockWarnings => "dismiss-clock-warnings",
+            Command::Undo => "undo",
+            Command::Redo => "redo",
+        }
+    }
+
+    /// The message that carries the command out
+    pub fn message(self) -> Message {
+        match self {
+            Command::Quit => Message::Quit,
+            Command::Help => Message::ToggleHelp,
+            Command::JumpBack => Message::JumpBack,
+            Command::JumpForward => Message::JumpForward,
+            Command::AddZone => Message::StartAddZone,
+            Command::RemoveZone => Message::RemoveCurrentZone,
+            Command::RenameZone => Message::StartRenameZone,
+            Command::ClearLabel => Message::ClearCustomName,
+            Command::ToggleTimeFormat => Message::ToggleTimeFormat,
+            Command::ToggleZoneNames => Message::ToggleTimezoneDisplayMode,
+            Command::ToggleDate => Message::ToggleDate,
+            Command::ToggleSunTimes => Message::ToggleSunTimes,
+            Command::ToggleFocusMode => Message::ToggleFocusMode,
+            Command::ToggleFocusRuler => Message::ToggleFocusRuler,
+            Command::ToggleAlignMidnight => Message::ToggleAlignMidnight,
+            Command::TogglePrivacy => Message::TogglePrivacyMode,
+            Command::CycleZoneOrder => Message::CycleZoneOrder,
+            Command::CycleTheme => Message::CycleColorTheme,
+            Command::Now => Message::ResetToNow,
+            Command::GotoTime => Message::StartGotoTime,
+            Command::CopyTimeCard => Message::CopyTimeCard,
+            Command::OffsetMatrix => Message::ToggleOffsetMatrix,
+            Command::ScrubLeft => Message::ScrubTimeline(Direction::Left),
+            Command::ScrubRight => Message::ScrubTimeline(Direction::Right),
+            Command::ScrubLeftFast => Message::ScrubTimelineWithShift(Direction::Left),
+            Command::ScrubRightFast => Message::ScrubTimelineWithShift(Direction::Right),
+            Command::NextZone => Message::NavigateZone(Direction::Down),
+            Command::PreviousZone => Message::NavigateZone(Direction::Up),
+            Command::ToggleSplitView => Message::ToggleSplitView,
+            Command::CycleSplitLayout => Message::CycleSplitLayout,
+            Command::SwitchPane => Message::SwitchPane,
+            Command::Earlier15m => Message::FineAdjust(-15),
+            Command::Later15m => Message::FineAdjust(15),
+            Command::Earlier1h => Message::FineAdjust(-60),
+            Command::Later1h => Message::FineAdjust(60),
+            Command::PreviousDay => Message::JumpDays(-1),
+            Command::NextDay => Message::JumpDays(1),
+            Command::MilestonesEarlier => Message::ShiftMilestones(-1),
+            Command::MilestonesLater => Message::ShiftMilestones(1),
+            Command::PlanMilestones => Message::StartMilestonePlan,
+            Command::EditHours => Message::StartHoursEditor,
+            Command::QrCode => Message::ToggleQrCode,
+            Command::ToggleMark => Message::ToggleMark,
+            Command::ClearMarks => Message::ClearMarks,
+            Command::AssignGroup => Message::StartAssignGroup,
+            Command::CycleAccent => Message::CycleAccent,
+            Command::ArchiveZone => Message::ArchiveZone,
+            Command::OpenArchive => Message::OpenArchive,
+            Command::DismissClockWarnings => Message::DismissClockWarnings,
+            Command::Undo => Message::Undo,
+            Command::Redo => Message::Redo,
+        }
+    }
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Command::ALL
+            .into_iter()
+            .find(|command| command.name() == name)
+            .ok_or_else(|| format!("unknown command `{name}`"))
+    }
+}
+
+/// The keys bound out of the box, as [`encode_key`] writes them. Ctrl-c,
+/// Ctrl-o, Ctrl-i, Ctrl-r, `Q` and `@` are handled before the keymap, so
+/// they work however it's changed. `Tab` bound to `switch-pane` goes
+/// forward through jumps outside split view, as Ctrl-i does.
+pub const DEFAULT_KEYS: &[(&str, Command)] = &[
+    ("q", Command::Quit),
+    ("?", Command::Help),
+    ("a", Command::AddZone),
+    ("r", Command::RemoveZone),
+    ("e", Command::RenameZone),
+    ("E", Command::ClearLabel),
+    ("m", Command::ToggleTimeFormat),
+    ("n", Command::ToggleZoneNames),
+    ("d", Command::ToggleDate),
+    ("s", Command::ToggleSunTimes),
+    ("z", Command::ToggleFocusMode),
+    ("f", Command::ToggleFocusRuler),
+    ("D", Command::ToggleAlignMidnight),
+    ("p", Command::TogglePrivacy),
+    ("o", Command::CycleZoneOrder),
+    ("c", Command::CycleTheme),
+    ("t", Command::Now),
+    (":", Command::GotoTime),
+    ("y", Command::CopyTimeCard),
+    ("x", Command::OffsetMatrix),
+    ("h", Command::ScrubLeft),
+    ("Left", Command::ScrubLeft),
+    ("l", Command::ScrubRight),
+    ("Right", Command::ScrubRight),
+    ("H", Command::ScrubLeftFast),
+    ("S-Left", Command::ScrubLeftFast),
+    ("L", Command::ScrubRightFast),
+    ("S-Right", Command::ScrubRightFast),
+    ("j", Command::NextZone),
+    ("Down", Command::NextZone),
+    ("k", Command::PreviousZone),
+    ("Up", Command::PreviousZone),
+    ("v", Command::ToggleSplitView),
+    ("V", Command::CycleSplitLayout),
+    ("Tab", Command::SwitchPane),
+    ("[", Command::Earlier15m),
+    ("]", Command::Later15m),
+    ("{", Command::Earlier1h),
+    ("}", Command::Later1h),
+    ("<", Command::PreviousDay),
+    (">", Command::NextDay),
+    ("(", Command::MilestonesEarlier),
+    (")", Command::MilestonesLater),
+    ("M", Command::PlanMilestones),
+    ("W", Command::EditHours),
+    ("R", Command::QrCode),
+    (" ", Command::ToggleMark),
+    ("Esc", Command::ClearMarks),
+    ("g", Command::AssignGroup),
+    ("A", Command::CycleAccent),
+    ("X", Command::ArchiveZone),
+    ("U", Command::OpenArchive),
+    ("w", Command::DismissClockWarnings),
+    ("u", Command::Undo),
+];
+
+/// The default keys with the config's `keys` table applied over them. A
+/// key bound to `none` does nothing; names that aren't keys or commands
+/// are skipped, and reported by `alltz config check`.
+pub fn keymap(overrides: &BTreeMap<String, String>) -> BTreeMap<String, Command> {
+    let mut keymap: BTreeMap<String, Command> = DEFAULT_KEYS
+        .iter()
+        .map(|&(key, command)| (key.to_string(), command))
+        .collect();
+    for (key, name) in overrides {
+        if decode_key(key).is_none() {
+            continue;
+        }
+        if name == "none" {
+            keymap.remove(key);
+        } else if let Ok(command) = name.parse() {
+            keymap.insert(key.clone(), command);
+        }
+    }
+    keymap
+}
+
+/// Serializes a key press for macros and the keymap: single characters
+/// as-is, named keys by name, with an `S-` prefix when Shift is held
+pub fn encode_key(key: &KeyEvent) -> Option<String> {
+    let name = match key.code {
+        KeyCode::Char(_) if key.modifiers.contains(KeyModifiers::CONTROL) => return None,
+        // Shift+letter is stored as the uppercase letter, which maps to the same action
+        KeyCode::Char(c) if key.modifiers.contains(KeyModifiers::SHIFT) => {
+            return Some(c.to_ascii_uppercase().to_string())
+        }
+        KeyCode::Char(c) => return Some(c.to_string()),
+        KeyCode::Left => "Left",
+        KeyCode::Right => "Right",
+        KeyCode::Up => "Up",
+        KeyCode::Down => "Down",
+        KeyCode::Tab => "Tab",
+        KeyCode::Enter => "Enter",
+        KeyCode::Esc => "Esc",
+        KeyCode::Backspace => "Backspace",
+        _ => return None,
+    };
+    if key.modifiers.contains(KeyModifiers::SHIFT) {
+        Some(format!("S-{name}"))
+    } else {
+        Some(name.to_string())
+    }
+}
+
+/// Parses a key stored by [`encode_key`]
+pub fn decode_key(encoded: &str) -> Option<KeyEvent> {
+    let mut chars = encoded.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+
+    let (name, modifiers) = match encoded.strip_prefix("S-") {
+        Some(name) => (name, KeyModifiers::SHIFT),
+        None => (encoded, KeyModifiers::NONE),
+    };
+    let code = match name {
+        "Left" => KeyCode::Left,
+        "Right" => KeyCode::Right,
+        "Up" => KeyCode::Up,
+        "Down" => KeyCode::Down,
+        "Tab" => KeyCode::Tab,
+        "Enter" => KeyCode::Enter,
+        "Esc" => KeyCode::Esc,
+        "Backspace" => KeyCode::Backspace,
+        _ => return None,
+    };
+    Some(KeyEvent::new(code, modifiers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_encoding_round_trip() {
+        let keys = [
+            KeyEvent::new(KeyCode::Char('>'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Char('L'), KeyModifiers::NONE),
+            KeyEvent::new(KeyCode::Left, KeyModifiers::SHIFT),
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        ];
+        for key in keys {
+            let encoded = encode_key(&key).unwrap();
+            assert_eq!(decode_key(&encoded), Some(key), "round trip of {encoded}");
+        }
+
+        assert_eq!(
+            encode_key(&KeyEvent::new(KeyCode::Char('h'), KeyModifiers::SHIFT)).as_deref(),
+            Some("H")
+        );
+        assert_eq!(
+            encode_key(&KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)),
+            None
+        );
+        assert_eq!(decode_key("Nonsense"), None);
+    }
+
+    #[test]
+    fn test_keymap() {
+        for command in Command::ALL {
+            assert_eq!(command.name().parse(), Ok(command));
+        }
+        assert!("scrub-sideways".parse::<Command>().is_err());
+        assert!(DEFAULT_KEYS
+            .iter()
+            .all(|(key, _)| decode_key(key).is_some()));
+
+        let overrides = BTreeMap::from([
+            ("h".to_string(), "previous-day".to_string()),
+            ("q".to_string(), "none".to_string()),
+            ("b".to_string(), "quit".to_string()),
+            ("Ctrl-x".to_string(), "quit".to_string()),
+            ("B".to_string(), "scrub-sideways".to_string()),
+        ]);
+        let keymap = keymap(&overrides);
+        assert_eq!(keymap["h"], Command::PreviousDay);
+        assert_eq!(keymap["Left"], Command::ScrubLeft);
+        assert_eq!(keymap.get("q"), None);
+        assert_eq!(keymap["b"], Command::Quit);
+        assert_eq!(keymap.get("Ctrl-x"), None);
+        assert_eq!(keymap.get("B"), None);
+    }
+}
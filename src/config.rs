@@ -1,14 +1,40 @@
 use crate::app::{TimeFormat, TimezoneDisplayMode};
+use crate::command::{self, Command};
+use crate::platform::{self, Platform};
+use crate::terminal::ColorSupport;
+use crate::time::{parse_month_day, CallWindow, TimeZone, TimeZoneManager};
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
 use std::fs;
+use std::ops::Range;
 use std::path::PathBuf;
+use toml::de::{DeTable, DeValue};
 
 fn default_true() -> bool {
     true
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+fn default_frame_budget_ms() -> u64 {
+    50
+}
+
+fn default_card_separator() -> String {
+    "\n".to_string()
+}
+
+fn default_imminent_minutes() -> u32 {
+    15
+}
+
+/// The furthest ahead `dst_countdown_days` can look, which finds the next
+/// clock change anywhere that has them
+pub const MAX_DST_COUNTDOWN_DAYS: u32 = 366;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TimeDisplayConfig {
     pub work_hours_start: u32,  // 8 (8 AM)
     pub work_hours_end: u32,    // 18 (6 PM)
@@ -28,6 +54,34 @@ impl Default for TimeDisplayConfig {
     }
 }
 
+/// One of the hour boundaries in [`TimeDisplayConfig`], in the order they
+/// fall through the day
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HourBoundary {
+    AwakeStart,
+    WorkStart,
+    WorkEnd,
+    AwakeEnd,
+}
+
+impl HourBoundary {
+    pub const ALL: [HourBoundary; 4] = [
+        HourBoundary::AwakeStart,
+        HourBoundary::WorkStart,
+        HourBoundary::WorkEnd,
+        HourBoundary::AwakeEnd,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            HourBoundary::AwakeStart => "Awake from",
+            HourBoundary::WorkStart => "Work from",
+            HourBoundary::WorkEnd => "Work until",
+            HourBoundary::AwakeEnd => "Awake until",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TimeActivity {
     Night, // 10 PM - 6 AM
@@ -35,6 +89,152 @@ pub enum TimeActivity {
     Work,  // 8 AM - 6 PM
 }
 
+/// Where a team's shared board is kept
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum SyncConfig {
+    /// A git repository holding `board.json`
+    Git { repo: String },
+    /// A JSON blob fetched with GET and stored with PUT, e.g. on WebDAV or
+    /// a presigned S3 URL
+    Http { url: String },
+}
+
+/// Sharing where this board is scrubbed to with teammates on the same shared
+/// board, whose positions show as ghost scrub lines
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PresenceConfig {
+    pub name: String, // Shown beside this board's position on teammates' boards
+    #[serde(default = "default_presence_seconds")]
+    pub every_seconds: u64, // How often positions are exchanged
+}
+
+fn default_presence_seconds() -> u64 {
+    10
+}
+
+/// Which characters to draw with. `Auto` uses ASCII on the legacy Windows
+/// console and Unicode everywhere else.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum GlyphMode {
+    #[default]
+    Auto,
+    Unicode,
+    Ascii,
+}
+
+/// How zones are ordered on the board. Grouped offsets get a separator row
+/// above each offset. The work hour orders change as the day goes on, for
+/// following handoffs around the world.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum ZoneOrder {
+    #[default]
+    Offset,
+    OffsetGroups,
+    WorkStartsNext,
+    WorkEndsNext,
+}
+
+impl ZoneOrder {
+    pub fn next(self) -> ZoneOrder {
+        match self {
+            ZoneOrder::Offset => ZoneOrder::OffsetGroups,
+            ZoneOrder::OffsetGroups => ZoneOrder::WorkStartsNext,
+            ZoneOrder::WorkStartsNext => ZoneOrder::WorkEndsNext,
+            ZoneOrder::WorkEndsNext => ZoneOrder::Offset,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ZoneOrder::Offset => "UTC offset",
+            ZoneOrder::OffsetGroups => "UTC offset, grouped",
+            ZoneOrder::WorkStartsNext => "work starts next",
+            ZoneOrder::WorkEndsNext => "work ends next",
+        }
+    }
+}
+
+/// Whether to use the light variant of the theme. `Auto` asks the terminal
+/// for its background color.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default, clap::ValueEnum)]
+pub enum BackgroundMode {
+    #[default]
+    Auto,
+    Dark,
+    Light,
+}
+
+/// Something drawn over a timeline bar. Where several land on the same cell
+/// the one earliest in `marker_priority` is shown.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Marker {
+    Now,
+    Scrub,
+    Dst,
+    Milestone,
+    Date,
+    Midnight,
+}
+
+impl Marker {
+    pub const DEFAULT_PRIORITY: [Marker; 6] = [
+        Marker::Now,
+        Marker::Scrub,
+        Marker::Dst,
+        Marker::Milestone,
+        Marker::Date,
+        Marker::Midnight,
+    ];
+}
+
+fn default_marker_priority() -> Vec<Marker> {
+    Marker::DEFAULT_PRIORITY.to_vec()
+}
+
+/// Overrides for how the now line is drawn, since the thin line is easy to
+/// lose on a busy board
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NowLineStyle {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub glyph: Option<char>, // Replaces the glyph set's line, e.g. "┃"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>, // Color name, index or #rrggbb; red when unset
+    pub highlight_hour: bool, // Tint each row's current local hour
+}
+
+impl NowLineStyle {
+    pub const DEFAULT: NowLineStyle = NowLineStyle {
+        glyph: None,
+        color: None,
+        highlight_hour: false,
+    };
+
+    /// The configured color, or the palette's when unset or unreadable
+    pub fn color(&self, palette: Palette) -> Color {
+        self.color
+            .as_deref()
+            .and_then(|color| color.parse().ok())
+            .unwrap_or(palette.current_time)
+    }
+}
+
+/// When a scrubbed time counts as too early or too late for someone, so the
+/// footer can suggest moving it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EtiquetteConfig {
+    pub early: u32, // Hour before which a time is too early; 0 never is
+    pub late: u32,  // Hour after which a time is too late; 24 never is
+}
+
+impl Default for EtiquetteConfig {
+    fn default() -> Self {
+        Self { early: 8, late: 20 }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub enum ColorTheme {
     #[default]
@@ -47,6 +247,13 @@ pub enum ColorTheme {
 }
 
 impl ColorTheme {
+    /// The built-in theme with this name, ignoring case
+    pub fn from_name(name: &str) -> Option<ColorTheme> {
+        Self::all_themes()
+            .into_iter()
+            .find(|theme| format!("{theme:?}").eq_ignore_ascii_case(name))
+    }
+
     pub fn all_themes() -> Vec<ColorTheme> {
         vec![
             ColorTheme::Default,
@@ -98,6 +305,17 @@ impl ColorTheme {
         }
     }
 
+    pub fn get_midnight_color(&self) -> Color {
+        match self {
+            ColorTheme::Default => Color::Gray,
+            ColorTheme::Ocean => Color::LightBlue,
+            ColorTheme::Forest => Color::LightGreen,
+            ColorTheme::Sunset => Color::LightRed,
+            ColorTheme::Cyberpunk => Color::LightCyan,
+            ColorTheme::Monochrome => Color::White,
+        }
+    }
+
     pub fn get_selected_border_color(&self) -> Color {
         match self {
             ColorTheme::Default => Color::Yellow,
@@ -123,46 +341,347 @@ impl ColorTheme {
     pub fn get_current_time_color(&self) -> Color {
         Color::Red // Keep consistent across all themes for clarity
     }
+
+    /// Background for the current hour, dark enough to leave the bar readable
+    pub fn get_current_hour_color(&self) -> Color {
+        match self {
+            ColorTheme::Default => Color::Indexed(237),
+            ColorTheme::Ocean => Color::Indexed(17),
+            ColorTheme::Forest => Color::Indexed(22),
+            ColorTheme::Sunset => Color::Indexed(52),
+            ColorTheme::Cyberpunk => Color::Indexed(53),
+            ColorTheme::Monochrome => Color::Indexed(236),
+        }
+    }
+
+    pub fn palette(&self) -> Palette {
+        Palette {
+            night: self.get_night_color(),
+            awake: self.get_awake_color(),
+            work: self.get_work_color(),
+            midnight: self.get_midnight_color(),
+            selected_border: self.get_selected_border_color(),
+            timeline_position: self.get_timeline_position_color(),
+            current_time: self.get_current_time_color(),
+            current_hour: self.get_current_hour_color(),
+        }
+    }
+
+    /// The palette, or its light variant for light terminal backgrounds
+    pub fn palette_for(&self, light: bool) -> Palette {
+        if light {
+            self.palette().light()
+        } else {
+            self.palette()
+        }
+    }
+}
+
+/// The colors the board is drawn with, from a built-in or user theme
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    pub night: Color,
+    pub awake: Color,
+    pub work: Color,
+    pub midnight: Color,
+    pub selected_border: Color,
+    pub timeline_position: Color,
+    pub current_time: Color,
+    pub current_hour: Color,
+}
+
+impl Palette {
+    /// A variant for light backgrounds. The built-in themes are drawn for
+    /// dark terminals, so their pale colors give way to darker ones and the
+    /// current hour tint becomes a pale one.
+    pub fn light(self) -> Palette {
+        let darker = |color: Color| match color {
+            Color::White => Color::Black,
+            Color::Gray => Color::DarkGray,
+            Color::Yellow | Color::LightYellow => Color::Indexed(136),
+            Color::Cyan | Color::LightCyan => Color::Indexed(30),
+            Color::LightGreen => Color::Green,
+            Color::LightBlue => Color::Blue,
+            Color::LightRed => Color::Red,
+            Color::LightMagenta => Color::Magenta,
+            other => other,
+        };
+        let tint = match self.current_hour {
+            Color::Indexed(236) => Color::Indexed(255),
+            Color::Indexed(237) => Color::Indexed(254),
+            Color::Indexed(17) => Color::Indexed(189),
+            Color::Indexed(22) => Color::Indexed(194),
+            Color::Indexed(52) => Color::Indexed(224),
+            Color::Indexed(53) => Color::Indexed(225),
+            other => other,
+        };
+        Palette {
+            night: darker(self.night),
+            awake: darker(self.awake),
+            work: darker(self.work),
+            midnight: darker(self.midnight),
+            selected_border: darker(self.selected_border),
+            timeline_position: darker(self.timeline_position),
+            current_time: darker(self.current_time),
+            current_hour: tint,
+        }
+    }
+
+    /// The nearest palette a terminal with `colors` can show
+    pub fn fit(self, colors: ColorSupport) -> Palette {
+        Palette {
+            night: colors.fit(self.night),
+            awake: colors.fit(self.awake),
+            work: colors.fit(self.work),
+            midnight: colors.fit(self.midnight),
+            selected_border: colors.fit(self.selected_border),
+            timeline_position: colors.fit(self.timeline_position),
+            current_time: colors.fit(self.current_time),
+            current_hour: colors.fit(self.current_hour),
+        }
+    }
+}
+
+/// A theme of the user's own: a built-in theme with some of its colors
+/// replaced. Colors are names, 256-color indexes or #rrggbb.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UserTheme {
+    pub name: String,
+    #[serde(default)]
+    pub inherits: ColorTheme, // Built-in theme supplying the colors not set here
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub night: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub awake: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub work: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub midnight: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_border: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline_position: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_time: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_hour: Option<String>,
+}
+
+impl UserTheme {
+    /// Each overridden color by key, set or not
+    fn overrides(&self) -> [(&'static str, &Option<String>); 8] {
+        [
+            ("night", &self.night),
+            ("awake", &self.awake),
+            ("work", &self.work),
+            ("midnight", &self.midnight),
+            ("selected_border", &self.selected_border),
+            ("timeline_position", &self.timeline_position),
+            ("current_time", &self.current_time),
+            ("current_hour", &self.current_hour),
+        ]
+    }
+
+    /// The inherited palette, in its light variant if asked, with the
+    /// overrides applied as given. Unreadable colors keep the inherited one;
+    /// `alltz config check` reports them.
+    pub fn palette(&self, light: bool) -> Palette {
+        let base = self.inherits.palette_for(light);
+        let color = |value: &Option<String>, inherited: Color| {
+            value
+                .as_deref()
+                .and_then(|color| color.parse().ok())
+                .unwrap_or(inherited)
+        };
+        Palette {
+            night: color(&self.night, base.night),
+            awake: color(&self.awake, base.awake),
+            work: color(&self.work, base.work),
+            midnight: color(&self.midnight, base.midnight),
+            selected_border: color(&self.selected_border, base.selected_border),
+            timeline_position: color(&self.timeline_position, base.timeline_position),
+            current_time: color(&self.current_time, base.current_time),
+            current_hour: color(&self.current_hour, base.current_hour),
+        }
+    }
+
+    /// Keys of colors that don't parse, with their values
+    pub fn unknown_colors(&self) -> Vec<(&'static str, &str)> {
+        self.overrides()
+            .into_iter()
+            .filter_map(|(key, value)| value.as_deref().map(|value| (key, value)))
+            .filter(|(_, value)| value.parse::<Color>().is_err())
+            .collect()
+    }
+}
+
+/// The palette for a theme name, looking at user themes before built-in ones
+pub fn find_palette(name: &str, themes: &[UserTheme], light: bool) -> Option<Palette> {
+    themes
+        .iter()
+        .find(|theme| theme.name.eq_ignore_ascii_case(name))
+        .map(|theme| theme.palette(light))
+        .or_else(|| ColorTheme::from_name(name).map(|theme| theme.palette_for(light)))
 }
 
 impl TimeDisplayConfig {
+    pub fn get(&self, boundary: HourBoundary) -> u32 {
+        match boundary {
+            HourBoundary::AwakeStart => self.awake_hours_start,
+            HourBoundary::WorkStart => self.work_hours_start,
+            HourBoundary::WorkEnd => self.work_hours_end,
+            HourBoundary::AwakeEnd => self.awake_hours_end,
+        }
+    }
+
+    /// Moves a boundary by `hours`, stopping at its neighbours so work hours
+    /// stay non-empty and inside awake hours, and everything within the day.
+    /// Hours whose neighbours already cross, such as overnight shifts, are
+    /// left alone.
+    pub fn nudge(&mut self, boundary: HourBoundary, hours: i32) {
+        let [awake_start, work_start, work_end, awake_end] = [
+            self.awake_hours_start,
+            self.work_hours_start,
+            self.work_hours_end,
+            self.awake_hours_end,
+        ]
+        .map(|hour| hour as i32);
+        let (min, max) = match boundary {
+            HourBoundary::AwakeStart => (0, work_start),
+            HourBoundary::WorkStart => (awake_start, work_end - 1),
+            HourBoundary::WorkEnd => (work_start + 1, awake_end),
+            HourBoundary::AwakeEnd => (work_end, 24),
+        };
+        if min > max {
+            return;
+        }
+        let value = (self.get(boundary) as i32)
+            .saturating_add(hours)
+            .clamp(min, max) as u32;
+        match boundary {
+            HourBoundary::AwakeStart => self.awake_hours_start = value,
+            HourBoundary::WorkStart => self.work_hours_start = value,
+            HourBoundary::WorkEnd => self.work_hours_end = value,
+            HourBoundary::AwakeEnd => self.awake_hours_end = value,
+        }
+    }
+
     pub fn get_time_activity(&self, hour: u32) -> TimeActivity {
         let hour = hour % 24; // Ensure valid hour range
+                              // Hours ending before they start run past midnight
+        let within = |start: u32, end: u32| {
+            if start <= end {
+                hour >= start && hour < end
+            } else {
+                hour >= start || hour < end
+            }
+        };
 
-        if hour >= self.work_hours_start && hour < self.work_hours_end {
+        if within(self.work_hours_start, self.work_hours_end) {
             TimeActivity::Work
-        } else if hour >= self.awake_hours_start && hour < self.awake_hours_end {
+        } else if within(self.awake_hours_start, self.awake_hours_end) {
             TimeActivity::Awake
         } else {
             TimeActivity::Night
         }
     }
 
+    /// The Unicode shade for `activity`; timelines draw with their
+    /// [`Glyphs`](crate::ui::Glyphs), which fall back to ASCII
     pub fn get_activity_char(&self, activity: TimeActivity) -> char {
-        match activity {
-            TimeActivity::Night => '░', // Light shade - low activity
-            TimeActivity::Awake => '▒', // Medium shade - moderate activity
-            TimeActivity::Work => '▓',  // Dark shade - high activity (less intense than █)
-        }
+        crate::ui::Glyphs::UNICODE.activity(activity)
     }
 
-    pub fn get_activity_color(&self, activity: TimeActivity, theme: ColorTheme) -> Color {
+    pub fn get_activity_color(&self, activity: TimeActivity, palette: Palette) -> Color {
         match activity {
-            TimeActivity::Night => theme.get_night_color(),
-            TimeActivity::Awake => theme.get_awake_color(),
-            TimeActivity::Work => theme.get_work_color(),
+            TimeActivity::Night => palette.night,
+            TimeActivity::Awake => palette.awake,
+            TimeActivity::Work => palette.work,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
 pub struct ZoneConfig {
     pub city_name: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub custom_label: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent: Option<String>, // Color for the zone's name and border
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hours: Option<TimeDisplayConfig>, // Hours kept there, instead of `time_config`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<String>, // Preferred call windows, e.g. "mornings" or "before 16:00"
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub moves: Vec<ZoneMove>, // Cities they're moving to, and from when
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub seasons: Vec<SeasonalHours>, // Hours kept for part of every year, e.g. summer hours
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Hours a zone keeps for part of every year, such as summer hours, over
+/// its usual ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SeasonalHours {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub from: MonthDay, // First day, "06-15" or "Jun 15"
+    pub to: MonthDay,   // Last day, inclusive; before `from` for seasons over new year
+    pub hours: TimeDisplayConfig,
+}
+
+impl SeasonalHours {
+    /// Whether the season takes in `date`; one whose days can't be read
+    /// (which `alltz config check` reports) never does
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        let (Some(from), Some(to)) = (self.from.day, self.to.day) else {
+            return false;
+        };
+        let day = (date.month(), date.day());
+        if from <= to {
+            (from..=to).contains(&day)
+        } else {
+            day >= from || day <= to
+        }
+    }
+}
+
+/// A day of the year as written in the config, read once when it's loaded
+/// rather than each time a season is looked up
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub struct MonthDay {
+    text: String,
+    day: Option<(u32, u32)>, // None if it can't be read
+}
+
+impl From<String> for MonthDay {
+    fn from(text: String) -> Self {
+        let day = parse_month_day(&text).ok();
+        MonthDay { text, day }
+    }
+}
+
+impl From<&str> for MonthDay {
+    fn from(text: &str) -> Self {
+        MonthDay::from(text.to_string())
+    }
+}
+
+impl From<MonthDay> for String {
+    fn from(day: MonthDay) -> Self {
+        day.text
+    }
+}
+
+/// A day whoever a zone stands for starts keeping another city's time, so
+/// the board and planner follow them there
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ZoneMove {
+    pub from: NaiveDate, // First day there, in the new city's time
+    pub city: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum ZoneConfigCompat {
     Simple(String),
@@ -170,6 +689,69 @@ pub enum ZoneConfigCompat {
 }
 
 impl ZoneConfigCompat {
+    /// A zone entry, in the short string form unless it has a custom label
+    pub fn new(city_name: String, custom_label: Option<String>) -> Self {
+        match custom_label {
+            Some(label) => ZoneConfigCompat::Simple(city_name).into_full(|config| {
+                config.custom_label = Some(label);
+            }),
+            None => ZoneConfigCompat::Simple(city_name),
+        }
+    }
+
+    /// The entry in the table form, with `change` made to it
+    fn into_full(self, change: impl FnOnce(&mut ZoneConfig)) -> Self {
+        let mut config = match self {
+            ZoneConfigCompat::Simple(city_name) => ZoneConfig {
+                city_name,
+                ..ZoneConfig::default()
+            },
+            ZoneConfigCompat::Full(config) => config,
+        };
+        change(&mut config);
+        ZoneConfigCompat::Full(config)
+    }
+
+    /// The same entry with an accent color, in the table form if it has one
+    pub fn with_accent(self, accent: Option<String>) -> Self {
+        match accent {
+            None => self,
+            accent => self.into_full(|config| config.accent = accent),
+        }
+    }
+
+    /// The same entry with its own hours, in the table form if it has them
+    pub fn with_hours(self, hours: Option<TimeDisplayConfig>) -> Self {
+        match hours {
+            None => self,
+            hours => self.into_full(|config| config.hours = hours),
+        }
+    }
+
+    /// The same entry with its call windows, in the table form if it has any
+    pub fn with_calls(self, calls: Vec<String>) -> Self {
+        if calls.is_empty() {
+            return self;
+        }
+        self.into_full(|config| config.calls = calls)
+    }
+
+    /// The same entry with its moves, in the table form if it has any
+    pub fn with_moves(self, moves: Vec<ZoneMove>) -> Self {
+        if moves.is_empty() {
+            return self;
+        }
+        self.into_full(|config| config.moves = moves)
+    }
+
+    /// The same entry with its seasonal hours, in the table form if it has any
+    pub fn with_seasons(self, seasons: Vec<SeasonalHours>) -> Self {
+        if seasons.is_empty() {
+            return self;
+        }
+        self.into_full(|config| config.seasons = seasons)
+    }
+
     pub fn city_name(&self) -> &str {
         match self {
             ZoneConfigCompat::Simple(name) => name,
@@ -183,6 +765,162 @@ impl ZoneConfigCompat {
             ZoneConfigCompat::Full(config) => config.custom_label.as_deref(),
         }
     }
+
+    pub fn accent(&self) -> Option<&str> {
+        match self {
+            ZoneConfigCompat::Simple(_) => None,
+            ZoneConfigCompat::Full(config) => config.accent.as_deref(),
+        }
+    }
+
+    pub fn hours(&self) -> Option<&TimeDisplayConfig> {
+        match self {
+            ZoneConfigCompat::Simple(_) => None,
+            ZoneConfigCompat::Full(config) => config.hours.as_ref(),
+        }
+    }
+
+    pub fn calls(&self) -> &[String] {
+        match self {
+            ZoneConfigCompat::Simple(_) => &[],
+            ZoneConfigCompat::Full(config) => &config.calls,
+        }
+    }
+
+    pub fn moves(&self) -> &[ZoneMove] {
+        match self {
+            ZoneConfigCompat::Simple(_) => &[],
+            ZoneConfigCompat::Full(config) => &config.moves,
+        }
+    }
+
+    pub fn seasons(&self) -> &[SeasonalHours] {
+        match self {
+            ZoneConfigCompat::Simple(_) => &[],
+            ZoneConfigCompat::Full(config) => &config.seasons,
+        }
+    }
+}
+
+/// A named moment marked on every bar, such as a release's code freeze.
+/// Milestones in the same group move together when a plan slips.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Milestone {
+    pub name: String,
+    pub time: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+}
+
+/// A time card layout of the user's own. `zone` is written out for each zone
+/// with `{name}`, `{city}`, `{time}`, `{date}`, `{abbr}`, `{offset}` and
+/// `{org}` filled in, and the results joined with `separator`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CardTemplate {
+    pub name: String,
+    pub zone: String,
+    #[serde(default = "default_card_separator")]
+    pub separator: String,
+}
+
+/// Days someone is away, such as a holiday or school break, shaded on their
+/// zone's row and left out of overlap counts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BlockedDates {
+    pub who: String, // Zone by label or city
+    pub from: NaiveDate,
+    pub to: NaiveDate, // Last blocked day, inclusive
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl BlockedDates {
+    pub fn covers(&self, zone: &TimeZone, date: NaiveDate) -> bool {
+        (self.from..=self.to).contains(&date) && zone.answers_to(&self.who)
+    }
+}
+
+/// Someone's calendar, exported or synced as an iCalendar file, whose
+/// all-day out-of-office events block their days
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CalendarConfig {
+    pub who: String, // Zone by label or city
+    pub file: PathBuf,
+}
+
+/// Task lists whose due dates are marked on the home zone's bar
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TasksConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub todo_txt: Option<PathBuf>, // todo.txt file, with `due:YYYY-MM-DD[THH:MM]` tags
+    #[serde(default)]
+    pub taskwarrior: bool, // Pending tasks from `task export`
+}
+
+/// Teams passing work around the world, each handing over to the next as
+/// its working day ends and the last back to the first
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandoffConfig {
+    pub chain: Vec<String>, // Zones by label or city, in handoff order
+    #[serde(default = "default_min_overlap_minutes")]
+    pub min_overlap_minutes: u32, // Warn when a DST change leaves less overlap than this
+}
+
+fn default_min_overlap_minutes() -> u32 {
+    60
+}
+
+/// Somewhere an alarm can be sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NotifyBackend {
+    /// The terminal bell
+    Bell,
+    /// A desktop notification, through notify-send or osascript
+    Desktop,
+    /// A JSON POST to `notify.webhook`
+    Webhook,
+    /// Running `notify.command`
+    Command,
+}
+
+/// Where each kind of alarm is sent, so a board left running headless can
+/// still reach someone
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotifyConfig {
+    #[serde(default = "default_reminder_backends")]
+    pub reminder: Vec<NotifyBackend>, // Reminders set with `alltz remind`
+    #[serde(default)]
+    pub milestone: Vec<NotifyBackend>, // Milestones as their time comes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>, // URL alarms are POSTed to as JSON
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>, // Program run with each alarm's title and text
+}
+
+fn default_reminder_backends() -> Vec<NotifyBackend> {
+    vec![NotifyBackend::Bell]
+}
+
+impl Default for NotifyConfig {
+    fn default() -> Self {
+        NotifyConfig {
+            reminder: default_reminder_backends(),
+            milestone: Vec::new(),
+            webhook: None,
+            command: None,
+        }
+    }
+}
+
+/// A named group of zones (e.g. "Team", "Customers") that can be shown
+/// alongside the main board in split view.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LayoutConfig {
+    pub name: String,
+    pub zones: Vec<ZoneConfigCompat>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>, // Built-in or user theme for this layout's pane
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -193,9 +931,89 @@ pub struct AppConfig {
     pub timezone_display_mode: TimezoneDisplayMode, // Short/Full names
     pub time_config: TimeDisplayConfig, // Work/awake/night hours
     pub color_theme: ColorTheme,      // Color theme for UI
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_theme: Option<String>, // Name of a theme in `themes`, used instead of color_theme
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub themes: Vec<UserTheme>, // User themes built on the built-in ones
+    #[serde(default)]
+    pub background: BackgroundMode, // Light or dark theme variant, or ask the terminal
+    #[serde(default)]
+    pub zone_order: ZoneOrder, // Offset order, or by who starts or ends work next
     pub show_date: bool,              // Date display toggle
     #[serde(default = "default_true")]
     pub show_sun_times: bool, // Sunrise/sunset times display toggle
+    #[serde(default)]
+    pub focus_mode: bool, // Hide borders, header, legend and footer
+    #[serde(default)]
+    pub focus_ruler: bool, // Hour ruler under the selected zone
+    #[serde(default)]
+    pub offset_minutes: bool, // Minutes in every zone's UTC offset, not just :30 and :45 zones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align_to: Option<String>, // Zone whose midnight starts the window, rather than centring on the scrub line
+    #[serde(default)]
+    pub dst_countdown_days: u32, // Count down in zone titles to clock changes this close; 0 never
+    #[serde(default)]
+    pub reduced_motion: bool, // Jump instantly instead of animating long scrubs
+    #[serde(default)]
+    pub low_bandwidth: bool, // Fewer redrawn cells for slow SSH/mosh links
+    #[serde(default = "default_frame_budget_ms")]
+    pub frame_budget_ms: u64, // Frames slower than this drop decorations; 0 never does
+    #[serde(default)]
+    pub glyphs: GlyphMode, // Unicode or ASCII drawing characters
+    #[serde(default = "default_marker_priority")]
+    pub marker_priority: Vec<Marker>, // Which marker wins a shared cell, highest first
+    #[serde(default)]
+    pub now_line: NowLineStyle, // Now line glyph, color and current hour highlight
+    #[serde(default)]
+    pub etiquette: EtiquetteConfig, // Hours outside which the footer suggests a kinder time
+    #[serde(default)]
+    pub clock_graphics: bool, // Analog clock image on kitty/sixel terminals
+    #[serde(default)]
+    pub privacy_mode: bool, // Hide custom labels and layout names when screen sharing
+    #[serde(default)]
+    pub update_check: bool, // Ask GitHub once a day whether a newer release is out
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layouts: Vec<LayoutConfig>, // Extra zone groups for split view
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub board_name: Option<String>, // Title over the main board in split view
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived: Vec<ZoneConfigCompat>, // Zones taken off the board, kept with their labels to restore
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub macros: BTreeMap<String, Vec<String>>, // Recorded key sequences by register
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub keys: BTreeMap<String, String>, // Commands bound to keys, over the defaults
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub milestones: Vec<Milestone>, // Named moments marked on every bar
+    #[serde(default = "default_imminent_minutes")]
+    pub imminent_minutes: u32, // Milestones this close stand out on every bar; 0 never
+    #[serde(default)]
+    pub pulse_imminent: bool, // Flash imminent milestones each second rather than holding them inverse
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub card_templates: Vec<CardTemplate>, // Time card layouts offered when copying with `y`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub blocked: Vec<BlockedDates>, // Days people are away, see `alltz blocked import`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calendars: Vec<CalendarConfig>, // Calendar files whose out-of-office days block their owner's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tasks: Option<TasksConfig>, // Task lists whose due dates mark the home zone's bar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handoff: Option<HandoffConfig>, // Follow-the-sun handoff chain
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notify: Option<NotifyConfig>, // Where reminders and milestones alert
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub abbreviations: BTreeMap<String, String>, // Zone each ambiguous abbreviation means, learned from searches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync: Option<SyncConfig>, // Shared team board backend
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence: Option<PresenceConfig>, // Sharing the scrub position on the shared board
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fiscal_year_start: Option<u32>, // Month (1-12) fiscal years start, for quarter labels
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub home: Option<String>, // Zone this machine should keep, checked at startup
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ntp_server: Option<String>, // Time server to check the clock against at startup
+    #[serde(skip)]
+    pub env_keys: Vec<String>, // Dotted keys overridden by ALLTZ_* env vars, never saved
 }
 
 impl Default for AppConfig {
@@ -215,26 +1033,232 @@ impl Default for AppConfig {
             timezone_display_mode: TimezoneDisplayMode::Short,
             time_config: TimeDisplayConfig::default(),
             color_theme: ColorTheme::default(),
+            user_theme: None,
+            themes: Vec::new(),
+            background: BackgroundMode::default(),
+            zone_order: ZoneOrder::default(),
             show_date: false,
             show_sun_times: true, // Enable by default
+            focus_mode: false,
+            focus_ruler: false,
+            offset_minutes: false,
+            align_to: None,
+            dst_countdown_days: 0,
+            reduced_motion: false,
+            low_bandwidth: false,
+            frame_budget_ms: default_frame_budget_ms(),
+            glyphs: GlyphMode::default(),
+            marker_priority: default_marker_priority(),
+            now_line: NowLineStyle::default(),
+            etiquette: EtiquetteConfig::default(),
+            clock_graphics: false,
+            privacy_mode: false,
+            update_check: false,
+            layouts: Vec::new(),
+            board_name: None,
+            archived: Vec::new(),
+            macros: BTreeMap::new(),
+            keys: BTreeMap::new(),
+            milestones: Vec::new(),
+            imminent_minutes: default_imminent_minutes(),
+            pulse_imminent: false,
+            card_templates: Vec::new(),
+            blocked: Vec::new(),
+            calendars: Vec::new(),
+            tasks: None,
+            handoff: None,
+            notify: None,
+            abbreviations: BTreeMap::new(),
+            sync: None,
+            presence: None,
+            fiscal_year_start: None,
+            home: None,
+            ntp_server: None,
+            env_keys: Vec::new(),
         }
     }
 }
 
+/// Keys whose arrays are combined across config layers rather than replaced,
+/// so users can add zones, layouts and themes on top of the org's board
+const APPENDED_KEYS: [&str; 3] = ["zones", "layouts", "themes"];
+
+/// Identity of a zone or layout entry, used to let user entries replace org ones
+fn entry_key(value: &toml::Value) -> Option<String> {
+    match value {
+        toml::Value::String(name) => Some(name.clone()),
+        toml::Value::Table(table) => table
+            .get("city_name")
+            .or_else(|| table.get("name"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string()),
+        _ => None,
+    }
+}
+
+/// Recursively merges `overlay` into `base`. Tables merge key by key; for
+/// `APPENDED_KEYS` (when `append` is set) arrays are unioned, with overlay
+/// entries replacing base entries of the same city/name.
+fn merge_tables(base: &mut toml::Table, overlay: toml::Table, append: bool) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(toml::Value::Table(base_table)), toml::Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table, false);
+            }
+            (Some(toml::Value::Array(base_array)), toml::Value::Array(overlay_array))
+                if append && APPENDED_KEYS.contains(&key.as_str()) =>
+            {
+                for entry in overlay_array {
+                    let existing = entry_key(&entry).and_then(|name| {
+                        base_array
+                            .iter()
+                            .position(|e| entry_key(e).as_deref() == Some(name.as_str()))
+                    });
+                    match existing {
+                        Some(index) => base_array[index] = entry,
+                        None => base_array.push(entry),
+                    }
+                }
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+/// How long a fetched org config is used before it's fetched again
+const ORG_REFRESH_EVERY: std::time::Duration = std::time::Duration::from_secs(24 * 60 * 60);
+
+fn is_url(source: &str) -> bool {
+    source.starts_with("https://") || source.starts_with("http://")
+}
+
+/// Downloads the org config at `url` to `cache`, only replacing the copy
+/// there with one that parses
+fn fetch_org_config(url: &str, cache: &std::path::Path) -> std::io::Result<()> {
+    let content = platform::run(std::process::Command::new("curl").args([
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--location",
+        "--max-time",
+        "10",
+        url,
+    ]))?;
+    if AppConfig::parse_table(&content).is_none() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "not a TOML config",
+        ));
+    }
+    if let Some(parent) = cache.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let staged = cache.with_extension(format!("toml.{}", std::process::id()));
+    fs::write(&staged, content.as_bytes())?;
+    fs::rename(&staged, cache)
+}
+
 impl AppConfig {
     pub fn config_path() -> Option<PathBuf> {
-        dirs::home_dir().map(|home_dir| home_dir.join(".config").join("alltz").join("config.toml"))
+        Platform::current()
+            .config_dir()
+            .map(|dir| dir.join("config.toml"))
     }
 
-    pub fn load() -> Self {
-        if let Some(config_path) = Self::config_path() {
-            if let Ok(content) = fs::read_to_string(&config_path) {
-                if let Ok(config) = toml::from_str::<AppConfig>(&content) {
-                    return config;
-                }
+    /// System-wide config shipped by an organisation, layered under the user's.
+    /// `ALLTZ_ORG_CONFIG` points at an alternative file, or at a URL whose
+    /// last fetched copy is kept next to the user's config.
+    pub fn org_config_path() -> Option<PathBuf> {
+        match std::env::var_os("ALLTZ_ORG_CONFIG") {
+            Some(source) if source.to_str().is_some_and(is_url) => Self::org_cache_path(),
+            Some(path) => Some(PathBuf::from(path)),
+            None => Platform::current()
+                .org_config_dir(platform::env_var)
+                .map(|dir| dir.join("config.toml")),
+        }
+    }
+
+    fn org_cache_path() -> Option<PathBuf> {
+        Platform::current()
+            .config_dir()
+            .map(|dir| dir.join("org-config.toml"))
+    }
+
+    /// Fetches an org config given as a URL: straight away when there's no
+    /// copy yet, or in the background once the copy is a day old, so a slow
+    /// or missing network only holds up the first start
+    fn refresh_org_cache() {
+        let Some(url) = platform::env_var("ALLTZ_ORG_CONFIG").filter(|source| is_url(source))
+        else {
+            return;
+        };
+        let Some(cache) = Self::org_cache_path() else {
+            return;
+        };
+        let age = fs::metadata(&cache)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok());
+        let fetch = move || {
+            if let Err(e) = fetch_org_config(&url, &cache) {
+                crate::log::write(&format!("Failed to fetch the org config from {url}: {e}"));
+            }
+        };
+        match age {
+            None => fetch(),
+            Some(age) if age > ORG_REFRESH_EVERY => {
+                std::thread::spawn(fetch);
             }
+            Some(_) => {}
         }
-        Self::default()
+    }
+
+    fn read_table(path: Option<PathBuf>) -> Option<toml::Table> {
+        Self::parse_table(&fs::read_to_string(path?).ok()?)
+    }
+
+    /// A config file's contents as a layer, or nothing if it isn't TOML
+    pub fn parse_table(content: &str) -> Option<toml::Table> {
+        platform::strip_bom(content).parse::<toml::Table>().ok()
+    }
+
+    pub fn load() -> Self {
+        ConfigLayers::load().resolve()
+    }
+
+    /// Deserializes `table` over the defaults, keeping the error for reporting
+    fn try_over_defaults(table: toml::Table) -> Result<Self, toml::de::Error> {
+        let mut merged = toml::Table::try_from(Self::default()).unwrap_or_default();
+        merge_tables(&mut merged, table, false);
+        toml::Value::Table(merged).try_into()
+    }
+
+    /// The part of this config that belongs in the user's file, given the
+    /// file as it is on disk and the org layer underneath it. Values the
+    /// defaults or the org already give are left out unless the user's file
+    /// sets them, so later changes to those reach the user; org zones,
+    /// layouts and themes are only kept where the user has changed them.
+    /// Values forced by the environment stay as they were on disk.
+    pub fn user_table(
+        &self,
+        on_disk: Option<&toml::Table>,
+        org: Option<toml::Table>,
+    ) -> Result<toml::Table, toml::ser::Error> {
+        let mut table = toml::Table::try_from(self)?;
+        let org = org.and_then(|org| Self::try_over_defaults(org).ok());
+        let append = org.is_some();
+        let inherited = toml::Table::try_from(org.unwrap_or_default())?;
+        let empty = toml::Table::new();
+        leave_out_inherited(&mut table, &inherited, on_disk.unwrap_or(&empty), append);
+
+        for key in &self.env_keys {
+            let path: Vec<&str> = key.split('.').collect();
+            let original = on_disk.and_then(|t| lookup(t, &path)).cloned();
+            set_path(&mut table, &path, original);
+        }
+        Ok(table)
     }
 
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
@@ -244,17 +1268,799 @@ impl AppConfig {
                 fs::create_dir_all(parent)?;
             }
 
-            let content = toml::to_string_pretty(self)?;
+            let on_disk = Self::read_table(Some(config_path.clone()));
+            let org = Self::read_table(Self::org_config_path());
+            let table = self.user_table(on_disk.as_ref(), org)?;
+            let content = toml::to_string_pretty(&table)?;
             fs::write(&config_path, content)?;
         }
         Ok(())
     }
 }
 
+/// The raw config layers as read from disk and the environment, before being merged
+#[derive(Debug, Clone, Default)]
+pub struct ConfigLayers {
+    pub org: Option<toml::Table>,
+    pub user: Option<toml::Table>,
+    pub env: Option<toml::Table>,
+    pub env_rejected: Vec<(String, String)>, // Variables left out of `env`, with why
+}
+
+/// Which layer an effective config value came from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConfigSource {
+    Default,
+    Org,
+    User,
+    Env,
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigSource::Default => write!(f, "default"),
+            ConfigSource::Org => write!(f, "org"),
+            ConfigSource::User => write!(f, "user"),
+            ConfigSource::Env => write!(f, "env"),
+        }
+    }
+}
+
+/// The effective value of a config key and the layers that supplied it.
+/// Appended keys such as `zones` can come from more than one layer.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    pub value: Option<toml::Value>,
+    pub sources: Vec<ConfigSource>,
+}
+
+/// Drops values from `table` that match `inherited` and aren't set in
+/// `user`. With `append`, for a top-level table over an org layer, entries
+/// of the appended arrays that match an inherited entry are dropped too,
+/// since the user's entries are added to those when loading.
+fn leave_out_inherited(
+    table: &mut toml::Table,
+    inherited: &toml::Table,
+    user: &toml::Table,
+    append: bool,
+) {
+    let empty = toml::Table::new();
+    table.retain(|key, value| {
+        let in_user = user.get(key);
+        match (value, inherited.get(key)) {
+            (toml::Value::Table(inner), Some(toml::Value::Table(base))) => {
+                let user_inner = in_user.and_then(|v| v.as_table()).unwrap_or(&empty);
+                leave_out_inherited(inner, base, user_inner, false);
+                !inner.is_empty() || in_user.is_some()
+            }
+            (toml::Value::Array(entries), Some(toml::Value::Array(base)))
+                if append && APPENDED_KEYS.contains(&key) =>
+            {
+                entries.retain(|entry| !base.contains(entry));
+                !entries.is_empty() || in_user.is_some()
+            }
+            (value, base) => in_user.is_some() || base != Some(value),
+        }
+    });
+}
+
+/// Looks up a dotted key path such as `time_config.work_hours_start`
+fn lookup<'a>(table: &'a toml::Table, path: &[&str]) -> Option<&'a toml::Value> {
+    let (first, rest) = path.split_first()?;
+    let value = table.get(*first)?;
+    if rest.is_empty() {
+        Some(value)
+    } else {
+        lookup(value.as_table()?, rest)
+    }
+}
+
+/// Sets (or with `None`, removes) the value at a dotted key path
+fn set_path(table: &mut toml::Table, path: &[&str], value: Option<toml::Value>) {
+    let Some((first, rest)) = path.split_first() else {
+        return;
+    };
+    if rest.is_empty() {
+        match value {
+            Some(value) => table.insert(first.to_string(), value),
+            None => table.remove(*first),
+        };
+        return;
+    }
+    if !table.get(*first).is_some_and(|v| v.is_table()) {
+        if value.is_none() {
+            return;
+        }
+        table.insert(first.to_string(), toml::Value::Table(toml::Table::new()));
+    }
+    if let Some(toml::Value::Table(inner)) = table.get_mut(*first) {
+        set_path(inner, rest, value);
+    }
+}
+
+/// Every dotted key path to a non-table value
+fn leaf_paths(table: &toml::Table, prefix: &str, paths: &mut Vec<String>) {
+    for (key, value) in table {
+        let path = format!("{prefix}{key}");
+        match value {
+            toml::Value::Table(inner) => leaf_paths(inner, &format!("{path}."), paths),
+            _ => paths.push(path),
+        }
+    }
+}
+
+/// Prefix for environment overrides; `__` separates nested keys, so
+/// `ALLTZ_TIME_CONFIG__WORK_HOURS_START=9` sets `time_config.work_hours_start`
+const ENV_PREFIX: &str = "ALLTZ_";
+
+/// Builds a config layer from `ALLTZ_*` variables. Names that don't match a
+/// config key are ignored. Values are read as TOML where the key isn't a
+/// string, and arrays such as zones can also be given comma separated. A
+/// variable with a value its key can't take is left out, and returned with
+/// the reason so it can be reported, without affecting the others.
+pub fn env_layer(
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> (Option<toml::Table>, Vec<(String, String)>) {
+    let Ok(defaults) = toml::Table::try_from(AppConfig::default()) else {
+        return (None, Vec::new());
+    };
+    let mut layer = toml::Table::new();
+    let mut rejected = Vec::new();
+
+    for (name, raw) in vars {
+        let Some(key) = name.strip_prefix(ENV_PREFIX) else {
+            continue;
+        };
+        let key = key.to_lowercase();
+        let path: Vec<&str> = key.split("__").collect();
+        let value = match lookup(&defaults, &path) {
+            Some(toml::Value::String(_)) => toml::Value::String(raw),
+            Some(toml::Value::Array(_)) if !raw.trim_start().starts_with('[') => {
+                toml::Value::Array(
+                    raw.split(',')
+                        .map(str::trim)
+                        .filter(|city| !city.is_empty())
+                        .map(|city| toml::Value::String(city.to_string()))
+                        .collect(),
+                )
+            }
+            Some(_) => match format!("value = {raw}").parse::<toml::Table>() {
+                Ok(mut parsed) => parsed.remove("value").unwrap_or(toml::Value::String(raw)),
+                Err(_) => toml::Value::String(raw),
+            },
+            None if path.len() == 1 && OPTIONAL_KEYS.contains(&key.as_str()) => {
+                match format!("value = {raw}")
+                    .parse::<toml::Table>()
+                    .map(|mut parsed| parsed.remove("value"))
+                {
+                    Ok(Some(value)) => value,
+                    _ => {
+                        rejected.push((name, "not a TOML value".to_string()));
+                        continue;
+                    }
+                }
+            }
+            None => continue,
+        };
+        let mut single = toml::Table::new();
+        set_path(&mut single, &path, Some(value.clone()));
+        if let Err(err) = AppConfig::try_over_defaults(single) {
+            rejected.push((name, err.message().to_string()));
+            continue;
+        }
+        set_path(&mut layer, &path, Some(value));
+    }
+
+    ((!layer.is_empty()).then_some(layer), rejected)
+}
+
+impl ConfigLayers {
+    pub fn load() -> Self {
+        AppConfig::refresh_org_cache();
+        let (env, env_rejected) = env_layer(std::env::vars());
+        for (name, err) in &env_rejected {
+            crate::log::write(&format!("Ignoring {name}: {err}"));
+        }
+        Self {
+            org: AppConfig::read_table(AppConfig::org_config_path()),
+            user: AppConfig::read_table(AppConfig::config_path()),
+            env,
+            env_rejected,
+        }
+    }
+
+    /// Merges defaults, then the org layer, then the user layer, then the
+    /// environment. Zones and layouts from the user are added to the org's;
+    /// the environment replaces whatever it sets.
+    fn merge(&self) -> Option<AppConfig> {
+        let mut merged = toml::Table::try_from(AppConfig::default()).ok()?;
+        let mut env_keys = Vec::new();
+
+        if let Some(org) = &self.org {
+            merge_tables(&mut merged, org.clone(), false);
+        }
+        if let Some(user) = &self.user {
+            merge_tables(&mut merged, user.clone(), self.org.is_some());
+        }
+        if let Some(env) = &self.env {
+            leaf_paths(env, "", &mut env_keys);
+            merge_tables(&mut merged, env.clone(), false);
+        }
+
+        let mut config: AppConfig = toml::Value::Table(merged).try_into().ok()?;
+        config.env_keys = env_keys;
+        Some(config)
+    }
+
+    /// The layers that actually take effect: a broken layer is ignored
+    /// rather than taking the others down with it
+    fn usable(&self) -> ConfigLayers {
+        let valid = |layer: &Option<toml::Table>| {
+            layer
+                .clone()
+                .filter(|table| AppConfig::try_over_defaults(table.clone()).is_ok())
+        };
+        Self {
+            org: valid(&self.org),
+            user: valid(&self.user),
+            env: valid(&self.env),
+            env_rejected: self.env_rejected.clone(),
+        }
+    }
+
+    pub fn resolve(&self) -> AppConfig {
+        self.usable().merge().unwrap_or_default()
+    }
+
+    /// Explains a dotted config key, or returns `None` if alltz doesn't know it
+    pub fn explain(&self, key: &str) -> Option<Explanation> {
+        let layers = self.usable();
+        let path: Vec<&str> = key.split('.').collect();
+        let effective = toml::Table::try_from(layers.resolve()).ok()?;
+        let value = lookup(&effective, &path).cloned();
+        if value.is_none() && !(path.len() == 1 && OPTIONAL_KEYS.contains(&key)) {
+            return None;
+        }
+
+        let defined_in = |layer: &Option<toml::Table>| {
+            layer
+                .as_ref()
+                .is_some_and(|table| lookup(table, &path).is_some())
+        };
+        let in_org = defined_in(&layers.org);
+        let in_user = defined_in(&layers.user);
+        let in_env = defined_in(&layers.env);
+
+        let sources = if in_env {
+            vec![ConfigSource::Env]
+        } else if in_user && in_org && APPENDED_KEYS.contains(&key) {
+            vec![ConfigSource::Org, ConfigSource::User]
+        } else if in_user {
+            vec![ConfigSource::User]
+        } else if in_org {
+            vec![ConfigSource::Org]
+        } else {
+            vec![ConfigSource::Default]
+        };
+
+        Some(Explanation { value, sources })
+    }
+}
+
+/// Keys that are only written when set, so don't appear in a default config
+const OPTIONAL_KEYS: [&str; 21] = [
+    "user_theme",
+    "themes",
+    "align_to",
+    "layouts",
+    "board_name",
+    "archived",
+    "macros",
+    "keys",
+    "milestones",
+    "card_templates",
+    "blocked",
+    "calendars",
+    "tasks",
+    "handoff",
+    "notify",
+    "abbreviations",
+    "sync",
+    "presence",
+    "fiscal_year_start",
+    "home",
+    "ntp_server",
+];
+
+fn table_keys<T: Serialize>(value: T) -> Vec<String> {
+    toml::Table::try_from(value)
+        .map(|table| table.keys().cloned().collect())
+        .unwrap_or_default()
+}
+
+/// A problem found by `alltz config check`, with a 1-based line and column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigIssue {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn at(content: &str, offset: usize, message: impl Into<String>) -> Self {
+        let before = content.get(..offset).unwrap_or(content);
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+/// Validates a config file's contents: syntax, unknown keys, value types,
+/// cities that can't be found and work/awake hour bands that don't fit together
+pub fn check_config(content: &str) -> Vec<ConfigIssue> {
+    let content = platform::strip_bom(content);
+    let document = match DeTable::parse(content) {
+        Ok(document) => document,
+        Err(err) => {
+            let offset = err.span().map_or(0, |span| span.start);
+            return vec![ConfigIssue::at(content, offset, err.message())];
+        }
+    };
+    let table = match content.parse::<toml::Table>() {
+        Ok(table) => table,
+        Err(err) => {
+            let offset = err.span().map_or(0, |span| span.start);
+            return vec![ConfigIssue::at(content, offset, err.message())];
+        }
+    };
+
+    let mut issues = Vec::new();
+    let mut known = table_keys(AppConfig::default());
+    known.extend(OPTIONAL_KEYS.iter().map(|key| key.to_string()));
+
+    for (key, value) in document.get_ref() {
+        let name: &str = key.get_ref();
+        if !known.iter().any(|k| k == name) {
+            issues.push(ConfigIssue::at(
+                content,
+                key.span().start,
+                format!("unknown key `{name}`"),
+            ));
+            continue;
+        }
+
+        // Check the value's type by dropping it into an otherwise default config
+        let mut single = toml::Table::new();
+        single.insert(name.to_string(), table[name].clone());
+        let config = match AppConfig::try_over_defaults(single) {
+            Ok(config) => config,
+            Err(err) => {
+                issues.push(ConfigIssue::at(
+                    content,
+                    value.span().start,
+                    format!("invalid `{name}`: {}", err.message()),
+                ));
+                continue;
+            }
+        };
+
+        match name {
+            "zones" => check_zones(content, value.get_ref(), &config.zones, &mut issues),
+            "archived" => check_zones(content, value.get_ref(), &config.archived, &mut issues),
+            "layouts" => {
+                if let DeValue::Array(layouts) = value.get_ref() {
+                    for (layout, parsed) in layouts.iter().zip(&config.layouts) {
+                        if let DeValue::Table(layout) = layout.get_ref() {
+                            if let Some(zones) = layout.get("zones") {
+                                check_zones(content, zones.get_ref(), &parsed.zones, &mut issues);
+                            }
+                        }
+                    }
+                }
+            }
+            "time_config" => check_time_config(
+                content,
+                "time_config",
+                key.span(),
+                value,
+                &config.time_config,
+                &mut issues,
+            ),
+            "etiquette" => {
+                let EtiquetteConfig { early, late } = config.etiquette;
+                if late > 24 || early >= late {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("`etiquette.early` ({early}) must be an hour before `etiquette.late` ({late}), which is at most 24"),
+                    ));
+                }
+            }
+            "now_line" => {
+                if let Some(color) = &config.now_line.color {
+                    if color.parse::<Color>().is_err() {
+                        issues.push(ConfigIssue::at(
+                            content,
+                            value.span().start,
+                            format!("unknown color `{color}` in `now_line.color`"),
+                        ));
+                    }
+                }
+            }
+            "themes" => {
+                for theme in &config.themes {
+                    for (key, color) in theme.unknown_colors() {
+                        issues.push(ConfigIssue::at(
+                            content,
+                            value.span().start,
+                            format!(
+                                "unknown color `{color}` for `{key}` in theme `{}`",
+                                theme.name
+                            ),
+                        ));
+                    }
+                }
+            }
+            "blocked" => {
+                for entry in config.blocked.iter().filter(|b| b.to < b.from) {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("blocked dates for `{}` end before they start", entry.who),
+                    ));
+                }
+            }
+            "keys" => {
+                for (key, name) in &config.keys {
+                    let message = if command::decode_key(key).is_none() {
+                        format!("unknown key `{key}` in `keys`, e.g. `x`, `Left` or `S-Tab`")
+                    } else if name != "none" && name.parse::<Command>().is_err() {
+                        format!("unknown command `{name}` for `{key}` in `keys`")
+                    } else {
+                        continue;
+                    };
+                    issues.push(ConfigIssue::at(content, value.span().start, message));
+                }
+            }
+            "abbreviations" => {
+                for (abbreviation, zone) in &config.abbreviations {
+                    if zone.parse::<chrono_tz::Tz>().is_err() {
+                        issues.push(ConfigIssue::at(
+                            content,
+                            value.span().start,
+                            format!(
+                                "unknown zone `{zone}` for `{abbreviation}` in `abbreviations`"
+                            ),
+                        ));
+                    }
+                }
+            }
+            "dst_countdown_days" if config.dst_countdown_days > MAX_DST_COUNTDOWN_DAYS => {
+                issues.push(ConfigIssue::at(
+                    content,
+                    value.span().start,
+                    format!(
+                        "`dst_countdown_days` can be at most {MAX_DST_COUNTDOWN_DAYS}, got {}",
+                        config.dst_countdown_days
+                    ),
+                ));
+            }
+            "fiscal_year_start" => {
+                if let Some(month) = config.fiscal_year_start.filter(|m| !(1..=12).contains(m)) {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("`fiscal_year_start` must be a month from 1 to 12, got {month}"),
+                    ));
+                }
+            }
+            "home" => {
+                if let Some(home) = config
+                    .home
+                    .as_deref()
+                    .filter(|home| crate::time::resolve_zone(home, &[]).is_none())
+                {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("unknown zone or city `{home}` for `home`"),
+                    ));
+                }
+            }
+            "notify" => {
+                let Some(notify) = &config.notify else {
+                    continue;
+                };
+                let backends = || notify.reminder.iter().chain(&notify.milestone);
+                let mut missing = Vec::new();
+                if notify.webhook.is_none() && backends().any(|b| *b == NotifyBackend::Webhook) {
+                    missing.push("webhook");
+                }
+                if notify.command.is_none() && backends().any(|b| *b == NotifyBackend::Command) {
+                    missing.push("command");
+                }
+                for backend in missing {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("alarms are sent to `{backend}` but `notify.{backend}` is not set"),
+                    ));
+                }
+            }
+            "align_to" => {
+                if let Some(zone) = config
+                    .align_to
+                    .as_deref()
+                    .filter(|zone| crate::time::resolve_zone(zone, &[]).is_none())
+                {
+                    issues.push(ConfigIssue::at(
+                        content,
+                        value.span().start,
+                        format!("unknown zone or city `{zone}` for `align_to`"),
+                    ));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+fn check_zones(
+    content: &str,
+    zones: &DeValue,
+    parsed: &[ZoneConfigCompat],
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let DeValue::Array(zones) = zones else {
+        return;
+    };
+    for (zone, entry) in zones.iter().zip(parsed) {
+        let city = match zone.get_ref() {
+            DeValue::String(name) => Some((name.as_ref(), zone.span())),
+            DeValue::Table(table) => table
+                .get("city_name")
+                .and_then(|city| match city.get_ref() {
+                    DeValue::String(name) => Some((name.as_ref(), city.span())),
+                    _ => None,
+                }),
+            _ => None,
+        };
+        if let DeValue::Table(table) = zone.get_ref() {
+            if let Some(accent) = table.get("accent") {
+                if let DeValue::String(color) = accent.get_ref() {
+                    if color.parse::<Color>().is_err() {
+                        issues.push(ConfigIssue::at(
+                            content,
+                            accent.span().start,
+                            format!("unknown color `{color}` for a zone's `accent`"),
+                        ));
+                    }
+                }
+            }
+            if let (Some(value), Some(hours)) = (table.get("hours"), entry.hours()) {
+                check_time_config(content, "hours", value.span(), value, hours, issues);
+            }
+            if let Some(DeValue::Array(calls)) = table.get("calls").map(|calls| calls.get_ref()) {
+                for call in calls {
+                    if let DeValue::String(window) = call.get_ref() {
+                        if let Err(message) = window.parse::<CallWindow>() {
+                            issues.push(ConfigIssue::at(content, call.span().start, message));
+                        }
+                    }
+                }
+            }
+            if let Some(DeValue::Array(moves)) = table.get("moves").map(|moves| moves.get_ref()) {
+                for entry in moves {
+                    let DeValue::Table(entry) = entry.get_ref() else {
+                        continue;
+                    };
+                    if let Some(city) = entry.get("city") {
+                        if let DeValue::String(name) = city.get_ref() {
+                            if TimeZoneManager::find_city(name).is_none() {
+                                issues.push(ConfigIssue::at(
+                                    content,
+                                    city.span().start,
+                                    format!("unknown city `{name}` to move to (see `alltz list`)"),
+                                ));
+                            }
+                        }
+                    }
+                }
+            }
+            if let Some(DeValue::Array(seasons)) =
+                table.get("seasons").map(|seasons| seasons.get_ref())
+            {
+                for (season, entry) in seasons.iter().zip(entry.seasons()) {
+                    let DeValue::Table(season) = season.get_ref() else {
+                        continue;
+                    };
+                    for key in ["from", "to"] {
+                        if let Some(DeValue::String(day)) = season.get(key).map(|day| day.get_ref())
+                        {
+                            if let Err(message) = parse_month_day(day) {
+                                let start = season[key].span().start;
+                                issues.push(ConfigIssue::at(content, start, message));
+                            }
+                        }
+                    }
+                    if let Some(value) = season.get("hours") {
+                        check_time_config(
+                            content,
+                            "hours",
+                            value.span(),
+                            value,
+                            &entry.hours,
+                            issues,
+                        );
+                    }
+                }
+            }
+        }
+        if let Some((name, span)) = city {
+            if TimeZoneManager::find_city(name).is_none() {
+                issues.push(ConfigIssue::at(
+                    content,
+                    span.start,
+                    format!("unknown city `{name}` (see `alltz list`)"),
+                ));
+            }
+        }
+    }
+}
+
+fn check_time_config(
+    content: &str,
+    section: &str,
+    key_span: Range<usize>,
+    value: &toml::Spanned<DeValue>,
+    hours: &TimeDisplayConfig,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let DeValue::Table(table) = value.get_ref() else {
+        return;
+    };
+    let known = table_keys(TimeDisplayConfig::default());
+    for key in table.keys() {
+        let name: &str = key.get_ref();
+        if !known.iter().any(|k| k == name) {
+            issues.push(ConfigIssue::at(
+                content,
+                key.span().start,
+                format!("unknown key `{section}.{name}`"),
+            ));
+        }
+    }
+
+    // Point at the offending key when it's in the file, otherwise at the table
+    let offset_of = |name: &str| {
+        table
+            .get(name)
+            .map_or(key_span.start, |value| value.span().start)
+    };
+    let mut report = |name: &str, message: String| {
+        issues.push(ConfigIssue::at(content, offset_of(name), message));
+    };
+
+    for (name, hour) in [
+        ("work_hours_start", hours.work_hours_start),
+        ("work_hours_end", hours.work_hours_end),
+        ("awake_hours_start", hours.awake_hours_start),
+        ("awake_hours_end", hours.awake_hours_end),
+    ] {
+        if hour > 24 {
+            report(
+                name,
+                format!("`{name}` must be an hour from 0 to 24, got {hour}"),
+            );
+        }
+    }
+    // Hours ending before they start run past midnight, as night shifts do
+    if hours.work_hours_start == hours.work_hours_end {
+        report(
+            "work_hours_end",
+            format!(
+                "work hours end ({}) where they start; hours past midnight end before they start, e.g. 22 to 6",
+                hours.work_hours_end
+            ),
+        );
+    }
+    if hours.awake_hours_start == hours.awake_hours_end {
+        report(
+            "awake_hours_end",
+            format!(
+                "awake hours end ({}) where they start; hours past midnight end before they start, e.g. 20 to 12",
+                hours.awake_hours_end
+            ),
+        );
+    }
+    // Whether work falls within waking hours is only clear within one day
+    let overnight = hours.work_hours_start > hours.work_hours_end
+        || hours.awake_hours_start > hours.awake_hours_end;
+    if overnight {
+        return;
+    }
+    if hours.work_hours_start < hours.awake_hours_start {
+        report(
+            "work_hours_start",
+            format!(
+                "work hours start ({}) before awake hours ({})",
+                hours.work_hours_start, hours.awake_hours_start
+            ),
+        );
+    }
+    if hours.work_hours_end > hours.awake_hours_end {
+        report(
+            "work_hours_end",
+            format!(
+                "work hours end ({}) after awake hours ({})",
+                hours.work_hours_end, hours.awake_hours_end
+            ),
+        );
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_nudge_hours() {
+        let mut hours = TimeDisplayConfig::default();
+        hours.nudge(HourBoundary::WorkStart, -5);
+        assert_eq!(hours.work_hours_start, 6); // Stops at awake start
+        hours.nudge(HourBoundary::WorkEnd, -20);
+        assert_eq!(hours.work_hours_end, 7); // Keeps an hour of work
+        hours.nudge(HourBoundary::AwakeEnd, 5);
+        assert_eq!(hours.awake_hours_end, 24);
+        hours.nudge(HourBoundary::AwakeStart, 3);
+        assert_eq!(hours.awake_hours_start, 6); // Can't pass work start
+
+        // Overnight hours are left as they are
+        let mut night_shift = TimeDisplayConfig {
+            work_hours_start: 22,
+            work_hours_end: 6,
+            ..TimeDisplayConfig::default()
+        };
+        night_shift.nudge(HourBoundary::WorkStart, 1);
+        night_shift.nudge(HourBoundary::WorkEnd, -1);
+        assert_eq!(
+            (night_shift.work_hours_start, night_shift.work_hours_end),
+            (22, 6)
+        );
+        night_shift.work_hours_end = 0;
+        night_shift.nudge(HourBoundary::WorkStart, -1);
+        assert_eq!(night_shift.work_hours_start, 22);
+    }
+
+    #[test]
+    fn test_overnight_hours() {
+        let night_shift = TimeDisplayConfig {
+            work_hours_start: 22,
+            work_hours_end: 6,
+            awake_hours_start: 20,
+            awake_hours_end: 10,
+        };
+        assert_eq!(night_shift.get_time_activity(23), TimeActivity::Work);
+        assert_eq!(night_shift.get_time_activity(3), TimeActivity::Work);
+        assert_eq!(night_shift.get_time_activity(6), TimeActivity::Awake);
+        assert_eq!(night_shift.get_time_activity(21), TimeActivity::Awake);
+        assert_eq!(night_shift.get_time_activity(14), TimeActivity::Night);
+    }
+
     #[test]
     fn test_default_time_periods() {
         let config = TimeDisplayConfig::default();
@@ -391,4 +2197,364 @@ awake_hours_end = 22
         assert_eq!(config.zones[2].city_name(), "London");
         assert_eq!(config.zones[2].custom_label(), Some("Bob (Sales)"));
     }
+
+    #[test]
+    fn test_org_config_layering() {
+        let org: toml::Table = r#"
+zones = ["London", "Tokyo"]
+color_theme = "Ocean"
+
+[time_config]
+work_hours_start = 9
+"#
+        .parse()
+        .unwrap();
+        let user: toml::Table = r#"
+zones = [{ city_name = "Tokyo", custom_label = "Alice" }, "Denver"]
+display_format = "TwelveHour"
+"#
+        .parse()
+        .unwrap();
+
+        let config = ConfigLayers {
+            org: Some(org.clone()),
+            user: Some(user.clone()),
+            ..ConfigLayers::default()
+        }
+        .merge()
+        .unwrap();
+
+        // User zones are added on top of the org's, replacing same-city entries
+        let cities: Vec<&str> = config.zones.iter().map(|z| z.city_name()).collect();
+        assert_eq!(cities, vec!["London", "Tokyo", "Denver"]);
+        assert_eq!(config.zones[1].custom_label(), Some("Alice"));
+
+        // Org settings apply unless the user overrides them; nested tables merge
+        assert_eq!(config.color_theme, ColorTheme::Ocean);
+        assert_eq!(config.display_format, TimeFormat::TwelveHour);
+        assert_eq!(config.time_config.work_hours_start, 9);
+        assert_eq!(config.time_config.work_hours_end, 18);
+
+        // Only the user's own zones and settings are written back, so later
+        // changes to the org's reach them
+        let saved = config.user_table(Some(&user), Some(org)).unwrap();
+        let cities: Vec<String> = saved["zones"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(entry_key)
+            .collect();
+        assert_eq!(cities, vec!["Tokyo", "Denver"]);
+        assert_eq!(saved["display_format"].as_str(), Some("TwelveHour"));
+        assert!(!saved.contains_key("color_theme"));
+        assert!(!saved.contains_key("time_config"));
+        assert!(!saved.contains_key("show_date"));
+
+        // A setting the user changes on the board is kept
+        let mut changed = config.clone();
+        changed.time_config.work_hours_end = 17;
+        let saved = changed.user_table(Some(&user), None).unwrap();
+        assert_eq!(
+            saved["time_config"]
+                .as_table()
+                .unwrap()
+                .keys()
+                .collect::<Vec<_>>(),
+            ["work_hours_end", "work_hours_start"]
+        );
+    }
+
+    #[test]
+    fn test_user_zones_replace_defaults_without_org() {
+        let user: toml::Table = r#"zones = ["Denver"]"#.parse().unwrap();
+        let config = ConfigLayers {
+            user: Some(user),
+            ..ConfigLayers::default()
+        }
+        .merge()
+        .unwrap();
+        assert_eq!(config.zones.len(), 1);
+    }
+
+    #[test]
+    fn test_layouts_config() {
+        let config_str = r#"
+zones = ["London"]
+selected_zone_index = 0
+display_format = "TwentyFourHour"
+timezone_display_mode = "Short"
+color_theme = "Default"
+show_date = false
+
+[time_config]
+work_hours_start = 8
+work_hours_end = 18
+awake_hours_start = 6
+awake_hours_end = 22
+
+[[layouts]]
+name = "Customers"
+zones = ["Tokyo", { city_name = "New York", custom_label = "Acme" }]
+"#;
+
+        let config: AppConfig = toml::from_str(config_str).unwrap();
+        assert_eq!(config.layouts.len(), 1);
+        assert_eq!(config.layouts[0].name, "Customers");
+        assert_eq!(config.layouts[0].zones[1].custom_label(), Some("Acme"));
+
+        // Layouts survive a round trip, and are omitted when empty
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.layouts[0].zones.len(), 2);
+        assert!(!toml::to_string(&AppConfig::default())
+            .unwrap()
+            .contains("layouts"));
+    }
+
+    #[test]
+    fn test_user_themes() {
+        let config_str = r##"
+user_theme = "Dusk"
+
+[[themes]]
+name = "Dusk"
+inherits = "Ocean"
+work = "#ff8800"
+night = "17"
+
+[[layouts]]
+name = "Customers"
+zones = ["Tokyo"]
+theme = "forest"
+"##;
+        let config = AppConfig::try_over_defaults(config_str.parse().unwrap()).unwrap();
+        let palette = config.themes[0].palette(false);
+        let ocean = ColorTheme::Ocean.palette();
+        assert_eq!(palette.work, Color::Rgb(0xff, 0x88, 0x00));
+        assert_eq!(palette.night, Color::Indexed(17));
+        assert_eq!(palette.awake, ocean.awake);
+
+        // Names find user themes first, then built-in ones in any case
+        assert_eq!(find_palette("dusk", &config.themes, false), Some(palette));
+        assert_eq!(
+            config.layouts[0]
+                .theme
+                .as_deref()
+                .and_then(|name| find_palette(name, &config.themes, false)),
+            Some(ColorTheme::Forest.palette())
+        );
+        assert_eq!(find_palette("Sepia", &config.themes, false), None);
+
+        // On light terminals the inherited colors darken but overrides stay
+        let light = config.themes[0].palette(true);
+        assert_eq!(light.work, palette.work);
+        assert_eq!(light.awake, ColorTheme::Ocean.palette().light().awake);
+        assert_ne!(light.awake, palette.awake);
+
+        let toml_str = toml::to_string_pretty(&config).unwrap();
+        let parsed: AppConfig = toml::from_str(&toml_str).unwrap();
+        assert_eq!(parsed.themes, config.themes);
+        assert_eq!(parsed.user_theme.as_deref(), Some("Dusk"));
+    }
+
+    #[test]
+    fn test_config_check() {
+        let content = r#"zones = ["London", "Atlantis"]
+colour_theme = "Ocean"
+display_format = "Sometimes"
+
+[time_config]
+work_hours_start = 5
+lunch = 12
+"#;
+
+        let issues = check_config(content);
+        let found: Vec<(usize, usize)> = issues.iter().map(|i| (i.line, i.column)).collect();
+        assert_eq!(found, vec![(1, 20), (2, 1), (3, 18), (6, 20), (7, 1)]);
+        assert!(issues[0].message.contains("Atlantis"));
+        assert!(issues[1].message.contains("colour_theme"));
+        assert!(issues[2].message.contains("Sometimes"));
+        assert!(issues[3].message.contains("awake hours"));
+        assert!(issues[4].message.contains("time_config.lunch"));
+
+        // Syntax errors are located too, and a default config is clean
+        let issues = check_config("zones = [\"London\"\nshow_date = true\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+        let issues = check_config("fiscal_year_start = 13\n");
+        assert!(issues[0].message.contains("month from 1 to 12"));
+        let issues = check_config("dst_countdown_days = 200000000\n");
+        assert!(issues[0].message.contains("at most 366"));
+        assert!(check_config("dst_countdown_days = 366\n").is_empty());
+        assert!(check_config("home = \"Europe/Madrid\"\n").is_empty());
+        let issues = check_config("home = \"Atlantis\"\n");
+        assert!(issues[0].message.contains("`Atlantis` for `home`"));
+        let issues = check_config("[now_line]\ncolor = \"reddish\"\n");
+        assert!(issues[0].message.contains("reddish"));
+        assert!(check_config("[now_line]\ncolor = \"#ff8800\"\n").is_empty());
+
+        let issues = check_config("[etiquette]\nearly = 21\n");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("`etiquette.early` (21)"));
+        assert!(check_config("[etiquette]\nearly = 7\nlate = 22\n").is_empty());
+        let issues = check_config("[notify]\nmilestone = [\"desktop\", \"webhook\"]\n");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("`notify.webhook` is not set"));
+        assert!(
+            check_config("[notify]\nreminder = [\"command\"]\ncommand = \"page-me\"\n").is_empty()
+        );
+        let issues = check_config("[[themes]]\nname = \"Dusk\"\nwork = \"amber\"\n");
+        assert!(issues[0]
+            .message
+            .contains("`amber` for `work` in theme `Dusk`"));
+        let issues = check_config("zones = [{ city_name = \"Tokyo\", accent = \"mauve\" }]\n");
+        assert!(issues[0].message.contains("`mauve`"));
+        let issues = check_config(
+            "[[zones]]\ncity_name = \"Tokyo\"\nhours = { work_hours_start = 9, work_hours_end = 9, lunch = 12 }\n",
+        );
+        assert_eq!(issues.len(), 2);
+        assert!(issues[0].message.contains("work hours end (9)"));
+        assert!(issues[1].message.contains("`hours.lunch`"));
+        // Night shifts run past midnight
+        assert!(check_config(
+            "[[zones]]\ncity_name = \"Tokyo\"\nhours = { work_hours_start = 19, work_hours_end = 9 }\n",
+        )
+        .is_empty());
+        let issues = check_config(
+            "[[zones]]\ncity_name = \"Berlin\"\nmoves = [{ from = \"2024-09-01\", city = \"Atlantis\" }]\n",
+        );
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("`Atlantis` to move to"));
+        let issues = check_config(
+            "[[zones]]\ncity_name = \"Madrid\"\n[[zones.seasons]]\nfrom = \"Jun 15\"\nto = \"08-32\"\nhours = { work_hours_start = 8, work_hours_end = 14 }\n",
+        );
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0]
+            .message
+            .contains("`08-32` is not a day of the year"));
+        let issues = check_config("[keys]\nb = \"quit\"\nCtrl-x = \"quit\"\nB = \"jump\"\n");
+        assert_eq!(issues.len(), 2);
+        assert!(issues
+            .iter()
+            .any(|issue| issue.message.contains("`Ctrl-x`")));
+        assert!(issues
+            .iter()
+            .any(|issue| issue.message.contains("`jump` for `B`")));
+        let issues = check_config("[abbreviations]\nCST = \"Asia/Shanghai\"\nIST = \"India\"\n");
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("`India` for `IST`"));
+        let default = toml::to_string_pretty(&AppConfig::default()).unwrap();
+        assert!(check_config(&default).is_empty());
+    }
+
+    #[test]
+    fn test_config_explain() {
+        let layers = ConfigLayers {
+            env: env_layer([("ALLTZ_COLOR_THEME".to_string(), "Ocean".to_string())]).0,
+            org: Some(toml::from_str("zones = [\"Paris\"]\nshow_date = true").unwrap()),
+            user: Some(
+                toml::from_str("zones = [\"Tokyo\"]\n[time_config]\nwork_hours_end = 17").unwrap(),
+            ),
+            ..ConfigLayers::default()
+        };
+
+        let zones = layers.explain("zones").unwrap();
+        assert_eq!(zones.sources, vec![ConfigSource::Org, ConfigSource::User]);
+        assert_eq!(zones.value.unwrap().as_array().unwrap().len(), 2);
+
+        let show_date = layers.explain("show_date").unwrap();
+        assert_eq!(show_date.sources, vec![ConfigSource::Org]);
+        assert_eq!(show_date.value, Some(toml::Value::Boolean(true)));
+
+        let work_end = layers.explain("time_config.work_hours_end").unwrap();
+        assert_eq!(work_end.sources, vec![ConfigSource::User]);
+        assert_eq!(work_end.value, Some(toml::Value::Integer(17)));
+
+        let theme = layers.explain("color_theme").unwrap();
+        assert_eq!(theme.sources, vec![ConfigSource::Env]);
+        assert_eq!(theme.value, Some(toml::Value::String("Ocean".to_string())));
+
+        let sun_times = layers.explain("show_sun_times").unwrap();
+        assert_eq!(sun_times.sources, vec![ConfigSource::Default]);
+        assert_eq!(layers.explain("layouts").unwrap().value, None);
+        assert!(layers.explain("bogus").is_none());
+    }
+
+    #[test]
+    fn test_env_overrides() {
+        let vars = [
+            ("ALLTZ_ZONES", "Tokyo, London"),
+            ("ALLTZ_COLOR_THEME", "Forest"),
+            ("ALLTZ_DISPLAY_FORMAT", "TwelveHour"),
+            ("ALLTZ_SHOW_DATE", "true"),
+            ("ALLTZ_TIME_CONFIG__WORK_HOURS_START", "9"),
+            ("ALLTZ_ORG_CONFIG", "/etc/other.toml"),
+            ("HOME", "/root"),
+        ]
+        .map(|(name, value)| (name.to_string(), value.to_string()));
+        let env = env_layer(vars).0.unwrap();
+
+        let user: toml::Table =
+            toml::from_str("zones = [\"Berlin\"]\nshow_sun_times = false").unwrap();
+        let config = ConfigLayers {
+            user: Some(user.clone()),
+            env: Some(env),
+            ..ConfigLayers::default()
+        }
+        .merge()
+        .unwrap();
+
+        let cities: Vec<&str> = config.zones.iter().map(|z| z.city_name()).collect();
+        assert_eq!(cities, vec!["Tokyo", "London"]);
+        assert_eq!(config.color_theme, ColorTheme::Forest);
+        assert_eq!(config.display_format, TimeFormat::TwelveHour);
+        assert!(config.show_date);
+        assert!(!config.show_sun_times);
+        assert_eq!(config.time_config.work_hours_start, 9);
+        assert_eq!(config.time_config.work_hours_end, 18);
+        assert!(config
+            .env_keys
+            .contains(&"time_config.work_hours_start".to_string()));
+        assert!(!config.env_keys.iter().any(|k| k.contains("org_config")));
+
+        // Saving keeps the user's own values where the environment overrides them
+        let saved = config.user_table(Some(&user), None).unwrap();
+        assert_eq!(saved["zones"], toml::Value::Array(vec!["Berlin".into()]));
+        assert!(!saved.contains_key("color_theme"));
+
+        // An invalid override is left out and reported, and the others still apply
+        let (env, env_rejected) = env_layer(
+            [
+                ("ALLTZ_COLOR_THEME", "Plaid"),
+                ("ALLTZ_SHOW_SUN_TIMES", "false"),
+            ]
+            .map(|(name, value)| (name.to_string(), value.to_string())),
+        );
+        assert_eq!(env_rejected.len(), 1);
+        assert_eq!(env_rejected[0].0, "ALLTZ_COLOR_THEME");
+        let config = ConfigLayers {
+            user: Some(toml::from_str("show_date = true").unwrap()),
+            env,
+            env_rejected,
+            ..ConfigLayers::default()
+        }
+        .resolve();
+        assert!(config.show_date);
+        assert!(!config.show_sun_times);
+        assert_eq!(config.color_theme, ColorTheme::Default);
+    }
+
+    #[test]
+    fn test_set_path() {
+        let mut table: toml::Table =
+            toml::from_str("show_date = true\n[time_config]\nwork_hours_start = 9").unwrap();
+        set_path(&mut table, &["time_config", "work_hours_start"], None);
+        set_path(
+            &mut table,
+            &["show_date"],
+            Some(toml::Value::Boolean(false)),
+        );
+        assert!(table["time_config"].as_table().unwrap().is_empty());
+        assert_eq!(table["show_date"], toml::Value::Boolean(false));
+    }
 }
@@ -0,0 +1,184 @@
+use crate::app::App;
+use crate::platform::Platform;
+use chrono::{DateTime, Local};
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// How many of the last actions a crash report lists
+const KEPT_ACTIONS: usize = 20;
+
+/// What a crash report says about the moments before the crash
+struct CrashState {
+    summary: String,
+    actions: VecDeque<String>,
+}
+
+static STATE: Mutex<CrashState> = Mutex::new(CrashState {
+    summary: String::new(),
+    actions: VecDeque::new(),
+});
+
+/// Replaces the text of every string in a `Debug` rendering, so labels,
+/// search input and reminder text never end up in a report
+pub fn redact(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        out.push(c);
+        if c == '"' {
+            out.push('…');
+            // Skip to the closing quote, stepping over escaped ones
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => {
+                        chars.next();
+                    }
+                    '"' => {
+                        out.push('"');
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Notes an action for the next crash report, redacted
+pub fn record(action: &impl Debug) {
+    if let Ok(mut state) = STATE.lock() {
+        if state.actions.len() == KEPT_ACTIONS {
+            state.actions.pop_front();
+        }
+        state.actions.push_back(redact(&format!("{action:?}")));
+    }
+}
+
+/// Keeps a summary of the board's settings for the next crash report
+pub fn remember(app: &App) {
+    let summary = summary(app);
+    if let Ok(mut state) = STATE.lock() {
+        state.summary = summary;
+    }
+}
+
+/// The settings that shape what's drawn, counting zones and labels rather
+/// than naming them
+pub fn summary(app: &App) -> String {
+    let zones = app.timezone_manager.zones();
+    let labelled = zones
+        .iter()
+        .filter(|zone| zone.display.label.is_some())
+        .count();
+    [
+        format!("version: {}", env!("CARGO_PKG_VERSION")),
+        format!(
+            "platform: {} {}",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        ),
+        format!("zones: {} ({labelled} labelled)", zones.len()),
+        format!("selected zone: {}", app.selected_zone_index),
+        format!("layouts: {}", app.layouts.len()),
+        format!("theme: {:?}", app.color_theme),
+        format!("display format: {:?}", app.display_format),
+        format!("zone names: {:?}", app.timezone_display_mode),
+        format!("glyphs: {:?}", app.glyph_mode),
+        format!("mini rows: {:?}", app.mini_rows),
+        format!(
+            "focus mode: {}, split view: {}, privacy mode: {}, low bandwidth: {}",
+            app.focus_mode, app.split_view, app.privacy_mode, app.low_bandwidth
+        ),
+    ]
+    .join("\n")
+}
+
+/// The report for a panic: what happened, where, the board's settings and
+/// the last actions leading up to it
+pub fn report(panic: &str, backtrace: &str, at: DateTime<Local>) -> String {
+    let (summary, actions) = match STATE.lock() {
+        Ok(state) => (
+            state.summary.clone(),
+            state.actions.iter().cloned().collect::<Vec<_>>(),
+        ),
+        Err(_) => (String::new(), Vec::new()),
+    };
+    format!(
+        "alltz crash report, {}\n\n{panic}\n\n## Settings\n{summary}\n\n## Last actions\n{}\n\n## Backtrace\n{backtrace}\n",
+        at.format("%Y-%m-%d %H:%M:%S %z"),
+        actions.join("\n")
+    )
+}
+
+/// Where a crash at `at` is reported, next to the config
+pub fn report_path(at: DateTime<Local>) -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join(format!("crash-{}.txt", at.format("%Y%m%d-%H%M%S"))))
+}
+
+/// Replaces the panic hook for the life of the board: `restore` puts the
+/// terminal back first so the message can be read, then the report is
+/// written and its path printed. Panics on background threads (sync,
+/// update checks) only end those threads, so they're just logged.
+pub fn install(restore: fn()) {
+    std::panic::set_hook(Box::new(move |info| {
+        if std::thread::current().name() != Some("main") {
+            crate::log::write(&format!("Background thread {info}"));
+            return;
+        }
+        restore();
+        let at = Local::now();
+        let report = report(
+            &info.to_string(),
+            &Backtrace::force_capture().to_string(),
+            at,
+        );
+        let saved = report_path(at).filter(|path| {
+            path.parent()
+                .is_some_and(|dir| fs::create_dir_all(dir).is_ok())
+                && fs::write(path, &report).is_ok()
+        });
+        match saved {
+            Some(path) => eprintln!(
+                "{}",
+                t!("cli.crash.saved", error = info, path = path.display())
+            ),
+            None => eprintln!("{}\n\n{report}", t!("cli.crash.unsaved", error = info)),
+        }
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::Message;
+
+    #[test]
+    fn test_crash_report() {
+        assert_eq!(
+            redact(r#"UpdateRenameInput("Maria \"M\" Lopez")"#),
+            r#"UpdateRenameInput("…")"#
+        );
+        assert_eq!(redact("NavigateZone(Down)"), "NavigateZone(Down)");
+
+        let app = App::default();
+        remember(&app);
+        for _ in 0..KEPT_ACTIONS {
+            record(&Message::Tick);
+        }
+        record(&Message::UpdateGroupInput("Berlin team".to_string()));
+        let at = Local::now();
+        let report = report("panicked at src/app.rs:1:1", "<backtrace>", at);
+        assert!(report.contains("panicked at src/app.rs:1:1"));
+        assert!(report.contains(&format!("zones: {}", app.timezone_manager.zone_count())));
+        assert!(report.ends_with("UpdateGroupInput(\"…\")\n\n## Backtrace\n<backtrace>\n"));
+        assert!(!report.contains("Berlin team"));
+        assert_eq!(report.matches("Tick").count(), KEPT_ACTIONS - 1);
+    }
+}
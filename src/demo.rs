@@ -0,0 +1,74 @@
+use crate::app::App;
+use crate::config::{AppConfig, ZoneConfigCompat};
+use crate::ui::day_layout::Offsets;
+use chrono::{DateTime, Duration, Utc};
+
+/// A spread of offsets, including half and quarter hours, a few labelled
+const ZONES: [(&str, Option<&str>); 10] = [
+    ("San Francisco", Some("Design")),
+    ("New York", None),
+    ("São Paulo", Some("Support")),
+    ("London", None),
+    ("Nairobi", None),
+    ("Mumbai", Some("Platform")),
+    ("Kathmandu", None),
+    ("Tokyo", None),
+    ("Sydney", Some("Sales")),
+    ("Auckland", None),
+];
+
+/// A sample board for screenshots and first looks. It is never saved, and
+/// opens on the next DST change of one of its zones so a marker is in view.
+pub fn app(now: DateTime<Utc>) -> App {
+    let config = AppConfig {
+        zones: ZONES
+            .iter()
+            .map(|(city, label)| ZoneConfigCompat::new(city.to_string(), label.map(String::from)))
+            .collect(),
+        selected_zone_index: 3,
+        show_date: true,
+        ..AppConfig::default()
+    };
+
+    let mut app = App::from_config(config);
+    app.ephemeral = true;
+    if let Some(transition) = next_transition(&app, now) {
+        app.timeline_position = transition;
+    }
+    app
+}
+
+/// The first time any zone on the board changes offset within a year
+fn next_transition(app: &App, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    app.timezone_manager
+        .zones()
+        .iter()
+        .filter_map(|zone| {
+            Offsets::new(zone.tz, now, now + Duration::days(366))
+                .transitions()
+                .next()
+                .map(|(time, ..)| time)
+        })
+        .min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_demo_board() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let app = app(now);
+
+        assert_eq!(app.timezone_manager.zone_count(), ZONES.len());
+        assert!(app.ephemeral);
+        // New York springs forward first, at 2 AM on 10 March
+        let new_york = DateTime::parse_from_rfc3339("2024-03-10T07:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(app.timeline_position, new_york);
+    }
+}
@@ -0,0 +1,230 @@
+use crate::config::{BlockedDates, TimeDisplayConfig};
+use crate::plan::{self, Slot};
+use crate::time::TimeZone;
+use chrono::{Duration, NaiveDate};
+use std::fmt::Write;
+
+/// How many days a grid covers
+pub const DAYS: usize = 7;
+
+/// A week of hours at home, each with who's at work for the whole of it
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekGrid {
+    pub days: Vec<NaiveDate>,
+    /// Each day's 24 hours, from home midnight
+    pub hours: Vec<Vec<Slot>>,
+    /// The zones' names, by board index
+    pub names: Vec<String>,
+}
+
+impl WeekGrid {
+    /// The week from `first`, its hours counted in `home`, leaving out
+    /// zones on the days their people are `away`
+    pub fn new<H: chrono::TimeZone>(
+        home: &H,
+        zones: &[TimeZone],
+        board: &TimeDisplayConfig,
+        away: &[BlockedDates],
+        first: NaiveDate,
+    ) -> Self {
+        let days: Vec<NaiveDate> = first.iter_days().take(DAYS).collect();
+        let hours = days
+            .iter()
+            .map(|&date| {
+                // Half-hourly slots an hour long, keeping those on the hour
+                plan::slots(home, zones, board, away, date, Duration::hours(1))
+                    .into_iter()
+                    .step_by(2)
+                    .collect()
+            })
+            .collect();
+        let names = zones
+            .iter()
+            .map(|zone| zone.effective_display_name().to_string())
+            .collect();
+        WeekGrid { days, hours, names }
+    }
+
+    /// Who's at work in a slot, by name
+    fn who(&self, slot: &Slot) -> String {
+        let names: Vec<&str> = slot
+            .at_work
+            .iter()
+            .map(|&index| self.names[index].as_str())
+            .collect();
+        names.join(", ")
+    }
+
+    /// The shade for a slot: pale with no one at work, deepening to green as
+    /// more of the team is
+    fn color(&self, slot: &Slot) -> String {
+        let share = match self.names.len() {
+            0 => 0.0,
+            zones => slot.at_work.len() as f32 / zones as f32,
+        };
+        let mix =
+            |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * share).round() as u8;
+        format!(
+            "#{:02x}{:02x}{:02x}",
+            mix(0xf2, 0x1b),
+            mix(0xf2, 0x7a),
+            mix(0xf2, 0x3a)
+        )
+    }
+
+    fn title(&self) -> String {
+        format!(
+            "Who's at work, week of {}",
+            self.days
+                .first()
+                .map(|day| day.format("%a %-d %b").to_string())
+                .unwrap_or_default()
+        )
+    }
+
+    /// A standalone page with the grid as a table, each hour's cell naming
+    /// who's at work when hovered
+    pub fn html(&self) -> String {
+        let mut page = String::new();
+        let _ = writeln!(page, "<!DOCTYPE html>");
+        let _ = writeln!(
+            page,
+            "<html><head><meta charset=\"utf-8\"><title>{}</title>",
+            escape(&self.title())
+        );
+        let _ = writeln!(
+            page,
+            "<style>body{{font-family:sans-serif}}table{{border-collapse:collapse}}td,th{{width:2em;height:1.6em;text-align:center;font-size:0.8em}}th{{font-weight:normal;color:#555}}td{{border:1px solid #fff}}</style>"
+        );
+        let _ = writeln!(page, "</head><body>");
+        let _ = writeln!(page, "<h1>{}</h1>", escape(&self.title()));
+        let _ = writeln!(page, "<table>");
+        page.push_str("<tr><th></th>");
+        for hour in 0..24 {
+            let _ = write!(page, "<th>{hour:02}</th>");
+        }
+        page.push_str("</tr>\n");
+        for (day, slots) in self.days.iter().zip(&self.hours) {
+            let _ = write!(page, "<tr><th>{}</th>", day.format("%a %-d %b"));
+            for slot in slots {
+                let _ = write!(
+                    page,
+                    "<td style=\"background:{}\" title=\"{}\">{}</td>",
+                    self.color(slot),
+                    escape(&self.who(slot)),
+                    slot.at_work.len()
+                );
+            }
+            page.push_str("</tr>\n");
+        }
+        let _ = writeln!(page, "</table>");
+        let _ = writeln!(
+            page,
+            "<p>Hours are your own; each cell counts how many of the {} zones are at work for the whole hour.</p>",
+            self.names.len()
+        );
+        let _ = writeln!(page, "</body></html>");
+        page
+    }
+
+    /// The grid as an image, for chats that show pictures but not pages
+    pub fn svg(&self) -> String {
+        const CELL: usize = 24;
+        const LABEL: usize = 96;
+        const TOP: usize = 48;
+        let width = LABEL + 24 * CELL + 8;
+        let height = TOP + self.days.len() * CELL + 8;
+
+        let mut image = String::new();
+        let _ = writeln!(
+            image,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" font-family=\"sans-serif\" font-size=\"11\">"
+        );
+        let _ = writeln!(
+            image,
+            "<rect width=\"100%\" height=\"100%\" fill=\"#ffffff\"/>"
+        );
+        let _ = writeln!(
+            image,
+            "<text x=\"8\" y=\"18\" font-size=\"14\">{}</text>",
+            escape(&self.title())
+        );
+        for hour in 0..24 {
+            let _ = writeln!(
+                image,
+                "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" fill=\"#555555\">{hour:02}</text>",
+                LABEL + hour * CELL + CELL / 2,
+                TOP - 6
+            );
+        }
+        for (row, (day, slots)) in self.days.iter().zip(&self.hours).enumerate() {
+            let y = TOP + row * CELL;
+            let _ = writeln!(
+                image,
+                "<text x=\"8\" y=\"{}\" fill=\"#555555\">{}</text>",
+                y + CELL / 2 + 4,
+                day.format("%a %-d %b")
+            );
+            for (hour, slot) in slots.iter().enumerate() {
+                let _ = writeln!(
+                    image,
+                    "<rect x=\"{}\" y=\"{y}\" width=\"{}\" height=\"{}\" fill=\"{}\"><title>{}</title></rect>",
+                    LABEL + hour * CELL,
+                    CELL - 1,
+                    CELL - 1,
+                    self.color(slot),
+                    escape(&self.who(slot))
+                );
+            }
+        }
+        let _ = writeln!(image, "</svg>");
+        image
+    }
+}
+
+/// Text made safe for HTML and SVG
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_week_grid() {
+        let mut london = TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into());
+        london.display.label = Some("Ops <EU>".to_string());
+        let zones = [
+            london,
+            TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into()),
+        ];
+        let board = TimeDisplayConfig::default();
+        // From Saturday 13 January 2024, so the weekend comes first
+        let first = NaiveDate::from_ymd_opt(2024, 1, 13).unwrap();
+        let grid = WeekGrid::new(&chrono_tz::Europe::London, &zones, &board, &[], first);
+
+        assert_eq!(grid.days.len(), DAYS);
+        assert!(grid.hours.iter().all(|day| day.len() == 24));
+        assert!(grid.hours[0].iter().all(|slot| slot.at_work.is_empty()));
+        // Monday: London alone from 08:00, both from 13:00 until 18:00
+        let monday = &grid.hours[2];
+        assert_eq!(monday[8].at_work, vec![0]);
+        assert_eq!(monday[13].at_work, vec![0, 1]);
+        assert_eq!(monday[17].at_work, vec![0, 1]);
+        assert_eq!(monday[18].at_work, vec![1]);
+        assert_eq!(grid.color(&monday[13]), "#1b7a3a");
+        assert_eq!(grid.color(&monday[0]), "#f2f2f2");
+
+        let html = grid.html();
+        assert!(html.contains("<h1>Who's at work, week of Sat 13 Jan</h1>"));
+        assert!(html.contains("title=\"Ops &lt;EU&gt;, NYC\">2</td>"));
+        assert!(!html.contains("<EU>"));
+        let svg = grid.svg();
+        assert!(svg.starts_with("<svg "));
+        assert_eq!(svg.matches("<rect x=").count(), DAYS * 24);
+    }
+}
@@ -0,0 +1,150 @@
+use crate::config::HandoffConfig;
+use crate::time::{next_work_end, resolve_zone, work_overlap, TimeZone};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// How far ahead to look for DST changes that squeeze a handoff
+const LOOKAHEAD_WEEKS: i64 = 8;
+
+/// One team passing work to the next as its working day ends
+#[derive(Debug, Clone, PartialEq)]
+pub struct Handoff {
+    pub from: String,
+    pub to: String,
+    pub time: DateTime<Utc>,
+    pub receiver_time: DateTime<Tz>,
+    /// How long both teams are at work on the handoff day
+    pub overlap: Duration,
+}
+
+/// A day the overlap of a link first falls below the minimum, when the same
+/// weekday a week before still cleared it: usually a DST change in one zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlapWarning {
+    pub from: String,
+    pub to: String,
+    pub date: NaiveDate,
+    pub overlap: Duration,
+}
+
+/// The chain's teams with their zones, skipping names that can't be found
+pub fn teams(config: &HandoffConfig, zones: &[TimeZone]) -> Vec<(String, Tz)> {
+    config
+        .chain
+        .iter()
+        .filter_map(|name| Some((name.clone(), resolve_zone(name, zones)?)))
+        .collect()
+}
+
+/// Each team with the one after it, the last handing back to the first
+fn links(teams: &[(String, Tz)]) -> impl Iterator<Item = (&(String, Tz), &(String, Tz))> {
+    let next = teams.iter().cycle().skip(1);
+    teams
+        .iter()
+        .zip(next)
+        .take(if teams.len() > 1 { teams.len() } else { 0 })
+}
+
+/// Every link's next handoff after `now`, soonest first
+pub fn upcoming(teams: &[(String, Tz)], now: DateTime<Utc>, hours: (u32, u32)) -> Vec<Handoff> {
+    let mut handoffs: Vec<Handoff> = links(teams)
+        .filter_map(|((from, sender), (to, receiver))| {
+            let time = next_work_end(*sender, now, hours)?;
+            // The sender's working day is the one ending at the handoff
+            let date = (time - Duration::seconds(1))
+                .with_timezone(sender)
+                .date_naive();
+            Some(Handoff {
+                from: from.clone(),
+                to: to.clone(),
+                time,
+                receiver_time: time.with_timezone(receiver),
+                overlap: work_overlap(sender, *receiver, date, hours, hours),
+            })
+        })
+        .collect();
+    handoffs.sort_by_key(|handoff| handoff.time);
+    handoffs
+}
+
+/// Links whose overlap drops below `minimum` in the coming weeks. Each day
+/// is compared with the week before, so weekends don't count as drops.
+pub fn warnings(
+    teams: &[(String, Tz)],
+    today: NaiveDate,
+    hours: (u32, u32),
+    minimum: Duration,
+) -> Vec<OverlapWarning> {
+    links(teams)
+        .filter_map(|((from, sender), (to, receiver))| {
+            let overlap = |date: NaiveDate| work_overlap(sender, *receiver, date, hours, hours);
+            (0..LOOKAHEAD_WEEKS * 7)
+                .filter_map(|days| today.checked_add_signed(Duration::days(days)))
+                .find(|date| {
+                    overlap(*date) < minimum && overlap(*date - Duration::days(7)) >= minimum
+                })
+                .map(|date| OverlapWarning {
+                    from: from.clone(),
+                    to: to.clone(),
+                    date,
+                    overlap: overlap(date),
+                })
+        })
+        .collect()
+}
+
+/// A length of time as hours and minutes, e.g. "2h 15m"
+pub fn format_duration(duration: Duration) -> String {
+    let minutes = duration.num_minutes().max(0);
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handoffs() {
+        let teams = vec![
+            ("Sydney".to_string(), chrono_tz::Australia::Sydney),
+            ("London".to_string(), chrono_tz::Europe::London),
+            ("New York".to_string(), chrono_tz::America::New_York),
+        ];
+        let hours = (8, 18);
+        // Wednesday 5 March 2025, 05:00 UTC: Sydney is mid-afternoon
+        let now = DateTime::parse_from_rfc3339("2025-03-05T05:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let handoffs = upcoming(&teams, now, hours);
+        assert_eq!(handoffs.len(), 3);
+        let first = &handoffs[0];
+        assert_eq!(
+            (first.from.as_str(), first.to.as_str()),
+            ("Sydney", "London")
+        );
+        // 18:00 AEDT is 07:00 in London, before its work day starts
+        assert_eq!(first.time.to_rfc3339(), "2025-03-05T07:00:00+00:00");
+        assert_eq!(first.receiver_time.format("%H:%M").to_string(), "07:00");
+        assert_eq!(first.overlap, Duration::zero());
+        assert_eq!(handoffs[1].from, "London");
+        assert_eq!(handoffs[1].overlap, Duration::hours(5));
+
+        // New York's DST start on 9 March takes an hour off its evening
+        // overlap with Sydney's morning
+        let today = now.date_naive();
+        let found = warnings(&teams, today, hours, Duration::hours(2));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].from, "New York");
+        assert_eq!(found[0].date, NaiveDate::from_ymd_opt(2025, 3, 10).unwrap());
+        assert_eq!(found[0].overlap, Duration::hours(1));
+
+        assert_eq!(format_duration(Duration::minutes(135)), "2h 15m");
+        assert_eq!(format_duration(Duration::hours(5)), "5h");
+        assert_eq!(format_duration(Duration::zero()), "0m");
+    }
+}
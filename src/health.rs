@@ -0,0 +1,257 @@
+use crate::handoff::format_duration;
+use crate::matrix::format_offset;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use std::fmt;
+use std::io;
+use std::net::UdpSocket;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration as StdDuration;
+
+/// How far this machine's clock may drift before it's worth a warning
+pub const TOLERANCE: Duration = Duration::minutes(5);
+
+/// Seconds between the NTP epoch (1900) and the Unix one
+const NTP_EPOCH_OFFSET: i64 = 2_208_988_800;
+
+/// Something about this machine's clock or zone that would make the board
+/// wrong
+#[derive(Debug, Clone, PartialEq)]
+pub enum ClockWarning {
+    /// The machine keeps a different offset from the configured home zone
+    HomeMismatch {
+        home: String,
+        home_offset: i32,
+        local_offset: i32,
+    },
+    /// The `TZ` variable picks the zone instead of the system setting
+    TzOverride(String),
+    /// A file was written later than the clock says it is now
+    FileFromFuture { path: PathBuf, ahead: Duration },
+    /// The time server disagrees with this machine; positive when the
+    /// machine is behind
+    ServerOffset { server: String, offset: Duration },
+}
+
+impl fmt::Display for ClockWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClockWarning::HomeMismatch {
+                home,
+                home_offset,
+                local_offset,
+            } => write!(
+                f,
+                "this machine keeps UTC{} but home ({home}) is UTC{}: is the system zone right?",
+                format_offset(*local_offset),
+                format_offset(*home_offset)
+            ),
+            ClockWarning::TzOverride(value) => {
+                write!(f, "TZ={value} is overriding the system zone")
+            }
+            ClockWarning::FileFromFuture { path, ahead } => write!(
+                f,
+                "{} was modified {} from now: is the clock behind?",
+                path.display(),
+                format_duration(*ahead)
+            ),
+            ClockWarning::ServerOffset { server, offset } => write!(
+                f,
+                "the clock is {} {} {server}",
+                format_duration(offset.abs()),
+                if *offset > Duration::zero() {
+                    "behind"
+                } else {
+                    "ahead of"
+                }
+            ),
+        }
+    }
+}
+
+/// The warnings `now` and this machine's `local_offset` (in minutes) call
+/// for, given the configured home zone, the `TZ` variable against the
+/// system's zone and when files were last modified
+pub fn check(
+    now: DateTime<Utc>,
+    local_offset: i32,
+    home: Option<(&str, Tz)>,
+    tz_env: Option<&str>,
+    system_zone: Option<&str>,
+    modified: &[(PathBuf, DateTime<Utc>)],
+) -> Vec<ClockWarning> {
+    let mut warnings = Vec::new();
+    if let Some((name, tz)) = home {
+        let home_offset = crate::time::TimeZone::from_tz(tz).offset_minutes_at(now);
+        if home_offset != local_offset {
+            warnings.push(ClockWarning::HomeMismatch {
+                home: name.to_string(),
+                home_offset,
+                local_offset,
+            });
+        }
+    }
+    // A leading colon is allowed, as in "TZ=:Europe/London"
+    if let Some(value) = tz_env.filter(|value| !value.is_empty()) {
+        if Some(value.trim_start_matches(':')) != system_zone {
+            warnings.push(ClockWarning::TzOverride(value.to_string()));
+        }
+    }
+    // One file is enough to show the clock is behind
+    if let Some((path, at)) = modified
+        .iter()
+        .filter(|(_, at)| *at - now > TOLERANCE)
+        .max_by_key(|(_, at)| *at)
+    {
+        warnings.push(ClockWarning::FileFromFuture {
+            path: path.clone(),
+            ahead: *at - now,
+        });
+    }
+    warnings
+}
+
+/// The time a server's SNTP reply was sent, from its transmit timestamp.
+/// Anything but a server-mode packet with a timestamp set isn't a reply.
+pub fn transmit_time(packet: &[u8; 48]) -> Option<DateTime<Utc>> {
+    // The mode is the low three bits of the first byte; 4 is a server
+    if packet[0] & 0x07 != 4 {
+        return None;
+    }
+    let seconds = u32::from_be_bytes(packet[40..44].try_into().ok()?) as i64;
+    let fraction = u32::from_be_bytes(packet[44..48].try_into().ok()?) as u64;
+    if seconds == 0 && fraction == 0 {
+        return None;
+    }
+    let nanos = (fraction * 1_000_000_000) >> 32;
+    DateTime::from_timestamp(seconds - NTP_EPOCH_OFFSET, nanos as u32)
+}
+
+/// A time server being asked for its clock in the background, since
+/// looking it up and waiting for the reply can take a while offline
+#[derive(Debug)]
+pub struct ServerCheck {
+    pub server: String,
+    receiver: mpsc::Receiver<io::Result<Duration>>,
+}
+
+impl ServerCheck {
+    pub fn spawn(server: &str) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let name = server.to_string();
+        thread::spawn(move || {
+            let _ = sender.send(server_offset(&name));
+        });
+        Self {
+            server: server.to_string(),
+            receiver,
+        }
+    }
+
+    /// The server's offset, once the background thread has finished
+    pub fn try_result(&self) -> Option<io::Result<Duration>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Some(Err(io::Error::other("clock check stopped")))
+            }
+        }
+    }
+}
+
+/// How far `server`'s clock is ahead of this machine's, asked over SNTP.
+/// Gives up a second after asking.
+pub fn server_offset(server: &str) -> io::Result<Duration> {
+    let socket = UdpSocket::bind("0.0.0.0:0")?;
+    socket.set_read_timeout(Some(StdDuration::from_secs(1)))?;
+    socket.connect((server, 123))?;
+    let mut packet = [0u8; 48];
+    // No leap indicator, version 3, client mode
+    packet[0] = 0x1b;
+    let sent = Utc::now();
+    socket.send(&packet)?;
+    socket.recv(&mut packet)?;
+    let received = Utc::now();
+    let server_time = transmit_time(&packet)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "not a server reply"))?;
+    Ok(server_time - (sent + (received - sent) / 2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_check_clock() {
+        let now = utc("2024-06-01T12:00:00Z");
+        let home = Some(("Madrid", chrono_tz::Europe::Madrid));
+        // Madrid is UTC+2 in June
+        assert!(check(now, 120, home, None, None, &[]).is_empty());
+        // TZ naming the system's own zone overrides nothing
+        let system = Some("Europe/Madrid");
+        assert!(check(now, 120, home, Some(":Europe/Madrid"), system, &[]).is_empty());
+
+        let files = [
+            (PathBuf::from("config.toml"), utc("2024-06-01T12:03:00Z")),
+            (PathBuf::from("history.jsonl"), utc("2024-06-01T14:30:00Z")),
+        ];
+        let warnings = check(now, 60, home, Some("Europe/London"), system, &files);
+        assert_eq!(
+            warnings,
+            [
+                ClockWarning::HomeMismatch {
+                    home: "Madrid".to_string(),
+                    home_offset: 120,
+                    local_offset: 60,
+                },
+                ClockWarning::TzOverride("Europe/London".to_string()),
+                ClockWarning::FileFromFuture {
+                    path: PathBuf::from("history.jsonl"),
+                    ahead: Duration::minutes(150),
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "this machine keeps UTC+1 but home (Madrid) is UTC+2: is the system zone right?"
+        );
+        assert_eq!(
+            warnings[2].to_string(),
+            "history.jsonl was modified 2h 30m from now: is the clock behind?"
+        );
+        assert_eq!(
+            ClockWarning::ServerOffset {
+                server: "pool.ntp.org".to_string(),
+                offset: Duration::minutes(-7),
+            }
+            .to_string(),
+            "the clock is 7m ahead of pool.ntp.org"
+        );
+    }
+
+    #[test]
+    fn test_transmit_time() {
+        // A server reply without a timestamp
+        let mut packet = [0u8; 48];
+        packet[0] = 0x1c;
+        assert_eq!(transmit_time(&packet), None);
+
+        let seconds = (utc("2024-06-01T12:00:00Z").timestamp() + NTP_EPOCH_OFFSET) as u32;
+        packet[40..44].copy_from_slice(&seconds.to_be_bytes());
+        // Half a second
+        packet[44..48].copy_from_slice(&0x8000_0000u32.to_be_bytes());
+        assert_eq!(transmit_time(&packet), Some(utc("2024-06-01T12:00:00.5Z")));
+        // The same packet in client mode, as an echo of the request would be
+        packet[0] = 0x1b;
+        assert_eq!(transmit_time(&packet), None);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::platform::Platform;
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// A change to the board worth keeping a record of
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum HistoryEvent {
+    ZoneAdded {
+        city: String,
+    },
+    ZoneRemoved {
+        city: String,
+        label: Option<String>,
+    },
+    LabelChanged {
+        city: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    ZoneArchived {
+        city: String,
+    },
+    ZoneRestored {
+        city: String,
+    },
+}
+
+impl fmt::Display for HistoryEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryEvent::ZoneAdded { city } => write!(f, "added {city}"),
+            HistoryEvent::ZoneRemoved { city, label: None } => write!(f, "removed {city}"),
+            HistoryEvent::ZoneRemoved {
+                city,
+                label: Some(label),
+            } => write!(f, "removed {city} (\"{label}\")"),
+            HistoryEvent::LabelChanged { city, from, to } => match (from, to) {
+                (None, Some(to)) => write!(f, "labelled {city} \"{to}\""),
+                (Some(from), Some(to)) => write!(f, "relabelled {city} \"{from}\" -> \"{to}\""),
+                (Some(from), None) => write!(f, "cleared label \"{from}\" from {city}"),
+                (None, None) => write!(f, "cleared label from {city}"),
+            },
+            HistoryEvent::ZoneArchived { city } => write!(f, "archived {city}"),
+            HistoryEvent::ZoneRestored { city } => write!(f, "restored {city}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+}
+
+impl fmt::Display for HistoryEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let local = self.timestamp.with_timezone(&Local);
+        write!(f, "{}  {}", local.format("%Y-%m-%d %H:%M:%S"), self.event)
+    }
+}
+
+/// Append-only log of board changes, one JSON object per line, kept next to
+/// the config so shared and kiosk boards can be audited
+pub fn history_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("history.jsonl"))
+}
+
+pub fn append(path: &Path, entry: &HistoryEntry) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(entry)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads the log, skipping any lines it can't parse (e.g. from a newer version)
+pub fn read(path: &Path) -> io::Result<Vec<HistoryEntry>> {
+    let content = fs::read_to_string(path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Records an event now in the default history file
+pub fn record(event: HistoryEvent) -> io::Result<()> {
+    match history_path() {
+        Some(path) => append(
+            &path,
+            &HistoryEntry {
+                timestamp: Utc::now(),
+                event,
+            },
+        ),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = std::env::temp_dir().join(format!("alltz-history-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+
+        let timestamp = DateTime::parse_from_rfc3339("2025-03-01T09:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let events = [
+            HistoryEvent::ZoneAdded {
+                city: "Tokyo".to_string(),
+            },
+            HistoryEvent::LabelChanged {
+                city: "Tokyo".to_string(),
+                from: None,
+                to: Some("HQ".to_string()),
+            },
+            HistoryEvent::ZoneRemoved {
+                city: "Tokyo".to_string(),
+                label: Some("HQ".to_string()),
+            },
+        ];
+        for event in &events {
+            let entry = HistoryEntry {
+                timestamp,
+                event: event.clone(),
+            };
+            append(&path, &entry).unwrap();
+        }
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "not json"))
+            .unwrap();
+
+        let entries = read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        let read_events: Vec<HistoryEvent> = entries.into_iter().map(|e| e.event).collect();
+        assert_eq!(read_events, events);
+
+        let line = serde_json::to_string(&HistoryEntry {
+            timestamp,
+            event: events[0].clone(),
+        })
+        .unwrap();
+        assert_eq!(
+            line,
+            r#"{"timestamp":"2025-03-01T09:30:00Z","event":"zone_added","city":"Tokyo"}"#
+        );
+        assert_eq!(events[1].to_string(), "labelled Tokyo \"HQ\"");
+        assert_eq!(events[2].to_string(), "removed Tokyo (\"HQ\")");
+    }
+}
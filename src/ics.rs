@@ -0,0 +1,301 @@
+use crate::config::{AppConfig, BlockedDates, CalendarConfig, Milestone};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::thread;
+use std::time::Duration as StdDuration;
+
+const TIMESTAMP: &str = "%Y%m%dT%H%M%SZ";
+
+/// How long a feed client may take to send its request or read the reply
+const CLIENT_TIMEOUT: StdDuration = StdDuration::from_secs(10);
+
+/// An iCalendar feed with one event per milestone. Events are keyed by name
+/// and group, so a slipped milestone moves in subscribers' calendars instead
+/// of appearing twice.
+pub fn calendar(milestones: &[Milestone], now: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//alltz//alltz {}//EN", env!("CARGO_PKG_VERSION")),
+        "CALSCALE:GREGORIAN".to_string(),
+        "X-WR-CALNAME:alltz milestones".to_string(),
+    ];
+    for milestone in milestones {
+        let group = milestone.group.as_deref().unwrap_or_default();
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@alltz", uid(&milestone.name, group)),
+            format!("DTSTAMP:{}", now.format(TIMESTAMP)),
+            format!("DTSTART:{}", milestone.time.format(TIMESTAMP)),
+            format!("SUMMARY:{}", escape(&milestone.name)),
+        ]);
+        if !group.is_empty() {
+            lines.push(format!("CATEGORIES:{}", escape(group)));
+        }
+        lines.push("END:VEVENT".to_string());
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line) + "\r\n").collect()
+}
+
+/// A stable identifier from the milestone's name and group: a readable
+/// slug, with a hash of the exact text so names differing only in
+/// punctuation or case stay apart
+fn uid(name: &str, group: &str) -> String {
+    let key = format!("{group}/{name}");
+    let slug: String = key
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("{slug}-{:016x}", fnv1a(key.as_bytes()))
+}
+
+/// 64-bit FNV-1a, which unlike the standard library's hasher gives the
+/// same value in every build
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Escapes the characters with meaning in iCalendar text values
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Splits lines longer than 75 bytes, continuing on lines starting with a space
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+    for c in line.chars() {
+        if width + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+/// Milestones from the config, optionally only those in one group
+pub fn milestones(config: &AppConfig, group: Option<&str>) -> Vec<Milestone> {
+    config
+        .milestones
+        .iter()
+        .filter(|m| group.is_none() || m.group.as_deref() == group)
+        .cloned()
+        .collect()
+}
+
+/// Words in an all-day event's summary that mark its owner as away
+const AWAY_WORDS: [&str; 6] = ["ooo", "vacation", "holiday", "holidays", "leave", "pto"];
+
+/// Whether an event summary reads as time away rather than, say, a launch
+fn is_away(summary: &str) -> bool {
+    let summary = summary.to_lowercase();
+    summary.contains("out of office")
+        || summary
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|word| AWAY_WORDS.contains(&word))
+}
+
+/// Undoes `fold`, joining continuation lines onto the line before
+fn unfold(content: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    for line in content.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(rest), Some(last)) => last.push_str(rest),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    lines
+}
+
+/// The days of all-day out-of-office events in an iCalendar file, each as
+/// its first and last day. Outlook marks these with an OOF busy status;
+/// other calendars only have the summary to go on.
+pub fn out_of_office(content: &str) -> Vec<(NaiveDate, NaiveDate)> {
+    let mut days = Vec::new();
+    let mut event: Option<(Option<NaiveDate>, Option<NaiveDate>, bool)> = None;
+    for line in unfold(content) {
+        let Some((name, value)) = line.split_once(':') else {
+            continue;
+        };
+        let (property, params) = name.split_once(';').unwrap_or((name, ""));
+        let date = || {
+            params
+                .contains("VALUE=DATE")
+                .then(|| NaiveDate::parse_from_str(value, "%Y%m%d").ok())
+                .flatten()
+        };
+        match (property, event.as_mut()) {
+            ("BEGIN", None) if value == "VEVENT" => event = Some((None, None, false)),
+            ("DTSTART", Some(event)) => event.0 = date(),
+            ("DTEND", Some(event)) => event.1 = date(),
+            ("X-MICROSOFT-CDO-BUSYSTATUS", Some(event)) => event.2 |= value == "OOF",
+            ("SUMMARY", Some(event)) => event.2 |= is_away(value),
+            ("END", Some(_)) if value == "VEVENT" => {
+                if let Some((Some(start), end, true)) = event.take() {
+                    // DTEND is exclusive, and a missing one means a single day
+                    let last = end.map_or(start, |end| end - Duration::days(1));
+                    days.push((start, last.max(start)));
+                }
+            }
+            _ => {}
+        }
+    }
+    days
+}
+
+/// Out-of-office days from each configured calendar file, as blocked dates
+/// for the calendar's owner. Calendars that can't be read are skipped.
+pub fn calendar_blocked(calendars: &[CalendarConfig]) -> Vec<BlockedDates> {
+    calendars
+        .iter()
+        .filter_map(|calendar| Some((calendar, std::fs::read_to_string(&calendar.file).ok()?)))
+        .flat_map(|(calendar, content)| {
+            out_of_office(&content)
+                .into_iter()
+                .map(|(from, to)| BlockedDates {
+                    who: calendar.who.clone(),
+                    from,
+                    to,
+                    note: Some("out of office".to_string()),
+                })
+        })
+        .collect()
+}
+
+/// Serves the feed over plain HTTP until interrupted, each client on its
+/// own thread so a slow one doesn't hold up the rest. The config is read
+/// afresh for every request, so slips made in alltz reach subscribers on
+/// their next refresh.
+pub fn serve(addr: impl ToSocketAddrs, group: Option<&str>) -> io::Result<()> {
+    let listener = TcpListener::bind(addr)?;
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let group = group.map(str::to_string);
+        thread::spawn(move || {
+            let _ = respond(stream, group.as_deref());
+        });
+    }
+    Ok(())
+}
+
+/// Answers any request with the feed. The whole request head is read first,
+/// since closing with some of it unread resets the connection and can cut
+/// the reply short.
+fn respond(mut stream: TcpStream, group: Option<&str>) -> io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+    let mut reader = BufReader::new(&stream);
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+    }
+    let body = calendar(&milestones(&AppConfig::load(), group), Utc::now());
+    write!(
+        stream,
+        "HTTP/1.1 200 OK\r\nContent-Type: text/calendar; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    stream.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_calendar() {
+        let time = DateTime::parse_from_rfc3339("2024-06-20T16:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let milestones = [
+            Milestone {
+                name: "GA, finally; really".to_string(),
+                time,
+                group: Some("release".to_string()),
+            },
+            Milestone {
+                name: "Offsite".to_string(),
+                time,
+                group: None,
+            },
+        ];
+        let ics = calendar(&milestones, time);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains(&format!(
+            "UID:release-ga--finally--really-{:016x}@alltz\r\n",
+            fnv1a(b"release/GA, finally; really")
+        )));
+        // Names that only differ in punctuation get their own events
+        assert_ne!(uid("GA, finally", ""), uid("GA; finally", ""));
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert!(ics.contains("DTSTART:20240620T160000Z\r\n"));
+        assert!(ics.contains("SUMMARY:GA\\, finally\\; really\r\n"));
+        assert!(ics.contains("CATEGORIES:release\r\n"));
+        assert_eq!(ics.matches("CATEGORIES").count(), 1);
+
+        let long = fold(&format!("SUMMARY:{}", "é".repeat(60)));
+        assert!(long.split("\r\n").all(|line| line.len() <= 75));
+        assert_eq!(
+            long.replace("\r\n ", ""),
+            format!("SUMMARY:{}", "é".repeat(60))
+        );
+    }
+
+    #[test]
+    fn test_out_of_office() {
+        let ics = "BEGIN:VCALENDAR\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20250714\r
+DTEND;VALUE=DATE:20250726\r
+SUMMARY:Summer \r
+ holiday\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20250801\r
+SUMMARY:Offsite\r
+X-MICROSOFT-CDO-BUSYSTATUS:OOF\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART;VALUE=DATE:20250805\r
+SUMMARY:Product launch\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+DTSTART:20250806T090000Z\r
+DTEND:20250806T100000Z\r
+SUMMARY:OOO for dentist\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+        let day = |m, d| NaiveDate::from_ymd_opt(2025, m, d).unwrap();
+        assert_eq!(
+            out_of_office(ics),
+            [(day(7, 14), day(7, 25)), (day(8, 1), day(8, 1))]
+        );
+
+        assert!(is_away("PTO (Alice)"));
+        assert!(is_away("Out of Office"));
+        assert!(!is_away("Sleave design review"));
+    }
+}
@@ -0,0 +1,54 @@
+//! The board, its config and the parsers behind alltz's commands. The
+//! `alltz` binary is the CLI and event loop on top, and the fuzz targets in
+//! `fuzz/` feed these parsers untrusted text through it.
+
+#[macro_use]
+extern crate rust_i18n;
+
+// Load translations from locales directory
+i18n!("locales");
+
+pub mod abbrev;
+pub mod app;
+pub mod blocked;
+pub mod card;
+pub mod cities;
+pub mod command;
+pub mod config;
+pub mod crash;
+pub mod demo;
+pub mod grid;
+pub mod handoff;
+pub mod health;
+pub mod history;
+pub mod ics;
+pub mod log;
+pub mod matrix;
+pub mod notify;
+pub mod org;
+pub mod pipe;
+pub mod plan;
+pub mod platform;
+pub mod preview;
+pub mod quiz;
+pub mod remind;
+pub mod report;
+pub mod schema;
+pub mod session;
+#[cfg(feature = "sync")]
+pub mod sync;
+pub mod tasks;
+pub mod templates;
+pub mod terminal;
+pub mod time;
+pub mod ui;
+pub mod update;
+
+/// Text for `key` in the current locale with its `%{name}` placeholders
+/// filled in, for the binary
+pub fn translate(key: &str, args: &[(&str, String)]) -> String {
+    args.iter()
+        .fold(t!(key).into_owned(), |text, (name, value)| {
+            text.replace(&format!("%{{{name}}}"), value)
+        })
+}
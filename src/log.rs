@@ -0,0 +1,33 @@
+use crate::platform::Platform;
+use chrono::Local;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// Diagnostic log for things worth knowing about but not worth interrupting
+/// the TUI for, kept next to the config
+pub fn log_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("alltz.log"))
+}
+
+pub fn append(path: &Path, message: &str) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(
+        file,
+        "{}  {message}",
+        Local::now().format("%Y-%m-%d %H:%M:%S")
+    )
+}
+
+/// Appends to the diagnostic log, ignoring failures: logging must never
+/// disturb the board
+pub fn write(message: &str) {
+    if let Some(path) = log_path() {
+        let _ = append(&path, message);
+    }
+}
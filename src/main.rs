@@ -1,36 +1,106 @@
-#[macro_use]
-extern crate rust_i18n;
-
-// Load translations from locales directory
-i18n!("locales");
+/// Looks text up in the library, which holds the one copy of the
+/// translations, filling in `%{name}` placeholders
+macro_rules! t {
+    ($key:expr $(, $name:ident = $value:expr)* $(,)?) => {
+        alltz::translate($key, &[$((stringify!($name), $value.to_string())),*])
+    };
+}
 
-mod app;
-mod config;
-mod time;
-mod ui;
+use alltz::{
+    abbrev, app, blocked, command, config, crash, demo, grid, handoff, history, ics, matrix, pipe,
+    plan, platform, preview, quiz, remind, report, schema, session, templates, terminal, time, ui,
+    update,
+};
 
-use app::{App, Direction, Message};
+use app::{App, Direction, MacroAction, Message, Preload};
 use clap::{Parser, Subcommand};
+use command::{decode_key, encode_key, Command};
 use crossterm::{
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind, KeyModifiers,
+        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyEventKind,
+        KeyModifiers,
     },
     execute,
-    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+    terminal::{
+        disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+    },
 };
+use platform::Platform;
 use ratatui::{backend::CrosstermBackend, Terminal};
 use std::{
     error::Error,
     io,
+    path::PathBuf,
+    sync::atomic::{AtomicBool, Ordering},
     time::{Duration, Instant},
 };
+use terminal::{Capabilities, ColorSupport, GraphicsProtocol};
+#[cfg(feature = "clock-graphics")]
+use ui::clock::{self, ClockImage};
 
 /// Rate at which the UI updates (1 second for time changes and animations)
 const TICK_RATE: Duration = Duration::from_millis(1000);
 
+/// Frame interval while a scrub animation is playing
+const ANIMATION_FRAME_RATE: Duration = Duration::from_millis(30);
+
+/// How soon after starting the board should be on screen; slower starts are
+/// logged
+const STARTUP_BUDGET: Duration = Duration::from_millis(50);
+
+/// Set by `--quiet`: subcommands print nothing, leaving the exit status to
+/// tell scripts how it went
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+/// `print!` for subcommand output, silenced by `--quiet`
+macro_rules! out {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            print!($($arg)*);
+        }
+    };
+}
+
+/// `println!` for subcommand output, silenced by `--quiet`
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            println!($($arg)*);
+        }
+    };
+}
+
+/// `eprintln!` for subcommand warnings and errors, silenced by `--quiet`
+macro_rules! errln {
+    ($($arg:tt)*) => {
+        if !QUIET.load(Ordering::Relaxed) {
+            eprintln!($($arg)*);
+        }
+    };
+}
+
+/// Exit statuses scripts can branch on. Clap exits with 2 for bad usage.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ExitStatus {
+    /// Something went wrong, such as an unreadable file or a bad config
+    Failure = 1,
+    /// A zone named on the command line or in the config can't be found
+    UnknownZone = 3,
+    /// Teams meant to overlap have no working hours in common
+    NoOverlap = 4,
+    /// A named template or config key doesn't exist
+    NotFound = 5,
+}
+
+impl ExitStatus {
+    fn exit(self) -> ! {
+        std::process::exit(self as i32)
+    }
+}
+
 #[derive(Parser)]
 #[command(name = "alltz")]
-#[command(version = "0.1.3")]
+#[command(version)]
 #[command(about = "🌍 Terminal-based timezone viewer for developers and remote teams")]
 #[command(
     long_about = "alltz is a terminal application for tracking multiple timezones simultaneously. Features include DST indicators, color themes, and intuitive timeline scrubbing."
@@ -49,14 +119,67 @@ struct Cli {
 
     /// Start with a specific color theme
     #[arg(long, value_parser = parse_theme)]
-    theme: Option<config::ColorTheme>,
+    theme: Option<String>,
+
+    /// Use the light or dark variant of the theme instead of asking the terminal
+    #[arg(long, value_enum)]
+    background: Option<config::BackgroundMode>,
+
+    /// Redraw as little as possible, for slow SSH or mosh connections
+    #[arg(long)]
+    low_bandwidth: bool,
+
+    /// Open a sample board, leaving your own config untouched
+    #[arg(long)]
+    demo: bool,
+
+    /// Pick up where the last session left off: zones, marks, split view and
+    /// where the timeline was scrubbed to
+    #[arg(long)]
+    resume: bool,
+
+    /// Print the terminal capabilities alltz detects, then exit
+    #[arg(long)]
+    diagnose: bool,
+
+    /// Print the JSON Schema of every `--format json` output, then exit
+    #[arg(long)]
+    schema: bool,
+
+    /// Print nothing from subcommands; the exit status tells how it went
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// After a subcommand, open the board on what it was about; zones it
+    /// adds are shown for this session only
+    #[arg(long, global = true)]
+    open: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
+    #[command(flatten)]
+    OneShot(OneShot),
+
+    /// Minimal view with one compact row per zone, for tiny floating terminals
+    Mini {
+        /// Number of zone rows to show
+        #[arg(short = 'n', long, default_value_t = 4)]
+        rows: usize,
+    },
+}
+
+/// Subcommands that do their work and exit, unless `--open` follows them
+/// with the board
+#[derive(Subcommand)]
+enum OneShot {
     /// List all available timezones
     #[command(alias = "ls")]
-    List,
+    List {
+        /// Print as text, or as JSON following `alltz --schema`
+        #[arg(long, value_enum, default_value = "text")]
+        format: schema::OutputFormat,
+    },
 
     /// Show current time in a specific timezone
     #[command(alias = "show")]
@@ -71,48 +194,301 @@ enum Commands {
         /// City name to get information for
         city: String,
     },
+
+    /// Print every zone's local time, for pasting into chat
+    Card {
+        /// Instant to show, e.g. 2024-06-20T16:00:00Z (defaults to now)
+        #[arg(long)]
+        at: Option<chrono::DateTime<chrono::Utc>>,
+
+        /// Layout from `[[card_templates]]` in the config (defaults to an aligned table)
+        #[arg(short, long)]
+        template: Option<String>,
+    },
+
+    /// Print how far ahead each zone's clocks are of each other's, noting DST changes this week
+    Matrix {
+        /// Instant to show, e.g. 2024-06-20T16:00:00Z (defaults to now)
+        #[arg(long)]
+        at: Option<chrono::DateTime<chrono::Utc>>,
+    },
+
+    /// Convert the timestamps in text on stdin to another zone, for use as an editor filter
+    Pipe {
+        /// Zone to convert to: a city, board label or IANA name
+        #[arg(long)]
+        to: String,
+
+        /// Zone that timestamps without an offset are in (they're left alone otherwise)
+        #[arg(long)]
+        from: Option<String>,
+
+        /// Replace each timestamp instead of noting the converted time after it
+        #[arg(long)]
+        replace: bool,
+    },
+
+    /// Quiz yourself on the time in your tracked zones
+    Quiz {
+        /// Number of questions (keeps going until an empty answer by default)
+        #[arg(short = 'n', long)]
+        rounds: Option<usize>,
+    },
+
+    /// Add a ready-made layout and its milestones to the config
+    Template {
+        /// Template to add
+        #[arg(value_enum)]
+        template: templates::Template,
+
+        /// Date the milestones lead up to, as YYYY-MM-DD (defaults to two weeks from today)
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+    },
+
+    /// Publish milestones as an iCalendar feed (printed unless --output or --serve is given)
+    Ics {
+        /// File to write the feed to
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Address to serve the feed on, e.g. 127.0.0.1:8080
+        #[arg(long)]
+        serve: Option<String>,
+
+        /// Only milestones in this group
+        #[arg(short, long)]
+        group: Option<String>,
+    },
+
+    /// Show the next handoff along the configured chain, and DST changes that squeeze it
+    Handoff,
+
+    /// Suggest call times, best first: when most zones are at work, then
+    /// when fewest are outside the call windows they prefer
+    Plan {
+        /// Day to plan, as YYYY-MM-DD in your own time (defaults to today)
+        #[arg(long)]
+        date: Option<chrono::NaiveDate>,
+
+        /// Length of the call in minutes
+        #[arg(long, default_value_t = 30)]
+        minutes: u32,
+
+        /// Number of suggestions to show
+        #[arg(long, default_value_t = 5)]
+        count: usize,
+    },
+
+    /// Total the work hours you share with each zone over the coming week,
+    /// following DST changes and days people are away
+    Report {
+        /// First day, as YYYY-MM-DD in your own time (defaults to today)
+        #[arg(long)]
+        from: Option<chrono::NaiveDate>,
+
+        /// Print as text, or as JSON following `alltz --schema`
+        #[arg(long, value_enum, default_value = "text")]
+        format: schema::OutputFormat,
+    },
+
+    /// Shade a week of hours by how many zones are at work, as a page or
+    /// image to share (HTML printed unless --output is given)
+    Grid {
+        /// First day, as YYYY-MM-DD in your own time (defaults to today)
+        #[arg(long)]
+        from: Option<chrono::NaiveDate>,
+
+        /// File to write: an .svg image, or an HTML page for any other name
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Set a reminder for the board to fire, e.g. --at "09:30 Europe/Madrid tomorrow"
+    Remind {
+        /// What to be reminded of
+        text: String,
+
+        /// A time, then optionally a zone (IANA name, city or board label) and a day
+        #[arg(long)]
+        at: String,
+    },
+
+    /// Import or list days people are away
+    Blocked {
+        #[command(subcommand)]
+        action: BlockedAction,
+    },
+
+    /// Show the log of zones added, removed and relabelled
+    History {
+        /// Number of most recent entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
+
+    /// Validate the config or inspect where its values come from
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+
+    /// Work on color themes
+    Theme {
+        #[command(subcommand)]
+        action: ThemeAction,
+    },
+
+    /// Update to the latest release, or say how for Homebrew and cargo installs
+    SelfUpdate,
+}
+
+#[derive(Subcommand)]
+enum ConfigAction {
+    /// Check config files for unknown keys, unknown cities and bad hour ranges
+    Check {
+        /// File to check (defaults to the org and user config files)
+        file: Option<PathBuf>,
+    },
+
+    /// Show a key's effective value and which layer set it
+    Explain {
+        /// Config key, dotted for nested keys (e.g. time_config.work_hours_start)
+        key: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum BlockedAction {
+    /// Import days people are away from a file of `who: YYYY-MM-DD[..YYYY-MM-DD] note` lines
+    Import {
+        /// File to import
+        file: PathBuf,
+    },
+
+    /// List the days people are away, including out-of-office days from calendars
+    List,
+}
+
+#[derive(Subcommand)]
+enum ThemeAction {
+    /// Render the demo board in a theme (or every theme) as colored text
+    Preview {
+        /// Theme to render (defaults to the configured theme)
+        #[arg(value_parser = parse_theme)]
+        theme: Option<String>,
+
+        /// Render every theme
+        #[arg(long, conflicts_with = "theme")]
+        all: bool,
+
+        /// Directory to write <theme>.txt and <theme>.ans files to, instead of printing
+        #[arg(short, long)]
+        out: Option<PathBuf>,
+
+        /// Board width in columns
+        #[arg(long, default_value_t = 100)]
+        width: u16,
+
+        /// Board height in rows
+        #[arg(long, default_value_t = 32)]
+        height: u16,
+    },
 }
 
-/// Parse theme name from CLI argument into ColorTheme enum
-fn parse_theme(s: &str) -> Result<config::ColorTheme, String> {
-    match s.to_lowercase().as_str() {
-        "default" => Ok(config::ColorTheme::Default),
-        "ocean" => Ok(config::ColorTheme::Ocean),
-        "forest" => Ok(config::ColorTheme::Forest),
-        "sunset" => Ok(config::ColorTheme::Sunset),
-        "cyberpunk" => Ok(config::ColorTheme::Cyberpunk),
-        "monochrome" => Ok(config::ColorTheme::Monochrome),
-        _ => Err(t!("cli.unknown_theme_error", s = s).to_string()),
+/// Check a theme name from the command line against the built-in themes and
+/// the user's own
+fn parse_theme(s: &str) -> Result<String, String> {
+    let themes = config::AppConfig::load().themes;
+    if config::find_palette(s, &themes, false).is_some() {
+        return Ok(s.to_string());
     }
+    let available: Vec<String> = config::ColorTheme::all_themes()
+        .iter()
+        .map(|theme| format!("{theme:?}").to_lowercase())
+        .chain(themes.into_iter().map(|theme| theme.name))
+        .collect();
+    Err(t!(
+        "cli.unknown_theme_error",
+        s = s,
+        themes = available.join(", ")
+    )
+    .to_string())
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    let started = Instant::now();
     // Set default locale
     rust_i18n::set_locale("en");
 
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
 
-    if let Some(command) = cli.command {
-        return handle_command(command);
+    if cli.diagnose {
+        return diagnose();
     }
+    if cli.schema {
+        println!("{:#}", schema::schema());
+        return Ok(());
+    }
+
+    QUIET.store(cli.quiet, Ordering::Relaxed);
+    let mut preload = None;
+    let mini_rows = match cli.command.take() {
+        Some(Commands::Mini { rows }) => Some(rows.max(1)),
+        Some(Commands::OneShot(command)) => {
+            let state = handle_command(command)?;
+            if !cli.open {
+                return Ok(());
+            }
+            preload = Some(state);
+            None
+        }
+        None => None,
+    };
 
     // Initialize terminal for TUI mode
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    execute!(stdout, EnterAlternateScreen)?;
+    crash::install(restore_terminal);
+
+    let mut capabilities = Capabilities::from_env(Platform::current(), platform::env_var);
+    if capabilities.unicode {
+        // A terminal that doesn't answer keeps what the environment suggested
+        let _ = capabilities.probe_ambiguous_width(&mut stdout);
+    }
+    if probes_background(&capabilities) {
+        let _ = capabilities.probe_background(&mut stdout);
+    }
+    if capabilities.mouse {
+        execute!(stdout, EnableMouseCapture)?;
+    }
+    if mini_rows.is_some() {
+        // A stable window title lets window managers pin the mini view on top
+        execute!(stdout, SetTitle("alltz mini"))?;
+    }
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     let mut app = create_app_with_options(cli)?;
-    let result = run_app(&mut terminal, &mut app);
+    app.mini_rows = mini_rows;
+    if let Some(preload) = preload {
+        app.preload(preload);
+    }
+    app.apply_capabilities(&capabilities);
+    app.check_clock();
+    app.check_for_update();
+    app.sync_board();
+    crash::remember(&app);
+    let result = run_app(&mut terminal, &mut app, started);
+    let _ = std::panic::take_hook();
 
     // Cleanup: restore terminal to original state
     disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    if capabilities.mouse {
+        execute!(terminal.backend_mut(), DisableMouseCapture)?;
+    }
     terminal.show_cursor()?;
 
     if let Err(err) = result {
@@ -122,158 +498,619 @@ fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Puts the terminal back the way it was found after a panic in the board,
+/// ignoring failures since there's nothing left to do about them
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        crossterm::cursor::Show
+    );
+}
+
+/// Whether to ask the terminal for its background color. Windows consoles
+/// don't hand replies to stdin, and without color the answer doesn't matter.
+fn probes_background(capabilities: &Capabilities) -> bool {
+    Platform::current() == Platform::Unix && capabilities.color != ColorSupport::None
+}
+
+/// Prints what alltz detects about the terminal and the rendering it will use
+fn diagnose() -> Result<(), Box<dyn Error>> {
+    use std::io::IsTerminal;
+
+    let mut capabilities = Capabilities::from_env(Platform::current(), platform::env_var);
+    let mut stdout = io::stdout();
+    if stdout.is_terminal() {
+        enable_raw_mode()?;
+        let probe = capabilities.probe_ambiguous_width(&mut stdout);
+        if probes_background(&capabilities) {
+            let _ = capabilities.probe_background(&mut stdout);
+        }
+        disable_raw_mode()?;
+        probe.ok();
+    }
+
+    let yes_no = |value: bool| {
+        if value {
+            t!("cli.diagnose.yes")
+        } else {
+            t!("cli.diagnose.no")
+        }
+    };
+    let color = match capabilities.color {
+        ColorSupport::TrueColor => "truecolor",
+        ColorSupport::Ansi256 => "256 colors",
+        ColorSupport::Ansi16 => "16 colors",
+        ColorSupport::None => "none",
+    };
+    let width = match capabilities.ambiguous_wide {
+        Some(true) => t!("cli.diagnose.width_double"),
+        Some(false) => t!("cli.diagnose.width_single"),
+        None => t!("cli.diagnose.width_unknown"),
+    };
+    let glyph_mode = config::AppConfig::load().glyphs;
+    let glyphs = match ui::Glyphs::for_mode(glyph_mode, capabilities.unicode_glyphs()) {
+        glyphs if glyphs == ui::Glyphs::ASCII => "ASCII",
+        _ => "Unicode",
+    };
+    let term = capabilities.term.clone().unwrap_or_default();
+    let (columns, rows) = crossterm::terminal::size().unwrap_or_default();
+
+    println!("{}", t!("cli.diagnose.header"));
+    println!("{}", t!("cli.diagnose.term", term = term));
+    println!(
+        "{}",
+        t!("cli.diagnose.size", columns = columns, rows = rows)
+    );
+    println!("{}", t!("cli.diagnose.color", color = color));
+    println!(
+        "{}",
+        t!("cli.diagnose.unicode", value = yes_no(capabilities.unicode))
+    );
+    println!("{}", t!("cli.diagnose.ambiguous_width", width = width));
+    println!(
+        "{}",
+        t!("cli.diagnose.mouse", value = yes_no(capabilities.mouse))
+    );
+    let graphics = match capabilities.graphics {
+        Some(GraphicsProtocol::Kitty) => "kitty",
+        Some(GraphicsProtocol::Sixel) => "sixel",
+        None => "none",
+    };
+    println!("{}", t!("cli.diagnose.graphics", graphics = graphics));
+    let background = match capabilities.background {
+        Some(terminal::Background::Light) => "light",
+        Some(terminal::Background::Dark) => "dark",
+        None => "unknown",
+    };
+    println!(
+        "{}",
+        t!(
+            "cli.diagnose.background",
+            background = background,
+            mode = format!("{:?}", config::AppConfig::load().background)
+        )
+    );
+    println!(
+        "{}",
+        t!(
+            "cli.diagnose.glyphs",
+            glyphs = glyphs,
+            mode = format!("{glyph_mode:?}")
+        )
+    );
+    Ok(())
+}
+
+/// Asks what time it is in one tracked zone at a time in another, reading
+/// answers from stdin until the rounds run out or an empty answer
+fn run_quiz(rounds: Option<usize>) -> Result<(), Box<dyn Error>> {
+    use chrono::Utc;
+    use std::io::BufRead;
+
+    let config = config::AppConfig::load();
+    let twelve_hour = matches!(config.display_format, app::TimeFormat::TwelveHour);
+    let app = App::from_config(config);
+    let zones = app.timezone_manager.zones();
+    let path = quiz::streak_path();
+    let mut streak = path.as_deref().map(quiz::load_streak).unwrap_or_default();
+    let mut rng = quiz::Rng::from_entropy();
+    let mut lines = io::stdin().lock().lines();
+
+    println!("{}", t!("cli.quiz.intro"));
+    for _ in 0..rounds.unwrap_or(usize::MAX) {
+        let Some(question) = quiz::question(zones, Utc::now(), &mut rng) else {
+            println!("{}", t!("cli.quiz.not_enough_zones"));
+            break;
+        };
+        println!();
+        println!(
+            "{}",
+            t!(
+                "cli.quiz.question",
+                to = question.to.effective_display_name(),
+                time = quiz::format_time(question.asked(), twelve_hour),
+                from = question.from.effective_display_name()
+            )
+        );
+
+        // Ask again until the answer reads as a time
+        let guess = loop {
+            let Some(line) = lines.next().transpose()? else {
+                break None;
+            };
+            if line.trim().is_empty() {
+                break None;
+            }
+            match quiz::parse_answer(&line) {
+                Some(guess) => break Some(guess),
+                None => println!("{}", t!("cli.quiz.unreadable")),
+            }
+        };
+        let Some(guess) = guess else {
+            break;
+        };
+
+        let correct = question.is_correct(guess);
+        let new_best = streak.record(correct);
+        if correct {
+            println!("{}", t!("cli.quiz.correct", streak = streak.current));
+            if new_best {
+                println!("{}", t!("cli.quiz.new_best"));
+            }
+        } else {
+            let day = match question.day_shift() {
+                0 => String::new(),
+                1.. => t!("cli.quiz.next_day").to_string(),
+                _ => t!("cli.quiz.previous_day").to_string(),
+            };
+            println!(
+                "{}",
+                t!(
+                    "cli.quiz.wrong",
+                    answer = quiz::format_time(question.answer(), twelve_hour),
+                    to = question.to.effective_display_name(),
+                    day = day
+                )
+            );
+        }
+    }
+
+    println!();
+    println!("{}", t!("cli.quiz.best", best = streak.best));
+    if let Some(path) = path {
+        quiz::save_streak(&path, &streak)?;
+    }
+    Ok(())
+}
+
 /// Main event loop for the TUI application
 /// Handles user input, renders the UI, and processes timed updates
 fn run_app<B: ratatui::backend::Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    started: Instant,
 ) -> io::Result<()> {
     let mut last_tick = Instant::now();
+    let mut last_clock = None;
+    let mut starting = true;
 
     loop {
+        app.poll_sync();
+        app.share_cursor();
+        app.poll_update();
+        app.poll_clock();
+        let frame_start = Instant::now();
         terminal.draw(|f| app.view(f))?;
+        app.record_frame_time(frame_start.elapsed());
+        if draw_clock(app, &mut last_clock)? {
+            // Sixel pixels stay until their cells are redrawn
+            terminal.clear()?;
+            terminal.draw(|f| app.view(f))?;
+            draw_clock(app, &mut last_clock)?;
+        }
+        if std::mem::take(&mut starting) {
+            let elapsed = started.elapsed();
+            if elapsed > STARTUP_BUDGET {
+                alltz::log::write(&format!(
+                    "First frame took {}ms, over the {}ms budget",
+                    elapsed.as_millis(),
+                    STARTUP_BUDGET.as_millis()
+                ));
+            }
+            // Calendars and task lists are read once the board is up, since
+            // they mean reading files and running programs
+            app.read_calendars();
+            app.load_tasks();
+            continue;
+        }
 
-        // Calculate timeout to maintain consistent TICK_RATE
-        let timeout = TICK_RATE
-            .checked_sub(last_tick.elapsed())
-            .unwrap_or_else(|| Duration::from_secs(0));
+        // Calculate timeout to maintain consistent TICK_RATE, or redraw
+        // quickly while an animated jump is in progress
+        let timeout = if app.is_animating() {
+            ANIMATION_FRAME_RATE
+        } else {
+            TICK_RATE
+                .checked_sub(last_tick.elapsed())
+                .unwrap_or_else(|| Duration::from_secs(0))
+        };
 
         if crossterm::event::poll(timeout)? {
             if let Event::Key(key) = event::read()? {
                 if key.kind == KeyEventKind::Press {
-                    let message = if app.show_help {
-                        Some(Message::ToggleHelp)
-                    } else if app.renaming_zone {
-                        // Special input handling for rename zone modal
-                        match key.code {
-                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                Some(Message::Quit)
-                            }
-                            KeyCode::Char(c) => {
-                                let mut input = app.rename_zone_input.clone();
-                                input.push(c);
-                                Some(Message::UpdateRenameInput(input))
-                            }
-                            KeyCode::Backspace => {
-                                let mut input = app.rename_zone_input.clone();
-                                input.pop();
-                                Some(Message::UpdateRenameInput(input))
-                            }
-                            KeyCode::Enter => Some(Message::ConfirmRename),
-                            KeyCode::Esc => Some(Message::CancelRename),
-                            _ => None,
-                        }
-                    } else if app.adding_zone {
-                        // Special input handling for add zone modal
-                        match key.code {
-                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                Some(Message::Quit)
-                            }
-                            KeyCode::Char(c) => {
-                                // Handle numeric selection of search results (1-9)
-                                if c.is_ascii_digit() && !app.zone_search_results.is_empty() {
-                                    let digit = c.to_digit(10).unwrap() as usize;
-                                    if digit >= 1 && digit <= app.zone_search_results.len() {
-                                        Some(Message::SelectSearchResult(digit - 1))
-                                    } else {
-                                        let mut input = app.add_zone_input.clone();
-                                        input.push(c);
-                                        Some(Message::UpdateAddZoneInput(input))
+                    if let Some(register_action) = app.pending_macro_register.take() {
+                        // The key after Q/@ names the macro register
+                        if let KeyCode::Char(register) = key.code {
+                            if register.is_ascii_alphanumeric() {
+                                match register_action {
+                                    MacroAction::Record => {
+                                        app.update(Message::StartMacroRecording(register));
                                     }
-                                } else {
-                                    let mut input = app.add_zone_input.clone();
-                                    input.push(c);
-                                    Some(Message::UpdateAddZoneInput(input))
+                                    MacroAction::Replay => replay_macro(app, register),
                                 }
                             }
-                            KeyCode::Backspace => {
-                                let mut input = app.add_zone_input.clone();
-                                input.pop();
-                                Some(Message::UpdateAddZoneInput(input))
-                            }
-                            KeyCode::Up => Some(Message::NavigateSearchResults(Direction::Up)),
-                            KeyCode::Down => Some(Message::NavigateSearchResults(Direction::Down)),
-                            KeyCode::Enter => Some(Message::ConfirmAddZone),
-                            KeyCode::Esc => Some(Message::CancelAddZone),
-                            _ => None,
                         }
+                    } else if is_normal_mode(app) && key.code == KeyCode::Char('Q') {
+                        if app.recording_macro.is_some() {
+                            app.update(Message::StopMacroRecording);
+                        } else {
+                            app.update(Message::AwaitMacroRegister(MacroAction::Record));
+                        }
+                    } else if is_normal_mode(app) && key.code == KeyCode::Char('@') {
+                        app.update(Message::AwaitMacroRegister(MacroAction::Replay));
                     } else {
-                        match key.code {
-                            KeyCode::Char('q') => Some(Message::Quit),
-                            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                                Some(Message::Quit)
-                            }
-                            KeyCode::Char('?') => Some(Message::ToggleHelp),
-                            KeyCode::Char('a') => Some(Message::StartAddZone),
-                            KeyCode::Char('r') => Some(Message::RemoveCurrentZone),
-                            KeyCode::Char('e') => Some(Message::StartRenameZone),
-                            KeyCode::Char('E') => Some(Message::ClearCustomName),
-                            KeyCode::Char('m') => Some(Message::ToggleTimeFormat),
-                            KeyCode::Char('n') => Some(Message::ToggleTimezoneDisplayMode),
-                            KeyCode::Char('d') => Some(Message::ToggleDate),
-                            KeyCode::Char('s') => Some(Message::ToggleSunTimes),
-                            KeyCode::Char('c') => Some(Message::CycleColorTheme),
-                            KeyCode::Char('t') => Some(Message::ResetToNow),
-                            KeyCode::Char('h') | KeyCode::Left => {
-                                if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                    Some(Message::ScrubTimelineWithShift(Direction::Left))
-                                } else {
-                                    Some(Message::ScrubTimeline(Direction::Left))
-                                }
-                            }
-                            KeyCode::Char('l') | KeyCode::Right => {
-                                if key.modifiers.contains(KeyModifiers::SHIFT) {
-                                    Some(Message::ScrubTimelineWithShift(Direction::Right))
-                                } else {
-                                    Some(Message::ScrubTimeline(Direction::Right))
-                                }
-                            }
-                            // Handle uppercase H and L (some terminals send these with Shift)
-                            KeyCode::Char('H') => {
-                                Some(Message::ScrubTimelineWithShift(Direction::Left))
-                            }
-                            KeyCode::Char('L') => {
-                                Some(Message::ScrubTimelineWithShift(Direction::Right))
-                            }
-                            KeyCode::Char('j') | KeyCode::Down => {
-                                Some(Message::NavigateZone(Direction::Down))
-                            }
-                            KeyCode::Char('k') | KeyCode::Up => {
-                                Some(Message::NavigateZone(Direction::Up))
-                            }
-                            KeyCode::Char('[') => Some(Message::FineAdjust(-15)),
-                            KeyCode::Char(']') => Some(Message::FineAdjust(15)),
-                            KeyCode::Char('{') => Some(Message::FineAdjust(-60)),
-                            KeyCode::Char('}') => Some(Message::FineAdjust(60)),
-                            _ => None,
+                        if let Some(encoded) = encode_key(&key) {
+                            app.record_macro_key(encoded);
                         }
-                    };
-
-                    if let Some(msg) = message {
-                        app.update(msg);
-                        if app.should_quit {
-                            return Ok(());
+                        if let Some(msg) = key_to_message(app, key) {
+                            crash::record(&msg);
+                            app.update(msg);
+                            crash::remember(app);
                         }
                     }
+
+                    if let Some(text) = app.clipboard.take() {
+                        execute!(
+                            io::stdout(),
+                            crossterm::style::Print(terminal::clipboard_sequence(&text))
+                        )?;
+                    }
+                    if app.should_quit {
+                        return Ok(());
+                    }
                 }
             }
         }
 
+        if app.is_animating() {
+            app.update(Message::AnimationFrame);
+        }
+
         // Send periodic tick for time updates and animations
         if last_tick.elapsed() >= TICK_RATE {
             app.update(Message::Tick);
             last_tick = Instant::now();
         }
+
+        if std::mem::take(&mut app.bell) {
+            execute!(io::stdout(), crossterm::style::Print('\x07'))?;
+        }
+    }
+}
+
+/// Draws the graphical clock into the area the last frame left for it, sending
+/// a new image only when the area or time changes. Returns whether the screen
+/// needs a full redraw to remove an old sixel image.
+#[cfg(feature = "clock-graphics")]
+fn draw_clock(app: &App, last: &mut Option<(ratatui::layout::Rect, u32, u32)>) -> io::Result<bool> {
+    use crossterm::{cursor::MoveTo, style::Print};
+
+    let Some(protocol) = app.graphics else {
+        return Ok(false);
+    };
+    let current = app.clock_image();
+    if current == *last {
+        return Ok(false);
+    }
+    let previous = std::mem::replace(last, current);
+
+    // A sixel image left behind in a moved or hidden clock area needs a redraw
+    let moved = previous.map(|(area, ..)| area) != current.map(|(area, ..)| area);
+    let stale_sixel = protocol == GraphicsProtocol::Sixel && previous.is_some() && moved;
+
+    let mut stdout = io::stdout();
+    match current {
+        Some((area, hour, minute)) if !stale_sixel => {
+            let image = ClockImage::new(hour, minute, clock::image_size(area.width, area.height));
+            let encoded = image.encode(protocol, area.width, area.height);
+            execute!(stdout, MoveTo(area.x, area.y), Print(encoded))?;
+        }
+        None if protocol == GraphicsProtocol::Kitty => {
+            execute!(stdout, Print(clock::kitty_delete()))?;
+        }
+        _ => {}
+    }
+    if stale_sixel {
+        // Draw the clock afresh after the redraw
+        *last = None;
+    }
+    Ok(stale_sixel)
+}
+
+#[cfg(not(feature = "clock-graphics"))]
+fn draw_clock(
+    _app: &App,
+    _last: &mut Option<(ratatui::layout::Rect, u32, u32)>,
+) -> io::Result<bool> {
+    Ok(false)
+}
+
+/// Whether the board itself has focus (no modal or text input open)
+fn is_normal_mode(app: &App) -> bool {
+    !app.show_help
+        && !app.show_qr
+        && !app.show_matrix
+        && !app.renaming_zone
+        && !app.adding_zone
+        && app.goto_time.is_none()
+        && app.group_input.is_none()
+        && app.share_menu.is_none()
+        && app.archive_picker.is_none()
+        && app.milestone_plan.is_none()
+        && app.hours_editor.is_none()
+}
+
+/// Maps a key press to the message it triggers in the app's current mode
+fn key_to_message(app: &App, key: KeyEvent) -> Option<Message> {
+    if app.show_help {
+        Some(Message::ToggleHelp)
+    } else if app.show_qr {
+        Some(Message::ToggleQrCode)
+    } else if app.show_matrix {
+        Some(Message::ToggleOffsetMatrix)
+    } else if app.renaming_zone {
+        // Special input handling for rename zone modal
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char(c) => {
+                let mut input = app.rename_zone_input.clone();
+                input.push(c);
+                Some(Message::UpdateRenameInput(input))
+            }
+            KeyCode::Backspace => {
+                let mut input = app.rename_zone_input.clone();
+                input.pop();
+                Some(Message::UpdateRenameInput(input))
+            }
+            KeyCode::Enter => Some(Message::ConfirmRename),
+            KeyCode::Esc => Some(Message::CancelRename),
+            _ => None,
+        }
+    } else if let Some(cursor) = app.share_menu {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char('k') | KeyCode::Up => Some(Message::NavigateShareMenu(Direction::Up)),
+            KeyCode::Char('j') | KeyCode::Down => Some(Message::NavigateShareMenu(Direction::Down)),
+            KeyCode::Char(c @ '1'..='9') => Some(Message::SelectShareOption(
+                c.to_digit(10).unwrap() as usize - 1,
+            )),
+            KeyCode::Enter => Some(Message::SelectShareOption(cursor)),
+            KeyCode::Esc => Some(Message::CancelShareMenu),
+            _ => None,
+        }
+    } else if let Some(cursor) = app.archive_picker {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char('k') | KeyCode::Up => Some(Message::NavigateArchive(Direction::Up)),
+            KeyCode::Char('j') | KeyCode::Down => Some(Message::NavigateArchive(Direction::Down)),
+            KeyCode::Char(c @ '1'..='9') => Some(Message::RestoreArchived(
+                c.to_digit(10).unwrap() as usize - 1,
+            )),
+            KeyCode::Enter => Some(Message::RestoreArchived(cursor)),
+            KeyCode::Esc => Some(Message::CancelArchive),
+            _ => None,
+        }
+    } else if let Some(input) = &app.goto_time {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char(c) => Some(Message::UpdateGotoInput(format!("{input}{c}"))),
+            KeyCode::Backspace => {
+                let mut input = input.clone();
+                input.pop();
+                Some(Message::UpdateGotoInput(input))
+            }
+            KeyCode::Enter => Some(Message::ConfirmGotoTime),
+            KeyCode::Esc => Some(Message::CancelGotoTime),
+            _ => None,
+        }
+    } else if let Some(input) = &app.group_input {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char(c) => Some(Message::UpdateGroupInput(format!("{input}{c}"))),
+            KeyCode::Backspace => {
+                let mut input = input.clone();
+                input.pop();
+                Some(Message::UpdateGroupInput(input))
+            }
+            KeyCode::Enter => Some(Message::ConfirmAssignGroup),
+            KeyCode::Esc => Some(Message::CancelAssignGroup),
+            _ => None,
+        }
+    } else if app.adding_zone {
+        // Special input handling for add zone modal
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char(c) => {
+                // Handle numeric selection of search results (1-9)
+                if c.is_ascii_digit() && !app.zone_search_results.is_empty() {
+                    let digit = c.to_digit(10).unwrap() as usize;
+                    if digit >= 1 && digit <= app.zone_search_results.len() {
+                        Some(Message::SelectSearchResult(digit - 1))
+                    } else {
+                        let mut input = app.add_zone_input.clone();
+                        input.push(c);
+                        Some(Message::UpdateAddZoneInput(input))
+                    }
+                } else {
+                    let mut input = app.add_zone_input.clone();
+                    input.push(c);
+                    Some(Message::UpdateAddZoneInput(input))
+                }
+            }
+            KeyCode::Backspace => {
+                let mut input = app.add_zone_input.clone();
+                input.pop();
+                Some(Message::UpdateAddZoneInput(input))
+            }
+            KeyCode::Up => Some(Message::NavigateSearchResults(Direction::Up)),
+            KeyCode::Down => Some(Message::NavigateSearchResults(Direction::Down)),
+            KeyCode::Enter => Some(Message::ConfirmAddZone),
+            KeyCode::Esc => Some(Message::CancelAddZone),
+            _ => None,
+        }
+    } else if app.hours_editor.is_some() {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char('k') | KeyCode::Up => Some(Message::NavigateHoursEditor(Direction::Up)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                Some(Message::NavigateHoursEditor(Direction::Down))
+            }
+            KeyCode::Char('h') | KeyCode::Left => {
+                Some(Message::NavigateHoursEditor(Direction::Left))
+            }
+            KeyCode::Char('l') | KeyCode::Right => {
+                Some(Message::NavigateHoursEditor(Direction::Right))
+            }
+            KeyCode::Enter => Some(Message::ConfirmHoursEditor),
+            KeyCode::Esc => Some(Message::CancelHoursEditor),
+            _ => None,
+        }
+    } else if app.milestone_plan.is_some() {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char('k') | KeyCode::Up => Some(Message::NavigateMilestonePlan(Direction::Up)),
+            KeyCode::Char('j') | KeyCode::Down => {
+                Some(Message::NavigateMilestonePlan(Direction::Down))
+            }
+            KeyCode::Char(' ') => Some(Message::ToggleMilestoneSelection),
+            KeyCode::Char('g') => Some(Message::SelectMilestoneGroup),
+            KeyCode::Char('(') => Some(Message::AdjustMilestonePlan(-24 * 60)),
+            KeyCode::Char(')') => Some(Message::AdjustMilestonePlan(24 * 60)),
+            KeyCode::Char('{') => Some(Message::AdjustMilestonePlan(-60)),
+            KeyCode::Char('}') => Some(Message::AdjustMilestonePlan(60)),
+            KeyCode::Enter => Some(Message::ConfirmMilestonePlan),
+            KeyCode::Esc => Some(Message::CancelMilestonePlan),
+            _ => None,
+        }
+    } else {
+        match key.code {
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Quit)
+            }
+            KeyCode::Char('o') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::JumpBack)
+            }
+            // Most terminals send Ctrl-i as Tab, which goes forward unless
+            // it's needed to switch panes
+            KeyCode::Char('i') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::JumpForward)
+            }
+            KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                Some(Message::Redo)
+            }
+            // Everything else goes through the keymap, which the config can change
+            _ => {
+                let encoded = encode_key(&key)?;
+                match app.keymap.get(&encoded)? {
+                    // Tab is also Ctrl-i, so it goes forward when there are
+                    // no panes to switch
+                    Command::SwitchPane if !app.split_view && encoded == "Tab" => {
+                        Some(Message::JumpForward)
+                    }
+                    command => Some(command.message()),
+                }
+            }
+        }
+    }
+}
+
+/// Feeds a recorded macro's keys back through the normal key handling
+fn replay_macro(app: &mut App, register: char) {
+    let Some(keys) = app.macros.get(&register.to_string()).cloned() else {
+        return;
+    };
+    for key in keys.iter().filter_map(|encoded| decode_key(encoded)) {
+        if let Some(msg) = key_to_message(app, key) {
+            crash::record(&msg);
+            app.update(msg);
+        }
+        if app.should_quit {
+            break;
+        }
     }
 }
 
 /// Handle CLI subcommands (list, time, zone) and exit without starting TUI
-fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
+/// Runs a subcommand, returning what it was about for `--open` to show
+fn handle_command(command: OneShot) -> Result<Preload, Box<dyn Error>> {
     use chrono::{Local, Offset, Utc};
     use time::TimeZoneManager;
 
+    let mut preload = Preload::default();
     match command {
-        Commands::List => {
+        // Every city is always listed, so there's nothing to report
+        OneShot::List { .. } if QUIET.load(Ordering::Relaxed) => {}
+
+        OneShot::List {
+            format: schema::OutputFormat::Json,
+        } => {
+            let timezones = TimeZoneManager::get_all_available_timezones()
+                .into_iter()
+                .map(|(tz, city, code, latitude, longitude)| schema::ListedZone {
+                    city,
+                    code,
+                    timezone: tz.name().to_string(),
+                    latitude,
+                    longitude,
+                })
+                .collect();
+            let output = schema::ListOutput {
+                version: schema::SCHEMA_VERSION,
+                timezones,
+            };
+            // Ignore broken pipe errors, as for text
+            let mut handle = io::stdout().lock();
+            if let Err(e) = serde_json::to_writer(&mut handle, &output)
+                .map_err(io::Error::from)
+                .and_then(|()| std::io::Write::write_all(&mut handle, b"\n"))
+            {
+                if e.kind() != io::ErrorKind::BrokenPipe {
+                    return Err(e.into());
+                }
+            }
+        }
+
+        OneShot::List {
+            format: schema::OutputFormat::Text,
+        } => {
             use std::io::{self, Write};
 
             let stdout = io::stdout();
@@ -300,61 +1137,102 @@ fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
             }
         }
 
-        Commands::Time { city } => {
+        OneShot::Time { city } => {
             let timezones = TimeZoneManager::get_all_available_timezones();
-            if let Some((tz, city_name, _, _, _)) = timezones
-                .iter()
-                .find(|(_, name, _, _, _)| name.eq_ignore_ascii_case(&city))
-            {
+            if let Some((tz, city_name, _, _, _)) = lookup_city(&timezones, &city) {
                 let now = Utc::now();
                 let local_time = now.with_timezone(tz);
                 let local_system = now.with_timezone(&Local);
 
-                println!("{}", t!("cli.time.header", city_name = city_name));
-                println!("   {}", local_time.format("%H:%M:%S %Z (%a, %b %d)"));
-                println!();
-                println!("{}", t!("cli.time.local_header"));
-                println!("   {}", local_system.format("%H:%M:%S %Z (%a, %b %d)"));
+                outln!("{}", t!("cli.time.header", city_name = city_name));
+                outln!("   {}", local_time.format("%H:%M:%S %Z (%a, %b %d)"));
+                outln!();
+                outln!("{}", t!("cli.time.local_header"));
+                outln!("   {}", local_system.format("%H:%M:%S %Z (%a, %b %d)"));
+                preload.zones.push(city_name.clone());
             } else {
-                eprintln!("{}", t!("cli.time.not_found", city = city));
-                std::process::exit(1);
+                errln!("{}", t!("cli.time.not_found", city = city));
+                ExitStatus::UnknownZone.exit();
+            }
+        }
+
+        OneShot::Matrix { at } => {
+            let app = App::from_config(config::AppConfig::load());
+            preload.at = at;
+            preload.matrix = true;
+            let at = at.unwrap_or_else(Utc::now);
+            out!("{}", matrix::render(app.timezone_manager.zones(), at));
+        }
+
+        OneShot::Card { at, template } => {
+            let mut app = App::from_config(config::AppConfig::load());
+            let template = match template {
+                Some(name) => {
+                    let found = app
+                        .card_templates
+                        .iter()
+                        .find(|template| template.name.eq_ignore_ascii_case(&name));
+                    let Some(found) = found else {
+                        let names: Vec<&str> = app
+                            .card_templates
+                            .iter()
+                            .map(|template| template.name.as_str())
+                            .collect();
+                        errln!(
+                            "{}",
+                            t!(
+                                "cli.card.unknown_template",
+                                name = name,
+                                templates = names.join(", ")
+                            )
+                        );
+                        ExitStatus::NotFound.exit();
+                    };
+                    Some(found.clone())
+                }
+                None => None,
+            };
+            if let Some(at) = at {
+                app.timeline_position = at;
             }
+            preload.at = at;
+            out!("{}", app.time_card(template.as_ref()));
         }
 
-        Commands::Zone { city } => {
+        OneShot::Zone { city } => {
             let timezones = TimeZoneManager::get_all_available_timezones();
-            if let Some((tz, city_name, code, lat, lon)) = timezones
-                .iter()
-                .find(|(_, name, _, _, _)| name.eq_ignore_ascii_case(&city))
-            {
+            if let Some((tz, city_name, code, lat, lon)) = lookup_city(&timezones, &city) {
                 let now = Utc::now();
                 let local_time = now.with_timezone(tz);
-                let offset_seconds = local_time.offset().fix().local_minus_utc();
-                let offset_hours = offset_seconds / 3600;
+                let offset = time::utc_offset_label(
+                    local_time.offset().fix().local_minus_utc() / 60,
+                    config::AppConfig::load().offset_minutes,
+                );
 
-                println!("{}", t!("cli.zone.header", city_name = city_name));
-                println!("{}", t!("cli.zone.code", code = code));
-                println!("{}", t!("cli.zone.timezone", tz = tz));
-                println!("{}", t!("cli.zone.utc_offset", offset_hours = offset_hours));
+                outln!("{}", t!("cli.zone.header", city_name = city_name));
+                outln!("{}", t!("cli.zone.code", code = code));
+                outln!("{}", t!("cli.zone.timezone", tz = tz));
+                outln!("{}", t!("cli.zone.utc_offset", offset = offset));
+                preload.zones.push(city_name.clone());
                 if *lat >= 0.0 && *lon <= 0.0 {
-                    println!(
+                    outln!(
                         "{}",
                         t!("cli.zone.coordinates_n_w", lat = lat, lon = lon.abs())
                     );
                 } else if *lat >= 0.0 && *lon > 0.0 {
-                    println!("{}", t!("cli.zone.coordinates_n_e", lat = lat, lon = lon));
+                    outln!("{}", t!("cli.zone.coordinates_n_e", lat = lat, lon = lon));
                 } else if *lat < 0.0 && *lon <= 0.0 {
-                    println!(
+                    outln!(
                         "{}",
                         t!("cli.zone.coordinates_s_w", lat = lat.abs(), lon = lon.abs())
                     );
                 } else {
-                    println!(
+                    outln!(
                         "{}",
                         t!("cli.zone.coordinates_s_e", lat = lat.abs(), lon = lon)
                     );
                 }
-                println!(
+                outln!(
                     "{}",
                     t!(
                         "cli.zone.current_time",
@@ -363,20 +1241,664 @@ fn handle_command(command: Commands) -> Result<(), Box<dyn Error>> {
                 );
 
                 // Simple DST status (just show current offset)
-                println!("{}", t!("cli.zone.dst_status", offset_hours = offset_hours));
+                outln!("{}", t!("cli.zone.dst_status", offset = offset));
+
+                let fiscal_year_start = config::AppConfig::load()
+                    .fiscal_year_start
+                    .filter(|month| (1..=12).contains(month));
+                if let Some(start_month) = fiscal_year_start {
+                    let quarter = time::FiscalQuarter::of(local_time.date_naive(), start_month);
+                    outln!("{}", t!("cli.zone.fiscal_quarter", quarter = quarter));
+                }
+            } else {
+                errln!("{}", t!("cli.zone.not_found", city = city));
+                ExitStatus::UnknownZone.exit();
+            }
+        }
+
+        OneShot::Quiz { rounds } => run_quiz(rounds)?,
+
+        OneShot::Template { template, date } => {
+            let date =
+                date.unwrap_or_else(|| (Utc::now() + chrono::Duration::days(14)).date_naive());
+            let mut config = config::AppConfig::load();
+            template.apply(&mut config, date);
+            config.save()?;
+            outln!(
+                "{}",
+                t!(
+                    "cli.template.added",
+                    layout = template.layout_name(),
+                    date = date
+                )
+            );
+            outln!("{}", t!("cli.template.hint"));
+        }
+
+        OneShot::Ics {
+            output,
+            serve,
+            group,
+        } => {
+            if let Some(addr) = serve {
+                outln!("{}", t!("cli.ics.serving", addr = addr));
+                ics::serve(addr.as_str(), group.as_deref())?;
+            } else {
+                let milestones = ics::milestones(&config::AppConfig::load(), group.as_deref());
+                let feed = ics::calendar(&milestones, Utc::now());
+                match output {
+                    Some(path) => {
+                        std::fs::write(&path, feed)?;
+                        outln!(
+                            "{}",
+                            t!(
+                                "cli.ics.written",
+                                count = milestones.len(),
+                                path = path.display()
+                            )
+                        );
+                    }
+                    None => out!("{feed}"),
+                }
+            }
+        }
+
+        OneShot::Report { from, format } => {
+            let mut app = App::from_config(config::AppConfig::load());
+            app.read_calendars();
+            let zones = app.timezone_manager.zones();
+            let from = from.unwrap_or_else(|| Local::now().date_naive());
+            let to = from + chrono::Duration::days(report::DAYS as i64 - 1);
+            // The zone standing for you, whose days away count for everyone
+            let you = app
+                .home
+                .as_deref()
+                .and_then(|name| zones.iter().find(|zone| zone.answers_to(name)));
+            let overlaps = report::week(&Local, zones, &app.time_config, &app.away, you, from);
+            match format {
+                schema::OutputFormat::Json => {
+                    let output = schema::ReportOutput {
+                        version: schema::SCHEMA_VERSION,
+                        from: from.to_string(),
+                        to: to.to_string(),
+                        zones: overlaps
+                            .iter()
+                            .map(|overlap| {
+                                let zone = &zones[overlap.zone];
+                                schema::ReportedZone {
+                                    name: zone.effective_display_name().to_string(),
+                                    timezone: overlap.tz.name().to_string(),
+                                    overlap_minutes: overlap.total().num_minutes(),
+                                    daily_minutes: overlap
+                                        .days
+                                        .iter()
+                                        .map(|day| day.num_minutes())
+                                        .collect(),
+                                    away: overlap.away.iter().map(|day| day.to_string()).collect(),
+                                    clock_change: overlap.clock_change.map(|(date, minutes)| {
+                                        schema::ReportedClockChange {
+                                            date: date.to_string(),
+                                            minutes,
+                                        }
+                                    }),
+                                }
+                            })
+                            .collect(),
+                    };
+                    outln!("{}", serde_json::to_string(&output)?);
+                }
+                schema::OutputFormat::Text => {
+                    outln!(
+                        "{}",
+                        t!(
+                            "cli.report.header",
+                            from = from.format("%a %-d %b"),
+                            to = to.format("%a %-d %b")
+                        )
+                    );
+                    let width = zones
+                        .iter()
+                        .map(|zone| zone.effective_display_name().chars().count())
+                        .max()
+                        .unwrap_or(0);
+                    for overlap in &overlaps {
+                        let mut notes = Vec::new();
+                        if !overlap.away.is_empty() {
+                            let days: Vec<String> = overlap
+                                .away
+                                .iter()
+                                .map(|day| day.format("%a %-d").to_string())
+                                .collect();
+                            notes.push(t!("cli.report.away", days = days.join(", ")).to_string());
+                        }
+                        if let Some((date, minutes)) = overlap.clock_change {
+                            let sign = if minutes < 0 { '−' } else { '+' };
+                            let shift =
+                                report::format_hours(chrono::Duration::minutes(minutes as i64));
+                            notes.push(
+                                t!(
+                                    "cli.report.clock_change",
+                                    shift = format!("{sign}{shift}"),
+                                    date = date.format("%a %-d")
+                                )
+                                .to_string(),
+                            );
+                        }
+                        let notes = match notes.is_empty() {
+                            true => String::new(),
+                            false => format!(" ({})", notes.join("; ")),
+                        };
+                        outln!(
+                            "{}",
+                            t!(
+                                "cli.report.line",
+                                name = format!(
+                                    "{:<width$}",
+                                    zones[overlap.zone].effective_display_name()
+                                ),
+                                total = format!("{:>7}", report::format_hours(overlap.total())),
+                                days = overlap.shared_days(),
+                                notes = notes
+                            )
+                        );
+                    }
+                }
+            }
+        }
+
+        OneShot::Grid { from, output } => {
+            let mut app = App::from_config(config::AppConfig::load());
+            app.read_calendars();
+            let from = from.unwrap_or_else(|| Local::now().date_naive());
+            let grid = grid::WeekGrid::new(
+                &Local,
+                app.timezone_manager.zones(),
+                &app.time_config,
+                &app.away,
+                from,
+            );
+            match output {
+                Some(path) => {
+                    let svg = path
+                        .extension()
+                        .is_some_and(|extension| extension.eq_ignore_ascii_case("svg"));
+                    std::fs::write(&path, if svg { grid.svg() } else { grid.html() })?;
+                    outln!(
+                        "{}",
+                        t!(
+                            "cli.grid.written",
+                            from = from.format("%a %-d %b"),
+                            path = path.display()
+                        )
+                    );
+                }
+                None => out!("{}", grid.html()),
+            }
+        }
+
+        OneShot::Pipe { to, from, replace } => {
+            use std::io::{BufRead, Write};
+
+            let app = App::from_config(config::AppConfig::load());
+            let zones = app.timezone_manager.zones();
+            let resolve = |name: &str| {
+                time::resolve_zone(name, zones)
+                    .or_else(|| abbrev::resolve(name, &app.abbreviations, Utc::now()))
+                    .unwrap_or_else(|| {
+                        errln!("{}", t!("cli.pipe.unknown_zone", name = name));
+                        ExitStatus::UnknownZone.exit();
+                    })
+            };
+            let options = pipe::PipeOptions {
+                to: resolve(&to),
+                from: from.as_deref().map(resolve),
+                replace,
+            };
+            if QUIET.load(Ordering::Relaxed) {
+                return Ok(preload);
+            }
+            let mut stdin = io::stdin().lock();
+            let mut stdout = io::stdout().lock();
+            let mut line = String::new();
+            while stdin.read_line(&mut line)? > 0 {
+                stdout.write_all(pipe::convert(&line, &options).as_bytes())?;
+                line.clear();
+            }
+        }
+
+        OneShot::Remind { text, at } => {
+            let app = App::from_config(config::AppConfig::load());
+            let (when, zone) = match remind::parse_when(
+                &at,
+                Utc::now(),
+                app.timezone_manager.zones(),
+                &app.abbreviations,
+            ) {
+                Ok(found) => found,
+                Err(error) => {
+                    errln!("{}", t!("cli.remind.invalid", error = error));
+                    ExitStatus::Failure.exit();
+                }
+            };
+            preload.at = Some(when);
+            let reminder = remind::Reminder {
+                text,
+                at: when,
+                zone: zone.map(|zone| zone.name().to_string()),
+            };
+            let Some(path) = remind::reminders_path() else {
+                errln!("{}", t!("cli.remind.no_path"));
+                ExitStatus::Failure.exit();
+            };
+            remind::update(&path, |reminders| reminders.push(reminder.clone()))?;
+            outln!(
+                "{}",
+                t!(
+                    "cli.remind.set",
+                    time = reminder.local_time(),
+                    utc = when.format("%H:%M UTC")
+                )
+            );
+        }
+
+        OneShot::Blocked { action } => {
+            let mut config = config::AppConfig::load();
+            match action {
+                BlockedAction::Import { file } => {
+                    let (entries, errors) = blocked::parse(&std::fs::read_to_string(file)?);
+                    for error in &errors {
+                        errln!("{}", t!("cli.blocked.invalid", line = error));
+                    }
+                    let app = App::from_config(config.clone());
+                    let zones = app.timezone_manager.zones();
+                    let mut unknown: Vec<&str> = entries
+                        .iter()
+                        .map(|entry| entry.who.as_str())
+                        .filter(|who| !zones.iter().any(|zone| zone.answers_to(who)))
+                        .collect();
+                    unknown.dedup();
+                    for name in unknown {
+                        errln!("{}", t!("cli.blocked.unknown", name = name));
+                    }
+
+                    let added = blocked::merge(&mut config.blocked, entries);
+                    config.save()?;
+                    outln!(
+                        "{}",
+                        t!(
+                            "cli.blocked.imported",
+                            added = added,
+                            total = config.blocked.len()
+                        )
+                    );
+                }
+                BlockedAction::List => {
+                    let mut entries = config.blocked.clone();
+                    entries.extend(ics::calendar_blocked(&config.calendars));
+                    if entries.is_empty() {
+                        outln!("{}", t!("cli.blocked.empty"));
+                    }
+                    for entry in &entries {
+                        let dates = if entry.from == entry.to {
+                            entry.from.to_string()
+                        } else {
+                            format!("{}..{}", entry.from, entry.to)
+                        };
+                        outln!(
+                            "{}",
+                            t!(
+                                "cli.blocked.line",
+                                who = entry.who,
+                                dates = dates,
+                                note = entry.note.as_deref().unwrap_or_default()
+                            )
+                            .trim_end()
+                        );
+                    }
+                }
+            }
+        }
+
+        OneShot::Handoff => {
+            let config = config::AppConfig::load();
+            let Some(chain) = &config.handoff else {
+                outln!("{}", t!("cli.handoff.none"));
+                return Ok(preload);
+            };
+            let app = App::from_config(config.clone());
+            let zones = app.timezone_manager.zones();
+            let mut status = None;
+            for name in &chain.chain {
+                if time::resolve_zone(name, zones).is_none() {
+                    errln!("{}", t!("cli.handoff.unknown", name = name));
+                    status = Some(ExitStatus::UnknownZone);
+                }
+            }
+
+            let teams = handoff::teams(chain, zones);
+            let hours = (
+                config.time_config.work_hours_start,
+                config.time_config.work_hours_end,
+            );
+            let minimum = chrono::Duration::minutes(chain.min_overlap_minutes as i64);
+            let now = Utc::now();
+            outln!("{}", t!("cli.handoff.header"));
+            for next in handoff::upcoming(&teams, now, hours) {
+                if next.overlap.is_zero() {
+                    status = status.or(Some(ExitStatus::NoOverlap));
+                }
+                let line = t!(
+                    "cli.handoff.line",
+                    from = next.from,
+                    to = next.to,
+                    time = next.time.format("%a %H:%M UTC"),
+                    local = next.receiver_time.format("%a %H:%M"),
+                    overlap = handoff::format_duration(next.overlap)
+                );
+                if next.overlap < minimum {
+                    outln!("{line} ⚠");
+                } else {
+                    outln!("{line}");
+                }
+            }
+            for warning in handoff::warnings(&teams, now.date_naive(), hours, minimum) {
+                outln!(
+                    "{}",
+                    t!(
+                        "cli.handoff.warning",
+                        from = warning.from,
+                        to = warning.to,
+                        overlap = handoff::format_duration(warning.overlap),
+                        date = warning.date.format("%a %-d %b"),
+                        minimum = handoff::format_duration(minimum)
+                    )
+                );
+            }
+            if let Some(status) = status {
+                status.exit();
+            }
+        }
+
+        OneShot::Plan {
+            date,
+            minutes,
+            count,
+        } => {
+            let mut app = App::from_config(config::AppConfig::load());
+            app.read_calendars();
+            let zones = app.timezone_manager.zones();
+            let date = date.unwrap_or_else(|| Local::now().date_naive());
+            let length = chrono::Duration::minutes(minutes as i64);
+            let ranked = plan::rank(plan::slots(
+                &Local,
+                zones,
+                &app.time_config,
+                &app.away,
+                date,
+                length,
+            ));
+            let names = |indices: &[usize]| {
+                indices
+                    .iter()
+                    .map(|&index| zones[index].effective_display_name())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            outln!(
+                "{}",
+                t!(
+                    "cli.plan.header",
+                    minutes = minutes,
+                    date = date.format("%a %-d %b")
+                )
+            );
+            for slot in ranked.iter().take(count) {
+                let fit = if slot.suits_everyone() {
+                    t!("cli.plan.suits")
+                } else {
+                    t!("cli.plan.unsuited", names = names(&slot.unsuited))
+                };
+                outln!(
+                    "{}",
+                    t!(
+                        "cli.plan.line",
+                        start = slot.start.with_timezone(&Local).format("%H:%M"),
+                        end = slot.end.with_timezone(&Local).format("%H:%M"),
+                        at_work = slot.at_work.len(),
+                        zones = zones.len(),
+                        fit = fit
+                    )
+                );
+            }
+            if ranked
+                .first()
+                .is_none_or(|slot| slot.at_work.len() < zones.len())
+            {
+                errln!("{}", t!("cli.plan.no_overlap"));
+                ExitStatus::NoOverlap.exit();
+            }
+        }
+
+        OneShot::SelfUpdate => {
+            let latest = match update::fetch_latest() {
+                Ok(latest) => latest,
+                Err(e) => {
+                    errln!("{}", t!("cli.self_update.check_failed", error = e));
+                    ExitStatus::Failure.exit();
+                }
+            };
+            if !update::is_newer(&latest, update::CURRENT_VERSION) {
+                outln!(
+                    "{}",
+                    t!(
+                        "cli.self_update.up_to_date",
+                        version = update::CURRENT_VERSION
+                    )
+                );
+                return Ok(preload);
+            }
+            let exe = std::env::current_exe()?;
+            let cargo_home = platform::env_var("CARGO_HOME")
+                .map(PathBuf::from)
+                .or_else(|| dirs::home_dir().map(|home| home.join(".cargo")));
+            let home = dirs::home_dir();
+            match update::InstallMethod::detect(&exe, cargo_home.as_deref(), home.as_deref()) {
+                update::InstallMethod::Homebrew => {
+                    outln!("{}", t!("cli.self_update.homebrew", version = latest));
+                }
+                update::InstallMethod::Cargo => {
+                    outln!("{}", t!("cli.self_update.cargo", version = latest));
+                }
+                update::InstallMethod::Other => {
+                    errln!(
+                        "{}",
+                        t!(
+                            "cli.self_update.other",
+                            version = latest,
+                            path = exe.display()
+                        )
+                    );
+                    ExitStatus::Failure.exit();
+                }
+                update::InstallMethod::Release => {
+                    let Some(asset) = update::asset_name() else {
+                        errln!("{}", t!("cli.self_update.unsupported", version = latest));
+                        ExitStatus::Failure.exit();
+                    };
+                    if let Err(e) = update::replace_binary(&exe, &latest, asset) {
+                        errln!(
+                            "{}",
+                            t!("cli.self_update.failed", path = exe.display(), error = e)
+                        );
+                        ExitStatus::Failure.exit();
+                    }
+                    outln!(
+                        "{}",
+                        t!(
+                            "cli.self_update.updated",
+                            from = update::CURRENT_VERSION,
+                            to = latest
+                        )
+                    );
+                }
+            }
+        }
+
+        OneShot::History { limit } => {
+            let entries = history::history_path()
+                .and_then(|path| history::read(&path).ok())
+                .unwrap_or_default();
+            if entries.is_empty() {
+                outln!("{}", t!("cli.history.empty"));
+            }
+            for entry in &entries[entries.len().saturating_sub(limit)..] {
+                outln!("{entry}");
+            }
+        }
+
+        OneShot::Config {
+            action: ConfigAction::Check { file },
+        } => {
+            let files: Vec<PathBuf> = match file {
+                Some(file) => vec![file],
+                None => [
+                    config::AppConfig::org_config_path(),
+                    config::AppConfig::config_path(),
+                ]
+                .into_iter()
+                .flatten()
+                .filter(|path| path.exists())
+                .collect(),
+            };
+            if files.is_empty() {
+                outln!("{}", t!("cli.config.no_files"));
+            }
+
+            let mut failed = false;
+            for (name, err) in config::ConfigLayers::load().env_rejected {
+                errln!("{}", t!("cli.config.env_invalid", name = name, err = err));
+                failed = true;
+            }
+            for path in files {
+                let content = std::fs::read_to_string(&path)?;
+                let issues = config::check_config(&content);
+                if issues.is_empty() {
+                    outln!("{}", t!("cli.config.ok", path = path.display()));
+                }
+                for issue in issues {
+                    errln!("{}:{issue}", path.display());
+                    failed = true;
+                }
+            }
+            if failed {
+                ExitStatus::Failure.exit();
+            }
+        }
+
+        OneShot::Theme {
+            action:
+                ThemeAction::Preview {
+                    theme,
+                    all,
+                    out,
+                    width,
+                    height,
+                },
+        } => {
+            let config = config::AppConfig::load();
+            let names: Vec<String> = if all {
+                config::ColorTheme::all_themes()
+                    .iter()
+                    .map(|theme| format!("{theme:?}"))
+                    .chain(config.themes.iter().map(|theme| theme.name.clone()))
+                    .collect()
             } else {
-                eprintln!("{}", t!("cli.zone.not_found", city = city));
-                std::process::exit(1);
+                vec![theme.unwrap_or_else(|| {
+                    config
+                        .user_theme
+                        .clone()
+                        .unwrap_or_else(|| format!("{:?}", config.color_theme))
+                })]
+            };
+            if let Some(dir) = &out {
+                std::fs::create_dir_all(dir)?;
+            }
+            let now = Utc::now();
+            for name in names {
+                let mut app = demo::app(now);
+                app.themes = config.themes.clone();
+                app.select_theme(&name);
+                let board = preview::render(&app, width, height)?;
+                match &out {
+                    Some(dir) => {
+                        let file = preview::file_stem(&name);
+                        std::fs::write(dir.join(format!("{file}.txt")), preview::to_plain(&board))?;
+                        std::fs::write(dir.join(format!("{file}.ans")), preview::to_ansi(&board))?;
+                        outln!(
+                            "{}",
+                            t!("cli.theme.written", theme = file, path = dir.display())
+                        );
+                    }
+                    None => {
+                        outln!("{}", t!("cli.theme.header", theme = name));
+                        out!("{}", preview::to_ansi(&board));
+                    }
+                }
             }
         }
+
+        OneShot::Config {
+            action: ConfigAction::Explain { key },
+        } => match config::ConfigLayers::load().explain(&key) {
+            Some(explanation) => {
+                match explanation.value {
+                    Some(value) => outln!("{key} = {value}"),
+                    None => outln!("{}", t!("cli.config.not_set", key = key)),
+                }
+                let sources: Vec<String> =
+                    explanation.sources.iter().map(|s| s.to_string()).collect();
+                outln!("{}", t!("cli.config.source", source = sources.join(" + ")));
+            }
+            None => {
+                errln!("{}", t!("cli.config.unknown_key", key = key));
+                ExitStatus::NotFound.exit();
+            }
+        },
     }
 
-    Ok(())
+    Ok(preload)
+}
+
+/// The listed city called `name`, or else a city in the zone `name` stands
+/// for as an abbreviation, e.g. Tokyo for JST
+fn lookup_city<'a>(
+    timezones: &'a [(chrono_tz::Tz, String, String, f64, f64)],
+    name: &str,
+) -> Option<&'a (chrono_tz::Tz, String, String, f64, f64)> {
+    timezones
+        .iter()
+        .find(|(_, city, _, _, _)| city.eq_ignore_ascii_case(name))
+        .or_else(|| {
+            let learned = config::AppConfig::load().abbreviations;
+            let tz = abbrev::resolve(name, &learned, chrono::Utc::now())?;
+            timezones.iter().find(|(zone, _, _, _, _)| *zone == tz)
+        })
 }
 
 /// Create App instance with CLI options applied (timezone, theme, format)
 fn create_app_with_options(cli: Cli) -> Result<App, Box<dyn Error>> {
-    let mut app = App::new();
+    let mut app = if cli.demo {
+        demo::app(chrono::Utc::now())
+    } else {
+        App::new()
+    };
+
+    if cli.resume && !cli.demo {
+        match session::session_path().and_then(|path| session::load(&path)) {
+            Some(snapshot) => app.restore(snapshot),
+            None => eprintln!("{}", t!("cli.resume_none")),
+        }
+    }
 
     if let Some(timezone_name) = cli.timezone {
         let timezones = time::TimeZoneManager::get_all_available_timezones();
@@ -410,8 +1932,53 @@ fn create_app_with_options(cli: Cli) -> Result<App, Box<dyn Error>> {
     }
 
     if let Some(theme) = cli.theme {
-        app.color_theme = theme;
+        app.select_theme(&theme);
+    }
+
+    if let Some(background) = cli.background {
+        app.background = background;
+    }
+
+    if cli.low_bandwidth {
+        app.low_bandwidth = true;
     }
 
     Ok(app)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_replay_macro() {
+        let mut app = App::default();
+        app.macros.insert(
+            "w".to_string(),
+            vec!["}".to_string(), "}".to_string(), "]".to_string()],
+        );
+        let start = app.timeline_position;
+
+        replay_macro(&mut app, 'w');
+        assert_eq!(
+            app.timeline_position - start,
+            chrono::Duration::minutes(135)
+        );
+
+        // Unknown registers are a no-op
+        replay_macro(&mut app, 'x');
+        assert_eq!(
+            app.timeline_position - start,
+            chrono::Duration::minutes(135)
+        );
+
+        // Macros replay through the keymap, so a remapped key does what it's bound to
+        app.keymap = command::keymap(&BTreeMap::from([("}".to_string(), "next-day".to_string())]));
+        replay_macro(&mut app, 'w');
+        assert_eq!(
+            app.timeline_position - start,
+            chrono::Duration::minutes(135 + 2 * 24 * 60 + 15)
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::time::TimeZone;
+use chrono::{DateTime, Duration, Utc};
+
+/// How far ahead to look for DST changes that alter a pair's offset
+const LOOKAHEAD: Duration = Duration::days(7);
+
+/// A pair of zones whose offset from each other changes within the week
+#[derive(Debug, Clone, PartialEq)]
+pub struct OffsetChange {
+    pub from: usize,
+    pub to: usize,
+    pub when: DateTime<Utc>,
+    pub before: i32,
+    pub after: i32,
+}
+
+/// How many minutes `to`'s clocks are ahead of `from`'s at `at`
+pub fn offset_between(from: &TimeZone, to: &TimeZone, at: DateTime<Utc>) -> i32 {
+    to.offset_minutes_at(at) - from.offset_minutes_at(at)
+}
+
+/// An offset in minutes as "+5:30", "-8" or "0"
+pub fn format_offset(minutes: i32) -> String {
+    let sign = if minutes < 0 { "-" } else { "+" };
+    match (minutes.abs() / 60, minutes.abs() % 60) {
+        (0, 0) => "0".to_string(),
+        (hours, 0) => format!("{sign}{hours}"),
+        (hours, minutes) => format!("{sign}{hours}:{minutes:02}"),
+    }
+}
+
+/// Pairs whose offset changes in the week after `at`, each at the first
+/// hour it does
+pub fn changes(zones: &[TimeZone], at: DateTime<Utc>) -> Vec<OffsetChange> {
+    let mut found = Vec::new();
+    for from in 0..zones.len() {
+        for to in from + 1..zones.len() {
+            let before = offset_between(&zones[from], &zones[to], at);
+            let change = (1..=LOOKAHEAD.num_hours())
+                .map(|hours| at + Duration::hours(hours))
+                .map(|when| (when, offset_between(&zones[from], &zones[to], when)))
+                .find(|&(_, after)| after != before);
+            if let Some((when, after)) = change {
+                found.push(OffsetChange {
+                    from,
+                    to,
+                    when,
+                    before,
+                    after,
+                });
+            }
+        }
+    }
+    found
+}
+
+/// The matrix as a text table: row zone to column zone, how far ahead the
+/// column's clocks are. Cells marked `*` change within the week, as the
+/// footnotes below the table say.
+pub fn render(zones: &[TimeZone], at: DateTime<Utc>) -> String {
+    let names: Vec<&str> = zones
+        .iter()
+        .map(|zone| zone.display.code.as_str())
+        .collect();
+    let changes = changes(zones, at);
+    let changing = |a: usize, b: usize| {
+        changes
+            .iter()
+            .any(|c| (c.from, c.to) == (a, b) || (c.from, c.to) == (b, a))
+    };
+
+    let cells: Vec<Vec<String>> = zones
+        .iter()
+        .enumerate()
+        .map(|(a, from)| {
+            zones
+                .iter()
+                .enumerate()
+                .map(|(b, to)| {
+                    let cell = format_offset(offset_between(from, to, at));
+                    if changing(a, b) {
+                        format!("{cell}*")
+                    } else {
+                        cell
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let width = cells
+        .iter()
+        .flatten()
+        .map(String::len)
+        .chain(names.iter().map(|name| name.chars().count()))
+        .max()
+        .unwrap_or_default();
+
+    let mut text = format!("{:width$}", "");
+    for name in &names {
+        text.push_str(&format!(" {name:>width$}"));
+    }
+    text.push('\n');
+    for (name, row) in names.iter().zip(&cells) {
+        text.push_str(&format!("{name:<width$}"));
+        for cell in row {
+            text.push_str(&format!(" {cell:>width$}"));
+        }
+        text.push('\n');
+    }
+
+    if !changes.is_empty() {
+        text.push('\n');
+    }
+    for change in &changes {
+        text.push_str(&format!(
+            "* {} → {}: {} becomes {} from {}\n",
+            names[change.from],
+            names[change.to],
+            format_offset(change.before),
+            format_offset(change.after),
+            change.when.format("%a %-d %b %H:00 UTC")
+        ));
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_matrix() {
+        let zones = [
+            TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into()),
+            TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into()),
+            TimeZone::new(chrono_tz::Asia::Kolkata, String::new(), "BOM".into()),
+        ];
+        // New York's clocks go forward on Sunday 10 March 2024, London's not until the 31st
+        let at = DateTime::parse_from_rfc3339("2024-03-06T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(offset_between(&zones[0], &zones[1], at), 300);
+        assert_eq!(format_offset(330), "+5:30");
+        assert_eq!(format_offset(-300), "-5");
+        assert_eq!(format_offset(0), "0");
+
+        let found = changes(&zones, at);
+        assert_eq!(found.len(), 2);
+        assert_eq!((found[0].from, found[0].to), (0, 1));
+        assert_eq!((found[0].before, found[0].after), (300, 240));
+        assert_eq!(found[0].when.to_rfc3339(), "2024-03-10T07:00:00+00:00");
+
+        let text = render(&zones, at);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "            NYC     LON     BOM");
+        assert_eq!(lines[1], "NYC           0     +5* +10:30*");
+        assert!(text.contains("* NYC → LON: +5 becomes +4 from Sun 10 Mar 07:00 UTC"));
+        assert!(!text.contains("LON → BOM"));
+    }
+}
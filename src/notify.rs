@@ -0,0 +1,226 @@
+use crate::config::{NotifyBackend, NotifyConfig};
+use crate::platform::{self, Platform};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+use std::thread;
+
+/// What raised an alarm
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlarmKind {
+    Reminder,
+    Milestone,
+}
+
+/// Something worth interrupting for, as sent to every backend
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alarm {
+    pub kind: AlarmKind,
+    pub title: String,
+    pub text: String,
+    /// When the alarm was due
+    pub at: DateTime<Utc>,
+}
+
+/// The backends an alarm of `kind` goes to
+pub fn backends(config: &NotifyConfig, kind: AlarmKind) -> &[NotifyBackend] {
+    match kind {
+        AlarmKind::Reminder => &config.reminder,
+        AlarmKind::Milestone => &config.milestone,
+    }
+}
+
+/// Somewhere alarms can be sent
+pub trait Notifier: Send {
+    fn notify(&self, alarm: &Alarm) -> io::Result<()>;
+}
+
+/// Rings the terminal bell on stdout
+pub struct Bell;
+
+impl Notifier for Bell {
+    fn notify(&self, _alarm: &Alarm) -> io::Result<()> {
+        let mut stdout = io::stdout();
+        stdout.write_all(b"\x07")?;
+        stdout.flush()
+    }
+}
+
+/// A desktop notification, through notify-send on Linux and the BSDs or
+/// osascript on macOS
+pub struct Desktop {
+    pub platform: Platform,
+}
+
+impl Notifier for Desktop {
+    fn notify(&self, alarm: &Alarm) -> io::Result<()> {
+        match self.platform {
+            Platform::Windows => Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "desktop notifications aren't supported on Windows; use the `command` backend",
+            )),
+            Platform::Unix if cfg!(target_os = "macos") => run(Command::new("osascript").args([
+                "-e",
+                &format!(
+                    "display notification {} with title {}",
+                    applescript_string(&alarm.text),
+                    applescript_string(&alarm.title)
+                ),
+            ])),
+            Platform::Unix => run(Command::new("notify-send").args([
+                "--app-name",
+                "alltz",
+                &alarm.title,
+                &alarm.text,
+            ])),
+        }
+    }
+}
+
+/// Text quoted for AppleScript
+fn applescript_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The alarm POSTed as JSON to a URL using curl, e.g. a chat webhook
+/// relay or ntfy topic
+pub struct Webhook {
+    pub url: String,
+}
+
+impl Notifier for Webhook {
+    fn notify(&self, alarm: &Alarm) -> io::Result<()> {
+        let mut curl = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--fail",
+                "--header",
+                "Content-Type: application/json",
+                "--data-binary",
+                "@-",
+                &self.url,
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = curl.stdin.take() {
+            stdin.write_all(payload(alarm)?.as_bytes())?;
+        }
+        let output = curl.wait_with_output()?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(io::Error::other(stderr.trim().to_string()))
+        }
+    }
+}
+
+fn payload(alarm: &Alarm) -> io::Result<String> {
+    serde_json::to_string(alarm).map_err(io::Error::other)
+}
+
+/// Runs a program with the alarm's title and text as its last two
+/// arguments. `command` is split on whitespace, so it can carry arguments
+/// of its own; the alarm's kind and time are in `ALLTZ_ALARM_KIND` and
+/// `ALLTZ_ALARM_AT`.
+pub struct External {
+    pub command: String,
+}
+
+impl Notifier for External {
+    fn notify(&self, alarm: &Alarm) -> io::Result<()> {
+        let mut words = self.command.split_whitespace();
+        let program = words
+            .next()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "empty notify command"))?;
+        let kind = match alarm.kind {
+            AlarmKind::Reminder => "reminder",
+            AlarmKind::Milestone => "milestone",
+        };
+        run(Command::new(program)
+            .args(words)
+            .args([&alarm.title, &alarm.text])
+            .env("ALLTZ_ALARM_KIND", kind)
+            .env("ALLTZ_ALARM_AT", alarm.at.to_rfc3339()))
+    }
+}
+
+/// Runs a notifier's command, which has nothing to say when it works
+fn run(command: &mut Command) -> io::Result<()> {
+    platform::run(command).map(drop)
+}
+
+/// The notifier for a backend, or `None` when the URL or command it needs
+/// isn't set
+pub fn notifier(backend: NotifyBackend, config: &NotifyConfig) -> Option<Box<dyn Notifier>> {
+    match backend {
+        NotifyBackend::Bell => Some(Box::new(Bell)),
+        NotifyBackend::Desktop => Some(Box::new(Desktop {
+            platform: Platform::current(),
+        })),
+        NotifyBackend::Webhook => config
+            .webhook
+            .clone()
+            .map(|url| Box::new(Webhook { url }) as Box<dyn Notifier>),
+        NotifyBackend::Command => config
+            .command
+            .clone()
+            .map(|command| Box::new(External { command }) as Box<dyn Notifier>),
+    }
+}
+
+/// Sends an alarm from a background thread, so a slow webhook or command
+/// never holds up the board. Failures go to the diagnostic log.
+pub fn send(notifier: Box<dyn Notifier>, alarm: Alarm) {
+    thread::spawn(move || {
+        if let Err(e) = notifier.notify(&alarm) {
+            crate::log::write(&format!("Failed to send alarm `{}`: {e}", alarm.text));
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifiers() {
+        let alarm = Alarm {
+            kind: AlarmKind::Milestone,
+            title: "alltz milestone".to_string(),
+            text: "Code freeze".to_string(),
+            at: DateTime::parse_from_rfc3339("2024-03-31T07:30:00Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        };
+        assert_eq!(
+            payload(&alarm).unwrap(),
+            r#"{"kind":"milestone","title":"alltz milestone","text":"Code freeze","at":"2024-03-31T07:30:00Z"}"#
+        );
+        assert_eq!(applescript_string(r#"say "hi""#), r#""say \"hi\"""#);
+
+        // Reminders ring the bell until told otherwise; milestones are quiet
+        let config = NotifyConfig::default();
+        assert_eq!(
+            backends(&config, AlarmKind::Reminder),
+            [NotifyBackend::Bell]
+        );
+        assert!(backends(&config, AlarmKind::Milestone).is_empty());
+        assert!(notifier(NotifyBackend::Webhook, &config).is_none());
+        assert!(notifier(NotifyBackend::Command, &config).is_none());
+
+        if cfg!(unix) {
+            let command = |command: &str| External {
+                command: command.to_string(),
+            };
+            assert!(command("true").notify(&alarm).is_ok());
+            assert!(command("false").notify(&alarm).is_err());
+            assert!(command("").notify(&alarm).is_err());
+        }
+    }
+}
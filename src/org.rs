@@ -0,0 +1,65 @@
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use std::fmt::Display;
+
+/// The date and time of an org-mode timestamp, active (`<2024-06-01 Sat
+/// 15:00>`) or inactive (`[2024-06-01 Sat 15:00]`). The weekday, repeaters,
+/// warning periods and the end of a time range are ignored; a timestamp
+/// without a time is for the start of its day.
+pub fn parse(input: &str) -> Option<NaiveDateTime> {
+    let input = input.trim();
+    let inner = input
+        .strip_prefix('<')
+        .and_then(|rest| rest.strip_suffix('>'))
+        .or_else(|| input.strip_prefix('[')?.strip_suffix(']'))?;
+    let mut words = inner.split_whitespace();
+    let date = NaiveDate::parse_from_str(words.next()?, "%Y-%m-%d").ok()?;
+    let time = words
+        .filter_map(|word| {
+            let start = word.split_once('-').map_or(word, |(start, _)| start);
+            NaiveTime::parse_from_str(start, "%H:%M").ok()
+        })
+        .next()
+        .unwrap_or(NaiveTime::MIN);
+    Some(date.and_time(time))
+}
+
+/// A local time as an active org-mode timestamp, e.g. `<2024-06-01 Sat 15:00>`
+pub fn format<Z: TimeZone>(local: &DateTime<Z>) -> String
+where
+    Z::Offset: Display,
+{
+    local.format("<%Y-%m-%d %a %H:%M>").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn test_org_timestamps() {
+        let at = |text| NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M").unwrap();
+        assert_eq!(
+            parse("<2024-06-01 Sat 15:00>"),
+            Some(at("2024-06-01 15:00"))
+        );
+        assert_eq!(
+            parse(" [2024-06-01 Sat 9:30] "),
+            Some(at("2024-06-01 09:30"))
+        );
+        assert_eq!(
+            parse("<2024-06-01 Sat 15:00-16:30 +1w>"),
+            Some(at("2024-06-01 15:00"))
+        );
+        assert_eq!(parse("<2024-06-01 Sat>"), Some(at("2024-06-01 00:00")));
+        assert_eq!(parse("2024-06-01 15:00"), None);
+        assert_eq!(parse("<2024-06-01 Sat 15:00]"), None);
+        assert_eq!(parse("<tomorrow>"), None);
+
+        let local = at("2024-06-01 15:00")
+            .and_local_timezone(chrono_tz::Europe::Madrid)
+            .unwrap();
+        assert_eq!(format(&local), "<2024-06-01 Sat 15:00>");
+        assert_eq!(format(&local.with_timezone(&Utc)), "<2024-06-01 Sat 13:00>");
+    }
+}
@@ -0,0 +1,183 @@
+use chrono::{
+    DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone as ChronoTimeZone, Utc,
+};
+use chrono_tz::Tz;
+
+/// What to do with each timestamp found
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PipeOptions {
+    /// Zone to convert timestamps to
+    pub to: Tz,
+    /// Zone timestamps without an offset are in; without it they're left alone
+    pub from: Option<Tz>,
+    /// Swap each timestamp for the converted one instead of noting it after
+    pub replace: bool,
+}
+
+/// A timestamp found in the text, as written
+struct Found {
+    len: usize,
+    local: NaiveDateTime,
+    offset: Option<FixedOffset>,
+    separator: char,
+    seconds: bool,
+}
+
+/// Rewrites `text` with every ISO 8601 timestamp (`2024-06-01T15:00:00Z`,
+/// `2024-06-01 15:00+02:00`, ...) converted to the target zone, either
+/// noted after it or put in its place. Everything else passes through
+/// untouched, so this works as an editor filter.
+pub fn convert(text: &str, options: &PipeOptions) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut index = 0;
+    while index < text.len() {
+        let rest = &text[index..];
+        // A timestamp starts a word, not the tail of a longer number
+        let starts_word = !text[..index]
+            .chars()
+            .next_back()
+            .is_some_and(|c| c.is_ascii_alphanumeric());
+        let converted = if starts_word { find(rest) } else { None }
+            .and_then(|found| Some((rewrite(&rest[..found.len], &found, options)?, found.len)));
+        match converted {
+            Some((rewritten, len)) => {
+                out.push_str(&rewritten);
+                index += len;
+            }
+            None => {
+                let c = rest.chars().next().unwrap_or_default();
+                out.push(c);
+                index += c.len_utf8();
+            }
+        }
+    }
+    out
+}
+
+fn rewrite(original: &str, found: &Found, options: &PipeOptions) -> Option<String> {
+    let instant: DateTime<Utc> = match (found.offset, options.from) {
+        (Some(offset), _) => offset.from_local_datetime(&found.local).single()?.to_utc(),
+        (None, Some(from)) => from.from_local_datetime(&found.local).earliest()?.to_utc(),
+        (None, None) => return None,
+    };
+    let local = instant.with_timezone(&options.to);
+    if options.replace {
+        let format = match (found.separator, found.seconds) {
+            ('T', true) => "%Y-%m-%dT%H:%M:%S%:z",
+            ('T', false) => "%Y-%m-%dT%H:%M%:z",
+            (_, true) => "%Y-%m-%d %H:%M:%S%:z",
+            (_, false) => "%Y-%m-%d %H:%M%:z",
+        };
+        Some(local.format(format).to_string())
+    } else {
+        Some(format!(
+            "{original} ({})",
+            local.format("%a %-d %b %H:%M %Z")
+        ))
+    }
+}
+
+/// The timestamp at the start of `text`, if there is one
+fn find(text: &str) -> Option<Found> {
+    let bytes = text.as_bytes();
+    let digits = |from: usize, count: usize| {
+        bytes
+            .get(from..from + count)
+            .is_some_and(|run| run.iter().all(u8::is_ascii_digit))
+    };
+    let at = |i: usize, expected: u8| bytes.get(i) == Some(&expected);
+
+    // YYYY-MM-DD, then T or a space, then HH:MM
+    if !(digits(0, 4) && at(4, b'-') && digits(5, 2) && at(7, b'-') && digits(8, 2)) {
+        return None;
+    }
+    let separator = *bytes.get(10)? as char;
+    if !(matches!(separator, 'T' | ' ') && digits(11, 2) && at(13, b':') && digits(14, 2)) {
+        return None;
+    }
+    let mut len = 16;
+    let seconds = at(len, b':') && digits(len + 1, 2);
+    if seconds {
+        len += 3;
+        // Fractions of a second are read but not kept
+        if at(len, b'.') && digits(len + 1, 1) {
+            len += 1;
+            while digits(len, 1) {
+                len += 1;
+            }
+        }
+    }
+
+    let mut offset = None;
+    if at(len, b'Z') {
+        offset = FixedOffset::east_opt(0);
+        len += 1;
+    } else if (at(len, b'+') || at(len, b'-')) && digits(len + 1, 2) {
+        let sign = if at(len, b'-') { -1 } else { 1 };
+        let hours: i32 = text[len + 1..len + 3].parse().ok()?;
+        let (minutes, end) = if at(len + 3, b':') && digits(len + 4, 2) {
+            (text[len + 4..len + 6].parse().ok()?, len + 6)
+        } else if digits(len + 3, 2) {
+            (text[len + 3..len + 5].parse().ok()?, len + 5)
+        } else {
+            (0, len + 3)
+        };
+        offset = FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60));
+        len = end;
+    }
+    // Part of something longer, like a version number
+    if bytes.get(len).is_some_and(u8::is_ascii_alphanumeric) {
+        return None;
+    }
+
+    let date = NaiveDate::parse_from_str(&text[..10], "%Y-%m-%d").ok()?;
+    let time = match seconds {
+        true => NaiveTime::parse_from_str(&text[11..19], "%H:%M:%S"),
+        false => NaiveTime::parse_from_str(&text[11..16], "%H:%M"),
+    }
+    .ok()?;
+    Some(Found {
+        len,
+        local: date.and_time(time),
+        offset,
+        separator,
+        seconds,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pipe_convert() {
+        let options = PipeOptions {
+            to: chrono_tz::Asia::Tokyo,
+            from: None,
+            replace: false,
+        };
+        let text = "Deploy at 2024-06-01T15:00:00Z, review 2024-06-01 18:30+02:00.\n\
+                    Local 2024-06-01 09:00 and v2024-06-01T15:00Z stay.\n";
+        assert_eq!(
+            convert(text, &options),
+            "Deploy at 2024-06-01T15:00:00Z (Sun 2 Jun 00:00 JST), \
+             review 2024-06-01 18:30+02:00 (Sun 2 Jun 01:30 JST).\n\
+             Local 2024-06-01 09:00 and v2024-06-01T15:00Z stay.\n"
+        );
+
+        let replacing = PipeOptions {
+            from: Some(chrono_tz::Europe::London),
+            replace: true,
+            ..options
+        };
+        assert_eq!(
+            convert(text, &replacing),
+            "Deploy at 2024-06-02T00:00:00+09:00, review 2024-06-02 01:30+09:00.\n\
+             Local 2024-06-01 17:00+09:00 and v2024-06-01T15:00Z stay.\n"
+        );
+        assert_eq!(
+            convert("ünïcode 2024-06-01T15:00:00.123-0430 ok", &replacing),
+            "ünïcode 2024-06-02T04:30:00+09:00 ok"
+        );
+    }
+}
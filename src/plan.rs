@@ -0,0 +1,273 @@
+use crate::config::{BlockedDates, EtiquetteConfig, TimeDisplayConfig};
+use crate::time::{at_work, TimeZone};
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
+
+/// How far apart the calls considered start
+const STEP: Duration = Duration::minutes(30);
+
+/// A possible call and how well it suits the board's zones
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    /// Zones at work for the whole call, by board index
+    pub at_work: Vec<usize>,
+    /// Zones at work whose call windows the call falls outside of
+    pub unsuited: Vec<usize>,
+}
+
+impl Slot {
+    /// Whether everyone at work would take the call in one of their windows
+    pub fn suits_everyone(&self) -> bool {
+        self.unsuited.is_empty()
+    }
+}
+
+/// Minutes into `zone`'s day that a call from `start` to `end` runs, the
+/// end counting past midnight if the call does
+fn local_minutes(zone: &TimeZone, start: DateTime<Utc>, end: DateTime<Utc>) -> (u32, u32) {
+    let local = start.with_timezone(&zone.tz);
+    let from = local.hour() * 60 + local.minute();
+    (from, from + (end - start).num_minutes() as u32)
+}
+
+/// Midnight starting `date` at home
+pub fn midnight<H: chrono::TimeZone>(home: &H, date: NaiveDate) -> Option<DateTime<Utc>> {
+    let midnight = home.from_local_datetime(&date.and_hms_opt(0, 0, 0)?);
+    Some(midnight.earliest()?.with_timezone(&Utc))
+}
+
+/// Every call of `length` starting on the half hour during `date` at home,
+/// which has 46 or 50 of them on days the clocks change. Zones whose people
+/// are `away` that day aren't at work.
+pub fn slots<H: chrono::TimeZone>(
+    home: &H,
+    zones: &[TimeZone],
+    board: &TimeDisplayConfig,
+    away: &[BlockedDates],
+    date: NaiveDate,
+    length: Duration,
+) -> Vec<Slot> {
+    let (Some(first), Some(next_day)) = (
+        midnight(home, date),
+        date.succ_opt().and_then(|next| midnight(home, next)),
+    ) else {
+        return Vec::new();
+    };
+    std::iter::successors(Some(first), |start| Some(*start + STEP))
+        .take_while(|start| *start < next_day)
+        .map(|start| {
+            let end = start + length;
+            let at_work: Vec<usize> = zones
+                .iter()
+                .enumerate()
+                .filter(|(_, zone)| {
+                    let there = zone.as_of(start);
+                    let date = start.with_timezone(&there.tz).date_naive();
+                    if away.iter().any(|entry| entry.covers(zone, date)) {
+                        return false;
+                    }
+                    let hours = there.hours_on(board, date);
+                    at_work(
+                        there.tz,
+                        start,
+                        end,
+                        (hours.work_hours_start, hours.work_hours_end),
+                    )
+                })
+                .map(|(index, _)| index)
+                .collect();
+            let unsuited = at_work
+                .iter()
+                .copied()
+                .filter(|&index| {
+                    let (from, to) = local_minutes(&zones[index].as_of(start), start, end);
+                    zones[index].schedule.suits_call(from, to) == Some(false)
+                })
+                .collect();
+            Slot {
+                start,
+                end,
+                at_work,
+                unsuited,
+            }
+        })
+        .collect()
+}
+
+/// The slots most worth suggesting first: those with the most zones at
+/// work, then those outside the fewest call windows, then the earliest.
+/// Slots with no one at work are left out.
+pub fn rank(mut slots: Vec<Slot>) -> Vec<Slot> {
+    slots.retain(|slot| !slot.at_work.is_empty());
+    slots.sort_by_key(|slot| {
+        (
+            std::cmp::Reverse(slot.at_work.len()),
+            slot.unsuited.len(),
+            slot.start,
+        )
+    });
+    slots
+}
+
+/// Someone a time is too early or too late for, and the nearest move that
+/// would suit them
+#[derive(Debug, Clone, PartialEq)]
+pub struct EtiquetteHint {
+    pub zone: usize,
+    pub local_time: DateTime<Tz>,
+    /// Later when positive, earlier when negative
+    pub shift: Duration,
+}
+
+/// The zone `at` is furthest outside `etiquette`'s hours for, if any: "this
+/// is 06:30 for Bob, consider 1h 30m later"
+pub fn etiquette_hint(
+    zones: &[TimeZone],
+    at: DateTime<Utc>,
+    etiquette: &EtiquetteConfig,
+) -> Option<EtiquetteHint> {
+    const DAY: i64 = 24 * 60;
+    let (early, late) = (etiquette.early as i64 * 60, etiquette.late as i64 * 60);
+    if early >= late {
+        return None;
+    }
+    zones
+        .iter()
+        .enumerate()
+        .filter_map(|(index, zone)| {
+            let local_time = at.with_timezone(&zone.as_of(at).tz);
+            let minutes = (local_time.hour() * 60 + local_time.minute()) as i64;
+            if (early..=late).contains(&minutes) {
+                return None;
+            }
+            let later = (early - minutes).rem_euclid(DAY);
+            let earlier = (minutes - late).rem_euclid(DAY);
+            let shift = if later <= earlier {
+                Duration::minutes(later)
+            } else {
+                Duration::minutes(-earlier)
+            };
+            Some(EtiquetteHint {
+                zone: index,
+                local_time,
+                shift,
+            })
+        })
+        .max_by_key(|hint| hint.shift.abs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_slots() {
+        let mut new_york = TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into());
+        new_york.schedule.calls = vec!["mornings".to_string()];
+        let mut london = TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into());
+        london.schedule.calls = vec!["before 16:00".to_string()];
+        let zones = [new_york, london];
+        let board = TimeDisplayConfig::default();
+        // A Monday in January: New York is five hours behind London
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        let all = slots(
+            &chrono_tz::Europe::London,
+            &zones,
+            &board,
+            &[],
+            date,
+            Duration::minutes(30),
+        );
+        assert_eq!(all.len(), 48);
+        let ranked = rank(all);
+        // Both are at work from London's 13:00 to 18:00, but only until 16:00
+        // is it before London's 16:00 and New York's noon
+        let best = &ranked[0];
+        assert_eq!(best.start.to_rfc3339(), "2024-01-15T13:00:00+00:00");
+        assert_eq!(best.at_work, vec![0, 1]);
+        assert!(best.suits_everyone());
+        assert_eq!(
+            ranked.iter().filter(|slot| slot.at_work.len() == 2).count(),
+            10
+        );
+        assert!(ranked[..6].iter().all(Slot::suits_everyone));
+        assert_eq!(ranked[6].start.to_rfc3339(), "2024-01-15T16:00:00+00:00");
+        assert_eq!(ranked[6].unsuited, vec![1]);
+        assert_eq!(ranked[9].unsuited, vec![0, 1]);
+        // Then the slots only one of them is at work for
+        assert_eq!(ranked[10].at_work.len(), 1);
+    }
+
+    #[test]
+    fn test_slots_follow_the_day() {
+        let zones = [
+            TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into()),
+            TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into()),
+        ];
+        let board = TimeDisplayConfig::default();
+        let london = chrono_tz::Europe::London;
+        let day = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        let length = Duration::minutes(30);
+
+        // London's clocks go forward on 31 March and back on 27 October
+        let spring = slots(&london, &zones, &board, &[], day("2024-03-31"), length);
+        assert_eq!(spring.len(), 46);
+        assert_eq!(
+            spring.last().unwrap().start.to_rfc3339(),
+            "2024-03-31T22:30:00+00:00"
+        );
+        let autumn = slots(&london, &zones, &board, &[], day("2024-10-27"), length);
+        assert_eq!(autumn.len(), 50);
+
+        // Nobody is at work in New York on a day they're away
+        let away = [BlockedDates {
+            who: "New York".to_string(),
+            from: day("2024-01-15"),
+            to: day("2024-01-15"),
+            note: None,
+        }];
+        let monday = slots(&london, &zones, &board, &away, day("2024-01-15"), length);
+        assert!(monday.iter().all(|slot| !slot.at_work.contains(&0)));
+        assert!(monday.iter().any(|slot| slot.at_work == [1]));
+    }
+
+    #[test]
+    fn test_etiquette_hint() {
+        let zones = [
+            TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into()),
+            TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into()),
+            TimeZone::new(chrono_tz::Asia::Tokyo, String::new(), "TYO".into()),
+        ];
+        let etiquette = EtiquetteConfig::default();
+        let at = |time: &str| {
+            DateTime::parse_from_rfc3339(time)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        // 06:30 in New York, 11:30 in London and 20:30 in Tokyo
+        let hint = etiquette_hint(&zones, at("2024-01-15T11:30:00Z"), &etiquette).unwrap();
+        assert_eq!(hint.zone, 0);
+        assert_eq!(hint.local_time.format("%H:%M").to_string(), "06:30");
+        assert_eq!(hint.shift, Duration::minutes(90));
+
+        // 01:00 in Tokyo is closer to the evening before than the morning after
+        let hint = etiquette_hint(&zones[1..], at("2024-01-15T16:00:00Z"), &etiquette).unwrap();
+        assert_eq!(hint.zone, 1);
+        assert_eq!(hint.shift, Duration::hours(-5));
+
+        // 09:00 in New York and 14:00 in London suit both
+        assert_eq!(
+            etiquette_hint(&zones[..2], at("2024-01-15T14:00:00Z"), &etiquette),
+            None
+        );
+        let never = EtiquetteConfig { early: 0, late: 24 };
+        assert_eq!(
+            etiquette_hint(&zones, at("2024-01-15T11:30:00Z"), &never),
+            None
+        );
+    }
+}
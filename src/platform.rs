@@ -0,0 +1,143 @@
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// The operating system family alltz is running on. Platform differences are
+/// decided from a value rather than `#[cfg]`, so every branch builds and is
+/// tested on any OS.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Platform {
+    Windows,
+    Unix,
+}
+
+impl Platform {
+    pub fn current() -> Self {
+        if cfg!(windows) {
+            Platform::Windows
+        } else {
+            Platform::Unix
+        }
+    }
+
+    /// Directory for the user's config: `%APPDATA%\alltz` on Windows,
+    /// `~/.config/alltz` elsewhere
+    pub fn config_dir(self) -> Option<PathBuf> {
+        match self {
+            Platform::Windows => dirs::config_dir().map(|dir| dir.join("alltz")),
+            Platform::Unix => dirs::home_dir().map(|home| home.join(".config").join("alltz")),
+        }
+    }
+
+    /// Directory for the organisation config: `%ProgramData%\alltz` on Windows,
+    /// `/etc/alltz` elsewhere
+    pub fn org_config_dir(self, env: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+        match self {
+            Platform::Windows => env("ProgramData").map(|dir| PathBuf::from(dir).join("alltz")),
+            Platform::Unix => Some(PathBuf::from("/etc/alltz")),
+        }
+    }
+
+    /// Whether output goes to the legacy Windows console host, which can't draw
+    /// most symbols or emoji. Windows Terminal sets `WT_SESSION`, and other
+    /// modern terminals set `TERM_PROGRAM`.
+    pub fn is_legacy_console(self, env: impl Fn(&str) -> Option<String>) -> bool {
+        self == Platform::Windows && env("WT_SESSION").is_none() && env("TERM_PROGRAM").is_none()
+    }
+
+    /// The IANA name of the zone the system is set to, where it can be read:
+    /// the zoneinfo file `/etc/localtime` links to, or else `/etc/timezone`
+    pub fn system_zone(self) -> Option<String> {
+        match self {
+            Platform::Windows => None,
+            Platform::Unix => std::fs::read_link("/etc/localtime")
+                .ok()
+                .and_then(|target| zone_from_zoneinfo(&target))
+                .or_else(|| {
+                    let name = std::fs::read_to_string("/etc/timezone").ok()?;
+                    Some(name.trim().to_string()).filter(|name| !name.is_empty())
+                }),
+        }
+    }
+}
+
+/// The zone name in a zoneinfo path, e.g. "Europe/Paris" from
+/// "/usr/share/zoneinfo/Europe/Paris"
+fn zone_from_zoneinfo(path: &Path) -> Option<String> {
+    let path = path.to_str()?;
+    let (_, name) = path.split_once("zoneinfo/")?;
+    Some(name.to_string()).filter(|name| !name.is_empty())
+}
+
+/// Reads an environment variable for the platform checks
+pub fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Strips the byte order mark some Windows editors put at the start of UTF-8 files
+pub fn strip_bom(content: &str) -> &str {
+    content.strip_prefix('\u{feff}').unwrap_or(content)
+}
+
+/// Runs a command for its output, turning a failure exit status into an
+/// error with its stderr
+pub fn run(command: &mut Command) -> io::Result<String> {
+    let output = command.stdin(Stdio::null()).output()?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(io::Error::other(stderr.trim().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_dirs() {
+        let unix = Platform::Unix.config_dir().unwrap();
+        assert!(unix.ends_with(".config/alltz"));
+        assert!(Platform::Windows.config_dir().unwrap().ends_with("alltz"));
+
+        let program_data = |name: &str| (name == "ProgramData").then(|| "C:\\ProgramData".into());
+        assert_eq!(
+            Platform::Windows.org_config_dir(program_data),
+            Some(PathBuf::from("C:\\ProgramData").join("alltz"))
+        );
+        assert_eq!(
+            Platform::Unix.org_config_dir(|_| None),
+            Some(PathBuf::from("/etc/alltz"))
+        );
+    }
+
+    #[test]
+    fn test_legacy_console_detection() {
+        let windows_terminal = |name: &str| (name == "WT_SESSION").then(|| "1".into());
+        assert!(Platform::Windows.is_legacy_console(|_| None));
+        assert!(!Platform::Windows.is_legacy_console(windows_terminal));
+        assert!(!Platform::Unix.is_legacy_console(|_| None));
+    }
+
+    #[test]
+    fn test_zone_from_zoneinfo() {
+        let zone = |path: &str| zone_from_zoneinfo(Path::new(path));
+        assert_eq!(
+            zone("/usr/share/zoneinfo/Europe/Paris").as_deref(),
+            Some("Europe/Paris")
+        );
+        // macOS
+        assert_eq!(
+            zone("/var/db/timezone/zoneinfo/America/New_York").as_deref(),
+            Some("America/New_York")
+        );
+        assert_eq!(zone("/etc/localtime"), None);
+    }
+
+    #[test]
+    fn test_strip_bom() {
+        assert_eq!(strip_bom("\u{feff}show_date = true"), "show_date = true");
+        assert_eq!(strip_bom("show_date = true"), "show_date = true");
+    }
+}
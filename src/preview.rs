@@ -0,0 +1,174 @@
+use crate::app::App;
+use ratatui::{
+    backend::TestBackend,
+    buffer::Buffer,
+    style::{Color, Modifier, Style},
+    Terminal,
+};
+use std::fmt::Write;
+use std::io;
+
+/// The board drawn off-screen at the given size
+pub fn render(app: &App, width: u16, height: u16) -> io::Result<Buffer> {
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    terminal.draw(|f| app.view(f))?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// A file name for a theme's preview, since user theme names can hold anything
+pub fn file_stem(theme: &str) -> String {
+    theme
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+/// The buffer's characters only, without trailing spaces
+pub fn to_plain(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for y in 0..buf.area.height {
+        let line: String = (0..buf.area.width).map(|x| buf[(x, y)].symbol()).collect();
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+/// The buffer with ANSI colors and attributes, for `cat` or converting to images
+pub fn to_ansi(buf: &Buffer) -> String {
+    let mut out = String::new();
+    for y in 0..buf.area.height {
+        let mut current = Style::default();
+        for x in 0..buf.area.width {
+            let cell = &buf[(x, y)];
+            let style = cell.style();
+            if style != current {
+                out.push_str(&sgr(style));
+                current = style;
+            }
+            out.push_str(cell.symbol());
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+/// The escape sequence selecting `style` from a reset state
+fn sgr(style: Style) -> String {
+    let mut codes = vec!["0".to_string()];
+    for (modifier, code) in [
+        (Modifier::BOLD, "1"),
+        (Modifier::DIM, "2"),
+        (Modifier::ITALIC, "3"),
+        (Modifier::UNDERLINED, "4"),
+        (Modifier::REVERSED, "7"),
+    ] {
+        if style.add_modifier.contains(modifier) {
+            codes.push(code.to_string());
+        }
+    }
+    if let Some(code) = style.fg.and_then(|color| color_code(color, false)) {
+        codes.push(code);
+    }
+    if let Some(code) = style.bg.and_then(|color| color_code(color, true)) {
+        codes.push(code);
+    }
+
+    let mut sequence = String::from("\x1b[");
+    let _ = write!(sequence, "{}m", codes.join(";"));
+    sequence
+}
+
+fn color_code(color: Color, background: bool) -> Option<String> {
+    let base = if background { 40 } else { 30 };
+    let named = |index: u8| {
+        // 0-7 are the normal colors, 8-15 their bright forms
+        if index < 8 {
+            base + index as u16
+        } else {
+            base + 60 + (index - 8) as u16
+        }
+    };
+    let code = match color {
+        Color::Reset => return None,
+        Color::Black => named(0).to_string(),
+        Color::Red => named(1).to_string(),
+        Color::Green => named(2).to_string(),
+        Color::Yellow => named(3).to_string(),
+        Color::Blue => named(4).to_string(),
+        Color::Magenta => named(5).to_string(),
+        Color::Cyan => named(6).to_string(),
+        Color::Gray => named(7).to_string(),
+        Color::DarkGray => named(8).to_string(),
+        Color::LightRed => named(9).to_string(),
+        Color::LightGreen => named(10).to_string(),
+        Color::LightYellow => named(11).to_string(),
+        Color::LightBlue => named(12).to_string(),
+        Color::LightMagenta => named(13).to_string(),
+        Color::LightCyan => named(14).to_string(),
+        Color::White => named(15).to_string(),
+        Color::Indexed(index) => format!("{};5;{index}", base + 8),
+        Color::Rgb(r, g, b) => format!("{};2;{r};{g};{b}", base + 8),
+    };
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{ColorTheme, UserTheme};
+    use crate::demo;
+    use chrono::{DateTime, Utc};
+    use ratatui::layout::Rect;
+
+    #[test]
+    fn test_export() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 2));
+        buf.set_string(0, 0, "ab", Style::default().fg(Color::Red));
+        buf.set_string(
+            2,
+            0,
+            "c",
+            Style::default()
+                .fg(Color::Indexed(208))
+                .bg(Color::LightBlue)
+                .add_modifier(Modifier::BOLD),
+        );
+
+        assert_eq!(to_plain(&buf), "abc\n\n");
+        let ansi = to_ansi(&buf);
+        assert!(ansi.starts_with("\x1b[0;31mab\x1b[0;1;38;5;208;104mc\x1b[0m   \x1b[0m\n"));
+        assert_eq!(ansi.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_theme_preview() {
+        let now = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let mut app = demo::app(now);
+        app.themes = vec![UserTheme {
+            name: "Dusk Mode".to_string(),
+            inherits: ColorTheme::Ocean,
+            selected_border: Some("#ff8800".to_string()),
+            ..UserTheme::default()
+        }];
+        assert!(app.select_theme("dusk mode"));
+        let buf = render(&app, 100, 40).unwrap();
+
+        let text = to_plain(&buf);
+        assert!(text.contains("Design"));
+        assert!(text.contains("Sales"));
+        assert!(buf
+            .content()
+            .iter()
+            .any(|cell| cell.fg == Color::Rgb(0xff, 0x88, 0x00)));
+        assert_eq!(file_stem("Dusk Mode"), "dusk-mode");
+    }
+}
@@ -0,0 +1,241 @@
+use crate::platform::Platform;
+use crate::time::TimeZone;
+use chrono::{DateTime, Duration, NaiveTime, Offset, TimeZone as ChronoTimeZone, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::RandomState;
+use std::fs;
+use std::hash::{BuildHasher, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// "What time is it in `to` when it's `asked` in `from`?"
+#[derive(Debug, Clone)]
+pub struct Question<'a> {
+    pub from: &'a TimeZone,
+    pub to: &'a TimeZone,
+    pub instant: DateTime<Utc>,
+}
+
+impl Question<'_> {
+    /// The time in the asking zone
+    pub fn asked(&self) -> NaiveTime {
+        self.from.convert_time(self.instant).time()
+    }
+
+    pub fn answer(&self) -> NaiveTime {
+        self.to.convert_time(self.instant).time()
+    }
+
+    /// How many days ahead (or behind) the answering zone's date is
+    pub fn day_shift(&self) -> i64 {
+        let from = self.from.convert_time(self.instant).date_naive();
+        let to = self.to.convert_time(self.instant).date_naive();
+        (to - from).num_days()
+    }
+
+    pub fn is_correct(&self, guess: NaiveTime) -> bool {
+        guess == self.answer()
+    }
+}
+
+/// Small xorshift generator: quizzes need variety, not cryptographic randomness
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    /// Seeded from the standard library's per-process random keys
+    pub fn from_entropy() -> Self {
+        Self::new(RandomState::new().build_hasher().finish())
+    }
+
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0 % bound
+    }
+}
+
+/// Picks two different tracked zones and a half hour today in the first.
+/// Zones sharing an offset are skipped as pairs, since the answer would be
+/// the question. `None` when every zone has the same offset.
+pub fn question<'a>(
+    zones: &'a [TimeZone],
+    now: DateTime<Utc>,
+    rng: &mut Rng,
+) -> Option<Question<'a>> {
+    let offset = |zone: &TimeZone, at| zone.convert_time(at).offset().fix();
+    let pairs: Vec<(&TimeZone, &TimeZone)> = zones
+        .iter()
+        .flat_map(|from| zones.iter().map(move |to| (from, to)))
+        .filter(|(from, to)| offset(from, now) != offset(to, now))
+        .collect();
+    if pairs.is_empty() {
+        return None;
+    }
+    let (from, to) = pairs[rng.below(pairs.len() as u64) as usize];
+
+    let half_hour = rng.below(48) as u32;
+    let asked = NaiveTime::from_hms_opt(half_hour / 2, (half_hour % 2) * 30, 0)?;
+    let date = from.convert_time(now).date_naive();
+    // Times skipped by a DST change have no instant; take the hour after
+    let instant = (0..2)
+        .find_map(|hours| {
+            from.tz
+                .from_local_datetime(&(date.and_time(asked) + Duration::hours(hours)))
+                .earliest()
+        })?
+        .with_timezone(&Utc);
+
+    Some(Question { from, to, instant })
+}
+
+/// Reads a time as typed: "9:30", "09:30", "21", "9pm" or "9:30 am"
+pub fn parse_answer(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().to_ascii_lowercase().replace(' ', "");
+    let (digits, meridiem) = if let Some(digits) = input.strip_suffix("am") {
+        (digits, Some(false))
+    } else if let Some(digits) = input.strip_suffix("pm") {
+        (digits, Some(true))
+    } else {
+        (input.as_str(), None)
+    };
+
+    let (hour, minute) = match digits.split_once(':') {
+        Some((hour, minute)) => (hour.parse::<u32>().ok()?, minute.parse::<u32>().ok()?),
+        None => (digits.parse::<u32>().ok()?, 0),
+    };
+    let hour = match meridiem {
+        Some(_) if !(1..=12).contains(&hour) => return None,
+        Some(pm) => hour % 12 + if pm { 12 } else { 0 },
+        None => hour,
+    };
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// Correct answers in a row, and the best run kept between quizzes
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Streak {
+    #[serde(skip)]
+    pub current: u32,
+    pub best: u32,
+}
+
+impl Streak {
+    /// Counts an answer; returns whether it set a new best
+    pub fn record(&mut self, correct: bool) -> bool {
+        if !correct {
+            self.current = 0;
+            return false;
+        }
+        self.current += 1;
+        let new_best = self.current > self.best;
+        self.best = self.best.max(self.current);
+        new_best
+    }
+}
+
+pub fn streak_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("quiz.json"))
+}
+
+pub fn load_streak(path: &Path) -> Streak {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_streak(path: &Path, streak: &Streak) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string(streak)?)
+}
+
+/// Formats a quiz time the way the board shows it
+pub fn format_time(time: NaiveTime, twelve_hour: bool) -> String {
+    if twelve_hour {
+        let (pm, hour) = time.hour12();
+        format!(
+            "{hour}:{:02} {}",
+            time.minute(),
+            if pm { "PM" } else { "AM" }
+        )
+    } else {
+        time.format("%H:%M").to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quiz() {
+        let zones = [
+            TimeZone::from_tz(chrono_tz::America::Denver),
+            TimeZone::from_tz(chrono_tz::Africa::Nairobi),
+            TimeZone::from_tz(chrono_tz::America::Edmonton),
+        ];
+        let now = DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let mut rng = Rng::new(42);
+        for _ in 0..50 {
+            let question = question(&zones, now, &mut rng).unwrap();
+            // Denver and Edmonton share an offset, so never face each other
+            assert_ne!(
+                question.from.convert_time(now).offset().fix(),
+                question.to.convert_time(now).offset().fix()
+            );
+            assert_eq!(question.asked().minute() % 30, 0);
+            assert!(question.is_correct(question.to.convert_time(question.instant).time()));
+        }
+        assert!(question(&zones[..1], now, &mut rng).is_none());
+
+        // 09:00 in Denver is 19:00 in Nairobi the same day
+        let question = Question {
+            from: &zones[0],
+            to: &zones[1],
+            instant: now + Duration::hours(4),
+        };
+        assert_eq!(question.asked(), NaiveTime::from_hms_opt(9, 0, 0).unwrap());
+        assert!(question.is_correct(parse_answer("7pm").unwrap()));
+        assert_eq!(question.day_shift(), 0);
+    }
+
+    #[test]
+    fn test_parse_answer() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_answer("9:30"), time(9, 30));
+        assert_eq!(parse_answer(" 21 "), time(21, 0));
+        assert_eq!(parse_answer("9pm"), time(21, 0));
+        assert_eq!(parse_answer("12:15 AM"), time(0, 15));
+        assert_eq!(parse_answer("12pm"), time(12, 0));
+        assert_eq!(parse_answer("13pm"), None);
+        assert_eq!(parse_answer("soon"), None);
+    }
+
+    #[test]
+    fn test_streak() {
+        let mut streak = Streak::default();
+        assert!(streak.record(true));
+        assert!(streak.record(true));
+        assert!(!streak.record(false));
+        assert!(!streak.record(true));
+        assert_eq!(
+            streak,
+            Streak {
+                current: 1,
+                best: 2
+            }
+        );
+    }
+}
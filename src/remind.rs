@@ -0,0 +1,407 @@
+use crate::abbrev;
+use crate::platform::Platform;
+use crate::time::{parse_clock_time, resolve_zone, TimeZone};
+use chrono::{
+    DateTime, Datelike, Duration, Local, NaiveDate, NaiveTime, TimeZone as ChronoTimeZone, Utc,
+    Weekday,
+};
+use chrono_tz::Tz;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Instant, SystemTime};
+
+/// How long to wait for another alltz to finish with the reminders file
+const LOCK_WAIT: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// How old a lock on the reminders file gets before it's taken to be left
+/// behind by an alltz that crashed
+const LOCK_STALE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Something to be told about at a moment in time. The moment is kept in UTC,
+/// so a DST change on this machine doesn't move it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Reminder {
+    pub text: String,
+    pub at: DateTime<Utc>,
+    /// The IANA name of the zone the time was given in, to show it the way
+    /// it was asked for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zone: Option<String>,
+}
+
+impl Reminder {
+    /// The reminder's time on the clocks it was given for, e.g.
+    /// "Fri 18 Oct 09:30 CEST"
+    pub fn local_time(&self) -> String {
+        const FORMAT: &str = "%a %-d %b %H:%M %Z";
+        match self
+            .zone
+            .as_deref()
+            .and_then(|name| name.parse::<Tz>().ok())
+        {
+            Some(zone) => self.at.with_timezone(&zone).format(FORMAT).to_string(),
+            None => self.at.with_timezone(&Local).format(FORMAT).to_string(),
+        }
+    }
+}
+
+/// Which day a time is meant for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Day {
+    /// The next time the clocks read it
+    Next,
+    Today,
+    Tomorrow,
+    Weekday(Weekday),
+    Date(NaiveDate),
+}
+
+impl Day {
+    fn parse(word: &str) -> Option<Self> {
+        match word.to_lowercase().as_str() {
+            "today" => Some(Day::Today),
+            "tomorrow" => Some(Day::Tomorrow),
+            word => word.parse().map(Day::Weekday).ok().or_else(|| {
+                NaiveDate::parse_from_str(word, "%Y-%m-%d")
+                    .ok()
+                    .map(Day::Date)
+            }),
+        }
+    }
+
+    /// The local dates the day could mean, given today's date, soonest first
+    fn candidates(self, today: NaiveDate) -> Vec<NaiveDate> {
+        let days = |offsets: &[i64]| {
+            offsets
+                .iter()
+                .filter_map(|&offset| today.checked_add_signed(Duration::days(offset)))
+                .collect()
+        };
+        match self {
+            Day::Next => days(&[0, 1, 2]),
+            Day::Today => days(&[0]),
+            Day::Tomorrow => days(&[1]),
+            Day::Weekday(weekday) => {
+                let ahead = (weekday.num_days_from_monday() + 7
+                    - today.weekday().num_days_from_monday())
+                    % 7;
+                days(&[ahead as i64, ahead as i64 + 7])
+            }
+            Day::Date(date) => vec![date],
+        }
+    }
+}
+
+/// Reads when a reminder is for: a time, then optionally a zone and a day,
+/// in any order, e.g. "09:30 Europe/Madrid tomorrow", "2pm Tokyo friday" or
+/// "17:00 2025-03-14". A zone can be an IANA name, a board label or city, or
+/// an abbreviation (see [`abbrev::resolve`]); without one the time is on this
+/// machine's clocks.
+pub fn parse_when(
+    input: &str,
+    now: DateTime<Utc>,
+    zones: &[TimeZone],
+    abbreviations: &BTreeMap<String, String>,
+) -> Result<(DateTime<Utc>, Option<Tz>), String> {
+    let words: Vec<&str> = input.split_whitespace().collect();
+    let mut time = None;
+    let mut day = None;
+    let mut rest = Vec::new();
+    let mut index = 0;
+    while index < words.len() {
+        let word = words[index];
+        // "9 am" is one time, typed as two words
+        let pair = words
+            .get(index + 1)
+            .filter(|next| matches!(next.to_lowercase().as_str(), "am" | "pm"))
+            .and_then(|next| parse_clock_time(&format!("{word}{next}")));
+        if let (None, Some(parsed)) = (time, pair) {
+            time = Some(parsed);
+            index += 2;
+            continue;
+        }
+        match (time, day, parse_clock_time(word), Day::parse(word)) {
+            (None, _, Some(parsed), _) => time = Some(parsed),
+            (_, None, _, Some(parsed)) => day = Some(parsed),
+            _ => rest.push(word),
+        }
+        index += 1;
+    }
+    let time = time.ok_or_else(|| format!("no time in `{input}`, e.g. 09:30 or 2pm"))?;
+    let day = day.unwrap_or(Day::Next);
+
+    if rest.is_empty() {
+        return Ok((instant(&Local, now, time, day)?, None));
+    }
+    let name = rest.join(" ");
+    let zone = resolve_zone(&name, zones)
+        .or_else(|| abbrev::resolve(&name, abbreviations, now))
+        .ok_or_else(|| format!("`{name}` is not a zone, city or board label"))?;
+    Ok((instant(&zone, now, time, day)?, Some(zone)))
+}
+
+/// The first moment after `now` that `zone`'s clocks read `time` on `day`
+fn instant<Z: ChronoTimeZone>(
+    zone: &Z,
+    now: DateTime<Utc>,
+    time: NaiveTime,
+    day: Day,
+) -> Result<DateTime<Utc>, String> {
+    let today = now.with_timezone(zone).date_naive();
+    let mut skipped = None;
+    for date in day.candidates(today) {
+        match date
+            .and_time(time)
+            .and_local_timezone(zone.clone())
+            .earliest()
+        {
+            Some(local) if local.with_timezone(&Utc) > now => return Ok(local.with_timezone(&Utc)),
+            Some(_) => {}
+            None => skipped = Some(date),
+        }
+    }
+    Err(match skipped {
+        // Clocks jump over the time on the day DST starts
+        Some(date) => format!(
+            "{} doesn't happen on {date}: the clocks skip it",
+            time.format("%H:%M")
+        ),
+        None => "that time has already passed".to_string(),
+    })
+}
+
+/// Reminders still to fire, kept next to the config so `alltz remind` can add
+/// them while the board is open
+pub fn reminders_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("reminders.json"))
+}
+
+/// Reads the reminders file, treating a missing or unreadable one as empty
+pub fn load(path: &Path) -> Vec<Reminder> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Writes the reminders file, through a file next to it that's renamed over
+/// it, so it's never read half written
+pub fn save(path: &Path, reminders: &[Reminder]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let staged = path.with_extension(format!("json.{}", std::process::id()));
+    fs::write(&staged, serde_json::to_string_pretty(reminders)?)?;
+    fs::rename(&staged, path)
+}
+
+/// Reads the reminders file, lets `change` alter them and writes them back,
+/// holding a lock the whole time so a board firing reminders and
+/// `alltz remind` adding one don't lose each other's change
+pub fn update<R>(path: &Path, change: impl FnOnce(&mut Vec<Reminder>) -> R) -> io::Result<R> {
+    let _lock = Lock::acquire(path)?;
+    let mut reminders = load(path);
+    let result = change(&mut reminders);
+    save(path, &reminders)?;
+    Ok(result)
+}
+
+/// A lock file next to the reminders file, removed when dropped
+struct Lock(PathBuf);
+
+impl Lock {
+    fn acquire(path: &Path) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let lock = path.with_extension("json.lock");
+        let deadline = Instant::now() + LOCK_WAIT;
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&lock)
+            {
+                Ok(_) => return Ok(Lock(lock)),
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e),
+            }
+            let stale = fs::metadata(&lock)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age > LOCK_STALE);
+            if stale {
+                let _ = fs::remove_file(&lock);
+            } else if Instant::now() >= deadline {
+                return Err(io::Error::new(
+                    io::ErrorKind::WouldBlock,
+                    format!("{} is held by another alltz", lock.display()),
+                ));
+            } else {
+                thread::sleep(std::time::Duration::from_millis(20));
+            }
+        }
+    }
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.0);
+    }
+}
+
+/// The reminders file as last read, so the board only reads it again once
+/// it has changed and only locks it when something is due
+#[derive(Debug, Default)]
+pub struct Cache {
+    modified: Option<SystemTime>,
+    pending: Vec<Reminder>,
+}
+
+impl Cache {
+    /// Takes the reminders due by `now` out of the file at `path`, soonest
+    /// first
+    pub fn take_due(&mut self, path: &Path, now: DateTime<Utc>) -> io::Result<Vec<Reminder>> {
+        let modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        if modified != self.modified {
+            self.pending = load(path);
+            self.modified = modified;
+        }
+        if !self.pending.iter().any(|reminder| reminder.at <= now) {
+            return Ok(Vec::new());
+        }
+        let (due, pending) = update(path, |reminders| {
+            let (due, pending) = take_due(std::mem::take(reminders), now);
+            *reminders = pending.clone();
+            (due, pending)
+        })?;
+        self.pending = pending;
+        self.modified = fs::metadata(path)
+            .and_then(|metadata| metadata.modified())
+            .ok();
+        Ok(due)
+    }
+}
+
+/// Splits reminders into those due by `now`, soonest first, and those still
+/// to come
+pub fn take_due(reminders: Vec<Reminder>, now: DateTime<Utc>) -> (Vec<Reminder>, Vec<Reminder>) {
+    let (mut due, pending): (Vec<_>, Vec<_>) = reminders
+        .into_iter()
+        .partition(|reminder| reminder.at <= now);
+    due.sort_by_key(|reminder| reminder.at);
+    (due, pending)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(text: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(text)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_when() {
+        let zones = [TimeZone::with_custom_label(
+            chrono_tz::Asia::Tokyo,
+            "TYO".to_string(),
+            Some("Maria".to_string()),
+        )];
+        let learned = BTreeMap::from([("CST".to_string(), "Asia/Shanghai".to_string())]);
+        // Madrid's clocks go forward overnight, so 09:30 tomorrow is CEST
+        let now = utc("2024-03-30T12:00:00Z");
+        let (at, zone) = parse_when("09:30 Europe/Madrid tomorrow", now, &zones, &learned).unwrap();
+        assert_eq!(at, utc("2024-03-31T07:30:00Z"));
+        assert_eq!(zone, Some(chrono_tz::Europe::Madrid));
+        // A day before, the same wall time is an hour later in UTC
+        let (at, _) = parse_when(
+            "tomorrow 9:30 am madrid",
+            utc("2024-03-29T12:00:00Z"),
+            &zones,
+            &learned,
+        )
+        .unwrap();
+        assert_eq!(at, utc("2024-03-30T08:30:00Z"));
+
+        // Without a day, the next time the clocks read it
+        let (at, _) = parse_when("9am Maria", now, &zones, &learned).unwrap();
+        assert_eq!(at, utc("2024-03-31T00:00:00Z"));
+        let (at, _) = parse_when("2pm new york monday", now, &zones, &learned).unwrap();
+        assert_eq!(at, utc("2024-04-01T18:00:00Z"));
+        let (at, _) = parse_when("17:00 Europe/London 2024-04-02", now, &zones, &learned).unwrap();
+        assert_eq!(at, utc("2024-04-02T16:00:00Z"));
+
+        // Abbreviations mean the zone they're known to
+        let (at, zone) = parse_when("9am cst", now, &zones, &learned).unwrap();
+        assert_eq!(at, utc("2024-03-31T01:00:00Z"));
+        assert_eq!(zone, Some(chrono_tz::Asia::Shanghai));
+
+        assert!(
+            parse_when("09:30 Europe/Madrid today", now, &zones, &learned)
+                .unwrap_err()
+                .contains("already passed")
+        );
+        assert!(
+            parse_when("02:30 Europe/Madrid tomorrow", now, &zones, &learned)
+                .unwrap_err()
+                .contains("skip")
+        );
+        assert!(parse_when("09:30 Atlantis", now, &zones, &learned)
+            .unwrap_err()
+            .contains("`Atlantis`"));
+        assert!(parse_when("Europe/Madrid", now, &zones, &learned).is_err());
+    }
+
+    #[test]
+    fn test_take_due() {
+        let reminder = |text: &str, at: &str| Reminder {
+            text: text.to_string(),
+            at: utc(at),
+            zone: Some("Europe/Madrid".to_string()),
+        };
+        let reminders = vec![
+            reminder("later", "2024-03-31T09:00:00Z"),
+            reminder("second", "2024-03-31T07:30:00Z"),
+            reminder("first", "2024-03-31T07:00:00Z"),
+        ];
+        let (due, pending) = take_due(reminders, utc("2024-03-31T07:30:00Z"));
+        let texts: Vec<&str> = due.iter().map(|r| r.text.as_str()).collect();
+        assert_eq!(texts, ["first", "second"]);
+        assert_eq!(pending.len(), 1);
+        assert_eq!(due[1].local_time(), "Sun 31 Mar 09:30 CEST");
+    }
+
+    #[test]
+    fn test_update_waits_for_lock() {
+        let dir = std::env::temp_dir().join(format!("alltz-remind-{}", std::process::id()));
+        let path = dir.join("reminders.json");
+        let reminder = Reminder {
+            text: "ping Maria".to_string(),
+            at: utc("2024-03-31T07:30:00Z"),
+            zone: None,
+        };
+        let held = Lock::acquire(&path).unwrap();
+        assert_eq!(
+            update(&path, |_| ()).unwrap_err().kind(),
+            io::ErrorKind::WouldBlock
+        );
+        drop(held);
+        update(&path, |reminders| reminders.push(reminder.clone())).unwrap();
+        let reminders = load(&path);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(reminders, [reminder]);
+    }
+}
@@ -0,0 +1,214 @@
+use crate::config::{BlockedDates, TimeDisplayConfig};
+use crate::plan::midnight;
+use crate::time::{work_overlap, TimeZone};
+use chrono::{DateTime, Duration, NaiveDate, Offset, Utc};
+use chrono_tz::Tz;
+
+/// How many days a report covers
+pub const DAYS: usize = 7;
+
+/// A zone's working hours shared with home, day by day
+#[derive(Debug, Clone, PartialEq)]
+pub struct ZoneOverlap {
+    /// The zone, by board index
+    pub zone: usize,
+    /// Its timezone on the first day, after any move
+    pub tz: Tz,
+    /// Each day's shared hours, from the first
+    pub days: Vec<Duration>,
+    /// Days the zone's people, or you, are away, which share nothing
+    pub away: Vec<NaiveDate>,
+    /// The first day the zone's clocks end up moved against home's, by how
+    /// many minutes, as DST starting or ending on one side does
+    pub clock_change: Option<(NaiveDate, i32)>,
+}
+
+impl ZoneOverlap {
+    pub fn total(&self) -> Duration {
+        self.days.iter().copied().sum()
+    }
+
+    /// How many days share any hours at all
+    pub fn shared_days(&self) -> usize {
+        self.days
+            .iter()
+            .filter(|day| **day > Duration::zero())
+            .count()
+    }
+}
+
+/// How many minutes `zone`'s clocks are ahead of home's at `at`, wherever
+/// the zone has moved to by then
+fn offset_from_home<H: chrono::TimeZone>(home: &H, zone: &TimeZone, at: DateTime<Utc>) -> i32 {
+    let home_offset = at.with_timezone(home).offset().fix().local_minus_utc() / 60;
+    zone.as_of(at).offset_minutes_at(at) - home_offset
+}
+
+/// Each zone's working hours shared with `home` over the week from
+/// `first`, counting home's hours as the board's. Each day is worked out
+/// with the offsets and hours of that day, so DST changes, seasonal hours
+/// and moves are followed. Days away share nothing, whether the zone's
+/// people or `you`, the zone standing for you, are away.
+pub fn week<H: chrono::TimeZone>(
+    home: &H,
+    zones: &[TimeZone],
+    board: &TimeDisplayConfig,
+    away: &[BlockedDates],
+    you: Option<&TimeZone>,
+    first: NaiveDate,
+) -> Vec<ZoneOverlap> {
+    let dates: Vec<NaiveDate> = first.iter_days().take(DAYS).collect();
+    let home_hours = (board.work_hours_start, board.work_hours_end);
+    let away_on = |zone: &TimeZone, date| away.iter().any(|entry| entry.covers(zone, date));
+    zones
+        .iter()
+        .enumerate()
+        .map(|(index, zone)| {
+            let mut report = ZoneOverlap {
+                zone: index,
+                tz: midnight(home, first)
+                    .map_or(zone.tz, |at| zone.as_of(at + Duration::hours(12)).tz),
+                days: Vec::with_capacity(DAYS),
+                away: Vec::new(),
+                clock_change: None,
+            };
+            let before = midnight(home, first).map(|at| offset_from_home(home, zone, at));
+            for &date in &dates {
+                let Some(start) = midnight(home, date) else {
+                    report.days.push(Duration::zero());
+                    continue;
+                };
+                // The day's end tells whether the clocks moved during it
+                let after = offset_from_home(home, zone, start + Duration::days(1));
+                if let (None, Some(before)) = (report.clock_change, before) {
+                    if after != before {
+                        report.clock_change = Some((date, after - before));
+                    }
+                }
+                if away_on(zone, date) || you.is_some_and(|you| away_on(you, date)) {
+                    report.away.push(date);
+                    report.days.push(Duration::zero());
+                    continue;
+                }
+                let there = zone.as_of(start + Duration::hours(12));
+                let hours = there.hours_on(board, date);
+                report.days.push(work_overlap(
+                    home,
+                    there.tz,
+                    date,
+                    home_hours,
+                    (hours.work_hours_start, hours.work_hours_end),
+                ));
+            }
+            report
+        })
+        .collect()
+}
+
+/// A length of time as "22h 30m", "45m" or "0h"
+pub fn format_hours(duration: Duration) -> String {
+    let minutes = duration.num_minutes().unsigned_abs();
+    match (minutes / 60, minutes % 60) {
+        (hours, 0) => format!("{hours}h"),
+        (0, minutes) => format!("{minutes}m"),
+        (hours, minutes) => format!("{hours}h {minutes:02}m"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ZoneMove;
+
+    #[test]
+    fn test_week_report() {
+        let zones = [
+            TimeZone::new(chrono_tz::America::New_York, String::new(), "NYC".into()),
+            TimeZone::new(chrono_tz::Europe::Paris, String::new(), "PAR".into()),
+        ];
+        let board = TimeDisplayConfig::default();
+        let away = [BlockedDates {
+            who: "Paris".to_string(),
+            from: NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(),
+            to: NaiveDate::from_ymd_opt(2024, 3, 12).unwrap(),
+            note: None,
+        }];
+        // From Monday 11 March 2024, the day after New York's clocks went
+        // forward and three weeks before London's and Paris's do
+        let first = NaiveDate::from_ymd_opt(2024, 3, 11).unwrap();
+        let report = week(
+            &chrono_tz::Europe::London,
+            &zones,
+            &board,
+            &away,
+            None,
+            first,
+        );
+
+        // New York is four hours behind for these weeks, not five: 12:00 to
+        // 18:00 in London every weekday
+        let new_york = &report[0];
+        assert_eq!(new_york.days[0], Duration::hours(6));
+        assert_eq!(new_york.total(), Duration::hours(30));
+        assert_eq!(new_york.shared_days(), 5);
+        assert_eq!(new_york.clock_change, None);
+
+        // Paris is an hour ahead, 08:00 to 17:00 in London, but away Tuesday
+        let paris = &report[1];
+        assert_eq!(paris.away, vec![first.succ_opt().unwrap()]);
+        assert_eq!(paris.days[1], Duration::zero());
+        assert_eq!(paris.total(), Duration::hours(36));
+
+        // The week London's clocks go back a week before New York's, New
+        // York comes an hour closer; Paris's go back the same night
+        let autumn = NaiveDate::from_ymd_opt(2024, 10, 21).unwrap();
+        let report = week(
+            &chrono_tz::Europe::London,
+            &zones,
+            &board,
+            &[],
+            None,
+            autumn,
+        );
+        let sunday = NaiveDate::from_ymd_opt(2024, 10, 27).unwrap();
+        assert_eq!(report[0].clock_change, Some((sunday, 60)));
+        assert_eq!(report[1].clock_change, None);
+
+        // Days you're away share nothing with anyone
+        let you = TimeZone::new(chrono_tz::Europe::London, String::new(), "LON".into());
+        let away = [BlockedDates {
+            who: "London".to_string(),
+            ..away[0].clone()
+        }];
+        let report = week(
+            &chrono_tz::Europe::London,
+            &zones,
+            &board,
+            &away,
+            Some(&you),
+            first,
+        );
+        assert_eq!(report[0].away, vec![first.succ_opt().unwrap()]);
+        assert_eq!(report[0].total(), Duration::hours(24));
+
+        // Zones are reported where they are on the first day
+        let mut moving = zones[1].clone();
+        moving.moves.push(ZoneMove {
+            from: first - Duration::days(1),
+            city: "Tokyo".to_string(),
+        });
+        let report = week(
+            &chrono_tz::Europe::London,
+            &[moving],
+            &board,
+            &[],
+            None,
+            first,
+        );
+        assert_eq!(report[0].tz, chrono_tz::Asia::Tokyo);
+
+        assert_eq!(format_hours(Duration::minutes(22 * 60 + 30)), "22h 30m");
+        assert_eq!(format_hours(Duration::minutes(45)), "45m");
+        assert_eq!(format_hours(Duration::zero()), "0h");
+    }
+}
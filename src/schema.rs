@@ -0,0 +1,202 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+
+/// Version of the `--format json` output structure. Bumped whenever a field
+/// is removed, renamed or changes type; adding a field doesn't bump it.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// How a command prints its results
+#[derive(Debug, Clone, Copy, Default, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// For people
+    #[default]
+    Text,
+    /// For scripts, following the structure printed by `alltz --schema`
+    Json,
+}
+
+/// `alltz list --format json`
+#[derive(Debug, Serialize)]
+pub struct ListOutput {
+    pub version: u32,
+    pub timezones: Vec<ListedZone>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ListedZone {
+    pub city: String,
+    pub code: String,
+    pub timezone: String,
+    pub latitude: f64,
+    pub longitude: f64,
+}
+
+/// `alltz report --format json`
+#[derive(Debug, Serialize)]
+pub struct ReportOutput {
+    pub version: u32,
+    pub from: String,
+    pub to: String,
+    pub zones: Vec<ReportedZone>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportedZone {
+    pub name: String,
+    pub timezone: String,
+    pub overlap_minutes: i64,
+    pub daily_minutes: Vec<i64>,
+    pub away: Vec<String>,
+    pub clock_change: Option<ReportedClockChange>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReportedClockChange {
+    pub date: String,
+    pub minutes: i32,
+}
+
+/// A JSON Schema (draft 2020-12) for every `--format json` output, each
+/// under `$defs` by command name
+pub fn schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "$id": format!("https://github.com/abradburne/alltz/schema/v{SCHEMA_VERSION}.json"),
+        "title": "alltz JSON output",
+        "description": "Outputs of alltz commands run with --format json",
+        "version": SCHEMA_VERSION,
+        "$defs": {
+            "list": {
+                "type": "object",
+                "required": ["version", "timezones"],
+                "properties": {
+                    "version": { "const": SCHEMA_VERSION },
+                    "timezones": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["city", "code", "timezone", "latitude", "longitude"],
+                            "properties": {
+                                "city": { "type": "string" },
+                                "code": { "type": "string", "description": "Short code shown on the board" },
+                                "timezone": { "type": "string", "description": "IANA zone name" },
+                                "latitude": { "type": "number" },
+                                "longitude": { "type": "number" }
+                            }
+                        }
+                    }
+                }
+            },
+            "report": {
+                "type": "object",
+                "required": ["version", "from", "to", "zones"],
+                "properties": {
+                    "version": { "const": SCHEMA_VERSION },
+                    "from": { "type": "string", "format": "date", "description": "First day, in your own time" },
+                    "to": { "type": "string", "format": "date", "description": "Last day, inclusive" },
+                    "zones": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "required": ["name", "timezone", "overlap_minutes", "daily_minutes", "away", "clock_change"],
+                            "properties": {
+                                "name": { "type": "string", "description": "Label, or short code shown on the board" },
+                                "timezone": { "type": "string", "description": "IANA zone name on the first day, after any move" },
+                                "overlap_minutes": { "type": "integer", "description": "Work minutes shared with you over the week" },
+                                "daily_minutes": {
+                                    "type": "array",
+                                    "items": { "type": "integer" },
+                                    "description": "Work minutes shared with you each day, from the first"
+                                },
+                                "away": {
+                                    "type": "array",
+                                    "items": { "type": "string", "format": "date" },
+                                    "description": "Days the zone's people, or you, are away"
+                                },
+                                "clock_change": {
+                                    "type": ["object", "null"],
+                                    "required": ["date", "minutes"],
+                                    "properties": {
+                                        "date": { "type": "string", "format": "date" },
+                                        "minutes": { "type": "integer", "description": "How far the zone's clocks move against yours" }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The property names the schema gives a definition, sorted
+    fn properties(schema: &Value) -> Vec<String> {
+        let mut names: Vec<String> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        names.sort();
+        names
+    }
+
+    fn keys(value: &Value) -> Vec<String> {
+        let mut names: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_schema_matches_output() {
+        let output = serde_json::to_value(ListOutput {
+            version: SCHEMA_VERSION,
+            timezones: vec![ListedZone {
+                city: "London".to_string(),
+                code: "LON".to_string(),
+                timezone: "Europe/London".to_string(),
+                latitude: 51.5,
+                longitude: -0.13,
+            }],
+        })
+        .unwrap();
+        let list = &schema()["$defs"]["list"];
+        assert_eq!(keys(&output), properties(list));
+        assert_eq!(
+            keys(&output["timezones"][0]),
+            properties(&list["properties"]["timezones"]["items"])
+        );
+        assert_eq!(output["version"], list["properties"]["version"]["const"]);
+
+        let output = serde_json::to_value(ReportOutput {
+            version: SCHEMA_VERSION,
+            from: "2024-03-11".to_string(),
+            to: "2024-03-17".to_string(),
+            zones: vec![ReportedZone {
+                name: "NYC".to_string(),
+                timezone: "America/New_York".to_string(),
+                overlap_minutes: 1800,
+                daily_minutes: vec![360, 360, 360, 360, 360, 0, 0],
+                away: Vec::new(),
+                clock_change: Some(ReportedClockChange {
+                    date: "2024-03-31".to_string(),
+                    minutes: 60,
+                }),
+            }],
+        })
+        .unwrap();
+        let report = &schema()["$defs"]["report"];
+        let zone = &report["properties"]["zones"]["items"];
+        assert_eq!(keys(&output), properties(report));
+        assert_eq!(keys(&output["zones"][0]), properties(zone));
+        assert_eq!(
+            keys(&output["zones"][0]["clock_change"]),
+            properties(&zone["properties"]["clock_change"])
+        );
+    }
+}
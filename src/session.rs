@@ -0,0 +1,115 @@
+use crate::app::{JumpList, TimeFormat, TimezoneDisplayMode};
+use crate::config::{
+    ColorTheme, LayoutConfig, Milestone, TimeDisplayConfig, ZoneConfigCompat, ZoneOrder,
+};
+use crate::platform::Platform;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How many changes can be undone
+pub const UNDO_LIMIT: usize = 100;
+
+/// The part of the board a person changes: its zones, what's marked and
+/// selected, and the settings toggled from the keyboard. It's small enough
+/// to copy whole, so undo keeps a copy from before each change, and the
+/// session file is one written out.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub zones: Vec<ZoneConfigCompat>,
+    pub selected_zone_index: usize,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub marked_zones: Vec<String>,
+    pub display_format: TimeFormat,
+    pub timezone_display_mode: TimezoneDisplayMode,
+    pub time_config: TimeDisplayConfig,
+    pub color_theme: ColorTheme,
+    pub zone_order: ZoneOrder,
+    pub show_date: bool,
+    pub show_sun_times: bool,
+    pub focus_mode: bool,
+    pub focus_ruler: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub align_to: Option<String>,
+    pub privacy_mode: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub layouts: Vec<LayoutConfig>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub archived: Vec<ZoneConfigCompat>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub milestones: Vec<Milestone>,
+    pub split_view: bool,
+    pub split_layout_index: usize,
+    /// Where the scrubber was, kept in the session file but not for undo,
+    /// which leaves moving around to the jump list
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeline_position: Option<DateTime<Utc>>,
+    /// The positions jumped from, likewise only in the session file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jumps: Option<JumpList>,
+}
+
+/// Snapshots from before each change, and from before each undo so it can
+/// be redone
+#[derive(Debug, Clone, Default)]
+pub struct UndoStack {
+    undo: Vec<Snapshot>,
+    redo: Vec<Snapshot>,
+}
+
+impl UndoStack {
+    /// Keeps the board as it was before a change; a new change can't be
+    /// redone past
+    pub fn record(&mut self, before: Snapshot) {
+        self.undo.push(before);
+        if self.undo.len() > UNDO_LIMIT {
+            self.undo.remove(0);
+        }
+        self.redo.clear();
+    }
+
+    /// The board to go back to, given the one being left
+    pub fn undo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let previous = self.undo.pop()?;
+        self.redo.push(current);
+        Some(previous)
+    }
+
+    /// The board an undo left, given the one being left
+    pub fn redo(&mut self, current: Snapshot) -> Option<Snapshot> {
+        let next = self.redo.pop()?;
+        self.undo.push(current);
+        Some(next)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+}
+
+/// The last session's board, kept next to the config for `alltz --resume`
+pub fn session_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("session.json"))
+}
+
+/// Reads the last session, treating a missing or unreadable file as none
+pub fn load(path: &Path) -> Option<Snapshot> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+pub fn save(path: &Path, snapshot: &Snapshot) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(snapshot)?)
+}
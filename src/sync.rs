@@ -0,0 +1,802 @@
+use crate::config::{SyncConfig, ZoneConfigCompat};
+use crate::platform::{run, Platform};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+use std::sync::mpsc;
+use std::thread;
+
+/// File name of the board inside a git backend
+const BOARD_FILE: &str = "board.json";
+
+/// How long a teammate's cursor is shown after it last moved, so boards
+/// that were closed drop out
+pub const CURSOR_TTL: Duration = Duration::minutes(30);
+
+/// One zone on the shared board, stamped with when it was last changed.
+/// Removed zones are kept as tombstones so a removal can win over an older
+/// copy of the zone still held by a teammate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoardEntry {
+    pub city: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub updated: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub removed: bool,
+}
+
+impl BoardEntry {
+    /// Last writer wins; exact ties are broken on content so every teammate
+    /// picks the same entry
+    fn wins_over(&self, other: &BoardEntry) -> bool {
+        (self.updated, self.removed, &self.label) > (other.updated, other.removed, &other.label)
+    }
+}
+
+/// Where a teammate's board is scrubbed to, shown as a ghost scrub line on
+/// everyone else's
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Cursor {
+    pub name: String,
+    pub position: DateTime<Utc>,
+    pub updated: DateTime<Utc>,
+}
+
+/// The part of the config a team shares: its roster of zones and labels,
+/// kept as a last-writer-wins map from city to entry, and where each
+/// teammate sharing their cursor is looking
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Board {
+    pub entries: Vec<BoardEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cursors: Vec<Cursor>,
+}
+
+impl Board {
+    /// The zones currently on the board
+    pub fn zones(&self) -> Vec<ZoneConfigCompat> {
+        self.entries
+            .iter()
+            .filter(|entry| !entry.removed)
+            .map(|entry| ZoneConfigCompat::new(entry.city.clone(), entry.label.clone()))
+            .collect()
+    }
+
+    /// Stamps every difference between the board and the local roster as a
+    /// change made at `now`
+    pub fn record(&mut self, zones: &[ZoneConfigCompat], now: DateTime<Utc>) {
+        for zone in zones {
+            let label = zone.custom_label().map(str::to_string);
+            match self.entries.iter_mut().find(|e| e.city == zone.city_name()) {
+                Some(entry) if entry.removed || entry.label != label => {
+                    entry.label = label;
+                    entry.removed = false;
+                    entry.updated = now;
+                }
+                Some(_) => {}
+                None => self.entries.push(BoardEntry {
+                    city: zone.city_name().to_string(),
+                    label,
+                    updated: now,
+                    removed: false,
+                }),
+            }
+        }
+
+        for entry in &mut self.entries {
+            if !entry.removed && !zones.iter().any(|zone| zone.city_name() == entry.city) {
+                entry.removed = true;
+                entry.updated = now;
+            }
+        }
+    }
+
+    /// Moves `name`'s cursor to `position`, stamped at `now` if it moved
+    pub fn place_cursor(&mut self, name: &str, position: DateTime<Utc>, now: DateTime<Utc>) {
+        match self.cursors.iter_mut().find(|cursor| cursor.name == name) {
+            Some(cursor) if cursor.position == position => {}
+            Some(cursor) => {
+                cursor.position = position;
+                cursor.updated = now;
+            }
+            None => self.cursors.push(Cursor {
+                name: name.to_string(),
+                position,
+                updated: now,
+            }),
+        }
+    }
+
+    /// Everyone else's cursors that moved within `CURSOR_TTL` of `now`
+    pub fn others(&self, name: &str, now: DateTime<Utc>) -> Vec<&Cursor> {
+        self.cursors
+            .iter()
+            .filter(|cursor| cursor.name != name && now - cursor.updated < CURSOR_TTL)
+            .collect()
+    }
+
+    /// Merges two boards entry by entry, keeping the newer version of each
+    /// zone and cursor. Merging is commutative, so teammates converge
+    /// whatever order their syncs happen in (only the row order may differ).
+    pub fn merge(&self, other: &Board) -> Board {
+        let mut merged = self.clone();
+        for theirs in &other.entries {
+            match merged.entries.iter_mut().find(|e| e.city == theirs.city) {
+                Some(ours) if theirs.wins_over(ours) => *ours = theirs.clone(),
+                Some(_) => {}
+                None => merged.entries.push(theirs.clone()),
+            }
+        }
+        for theirs in &other.cursors {
+            match merged.cursors.iter_mut().find(|c| c.name == theirs.name) {
+                Some(ours) if (theirs.updated, theirs.position) > (ours.updated, ours.position) => {
+                    *ours = theirs.clone()
+                }
+                Some(_) => {}
+                None => merged.cursors.push(theirs.clone()),
+            }
+        }
+        merged
+    }
+}
+
+/// How many times an exchange pulls again after a teammate's push got in
+/// between its pull and push
+const PUSH_ATTEMPTS: usize = 3;
+
+/// Storage for the shared board
+pub trait BoardStore: Send {
+    /// Fetches the board with its version, if the store has one (an HTTP
+    /// ETag), or `None` if nothing has been shared yet
+    fn pull(&self) -> io::Result<Option<(Board, Option<String>)>>;
+    /// Writes the board, unless the shared one has changed since it was
+    /// pulled at `version` (or, without one, since it was seen empty), in
+    /// which case nothing is written and this returns `false`
+    fn push(&self, board: &Board, version: Option<&str>) -> io::Result<bool>;
+}
+
+fn parse_board(content: &str) -> io::Result<Board> {
+    serde_json::from_str(content).map_err(io::Error::other)
+}
+
+fn board_json(board: &Board) -> io::Result<String> {
+    serde_json::to_string_pretty(board).map_err(io::Error::other)
+}
+
+/// A git repository, used through a local checkout kept next to the config
+pub struct GitStore {
+    pub repo: String,
+    pub checkout: PathBuf,
+}
+
+impl GitStore {
+    fn git(&self) -> Command {
+        let mut command = Command::new("git");
+        command.arg("-C").arg(&self.checkout);
+        command
+    }
+}
+
+impl BoardStore for GitStore {
+    fn pull(&self) -> io::Result<Option<(Board, Option<String>)>> {
+        if self.checkout.join(".git").exists() {
+            run(self.git().args(["fetch", "--quiet", "origin"]))?;
+        } else {
+            fs::create_dir_all(&self.checkout)?;
+            run(Command::new("git")
+                .args(["clone", "--quiet", &self.repo])
+                .arg(&self.checkout))?;
+        }
+
+        // An empty repository has no upstream branch yet
+        if run(self
+            .git()
+            .args(["rev-parse", "--verify", "--quiet", "@{u}"]))
+        .is_err()
+        {
+            return Ok(None);
+        }
+        run(self.git().args(["reset", "--quiet", "--hard", "@{u}"]))?;
+        // Git keeps its own version: a push that isn't a fast-forward fails
+        match fs::read_to_string(self.checkout.join(BOARD_FILE)) {
+            Ok(content) => parse_board(&content).map(|board| Some((board, None))),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    fn push(&self, board: &Board, _version: Option<&str>) -> io::Result<bool> {
+        fs::write(self.checkout.join(BOARD_FILE), board_json(board)?)?;
+        run(self.git().args(["add", BOARD_FILE]))?;
+        if run(self.git().args(["status", "--porcelain"]))?.is_empty() {
+            return Ok(true);
+        }
+
+        // Commit as the user when git knows who they are
+        let mut commit = self.git();
+        if run(self.git().args(["config", "user.email"])).is_err() {
+            commit.args(["-c", "user.name=alltz", "-c", "user.email=alltz@localhost"]);
+        }
+        run(commit.args(["commit", "--quiet", "-m", "Update alltz board"]))?;
+        match run(self.git().args(["push", "--quiet", "-u", "origin", "HEAD"])) {
+            Ok(_) => Ok(true),
+            // Someone pushed first; the next pull resets onto their commit
+            Err(e) if e.to_string().contains("[rejected]") => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// A JSON document behind a URL, read with GET and written with PUT using
+/// curl. Writes are conditional on the ETag read, so teammates' boards
+/// can't overwrite each other's changes.
+pub struct HttpStore {
+    pub url: String,
+}
+
+/// The status code and ETag in the headers curl dumps before a response
+/// body, and the body
+fn split_response(output: &str) -> (&str, Option<String>, &str) {
+    let (headers, body) = output.split_once("\r\n\r\n").unwrap_or((output, ""));
+    let mut lines = headers.lines();
+    let status = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("");
+    let etag = lines.find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.eq_ignore_ascii_case("etag")
+            .then(|| value.trim().to_string())
+    });
+    (status, etag, body)
+}
+
+impl BoardStore for HttpStore {
+    fn pull(&self) -> io::Result<Option<(Board, Option<String>)>> {
+        let output = run(Command::new("curl").args([
+            "--silent",
+            "--show-error",
+            "--dump-header",
+            "-",
+            &self.url,
+        ]))?;
+        match split_response(&output) {
+            ("200", etag, body) => parse_board(body).map(|board| Some((board, etag))),
+            ("404", _, _) => Ok(None),
+            (status, _, _) => Err(io::Error::other(format!("HTTP {status} from {}", self.url))),
+        }
+    }
+
+    fn push(&self, board: &Board, version: Option<&str>) -> io::Result<bool> {
+        let condition = match version {
+            Some(etag) => format!("If-Match: {etag}"),
+            None => "If-None-Match: *".to_string(),
+        };
+        let mut curl = Command::new("curl")
+            .args([
+                "--silent",
+                "--show-error",
+                "--output",
+                "/dev/null",
+                "--write-out",
+                "%{http_code}",
+                "--request",
+                "PUT",
+                "--header",
+                "Content-Type: application/json",
+                "--header",
+                &condition,
+                "--data-binary",
+                "@-",
+                &self.url,
+            ])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = curl.stdin.take() {
+            io::Write::write_all(&mut stdin, board_json(board)?.as_bytes())?;
+        }
+        let output = curl.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(stderr.trim().to_string()));
+        }
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "412" => Ok(false),
+            status if status.starts_with('2') => Ok(true),
+            status => Err(io::Error::other(format!("HTTP {status} from {}", self.url))),
+        }
+    }
+}
+
+/// Keeps the local board in step with the team's shared one
+pub struct BoardSync {
+    store: Box<dyn BoardStore>,
+    state_path: PathBuf,
+}
+
+impl fmt::Debug for BoardSync {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BoardSync")
+            .field("state_path", &self.state_path)
+            .finish()
+    }
+}
+
+impl BoardSync {
+    pub fn new(store: Box<dyn BoardStore>, state_path: PathBuf) -> Self {
+        Self { store, state_path }
+    }
+
+    /// Sync state lives next to the config: the board as last seen here,
+    /// and for git a checkout of the repository
+    pub fn from_config(config: &SyncConfig) -> Option<Self> {
+        let dir = Platform::current().config_dir()?;
+        let store: Box<dyn BoardStore> = match config {
+            SyncConfig::Git { repo } => Box::new(GitStore {
+                repo: repo.clone(),
+                checkout: dir.join("board"),
+            }),
+            SyncConfig::Http { url } => Box::new(HttpStore { url: url.clone() }),
+        };
+        Some(Self::new(store, dir.join("board-state.json")))
+    }
+
+    fn read_state(&self) -> Option<Board> {
+        parse_board(&fs::read_to_string(&self.state_path).ok()?).ok()
+    }
+
+    fn write_state(&self, board: &Board) -> io::Result<()> {
+        fs::write(&self.state_path, board_json(board)?)
+    }
+
+    /// Stamps local changes into the saved state. This is quick and works
+    /// offline, so edits keep the time they were made even if the exchange
+    /// with the shared board happens much later.
+    pub fn record(&self, zones: &[ZoneConfigCompat]) -> io::Result<()> {
+        self.record_at(zones, Utc::now())
+    }
+
+    fn record_at(&self, zones: &[ZoneConfigCompat], now: DateTime<Utc>) -> io::Result<()> {
+        // Before the first sync there is nothing to compare against
+        let Some(mut state) = self.read_state() else {
+            return Ok(());
+        };
+        state.record(zones, now);
+        self.write_state(&state)
+    }
+
+    /// Whether this board has exchanged with the shared one yet
+    pub fn joined(&self) -> bool {
+        self.read_state().is_some()
+    }
+
+    /// Pulls the shared board, works out what it should become with
+    /// `update` and pushes that if it differs. When a teammate pushed in
+    /// between, their board is pulled and updated again.
+    fn update_shared(&self, update: impl Fn(Option<&Board>) -> Board) -> io::Result<Board> {
+        for _ in 0..PUSH_ATTEMPTS {
+            let (remote, version) = self.store.pull()?.unzip();
+            let board = update(remote.as_ref());
+            if remote.as_ref() == Some(&board)
+                || self.store.push(&board, version.flatten().as_deref())?
+            {
+                return Ok(board);
+            }
+        }
+        Err(io::Error::other(
+            "the shared board kept changing while syncing; will try again",
+        ))
+    }
+
+    /// Pulls the shared board, merges the saved state into it and pushes the
+    /// result if it differs. Slow, as it goes over the network: see `spawn`.
+    fn exchange_at(&self, zones: &[ZoneConfigCompat], now: DateTime<Utc>) -> io::Result<Board> {
+        let state = self.read_state();
+        self.update_shared(|remote| match (&state, remote) {
+            (Some(state), Some(remote)) => state.merge(remote),
+            // Joining a board for the first time adds the zones only this
+            // board has to it
+            (None, Some(remote)) => {
+                let shared = remote.zones();
+                let mut joined = shared.clone();
+                joined.extend(
+                    zones
+                        .iter()
+                        .filter(|zone| !shared.iter().any(|s| s.city_name() == zone.city_name()))
+                        .cloned(),
+                );
+                let mut board = remote.clone();
+                board.record(&joined, now);
+                board
+            }
+            // Nothing shared yet: this board becomes the team's
+            (Some(state), None) => state.clone(),
+            (None, None) => {
+                let mut board = Board::default();
+                board.record(zones, now);
+                board
+            }
+        })
+    }
+
+    /// Moves `name`'s cursor on the shared board to `position`, leaving the
+    /// zones there as they are. The board is only pushed back if the cursor
+    /// moved, so a board left open doesn't write every few seconds.
+    fn share_cursor_at(
+        &self,
+        name: &str,
+        position: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> io::Result<Board> {
+        let state = self.read_state().unwrap_or_default();
+        self.update_shared(|remote| {
+            let mut board = remote.unwrap_or(&state).clone();
+            board.place_cursor(name, position, now);
+            board
+        })
+    }
+
+    /// Folds an exchanged board into the saved state, keeping any changes
+    /// recorded while the exchange ran. Returns the board as it now stands.
+    pub fn apply(&self, board: &Board) -> io::Result<Board> {
+        let state = match self.read_state() {
+            Some(state) => state.merge(board),
+            None => board.clone(),
+        };
+        self.write_state(&state)?;
+        Ok(state)
+    }
+
+    /// Runs the exchange on a background thread so startup and editing never
+    /// wait on the network
+    pub fn spawn(self, zones: Vec<ZoneConfigCompat>) -> SyncTask {
+        self.spawn_with(move |sync| sync.exchange_at(&zones, Utc::now()))
+    }
+
+    /// Shares where this board is scrubbed to on a background thread,
+    /// fetching teammates' cursors and zones as it goes
+    pub fn spawn_cursor(self, name: String, position: DateTime<Utc>) -> SyncTask {
+        self.spawn_with(move |sync| sync.share_cursor_at(&name, position, Utc::now()))
+    }
+
+    fn spawn_with(
+        self,
+        exchange: impl FnOnce(&Self) -> io::Result<Board> + Send + 'static,
+    ) -> SyncTask {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(exchange(&self));
+        });
+        SyncTask {
+            receiver,
+            rerun: false,
+        }
+    }
+}
+
+/// A board exchange running in the background
+#[derive(Debug)]
+pub struct SyncTask {
+    receiver: mpsc::Receiver<io::Result<Board>>,
+    /// Set when local changes were recorded after the exchange started
+    pub rerun: bool,
+}
+
+impl SyncTask {
+    /// The exchanged board, once the background thread has finished
+    pub fn try_result(&self) -> Option<io::Result<Board>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Some(Err(io::Error::other("sync thread stopped")))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn zones(zones: &[(&str, Option<&str>)]) -> Vec<ZoneConfigCompat> {
+        zones
+            .iter()
+            .map(|(city, label)| ZoneConfigCompat::new(city.to_string(), label.map(str::to_string)))
+            .collect()
+    }
+
+    fn at(hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 1, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut shared = Board::default();
+        shared.record(
+            &zones(&[("London", None), ("Tokyo", None), ("Berlin", None)]),
+            at(8),
+        );
+
+        // Offline, Alice labels Tokyo and removes Berlin; Bob adds Lima, then
+        // labels Tokyo too and relabels Berlin later than Alice removed it
+        let mut alice = shared.clone();
+        alice.record(&zones(&[("London", None), ("Tokyo", Some("HQ"))]), at(9));
+        let mut bob = shared.clone();
+        bob.record(
+            &zones(&[
+                ("London", None),
+                ("Tokyo", Some("Office")),
+                ("Berlin", Some("Ops")),
+                ("Lima", None),
+            ]),
+            at(10),
+        );
+
+        let merged = alice.merge(&bob);
+        assert_eq!(merged, bob.merge(&alice).merge(&merged));
+        assert_eq!(
+            merged.zones(),
+            zones(&[
+                ("London", None),
+                ("Tokyo", Some("Office")),
+                ("Berlin", Some("Ops")),
+                ("Lima", None),
+            ])
+        );
+
+        // A later removal wins over Bob's older copy, and stays as a tombstone
+        let mut alice = merged;
+        alice.record(&zones(&[("London", None)]), at(11));
+        let merged = bob.merge(&alice);
+        assert_eq!(merged.zones(), zones(&[("London", None)]));
+        assert!(merged
+            .entries
+            .iter()
+            .all(|e| e.city == "London" || e.removed));
+    }
+
+    #[test]
+    fn test_cursors() {
+        let mut alice = Board::default();
+        alice.place_cursor("Alice", at(15), at(9));
+        let mut bob = Board::default();
+        bob.place_cursor("Bob", at(12), at(9));
+        bob.place_cursor("Alice", at(13), at(8));
+
+        // Alice's newer position wins on both boards
+        let merged = alice.merge(&bob);
+        let names = |board: &Board| {
+            let mut cursors = board.cursors.clone();
+            cursors.sort_by(|a, b| a.name.cmp(&b.name));
+            cursors
+        };
+        assert_eq!(names(&merged), names(&bob.merge(&alice)));
+        let others: Vec<(&str, DateTime<Utc>)> = merged
+            .others("Bob", at(9) + Duration::minutes(10))
+            .iter()
+            .map(|cursor| (cursor.name.as_str(), cursor.position))
+            .collect();
+        assert_eq!(others, vec![("Alice", at(15))]);
+
+        // Staying put keeps the stamp, so a closed board drops out in time
+        let mut merged = merged;
+        merged.place_cursor("Alice", at(15), at(11));
+        assert!(merged.others("Bob", at(9) + CURSOR_TTL).is_empty());
+        merged.place_cursor("Alice", at(16), at(11));
+        assert_eq!(merged.others("Bob", at(9) + CURSOR_TTL).len(), 1);
+    }
+
+    /// A shared board held in memory, versioned by how many pushes it has
+    /// had, the way a server's ETag would change
+    #[derive(Clone, Default)]
+    struct MemoryStore {
+        board: std::sync::Arc<std::sync::Mutex<(Option<Board>, usize)>>,
+    }
+
+    impl MemoryStore {
+        fn pushes(&self) -> usize {
+            self.board.lock().unwrap().1
+        }
+    }
+
+    impl BoardStore for MemoryStore {
+        fn pull(&self) -> io::Result<Option<(Board, Option<String>)>> {
+            let (board, pushes) = self.board.lock().unwrap().clone();
+            Ok(board.map(|board| (board, Some(pushes.to_string()))))
+        }
+
+        fn push(&self, board: &Board, version: Option<&str>) -> io::Result<bool> {
+            let mut shared = self.board.lock().unwrap();
+            let current = shared.0.as_ref().map(|_| shared.1.to_string());
+            if version.map(str::to_string) != current {
+                return Ok(false);
+            }
+            *shared = (Some(board.clone()), shared.1 + 1);
+            Ok(true)
+        }
+    }
+
+    /// A store that lets a teammate push just before its first push
+    struct RacingStore {
+        store: MemoryStore,
+        teammate: std::sync::Mutex<Option<Board>>,
+    }
+
+    impl BoardStore for RacingStore {
+        fn pull(&self) -> io::Result<Option<(Board, Option<String>)>> {
+            self.store.pull()
+        }
+
+        fn push(&self, board: &Board, version: Option<&str>) -> io::Result<bool> {
+            if let Some(theirs) = self.teammate.lock().unwrap().take() {
+                let (_, their_version) = self.store.pull()?.unzip();
+                assert!(self
+                    .store
+                    .push(&theirs, their_version.flatten().as_deref())?);
+            }
+            self.store.push(board, version)
+        }
+    }
+
+    #[test]
+    fn test_split_response() {
+        let output = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nETag: \"v7\"\r\n\r\n{}";
+        assert_eq!(
+            split_response(output),
+            ("200", Some("\"v7\"".to_string()), "{}")
+        );
+        assert_eq!(split_response("HTTP/2 404\r\n\r\n"), ("404", None, ""));
+    }
+
+    #[test]
+    fn test_push_race() {
+        let root = std::env::temp_dir().join(format!("alltz-race-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let store = MemoryStore::default();
+        let mut shared = Board::default();
+        shared.record(&zones(&[("London", None)]), at(8));
+        assert!(store.push(&shared, None).unwrap());
+
+        // A teammate adds Lima between this board's pull and push; the push
+        // is refused and retried on top of theirs
+        let mut theirs = shared.clone();
+        theirs.record(&zones(&[("London", None), ("Lima", None)]), at(9));
+        let racing = RacingStore {
+            store: store.clone(),
+            teammate: std::sync::Mutex::new(Some(theirs)),
+        };
+        let sync = BoardSync::new(Box::new(racing), root.join("state.json"));
+        fs::write(root.join("state.json"), board_json(&shared).unwrap()).unwrap();
+        sync.record_at(&zones(&[("London", None), ("Tokyo", None)]), at(10))
+            .unwrap();
+        let board = sync.exchange_at(&[], at(10)).unwrap();
+        assert_eq!(
+            board.zones(),
+            zones(&[("London", None), ("Tokyo", None), ("Lima", None)])
+        );
+        assert_eq!(store.pull().unwrap().unwrap().0, board);
+        assert_eq!(store.pushes(), 3);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_first_join_merges() {
+        let root = std::env::temp_dir().join(format!("alltz-join-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let store = MemoryStore::default();
+        let mut shared = Board::default();
+        shared.record(&zones(&[("London", Some("HQ")), ("Tokyo", None)]), at(8));
+        assert!(store.push(&shared, None).unwrap());
+
+        // The team's label wins; zones only this board has are added
+        let sync = BoardSync::new(Box::new(store.clone()), root.join("state.json"));
+        let board = sync
+            .exchange_at(&zones(&[("London", None), ("Lima", None)]), at(9))
+            .unwrap();
+        assert_eq!(
+            board.zones(),
+            zones(&[("London", Some("HQ")), ("Tokyo", None), ("Lima", None)])
+        );
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_share_cursor() {
+        let root = std::env::temp_dir().join(format!("alltz-cursor-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let store = MemoryStore::default();
+        let sync = BoardSync::new(Box::new(store.clone()), root.join("state.json"));
+        let shared = zones(&[("London", None), ("Tokyo", None)]);
+        sync.exchange_at(&shared, at(8)).unwrap();
+        assert_eq!(store.pushes(), 1);
+
+        // Only the cursor is shared, however the local zones have changed
+        let board = sync.share_cursor_at("Ana", at(15), at(9)).unwrap();
+        assert_eq!(board.zones(), shared);
+        assert_eq!(board.cursors[0].position, at(15));
+        assert_eq!(store.pushes(), 2);
+
+        // A cursor that stayed put isn't pushed again
+        sync.share_cursor_at("Ana", at(15), at(10)).unwrap();
+        assert_eq!(store.pushes(), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_git_sync() {
+        if Command::new("git").arg("--version").output().is_err() {
+            return;
+        }
+        let root = std::env::temp_dir().join(format!("alltz-sync-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        let remote = root.join("remote.git");
+        run(Command::new("git")
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote))
+        .unwrap();
+
+        let teammate = |name: &str| {
+            let store = GitStore {
+                repo: remote.to_string_lossy().into_owned(),
+                checkout: root.join(name).join("board"),
+            };
+            BoardSync::new(Box::new(store), root.join(name).join("state.json"))
+        };
+        // What the app does: record, exchange in the background, then apply
+        let sync_at = |sync: &BoardSync, zones: &[ZoneConfigCompat], now| {
+            sync.record_at(zones, now).unwrap();
+            let board = sync.exchange_at(zones, now).unwrap();
+            sync.apply(&board).unwrap().zones()
+        };
+        let alice = teammate("alice");
+        let bob = teammate("bob");
+
+        // Alice shares first; Bob joins, adding his zone to her board
+        let shared = zones(&[("London", None), ("Tokyo", None)]);
+        assert_eq!(sync_at(&alice, &shared, at(8)), shared);
+        assert_eq!(
+            sync_at(&bob, &zones(&[("Sydney", None)]), at(8)),
+            zones(&[("London", None), ("Tokyo", None), ("Sydney", None)])
+        );
+
+        // Alice labels London while offline; Bob meanwhile adds Lima
+        let offline = zones(&[("London", Some("Office")), ("Tokyo", None)]);
+        alice.record_at(&offline, at(9)).unwrap();
+        sync_at(
+            &bob,
+            &zones(&[("London", None), ("Tokyo", None), ("Lima", None)]),
+            at(10),
+        );
+
+        // Back online, her older edit still merges with Bob's newer one
+        let merged = sync_at(&alice, &offline, at(11));
+        assert_eq!(
+            merged,
+            zones(&[("London", Some("Office")), ("Tokyo", None), ("Lima", None)])
+        );
+
+        // Bob's exchange runs in the background, as in the app
+        let task = bob.spawn(merged.clone());
+        let board = loop {
+            if let Some(result) = task.try_result() {
+                break result.unwrap();
+            }
+            thread::sleep(std::time::Duration::from_millis(10));
+        };
+        assert_eq!(board.zones(), merged);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
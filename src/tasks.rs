@@ -0,0 +1,152 @@
+use crate::config::{Milestone, TasksConfig};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use serde::Deserialize;
+use std::process::Command;
+
+/// Due dates from todo.txt lines, as milestones named for the task.
+/// Finished tasks (`x ...`) and tasks without `due:` are skipped.
+///
+/// ```text
+/// (A) Send the release notes +launch due:2024-06-01T15:00
+/// Book flights due:2024-06-03
+/// ```
+///
+/// A due date without a time means the end of that day on `zone`'s clocks.
+pub fn todo_txt<Z: TimeZone>(content: &str, zone: &Z) -> Vec<Milestone> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with("x "))
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let due = words.iter().find_map(|word| word.strip_prefix("due:"))?;
+            let time = parse_due(due, zone)?;
+            // The description without its priority and key:value tags
+            let first = usize::from(words.first().is_some_and(|word| is_priority(word)));
+            let name: Vec<&str> = words[first..]
+                .iter()
+                .copied()
+                .filter(|word| !is_tag(word))
+                .collect();
+            Some(Milestone {
+                name: name.join(" "),
+                time,
+                group: None,
+            })
+        })
+        .collect()
+}
+
+fn is_priority(word: &str) -> bool {
+    matches!(word.as_bytes(), [b'(', b'A'..=b'Z', b')'])
+}
+
+fn is_tag(word: &str) -> bool {
+    word.split_once(':')
+        .is_some_and(|(key, value)| !key.is_empty() && !value.is_empty() && !value.starts_with('/'))
+}
+
+fn parse_due<Z: TimeZone>(due: &str, zone: &Z) -> Option<DateTime<Utc>> {
+    let local = match NaiveDateTime::parse_from_str(due, "%Y-%m-%dT%H:%M") {
+        Ok(local) => local,
+        Err(_) => NaiveDate::parse_from_str(due, "%Y-%m-%d")
+            .ok()?
+            .and_hms_opt(0, 0, 0)?
+            .checked_add_signed(Duration::days(1))?,
+    };
+    let time = local.and_local_timezone(zone.clone()).earliest()?;
+    Some(time.with_timezone(&Utc))
+}
+
+/// A task as `task export` prints it
+#[derive(Deserialize)]
+struct ExportedTask {
+    description: String,
+    #[serde(default)]
+    status: String,
+    due: Option<String>,
+}
+
+/// Due dates from taskwarrior's JSON export, as milestones named for the
+/// task. Only pending tasks are kept.
+pub fn taskwarrior(export: &str) -> Vec<Milestone> {
+    let tasks: Vec<ExportedTask> = serde_json::from_str(export).unwrap_or_default();
+    tasks
+        .into_iter()
+        .filter(|task| task.status == "pending")
+        .filter_map(|task| {
+            let due = NaiveDateTime::parse_from_str(task.due.as_deref()?, "%Y%m%dT%H%M%SZ").ok()?;
+            Some(Milestone {
+                name: task.description,
+                time: due.and_utc(),
+                group: None,
+            })
+        })
+        .collect()
+}
+
+/// Every configured source's due dates. A source that can't be read is
+/// noted in the log and left out, so a missing file or `task` binary never
+/// stops the board opening.
+pub fn load(config: &TasksConfig) -> Vec<Milestone> {
+    let mut tasks = Vec::new();
+    if let Some(path) = &config.todo_txt {
+        match std::fs::read_to_string(path) {
+            Ok(content) => tasks.extend(todo_txt(&content, &Local)),
+            Err(e) => crate::log::write(&format!("Failed to read {}: {e}", path.display())),
+        }
+    }
+    if config.taskwarrior {
+        match Command::new("task")
+            .args(["status:pending", "export"])
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                tasks.extend(taskwarrior(&String::from_utf8_lossy(&output.stdout)))
+            }
+            Ok(output) => crate::log::write(&format!(
+                "taskwarrior failed ({}): {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )),
+            Err(e) => crate::log::write(&format!("Failed to run taskwarrior: {e}")),
+        }
+    }
+    tasks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_todo_txt() {
+        let content = "\
+(A) Send the release notes +launch @email due:2024-06-01T15:00
+x 2024-05-30 Done already due:2024-05-31
+Book flights due:2024-06-03 see https://example.com
+No deadline here
+";
+        let tasks = todo_txt(content, &chrono_tz::Europe::Madrid);
+        assert_eq!(tasks.len(), 2);
+        assert_eq!(tasks[0].name, "Send the release notes +launch @email");
+        assert_eq!(tasks[0].time.to_rfc3339(), "2024-06-01T13:00:00+00:00");
+        // A date alone is due by the end of the day
+        assert_eq!(tasks[1].name, "Book flights see https://example.com");
+        assert_eq!(tasks[1].time.to_rfc3339(), "2024-06-03T22:00:00+00:00");
+    }
+
+    #[test]
+    fn test_taskwarrior() {
+        let export = r#"[
+            {"id":1,"description":"Review the RFC","status":"pending","due":"20240601T130000Z"},
+            {"id":0,"description":"Old task","status":"completed","due":"20240501T130000Z"},
+            {"id":2,"description":"Someday","status":"pending"}
+        ]"#;
+        let tasks = taskwarrior(export);
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].name, "Review the RFC");
+        assert_eq!(tasks[0].time.to_rfc3339(), "2024-06-01T13:00:00+00:00");
+        assert!(taskwarrior("not json").is_empty());
+    }
+}
@@ -0,0 +1,106 @@
+use crate::config::{AppConfig, LayoutConfig, Milestone, ZoneConfigCompat};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use clap::ValueEnum;
+
+/// Ready-made layouts with milestones, added to the config on request
+#[derive(Debug, Clone, Copy, PartialEq, ValueEnum)]
+pub enum Template {
+    /// Deployment regions plus code freeze, canary and GA milestones
+    ReleaseTrain,
+}
+
+impl Template {
+    pub fn layout_name(self) -> &'static str {
+        match self {
+            Template::ReleaseTrain => "Release train",
+        }
+    }
+
+    /// The milestone group the template's milestones share
+    pub fn group(self) -> &'static str {
+        match self {
+            Template::ReleaseTrain => "release",
+        }
+    }
+
+    /// The layout and milestones, scheduled around `date` (GA for a release)
+    pub fn build(self, date: NaiveDate) -> (LayoutConfig, Vec<Milestone>) {
+        match self {
+            Template::ReleaseTrain => {
+                // One city per common cloud region, labelled with the region
+                let regions = [
+                    ("Portland", "us-west-2"),
+                    ("New York", "us-east-1"),
+                    ("São Paulo", "sa-east-1"),
+                    ("Dublin", "eu-west-1"),
+                    ("Frankfurt", "eu-central-1"),
+                    ("Mumbai", "ap-south-1"),
+                    ("Singapore", "ap-southeast-1"),
+                    ("Tokyo", "ap-northeast-1"),
+                    ("Sydney", "ap-southeast-2"),
+                ];
+                let layout = LayoutConfig {
+                    name: self.layout_name().to_string(),
+                    zones: regions
+                        .iter()
+                        .map(|(city, region)| {
+                            ZoneConfigCompat::new(city.to_string(), Some(region.to_string()))
+                        })
+                        .collect(),
+                    theme: None,
+                };
+
+                // GA at 16:00 UTC, when the US east coast and Europe are both at work
+                let ga = date.and_hms_opt(16, 0, 0).unwrap().and_utc();
+                let milestone = |name: &str, time: DateTime<Utc>| Milestone {
+                    name: name.to_string(),
+                    time,
+                    group: Some(self.group().to_string()),
+                };
+                let milestones = vec![
+                    milestone("Code freeze", ga - Duration::days(7)),
+                    milestone("Canary", ga - Duration::days(2)),
+                    milestone("GA", ga),
+                ];
+                (layout, milestones)
+            }
+        }
+    }
+
+    /// Adds the template to a config, replacing an earlier copy of it
+    pub fn apply(self, config: &mut AppConfig, date: NaiveDate) {
+        let (layout, milestones) = self.build(date);
+        config.layouts.retain(|l| l.name != layout.name);
+        config.layouts.push(layout);
+        config
+            .milestones
+            .retain(|m| m.group.as_deref() != Some(self.group()));
+        config.milestones.extend(milestones);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time::TimeZoneManager;
+
+    #[test]
+    fn test_release_train() {
+        let ga = NaiveDate::from_ymd_opt(2024, 6, 20).unwrap();
+        let mut config = AppConfig::default();
+        Template::ReleaseTrain.apply(&mut config, ga);
+        Template::ReleaseTrain.apply(&mut config, ga);
+
+        // Applying again replaces rather than duplicates
+        assert_eq!(config.layouts.len(), 1);
+        assert_eq!(config.milestones.len(), 3);
+        assert!(config.layouts[0]
+            .zones
+            .iter()
+            .all(|zone| TimeZoneManager::find_city(zone.city_name()).is_some()));
+        assert_eq!(
+            config.milestones[0].time.to_rfc3339(),
+            "2024-06-13T16:00:00+00:00"
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use crate::platform::Platform;
+use crossterm::{
+    cursor::{self, MoveTo},
+    execute,
+    style::Print,
+    terminal::{Clear, ClearType},
+};
+use ratatui::style::Color;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+/// How many colors the terminal can show
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorSupport {
+    TrueColor,
+    Ansi256,
+    Ansi16,
+    None,
+}
+
+/// The 16 ANSI colors with xterm's default values, in index order
+const ANSI_COLORS: [(Color, [u8; 3]); 16] = [
+    (Color::Black, [0, 0, 0]),
+    (Color::Red, [205, 0, 0]),
+    (Color::Green, [0, 205, 0]),
+    (Color::Yellow, [205, 205, 0]),
+    (Color::Blue, [0, 0, 238]),
+    (Color::Magenta, [205, 0, 205]),
+    (Color::Cyan, [0, 205, 205]),
+    (Color::Gray, [229, 229, 229]),
+    (Color::DarkGray, [127, 127, 127]),
+    (Color::LightRed, [255, 0, 0]),
+    (Color::LightGreen, [0, 255, 0]),
+    (Color::LightYellow, [255, 255, 0]),
+    (Color::LightBlue, [92, 92, 255]),
+    (Color::LightMagenta, [255, 0, 255]),
+    (Color::LightCyan, [0, 255, 255]),
+    (Color::White, [255, 255, 255]),
+];
+
+/// Channel values of the 6×6×6 cube in the 256-color palette
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+impl ColorSupport {
+    /// The nearest color the terminal can show to `color`, or the
+    /// terminal's own when it shows none
+    pub fn fit(self, color: Color) -> Color {
+        match (self, color) {
+            (ColorSupport::TrueColor, _) | (_, Color::Reset) => color,
+            (ColorSupport::None, _) => Color::Reset,
+            (ColorSupport::Ansi256, Color::Rgb(r, g, b)) => {
+                Color::Indexed(nearest_indexed([r, g, b]))
+            }
+            (ColorSupport::Ansi256, _) => color,
+            (ColorSupport::Ansi16, Color::Indexed(index)) if index < 16 => {
+                ANSI_COLORS[index as usize].0
+            }
+            (ColorSupport::Ansi16, Color::Indexed(index)) => nearest_ansi(indexed_rgb(index)),
+            (ColorSupport::Ansi16, Color::Rgb(r, g, b)) => nearest_ansi([r, g, b]),
+            (ColorSupport::Ansi16, _) => color,
+        }
+    }
+}
+
+fn distance(a: [u8; 3], b: [u8; 3]) -> u32 {
+    a.iter()
+        .zip(b)
+        .map(|(&a, b)| (a as i32 - b as i32).pow(2) as u32)
+        .sum()
+}
+
+/// The value of a 256-color palette entry past the 16 ANSI ones
+fn indexed_rgb(index: u8) -> [u8; 3] {
+    if index >= 232 {
+        let gray = 8 + 10 * (index - 232);
+        return [gray; 3];
+    }
+    let cube = index.saturating_sub(16);
+    [cube / 36, cube / 6 % 6, cube % 6].map(|level| CUBE_LEVELS[level as usize])
+}
+
+/// The closest color of the cube or the gray ramp in the 256-color palette
+fn nearest_indexed(rgb: [u8; 3]) -> u8 {
+    let level = |value: u8| {
+        (0..6)
+            .min_by_key(|&i| (CUBE_LEVELS[i] as i32 - value as i32).abs())
+            .unwrap_or(0) as u8
+    };
+    let [r, g, b] = rgb.map(level);
+    let cube = 16 + 36 * r + 6 * g + b;
+    let average = rgb.iter().map(|&c| c as u32).sum::<u32>() / 3;
+    let gray = 232 + ((average.saturating_sub(3)) / 10).min(23) as u8;
+    if distance(indexed_rgb(gray), rgb) < distance(indexed_rgb(cube), rgb) {
+        gray
+    } else {
+        cube
+    }
+}
+
+fn nearest_ansi(rgb: [u8; 3]) -> Color {
+    ANSI_COLORS
+        .iter()
+        .min_by_key(|(_, value)| distance(*value, rgb))
+        .map(|(color, _)| *color)
+        .unwrap_or(Color::Reset)
+}
+
+/// Inline image protocols for drawing real graphics in the terminal
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Sixel,
+}
+
+/// Whether the terminal draws on a light or a dark background
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+/// What the terminal can do, so rendering can avoid output it would garble
+#[derive(Debug, Clone, PartialEq)]
+pub struct Capabilities {
+    pub term: Option<String>,
+    pub color: ColorSupport,
+    pub unicode: bool,
+    /// Whether box drawing and shade characters take two columns, as in many
+    /// CJK terminal setups. `None` until measured with `probe_ambiguous_width`.
+    pub ambiguous_wide: Option<bool>,
+    pub mouse: bool,
+    pub graphics: Option<GraphicsProtocol>,
+    /// From `COLORFGBG` until asked with `probe_background`
+    pub background: Option<Background>,
+}
+
+impl Capabilities {
+    /// Works out capabilities from the environment the terminal sets up
+    pub fn from_env(platform: Platform, env: impl Fn(&str) -> Option<String>) -> Self {
+        let term = env("TERM");
+        let term_name = term.as_deref().unwrap_or_default();
+        let legacy_console = platform.is_legacy_console(&env);
+        let dumb = term_name == "dumb";
+
+        let color = if env("NO_COLOR").is_some_and(|v| !v.is_empty()) || dumb {
+            ColorSupport::None
+        } else if env("COLORTERM").is_some_and(|v| v == "truecolor" || v == "24bit")
+            || env("WT_SESSION").is_some()
+        {
+            ColorSupport::TrueColor
+        } else if term_name.contains("256color") {
+            ColorSupport::Ansi256
+        } else {
+            ColorSupport::Ansi16
+        };
+
+        // The Linux virtual console only has a small hardware font. Without
+        // any locale set, as under some service managers and minimal
+        // containers, the terminal's own encoding isn't known, and nearly
+        // every terminal now uses UTF-8.
+        let unicode = match platform {
+            Platform::Windows => !legacy_console,
+            Platform::Unix => {
+                let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+                    .iter()
+                    .find_map(|name| env(name).filter(|v| !v.is_empty()))
+                    .map(|locale| locale.to_lowercase());
+                let utf8 =
+                    locale.is_none_or(|locale| locale.contains("utf-8") || locale.contains("utf8"));
+                utf8 && term_name != "linux" && !dumb
+            }
+        };
+
+        // Terminals don't advertise graphics support in the environment, so
+        // recognise the ones known to implement each protocol
+        let term_program = env("TERM_PROGRAM").unwrap_or_default();
+        let graphics = if env("KITTY_WINDOW_ID").is_some()
+            || matches!(term_name, "xterm-kitty" | "xterm-ghostty")
+            || matches!(term_program.as_str(), "WezTerm" | "ghostty")
+        {
+            Some(GraphicsProtocol::Kitty)
+        } else if matches!(
+            term_name,
+            "foot" | "foot-extra" | "mlterm" | "yaft-256color"
+        ) || term_name.contains("sixel")
+            || term_program == "iTerm.app"
+        {
+            Some(GraphicsProtocol::Sixel)
+        } else {
+            None
+        };
+
+        // rxvt-style "fg;bg" (sometimes "fg;default;bg") with ANSI color
+        // numbers, where only white and light gray backgrounds are light
+        let background = env("COLORFGBG")
+            .and_then(|value| value.rsplit(';').next()?.parse::<u8>().ok())
+            .map(|bg| match bg {
+                7 | 15 => Background::Light,
+                _ => Background::Dark,
+            });
+
+        Self {
+            term,
+            color,
+            unicode,
+            ambiguous_wide: None,
+            mouse: !dumb && !legacy_console,
+            graphics,
+            background,
+        }
+    }
+
+    /// Prints an ambiguous-width character and asks the terminal where the
+    /// cursor ended up, giving up quickly on one that doesn't say. Needs raw
+    /// mode so the position report can be read.
+    pub fn probe_ambiguous_width(&mut self, out: &mut impl Write) -> io::Result<()> {
+        execute!(out, MoveTo(0, 0), Print('░'))?;
+        let column = if cfg!(unix) {
+            write!(out, "\x1b[6n")?;
+            out.flush()?;
+            let reply = read_reply(Duration::from_millis(300), |reply| reply.ends_with(b"R"))?;
+            parse_cursor_column(&String::from_utf8_lossy(&reply))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "bad cursor report"))
+        } else {
+            // The Windows console is asked directly rather than through a reply
+            cursor::position().map(|(column, _)| column)
+        };
+        execute!(out, MoveTo(0, 0), Clear(ClearType::CurrentLine))?;
+        self.ambiguous_wide = Some(column? >= 2);
+        Ok(())
+    }
+
+    /// Asks the terminal for its background color (OSC 11). A device
+    /// attributes query follows, which every terminal answers, so one that
+    /// ignores OSC 11 is known not to reply without waiting out the timeout.
+    /// Needs raw mode so the replies can be read.
+    pub fn probe_background(&mut self, out: &mut impl Write) -> io::Result<()> {
+        write!(out, "\x1b]11;?\x1b\\\x1b[c")?;
+        out.flush()?;
+
+        // The device attributes reply, ESC [ ? ... c, comes last
+        let reply = read_reply(Duration::from_millis(300), |reply| {
+            reply.ends_with(b"c") && reply.windows(3).any(|w| w == b"\x1b[?")
+        })?;
+        if let Some(background) = parse_background_reply(&String::from_utf8_lossy(&reply)) {
+            self.background = Some(background);
+        }
+        Ok(())
+    }
+
+    /// Whether the Unicode glyph set will draw correctly
+    pub fn unicode_glyphs(&self) -> bool {
+        self.unicode && self.ambiguous_wide != Some(true)
+    }
+}
+
+/// Reads the terminal's reply to a query from stdin a byte at a time until
+/// `done` says it's complete, giving up after `timeout`. Nothing is left
+/// reading afterwards, so keys pressed once the probe is over reach the
+/// board.
+#[cfg(unix)]
+fn read_reply(timeout: Duration, done: impl Fn(&[u8]) -> bool) -> io::Result<Vec<u8>> {
+    use std::os::fd::AsRawFd;
+
+    let fd = io::stdin().as_raw_fd();
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    while !done(&reply) {
+        let left = deadline.saturating_duration_since(Instant::now());
+        if left.is_zero() {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "no reply from terminal",
+            ));
+        }
+        let mut ready = libc::pollfd {
+            fd,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: one valid pollfd for the duration of the call
+        let polled = unsafe { libc::poll(&mut ready, 1, left.as_millis().max(1) as libc::c_int) };
+        if polled < 0 {
+            let err = io::Error::last_os_error();
+            if err.kind() == io::ErrorKind::Interrupted {
+                continue;
+            }
+            return Err(err);
+        }
+        if polled == 0 {
+            continue;
+        }
+        let mut byte = 0u8;
+        // SAFETY: reads at most one byte into a live local
+        let read = unsafe { libc::read(fd, (&mut byte as *mut u8).cast(), 1) };
+        match read {
+            1 => reply.push(byte),
+            0 => return Err(io::ErrorKind::UnexpectedEof.into()),
+            _ => return Err(io::Error::last_os_error()),
+        }
+    }
+    Ok(reply)
+}
+
+/// Windows consoles don't hand query replies to stdin
+#[cfg(not(unix))]
+fn read_reply(_timeout: Duration, _done: impl Fn(&[u8]) -> bool) -> io::Result<Vec<u8>> {
+    Err(io::ErrorKind::Unsupported.into())
+}
+
+/// The zero-based column in a cursor position report, `ESC [ row ; column R`
+fn parse_cursor_column(reply: &str) -> Option<u16> {
+    let start = reply.rfind("\x1b[")? + 2;
+    let (_, column) = reply[start..].strip_suffix('R')?.split_once(';')?;
+    column.parse::<u16>().ok()?.checked_sub(1)
+}
+
+/// Reads an OSC 11 reply such as `ESC ] 11 ; rgb:ffff/ffff/dddd ESC \`,
+/// where each channel has one to four hex digits
+fn parse_background_reply(reply: &str) -> Option<Background> {
+    let start = reply.find("]11;rgb:")? + "]11;rgb:".len();
+    let channels: Vec<f64> = reply[start..]
+        .split(['/', '\x1b', '\x07'])
+        .take(3)
+        .map(|hex| {
+            let value = u16::from_str_radix(hex, 16).ok()?;
+            let max = (1u32 << (4 * hex.len())) - 1;
+            Some(value as f64 / max as f64)
+        })
+        .collect::<Option<_>>()?;
+    let [r, g, b] = channels[..] else {
+        return None;
+    };
+    // Relative luminance, weighting green as the eye does
+    let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    Some(if luminance > 0.5 {
+        Background::Light
+    } else {
+        Background::Dark
+    })
+}
+
+/// The OSC 52 sequence asking the terminal to put `text` on the system
+/// clipboard. It travels with the output, so it works over SSH too;
+/// terminals that don't support it ignore it.
+pub fn clipboard_sequence(text: &str) -> String {
+    format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, &byte)| n | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn env(vars: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name: &str| vars.get(name).cloned()
+    }
+
+    #[test]
+    fn test_detects_modern_terminal() {
+        let caps = Capabilities::from_env(
+            Platform::Unix,
+            env(&[
+                ("TERM", "xterm-256color"),
+                ("COLORTERM", "truecolor"),
+                ("LANG", "en_GB.UTF-8"),
+            ]),
+        );
+        assert_eq!(caps.color, ColorSupport::TrueColor);
+        assert!(caps.unicode && caps.mouse);
+        assert!(caps.unicode_glyphs());
+        assert_eq!(caps.graphics, None);
+    }
+
+    #[test]
+    fn test_detects_graphics_protocols() {
+        let kitty = Capabilities::from_env(Platform::Unix, env(&[("TERM", "xterm-kitty")]));
+        assert_eq!(kitty.graphics, Some(GraphicsProtocol::Kitty));
+        let wezterm = Capabilities::from_env(Platform::Unix, env(&[("TERM_PROGRAM", "WezTerm")]));
+        assert_eq!(wezterm.graphics, Some(GraphicsProtocol::Kitty));
+        let foot = Capabilities::from_env(Platform::Unix, env(&[("TERM", "foot")]));
+        assert_eq!(foot.graphics, Some(GraphicsProtocol::Sixel));
+    }
+
+    #[test]
+    fn test_detects_limited_terminals() {
+        let linux_console = Capabilities::from_env(
+            Platform::Unix,
+            env(&[("TERM", "linux"), ("LANG", "C.UTF-8")]),
+        );
+        assert_eq!(linux_console.color, ColorSupport::Ansi16);
+        assert!(!linux_console.unicode_glyphs());
+
+        let dumb = Capabilities::from_env(Platform::Unix, env(&[("TERM", "dumb")]));
+        assert_eq!(dumb.color, ColorSupport::None);
+        assert!(!dumb.mouse && !dumb.unicode);
+
+        let no_color = Capabilities::from_env(
+            Platform::Unix,
+            env(&[("TERM", "xterm-256color"), ("NO_COLOR", "1")]),
+        );
+        assert_eq!(no_color.color, ColorSupport::None);
+
+        let conhost = Capabilities::from_env(Platform::Windows, env(&[]));
+        assert!(!conhost.unicode && !conhost.mouse);
+        let windows_terminal =
+            Capabilities::from_env(Platform::Windows, env(&[("WT_SESSION", "1")]));
+        assert_eq!(windows_terminal.color, ColorSupport::TrueColor);
+        assert!(windows_terminal.unicode_glyphs());
+    }
+
+    #[test]
+    fn test_unknown_locale_keeps_unicode() {
+        let unset = Capabilities::from_env(Platform::Unix, env(&[("TERM", "xterm-256color")]));
+        assert!(unset.unicode_glyphs());
+        let c_locale = Capabilities::from_env(
+            Platform::Unix,
+            env(&[("TERM", "xterm-256color"), ("LANG", "C")]),
+        );
+        assert!(!c_locale.unicode_glyphs());
+    }
+
+    #[test]
+    fn test_fits_colors_to_terminal() {
+        let orange = Color::Rgb(0xff, 0x88, 0x00);
+        assert_eq!(ColorSupport::TrueColor.fit(orange), orange);
+        assert_eq!(ColorSupport::Ansi256.fit(orange), Color::Indexed(208));
+        assert_eq!(
+            ColorSupport::Ansi256.fit(Color::Rgb(0x30, 0x30, 0x30)),
+            Color::Indexed(236)
+        );
+        assert_eq!(ColorSupport::Ansi16.fit(orange), Color::Yellow);
+        assert_eq!(ColorSupport::Ansi16.fit(Color::Indexed(17)), Color::Black);
+        assert_eq!(ColorSupport::Ansi16.fit(Color::Indexed(9)), Color::LightRed);
+        assert_eq!(ColorSupport::Ansi16.fit(Color::Cyan), Color::Cyan);
+        assert_eq!(ColorSupport::None.fit(Color::Cyan), Color::Reset);
+    }
+
+    #[test]
+    fn test_cursor_report() {
+        assert_eq!(parse_cursor_column("\x1b[1;3R"), Some(2));
+        assert_eq!(parse_cursor_column("\x1b[12;1R"), Some(0));
+        assert_eq!(parse_cursor_column("\x1b[?1;2c"), None);
+    }
+
+    #[test]
+    fn test_wide_ambiguous_glyphs_fall_back() {
+        let mut caps = Capabilities::from_env(
+            Platform::Unix,
+            env(&[("LANG", "ja_JP.UTF-8"), ("TERM", "xterm")]),
+        );
+        assert!(caps.unicode_glyphs());
+        caps.ambiguous_wide = Some(true);
+        assert!(!caps.unicode_glyphs());
+    }
+
+    #[test]
+    fn test_detects_background() {
+        let light = Capabilities::from_env(Platform::Unix, env(&[("COLORFGBG", "0;15")]));
+        assert_eq!(light.background, Some(Background::Light));
+        let dark = Capabilities::from_env(Platform::Unix, env(&[("COLORFGBG", "15;default;0")]));
+        assert_eq!(dark.background, Some(Background::Dark));
+        let unknown = Capabilities::from_env(Platform::Unix, env(&[]));
+        assert_eq!(unknown.background, None);
+
+        let reply = |color: &str| format!("\x1b]11;rgb:{color}\x1b\\\x1b[?62;22c");
+        assert_eq!(
+            parse_background_reply(&reply("ffff/ffff/ffff")),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_background_reply(&reply("1e1e/1e1e/2e2e")),
+            Some(Background::Dark)
+        );
+        // Solarized light, with two-digit channels and a BEL terminator
+        assert_eq!(
+            parse_background_reply("\x1b]11;rgb:fd/f6/e3\x07"),
+            Some(Background::Light)
+        );
+        // Terminals without OSC 11 only answer the device attributes query
+        assert_eq!(parse_background_reply("\x1b[?1;2c"), None);
+    }
+
+    #[test]
+    fn test_clipboard_sequence() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+        assert_eq!(clipboard_sequence("hi\n"), "\x1b]52;c;aGkK\x07");
+    }
+}
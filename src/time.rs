@@ -1,87 +1,239 @@
-use chrono::{DateTime, Offset, Utc};
+use crate::cities::{self, CityData, CITIES, MAJOR_CITIES};
+use crate::config::{SeasonalHours, TimeDisplayConfig, ZoneConfigCompat, ZoneMove};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Offset, Timelike, Utc};
 use chrono_tz::Tz;
-use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::fmt;
 use std::str::FromStr;
-use std::sync::OnceLock;
 use sunrise::{Coordinates, SolarDay, SolarEvent};
 
-static CITIES_DATA: OnceLock<CitiesData> = OnceLock::new();
+/// How a zone is shown on the board, whoever it's for
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ZoneDisplay {
+    pub code: String,           // Short code from the city data, e.g. TYO
+    pub label: Option<String>,  // Name the user gave the zone, shown instead of the code
+    pub accent: Option<String>, // Color for the zone's name and border
+}
+
+/// The day kept by whoever a zone stands for, where it differs from the
+/// board's
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Schedule {
+    pub hours: Option<TimeDisplayConfig>, // Work and awake hours, instead of `time_config`
+    pub calls: Vec<String>, // When they'd rather take calls, e.g. "mornings" or "before 16:00"
+    pub seasons: Vec<SeasonalHours>, // Hours kept for part of every year, over `hours`
+}
+
+impl Schedule {
+    /// The hours to shade the zone's bar with, given the board's
+    pub fn hours<'a>(&'a self, board: &'a TimeDisplayConfig) -> &'a TimeDisplayConfig {
+        self.hours.as_ref().unwrap_or(board)
+    }
+
+    /// The hours kept on `date`: the first season taking it in, if any, or
+    /// else the usual ones
+    pub fn hours_on<'a>(
+        &'a self,
+        board: &'a TimeDisplayConfig,
+        date: NaiveDate,
+    ) -> &'a TimeDisplayConfig {
+        self.seasons
+            .iter()
+            .find(|season| season.covers(date))
+            .map_or_else(|| self.hours(board), |season| &season.hours)
+    }
+
+    /// The call windows that can be read, skipping any that can't (which
+    /// `alltz config check` reports)
+    pub fn call_windows(&self) -> impl Iterator<Item = CallWindow> + '_ {
+        self.calls.iter().filter_map(|window| window.parse().ok())
+    }
+
+    /// Whether a call from `start` to `end`, in minutes into their day,
+    /// falls in one of their call windows, or `None` if they haven't said
+    pub fn suits_call(&self, start: u32, end: u32) -> Option<bool> {
+        let mut windows = self.call_windows().peekable();
+        windows.peek()?;
+        Some(windows.any(|window| window.contains(start, end)))
+    }
+}
+
+/// A day of the year as `(month, day)`, written "06-15" or "Jun 15" and
+/// the same every year
+pub fn parse_month_day(text: &str) -> Result<(u32, u32), String> {
+    let text = text.trim();
+    // A leap year, so 29 February can be given
+    NaiveDate::parse_from_str(&format!("2024-{text}"), "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(&format!("{text} 2024"), "%b %d %Y"))
+        .map(|date| (date.month(), date.day()))
+        .map_err(|_| format!("`{text}` is not a day of the year, e.g. \"06-15\" or \"Jun 15\""))
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CityData {
-    pub name: String,
-    pub code: String,
-    pub timezone: String,
-    pub country: String,
-    pub coordinates: [f64; 2],
-    pub aliases: Vec<String>,
+/// Part of someone's day they'd rather take calls in, within their work
+/// hours. Written as "09:00-12:00", "before 16:00", "after 10:00",
+/// "mornings" or "afternoons".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CallWindow {
+    pub start: u32, // Minutes into the day
+    pub end: u32,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct CitiesData {
-    pub cities: Vec<CityData>,
-    pub major_cities: Vec<String>,
+impl CallWindow {
+    const NOON: u32 = 12 * 60;
+    const MIDNIGHT: u32 = 24 * 60;
+
+    /// Whether a call from `start` to `end`, in minutes into the day, fits
+    pub fn contains(&self, start: u32, end: u32) -> bool {
+        self.start <= start && end <= self.end
+    }
 }
 
+impl FromStr for CallWindow {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let minutes = |text: &str| {
+            parse_clock_time(text)
+                .map(|time| time.num_seconds_from_midnight() / 60)
+                .ok_or_else(|| format!("`{}` is not a time of day", text.trim()))
+        };
+        let text = s.trim().to_lowercase();
+        let (start, end) = match text.as_str() {
+            "mornings" => (0, Self::NOON),
+            "afternoons" => (Self::NOON, Self::MIDNIGHT),
+            _ => {
+                if let Some(time) = text.strip_prefix("before ") {
+                    (0, minutes(time)?)
+                } else if let Some(time) = text.strip_prefix("after ") {
+                    (minutes(time)?, Self::MIDNIGHT)
+                } else if let Some((from, to)) = text.split_once('-') {
+                    (minutes(from)?, minutes(to)?)
+                } else {
+                    return Err(format!(
+                        "`{}` is not a call window, e.g. \"mornings\", \"before 16:00\" or \"13:00-15:00\"",
+                        s.trim()
+                    ));
+                }
+            }
+        };
+        if start >= end {
+            return Err(format!("call window `{}` ends before it starts", s.trim()));
+        }
+        Ok(CallWindow { start, end })
+    }
+}
+
+impl fmt::Display for CallWindow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let time = |minutes: u32| format!("{:02}:{:02}", minutes / 60, minutes % 60);
+        match (self.start, self.end) {
+            (0, Self::NOON) => write!(f, "mornings"),
+            (Self::NOON, Self::MIDNIGHT) => write!(f, "afternoons"),
+            (0, end) => write!(f, "before {}", time(end)),
+            (start, Self::MIDNIGHT) => write!(f, "after {}", time(start)),
+            (start, end) => write!(f, "{}-{}", time(start), time(end)),
+        }
+    }
+}
+
+/// A zone on the board: where its clocks are (the IANA zone, and the city
+/// picked for it), how it's shown, and the schedule of whoever it's for
 #[derive(Debug, Clone)]
 pub struct TimeZone {
     pub tz: Tz,
-    pub display_name: String,
-    pub custom_label: Option<String>,
     pub source_city: Option<String>, // Store the original city name that was selected
+    pub display: ZoneDisplay,
+    pub schedule: Schedule,
+    pub moves: Vec<ZoneMove>, // Cities whoever it stands for is moving to, and from when
 }
 
 impl TimeZone {
-    pub fn new(tz: Tz, _name: String, display_name: String) -> Self {
-        Self {
-            tz,
-            display_name,
-            custom_label: None,
-            source_city: None,
-        }
+    pub fn new(tz: Tz, _name: String, code: String) -> Self {
+        Self::with_source_city(tz, code, None, None)
     }
 
     #[cfg(test)]
-    pub fn with_custom_label(tz: Tz, display_name: String, custom_label: Option<String>) -> Self {
-        Self {
-            tz,
-            display_name,
-            custom_label,
-            source_city: None,
-        }
+    pub fn with_custom_label(tz: Tz, code: String, label: Option<String>) -> Self {
+        Self::with_source_city(tz, code, label, None)
     }
 
     pub fn with_source_city(
         tz: Tz,
-        display_name: String,
-        custom_label: Option<String>,
+        code: String,
+        label: Option<String>,
         source_city: Option<String>,
     ) -> Self {
         Self {
             tz,
-            display_name,
-            custom_label,
             source_city,
+            display: ZoneDisplay {
+                code,
+                label,
+                accent: None,
+            },
+            schedule: Schedule::default(),
+            moves: Vec::new(),
         }
     }
 
+    /// The zone as it stands at `at`: moved to the city of the latest move
+    /// begun by then, keeping its label and schedule
+    pub fn as_of(&self, at: DateTime<Utc>) -> Cow<'_, TimeZone> {
+        let moved = self
+            .moves
+            .iter()
+            .filter_map(|entry| {
+                let city = TimeZoneManager::find_city(&entry.city)?;
+                let tz: Tz = city.timezone.parse().ok()?;
+                let starts = entry
+                    .from
+                    .and_hms_opt(0, 0, 0)?
+                    .and_local_timezone(tz)
+                    .earliest()?
+                    .with_timezone(&Utc);
+                (starts <= at).then_some((entry.from, city, tz))
+            })
+            .max_by_key(|(from, _, _)| *from);
+        match moved {
+            Some((_, city, tz)) => {
+                let mut zone = self.clone();
+                zone.tz = tz;
+                zone.source_city = Some(city.name.to_string());
+                zone.display.code = city.code.to_string();
+                Cow::Owned(zone)
+            }
+            None => Cow::Borrowed(self),
+        }
+    }
+
+    /// The hours this zone's bar is shaded with, given the board's
+    pub fn hours<'a>(&'a self, board: &'a TimeDisplayConfig) -> &'a TimeDisplayConfig {
+        self.schedule.hours(board)
+    }
+
+    /// The hours kept here on `date`, seasons included, given the board's
+    pub fn hours_on<'a>(
+        &'a self,
+        board: &'a TimeDisplayConfig,
+        date: NaiveDate,
+    ) -> &'a TimeDisplayConfig {
+        self.schedule.hours_on(board, date)
+    }
+
     pub fn from_tz(tz: Tz) -> Self {
         let name = tz.to_string();
-        let cities_data = TimeZoneManager::load_cities_data();
 
         // Try to find the timezone in our cities data to get the proper airport code
-        let display_name = cities_data
-            .cities
+        let display_name = CITIES
             .iter()
             .find(|city| {
-                if let Ok(city_tz) = Tz::from_str(&city.timezone) {
+                if let Ok(city_tz) = Tz::from_str(city.timezone) {
                     city_tz == tz
                 } else {
                     false
                 }
             })
-            .map(|city| city.code.clone())
+            .map(|city| city.code.to_string())
             .unwrap_or_else(|| {
                 // Fallback: generate a 3-letter code from timezone string
                 let tz_string = tz.to_string();
@@ -103,9 +255,13 @@ impl TimeZone {
     }
 
     pub fn get_timezone_abbreviation(&self) -> String {
+        self.abbreviation_at(Utc::now())
+    }
+
+    /// The zone's abbreviation in effect at `at`
+    pub fn abbreviation_at(&self, at: DateTime<Utc>) -> String {
         // Use chrono's built-in timezone formatting for accurate, DST-aware abbreviations
-        let now = Utc::now();
-        let local_time = now.with_timezone(&self.tz);
+        let local_time = at.with_timezone(&self.tz);
         let chrono_abbrev = local_time.format("%Z").to_string();
 
         // If chrono returns a numeric offset (like "-03" or "+05:30"),
@@ -210,17 +366,24 @@ impl TimeZone {
         local_time.offset().fix().local_minus_utc() / 3600
     }
 
+    /// Minutes ahead of UTC at `at`, which DST changes during the year
+    pub fn offset_minutes_at(&self, at: DateTime<Utc>) -> i32 {
+        self.convert_time(at).offset().fix().local_minus_utc() / 60
+    }
+
     pub fn offset_string(&self) -> String {
-        let offset_hours = self.utc_offset_hours();
-        if offset_hours >= 0 {
-            format!("UTC+{offset_hours}")
-        } else {
-            format!("UTC{offset_hours}")
-        }
+        self.offset_label(false)
+    }
+
+    /// The offset from UTC now, e.g. "UTC+9" or "UTC+5:45". Zones a
+    /// fraction of an hour off always show their minutes, which whole hours
+    /// would round away; with `always_minutes` every zone does, as "UTC+9:00".
+    pub fn offset_label(&self, always_minutes: bool) -> String {
+        utc_offset_label(self.offset_minutes_at(Utc::now()), always_minutes)
     }
 
     pub fn effective_display_name(&self) -> &str {
-        self.custom_label.as_deref().unwrap_or(&self.display_name)
+        self.display.label.as_deref().unwrap_or(&self.display.code)
     }
 
     pub fn get_city_name(&self) -> String {
@@ -230,35 +393,34 @@ impl TimeZone {
         }
 
         // Fallback: lookup by airport code
-        let cities_data = TimeZoneManager::load_cities_data();
-        if let Some(city) = cities_data
-            .cities
-            .iter()
-            .find(|c| c.code == self.display_name)
-        {
-            return city.name.clone();
+        if let Some(city) = cities::by_code(&self.display.code) {
+            return city.name.to_string();
         }
 
         // Last resort: use display_name
-        self.display_name.clone()
+        self.display.code.clone()
     }
 
-    pub fn get_coordinates(&self) -> Option<(f64, f64)> {
-        let cities_data = TimeZoneManager::load_cities_data();
+    /// Whether `name` is this zone's label or city, ignoring case, as
+    /// config entries that refer to zones spell them
+    pub fn answers_to(&self, name: &str) -> bool {
+        self.display
+            .label
+            .as_deref()
+            .is_some_and(|label| label.eq_ignore_ascii_case(name))
+            || self.get_city_name().eq_ignore_ascii_case(name)
+    }
 
+    pub fn get_coordinates(&self) -> Option<(f64, f64)> {
         // First try to find by source city name
         if let Some(source_city) = &self.source_city {
-            if let Some(city) = cities_data.cities.iter().find(|c| c.name == *source_city) {
+            if let Some(city) = cities::by_name(source_city).find(|c| c.name == *source_city) {
                 return Some((city.coordinates[0], city.coordinates[1]));
             }
         }
 
         // Fallback: lookup by airport code
-        if let Some(city) = cities_data
-            .cities
-            .iter()
-            .find(|c| c.code == self.display_name)
-        {
+        if let Some(city) = cities::by_code(&self.display.code) {
             return Some((city.coordinates[0], city.coordinates[1]));
         }
 
@@ -286,24 +448,29 @@ impl TimeZone {
         Some((sunrise_tz, sunset_tz))
     }
 
-    pub fn format_sun_times(&self, date: DateTime<Utc>, use_12_hour: bool) -> Option<String> {
-        let (sunrise, sunset) = self.get_sunrise_sunset(date)?;
+    /// Formats sunrise and sunset, each preceded by its symbol from `symbols`
+    pub fn format_sun_times(
+        (sunrise, sunset): (DateTime<Tz>, DateTime<Tz>),
+        use_12_hour: bool,
+        symbols: (char, char),
+    ) -> String {
+        let (sunrise_symbol, sunset_symbol) = symbols;
         if use_12_hour {
-            Some(format!(
-                "☀ {}:{}{} ☽ {}:{}{}",
+            format!(
+                "{sunrise_symbol} {}:{}{} {sunset_symbol} {}:{}{}",
                 sunrise.format("%I"),
                 sunrise.format("%M"),
                 sunrise.format("%P"),
                 sunset.format("%I"),
                 sunset.format("%M"),
                 sunset.format("%P")
-            ))
+            )
         } else {
-            Some(format!(
-                "☀ {} ☽ {}",
+            format!(
+                "{sunrise_symbol} {} {sunset_symbol} {}",
                 sunrise.format("%H:%M"),
                 sunset.format("%H:%M")
-            ))
+            )
         }
     }
 }
@@ -319,6 +486,216 @@ impl fmt::Display for TimeZone {
     }
 }
 
+impl From<&TimeZone> for ZoneConfigCompat {
+    /// The zone as the config keeps it: named by the city it was added as,
+    /// or else the first city keeping its time
+    fn from(zone: &TimeZone) -> Self {
+        let city_name = zone.source_city.clone().unwrap_or_else(|| {
+            CITIES
+                .iter()
+                .find(|city| city.timezone == zone.tz.name())
+                .map_or_else(|| zone.tz.to_string(), |city| city.name.to_string())
+        });
+        ZoneConfigCompat::new(city_name, zone.display.label.clone())
+            .with_accent(zone.display.accent.clone())
+            .with_hours(zone.schedule.hours.clone())
+            .with_calls(zone.schedule.calls.clone())
+            .with_moves(zone.moves.clone())
+            .with_seasons(zone.schedule.seasons.clone())
+    }
+}
+
+/// A date's quarter of a fiscal year starting on the first of `start_month`.
+/// Fiscal years are named for the calendar year they end in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FiscalQuarter {
+    pub quarter: u32,
+    pub year: i32,
+}
+
+impl FiscalQuarter {
+    pub fn of(date: NaiveDate, start_month: u32) -> Self {
+        let months_in = (date.month() + 12 - start_month) % 12;
+        let year = if start_month > 1 && date.month() >= start_month {
+            date.year() + 1
+        } else {
+            date.year()
+        };
+        Self {
+            quarter: months_in / 3 + 1,
+            year,
+        }
+    }
+
+    /// Whether `date` is the first day of a fiscal quarter
+    pub fn starts_on(date: NaiveDate, start_month: u32) -> bool {
+        date.day() == 1 && (date.month() + 12 - start_month).is_multiple_of(3)
+    }
+}
+
+impl fmt::Display for FiscalQuarter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Q{} FY{:02}", self.quarter, self.year.rem_euclid(100))
+    }
+}
+
+/// How many hours work from `start` to `end` lasts, running past midnight
+/// when `end` comes first
+pub fn work_length((start, end): (u32, u32)) -> u32 {
+    if end >= start {
+        end - start
+    } else {
+        end + 24 - start
+    }
+}
+
+/// A zone's working hours starting on its local `date`, or `None` at the
+/// weekend. Hours past midnight end on the next day.
+fn work_day<T: chrono::TimeZone>(
+    tz: &T,
+    date: NaiveDate,
+    (start, end): (u32, u32),
+) -> Option<(DateTime<Utc>, DateTime<Utc>)> {
+    if date.weekday().number_from_monday() > 5 {
+        return None;
+    }
+    let at = |hour: u32| {
+        let local = date.and_hms_opt(0, 0, 0)? + Duration::hours(hour as i64);
+        tz.from_local_datetime(&local)
+            .earliest()
+            .map(|time| time.with_timezone(&Utc))
+    };
+    Some((at(start)?, at(start + work_length((start, end)))?))
+}
+
+/// How much of `home`'s working day on `date` falls in `zone`'s working
+/// hours, for work hours running from `home_hours.0` to `home_hours.1` at
+/// home and over `zone_hours` in the zone. Weekends, in either zone, share
+/// nothing.
+pub fn work_overlap<H: chrono::TimeZone>(
+    home: &H,
+    zone: Tz,
+    date: NaiveDate,
+    home_hours: (u32, u32),
+    zone_hours: (u32, u32),
+) -> Duration {
+    let Some((home_start, home_end)) = work_day(home, date, home_hours) else {
+        return Duration::zero();
+    };
+    // The zone's working days that could touch the home one
+    [date.pred_opt(), Some(date), date.succ_opt()]
+        .into_iter()
+        .flatten()
+        .filter_map(|day| work_day(&zone, day, zone_hours))
+        .map(|(start, end)| (end.min(home_end) - start.max(home_start)).max(Duration::zero()))
+        .sum()
+}
+
+/// The zone's working days, as UTC ranges, from the day before `now` there
+/// to a week after
+fn work_days_around(
+    zone: Tz,
+    now: DateTime<Utc>,
+    hours: (u32, u32),
+) -> impl Iterator<Item = (DateTime<Utc>, DateTime<Utc>)> {
+    let today = now.with_timezone(&zone).date_naive();
+    (-1..=7)
+        .filter_map(move |offset| today.checked_add_signed(Duration::days(offset)))
+        .filter_map(move |day| work_day(&zone, day, hours))
+}
+
+/// Whether `zone` is at work for the whole of `start` to `end`
+pub fn at_work(zone: Tz, start: DateTime<Utc>, end: DateTime<Utc>, hours: (u32, u32)) -> bool {
+    let date = start.with_timezone(&zone).date_naive();
+    [date.pred_opt(), Some(date)]
+        .into_iter()
+        .flatten()
+        .filter_map(|day| work_day(&zone, day, hours))
+        .any(|(day_start, day_end)| day_start <= start && end <= day_end)
+}
+
+/// When `zone` next starts a working day after `now`
+pub fn next_work_start(zone: Tz, now: DateTime<Utc>, hours: (u32, u32)) -> Option<DateTime<Utc>> {
+    work_days_around(zone, now, hours)
+        .map(|(start, _)| start)
+        .find(|start| *start > now)
+}
+
+/// When `zone`'s current or next working day ends after `now`
+pub fn next_work_end(zone: Tz, now: DateTime<Utc>, hours: (u32, u32)) -> Option<DateTime<Utc>> {
+    work_days_around(zone, now, hours)
+        .map(|(_, end)| end)
+        .find(|end| *end > now)
+}
+
+/// A wall-clock time as typed: "14:30", "1430", "14", "2:30pm" or "2 pm"
+pub fn parse_clock_time(input: &str) -> Option<NaiveTime> {
+    let input = input.trim().to_lowercase();
+    let (digits, meridiem) = match input.strip_suffix("am").or(input.strip_suffix('a')) {
+        Some(rest) => (rest.trim_end(), Some(false)),
+        None => match input.strip_suffix("pm").or(input.strip_suffix('p')) {
+            Some(rest) => (rest.trim_end(), Some(true)),
+            None => (input.as_str(), None),
+        },
+    };
+    let (hour, minute) = match digits.split_once(':') {
+        Some((hour, minute)) if minute.len() == 2 => (hour, minute),
+        Some(_) => return None,
+        // Splitting by bytes is only safe on ASCII, which any time is
+        None if !digits.is_ascii() => return None,
+        None if digits.len() > 2 => digits.split_at(digits.len() - 2),
+        None => (digits, "0"),
+    };
+    let (mut hour, minute): (u32, u32) = (hour.parse().ok()?, minute.parse().ok()?);
+    if let Some(pm) = meridiem {
+        if !(1..=12).contains(&hour) {
+            return None;
+        }
+        hour = hour % 12 + if pm { 12 } else { 0 };
+    }
+    NaiveTime::from_hms_opt(hour, minute, 0)
+}
+
+/// The next time after `now` that `zone`'s clocks read `time`: today there
+/// if it is still to come, otherwise tomorrow. Days where DST skips the
+/// time are passed over.
+pub fn next_local_time(zone: Tz, now: DateTime<Utc>, time: NaiveTime) -> Option<DateTime<Utc>> {
+    let today = now.with_timezone(&zone).date_naive();
+    (0..=2)
+        .filter_map(|offset| today.checked_add_signed(Duration::days(offset)))
+        .filter_map(|day| day.and_time(time).and_local_timezone(zone).earliest())
+        .map(|local| local.with_timezone(&Utc))
+        .find(|utc| *utc > now)
+}
+
+/// An offset from UTC in minutes as "UTC+9" or "UTC-9:30", with minutes
+/// for whole hours too when `always_minutes`
+pub fn utc_offset_label(offset: i32, always_minutes: bool) -> String {
+    let sign = if offset < 0 { '-' } else { '+' };
+    let (hours, minutes) = (offset.abs() / 60, offset.abs() % 60);
+    if always_minutes || minutes != 0 {
+        format!("UTC{sign}{hours}:{minutes:02}")
+    } else {
+        format!("UTC{sign}{hours}")
+    }
+}
+
+/// A zone named the way config entries and commands name them: a board
+/// zone's label or city, an IANA name like `Europe/Madrid`, or any known city
+pub fn resolve_zone(name: &str, zones: &[TimeZone]) -> Option<Tz> {
+    zones
+        .iter()
+        .find(|zone| zone.answers_to(name))
+        .map(|zone| zone.tz)
+        .or_else(|| {
+            chrono_tz::TZ_VARIANTS
+                .iter()
+                .copied()
+                .find(|tz| tz.name().eq_ignore_ascii_case(name))
+        })
+        .or_else(|| TimeZoneManager::find_city(name).and_then(|city| city.timezone.parse().ok()))
+}
+
 #[derive(Debug, Clone)]
 pub struct TimeZoneManager {
     zones: Vec<TimeZone>,
@@ -329,24 +706,15 @@ impl TimeZoneManager {
         Self { zones: Vec::new() }
     }
 
-    fn load_cities_data() -> &'static CitiesData {
-        CITIES_DATA.get_or_init(|| {
-            let json_str = include_str!("cities.json");
-            serde_json::from_str(json_str).expect("Failed to parse cities.json")
-        })
-    }
-
     pub fn get_all_available_timezones() -> Vec<(Tz, String, String, f64, f64)> {
-        let cities_data = Self::load_cities_data();
-        cities_data
-            .cities
+        CITIES
             .iter()
             .map(|city| {
-                let tz = Tz::from_str(&city.timezone).expect("Invalid timezone in cities.json");
+                let tz = Tz::from_str(city.timezone).expect("Invalid timezone in cities.json");
                 (
                     tz,
-                    city.name.clone(),
-                    city.code.clone(),
+                    city.name.to_string(),
+                    city.code.to_string(),
                     city.coordinates[0],
                     city.coordinates[1],
                 )
@@ -360,10 +728,9 @@ impl TimeZoneManager {
             return Vec::new();
         }
 
-        let cities_data = Self::load_cities_data();
         let mut results: Vec<(String, i32)> = Vec::new();
 
-        for city in &cities_data.cities {
+        for city in CITIES {
             let mut score = 0;
             let name_lower = city.name.to_lowercase();
             let code_lower = city.code.to_lowercase();
@@ -384,7 +751,7 @@ impl TimeZoneManager {
             }
 
             // Check aliases
-            for alias in &city.aliases {
+            for alias in city.aliases {
                 let alias_lower = alias.to_lowercase();
                 if alias_lower == query_lower {
                     score += 800;
@@ -406,7 +773,7 @@ impl TimeZoneManager {
             }
 
             // Bonus for major cities
-            if cities_data.major_cities.contains(&city.name) {
+            if MAJOR_CITIES.contains(&city.name) {
                 score += 25;
             }
 
@@ -429,12 +796,9 @@ impl TimeZoneManager {
     }
 
     pub fn get_country_for_city(city: &str) -> String {
-        let cities_data = Self::load_cities_data();
-        cities_data
-            .cities
-            .iter()
+        cities::by_name(city)
             .find(|c| c.name == city)
-            .map(|c| c.country.clone())
+            .map(|c| c.country.to_string())
             .unwrap_or_else(|| "Unknown".to_string())
     }
 
@@ -442,7 +806,8 @@ impl TimeZoneManager {
         self.add_timezone_with_label(name, None)
     }
 
-    pub fn add_timezone_with_label(&mut self, name: &str, custom_label: Option<String>) -> bool {
+    /// Looks up a city by name, accepting the "City, Country" form used by search results
+    pub fn find_city(name: &str) -> Option<&'static CityData> {
         // Handle "City, Country" format from search results
         let (city_name, country) = if name.contains(", ") {
             let parts: Vec<&str> = name.splitn(2, ", ").collect();
@@ -452,32 +817,28 @@ impl TimeZoneManager {
         };
 
         // Find city, considering country if provided
-        let cities_data = Self::load_cities_data();
-        let city = if let Some(country_name) = country {
+        let mut matches = cities::by_name(city_name);
+        if let Some(country_name) = country {
             // Look for exact match with city name and country
-            cities_data.cities.iter().find(|c| {
-                c.name.eq_ignore_ascii_case(city_name)
-                    && c.country.eq_ignore_ascii_case(country_name)
-            })
+            matches.find(|c| c.country.eq_ignore_ascii_case(country_name))
         } else {
             // Fallback to just city name
-            cities_data
-                .cities
-                .iter()
-                .find(|c| c.name.eq_ignore_ascii_case(city_name))
-        };
+            matches.next()
+        }
+    }
 
-        if let Some(city) = city {
-            if let Ok(tz) = Tz::from_str(&city.timezone) {
+    pub fn add_timezone_with_label(&mut self, name: &str, custom_label: Option<String>) -> bool {
+        if let Some(city) = Self::find_city(name) {
+            if let Ok(tz) = Tz::from_str(city.timezone) {
                 let timezone = TimeZone::with_source_city(
                     tz,
-                    city.code.clone(),
+                    city.code.to_string(),
                     custom_label,
-                    Some(city.name.clone()),
+                    Some(city.name.to_string()),
                 );
 
                 // Check if we already have this exact city (by airport code)
-                if !self.zones.iter().any(|z| z.display_name == city.code) {
+                if !self.zones.iter().any(|z| z.display.code == city.code) {
                     self.add_zone(timezone);
                     return true;
                 }
@@ -487,7 +848,6 @@ impl TimeZoneManager {
     }
 
     pub fn with_default_zones() -> Self {
-        let cities_data = Self::load_cities_data();
         let default_city_names = vec![
             "Los Angeles",
             "New York",
@@ -501,14 +861,12 @@ impl TimeZoneManager {
         let mut zones: Vec<TimeZone> = default_city_names
             .into_iter()
             .filter_map(|name| {
-                cities_data
-                    .cities
-                    .iter()
+                cities::by_name(name)
                     .find(|c| c.name == name)
                     .and_then(|city| {
-                        Tz::from_str(&city.timezone)
+                        Tz::from_str(city.timezone)
                             .ok()
-                            .map(|tz| TimeZone::new(tz, tz.to_string(), city.code.clone()))
+                            .map(|tz| TimeZone::new(tz, tz.to_string(), city.code.to_string()))
                     })
             })
             .collect();
@@ -537,13 +895,60 @@ impl TimeZoneManager {
         &self.zones
     }
 
+    /// Reorders zones by `key`, keeping UTC offset order among equal keys.
+    /// Returns where the zone at `index` ended up, to keep it selected.
+    pub fn sort_by_key<K: Ord>(
+        &mut self,
+        index: usize,
+        mut key: impl FnMut(&TimeZone) -> K,
+    ) -> usize {
+        let mut zones: Vec<(usize, TimeZone)> = self.zones.drain(..).enumerate().collect();
+        zones.sort_by_cached_key(|(_, zone)| (key(zone), zone.utc_offset_hours()));
+        let moved = zones.iter().position(|(i, _)| *i == index).unwrap_or(index);
+        self.zones = zones.into_iter().map(|(_, zone)| zone).collect();
+        moved
+    }
+
     pub fn zone_count(&self) -> usize {
         self.zones.len()
     }
 
+    pub fn update_zone_accent(&mut self, index: usize, accent: Option<String>) -> bool {
+        if index < self.zones.len() {
+            self.zones[index].display.accent = accent;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Gives the zone at `index` the label, accent, hours, call windows,
+    /// moves and seasons `entry` has, as when it's loaded or restored
+    pub fn apply(&mut self, index: usize, entry: &ZoneConfigCompat) -> bool {
+        let Some(zone) = self.zones.get_mut(index) else {
+            return false;
+        };
+        zone.display.label = entry.custom_label().map(String::from);
+        zone.display.accent = entry.accent().map(String::from);
+        zone.schedule.hours = entry.hours().cloned();
+        zone.schedule.calls = entry.calls().to_vec();
+        zone.schedule.seasons = entry.seasons().to_vec();
+        zone.moves = entry.moves().to_vec();
+        true
+    }
+
+    pub fn update_zone_hours(&mut self, index: usize, hours: Option<TimeDisplayConfig>) -> bool {
+        if index < self.zones.len() {
+            self.zones[index].schedule.hours = hours;
+            true
+        } else {
+            false
+        }
+    }
+
     pub fn update_zone_label(&mut self, index: usize, custom_label: Option<String>) -> bool {
         if index < self.zones.len() {
-            self.zones[index].custom_label = custom_label;
+            self.zones[index].display.label = custom_label;
             true
         } else {
             false
@@ -561,11 +966,297 @@ impl Default for TimeZoneManager {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_fiscal_quarter() {
+        let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+        // A fiscal year starting in April, named for the year it ends in
+        assert_eq!(
+            FiscalQuarter::of(date(2024, 4, 1), 4).to_string(),
+            "Q1 FY25"
+        );
+        assert_eq!(
+            FiscalQuarter::of(date(2025, 3, 31), 4).to_string(),
+            "Q4 FY25"
+        );
+        assert_eq!(
+            FiscalQuarter::of(date(2024, 12, 25), 10).to_string(),
+            "Q1 FY25"
+        );
+        assert_eq!(
+            FiscalQuarter::of(date(2024, 8, 15), 1).to_string(),
+            "Q3 FY24"
+        );
+
+        assert!(FiscalQuarter::starts_on(date(2024, 7, 1), 4));
+        assert!(FiscalQuarter::starts_on(date(2024, 11, 1), 2));
+        assert!(!FiscalQuarter::starts_on(date(2024, 8, 1), 4));
+        assert!(!FiscalQuarter::starts_on(date(2024, 7, 2), 4));
+    }
+
+    #[test]
+    fn test_call_windows() {
+        let window: CallWindow = "Before 4pm".parse().unwrap();
+        assert_eq!((window.start, window.end), (0, 16 * 60));
+        assert_eq!(window.to_string(), "before 16:00");
+        assert_eq!(
+            "9:30-12".parse::<CallWindow>().unwrap().to_string(),
+            "09:30-12:00"
+        );
+        assert_eq!(
+            "afternoons".parse::<CallWindow>().unwrap().to_string(),
+            "afternoons"
+        );
+        assert!("15:00-14:00".parse::<CallWindow>().is_err());
+        assert!("whenever".parse::<CallWindow>().is_err());
+
+        let schedule = Schedule {
+            calls: vec!["mornings".to_string(), "whenever".to_string()],
+            ..Schedule::default()
+        };
+        assert_eq!(schedule.suits_call(9 * 60, 10 * 60), Some(true));
+        assert_eq!(schedule.suits_call(11 * 60 + 30, 12 * 60 + 30), Some(false));
+        assert_eq!(Schedule::default().suits_call(0, 30), None);
+    }
+
+    #[test]
+    fn test_seasonal_hours() {
+        assert_eq!(parse_month_day("06-15"), Ok((6, 15)));
+        assert_eq!(parse_month_day(" Aug 31"), Ok((8, 31)));
+        assert_eq!(parse_month_day("02-29"), Ok((2, 29)));
+        assert!(parse_month_day("06-31").is_err());
+        assert!(parse_month_day("midsummer").is_err());
+
+        let season = |from: &str, to: &str, end: u32| SeasonalHours {
+            name: None,
+            from: from.into(),
+            to: to.into(),
+            hours: TimeDisplayConfig {
+                work_hours_start: 8,
+                work_hours_end: end,
+                ..TimeDisplayConfig::default()
+            },
+        };
+        let schedule = Schedule {
+            seasons: vec![season("Jun 15", "08-31", 14), season("12-20", "01-05", 12)],
+            ..Schedule::default()
+        };
+        let board = TimeDisplayConfig::default();
+        let end_on = |m, d| {
+            let date = NaiveDate::from_ymd_opt(2024, m, d).unwrap();
+            schedule.hours_on(&board, date).work_hours_end
+        };
+        assert_eq!(end_on(6, 14), 18);
+        assert_eq!(end_on(6, 15), 14);
+        assert_eq!(end_on(8, 31), 14);
+        assert_eq!(end_on(9, 1), 18);
+        // Over new year, on either side of it
+        assert_eq!(end_on(12, 31), 12);
+        assert_eq!(end_on(1, 5), 12);
+        assert_eq!(end_on(1, 6), 18);
+    }
+
+    #[test]
+    fn test_zone_moves() {
+        let mut bob = TimeZone::with_custom_label(
+            chrono_tz::Europe::Berlin,
+            "BER".to_string(),
+            Some("Bob".to_string()),
+        );
+        bob.moves = vec![
+            ZoneMove {
+                from: NaiveDate::from_ymd_opt(2024, 9, 1).unwrap(),
+                city: "Toronto".to_string(),
+            },
+            ZoneMove {
+                from: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                city: "Nowhere In Particular".to_string(),
+            },
+        ];
+        let at = |time: &str| {
+            DateTime::parse_from_rfc3339(time)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+
+        // Still in Berlin until Toronto's midnight
+        let before = bob.as_of(at("2024-09-01T03:59:00Z"));
+        assert!(matches!(before, Cow::Borrowed(_)));
+        assert_eq!(before.tz, chrono_tz::Europe::Berlin);
+
+        let after = bob.as_of(at("2024-09-01T04:00:00Z"));
+        assert_eq!(after.tz, chrono_tz::Canada::Eastern);
+        assert_eq!(after.get_city_name(), "Toronto");
+        assert_eq!(after.effective_display_name(), "Bob");
+        // A move to a city that can't be found is skipped
+        assert_eq!(
+            bob.as_of(at("2025-06-01T00:00:00Z")).tz,
+            chrono_tz::Canada::Eastern
+        );
+    }
+
+    #[test]
+    fn test_work_overlap() {
+        let date = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let london = chrono_tz::Europe::London;
+        let hours = (8, 18);
+
+        // Monday: New York's 8:00 is London's 13:00
+        assert_eq!(
+            work_overlap(
+                &london,
+                chrono_tz::America::New_York,
+                date(15),
+                hours,
+                hours
+            ),
+            Duration::hours(5)
+        );
+        assert_eq!(
+            work_overlap(&london, chrono_tz::Europe::Berlin, date(15), hours, hours),
+            Duration::hours(9)
+        );
+        // Saturday in London, and Tokyo's Monday ends an hour into London's
+        assert_eq!(
+            work_overlap(&london, chrono_tz::Europe::Berlin, date(13), hours, hours),
+            Duration::zero()
+        );
+        assert_eq!(
+            work_overlap(&london, chrono_tz::Asia::Tokyo, date(15), hours, hours),
+            Duration::hours(1)
+        );
+        // Friday in London overlaps Sydney's Friday, not its Saturday
+        assert_eq!(
+            work_overlap(
+                &london,
+                chrono_tz::Australia::Sydney,
+                date(19),
+                hours,
+                hours
+            ),
+            Duration::zero()
+        );
+        // New York's people keep 10:00 to 19:00, London's 15:00 to midnight
+        assert_eq!(
+            work_overlap(
+                &london,
+                chrono_tz::America::New_York,
+                date(15),
+                hours,
+                (10, 19)
+            ),
+            Duration::hours(3)
+        );
+        // New York's Monday night shift, 22:00 to 06:00, is 03:00 to 11:00
+        // on London's Tuesday, three hours of its working day
+        assert_eq!(
+            work_overlap(
+                &london,
+                chrono_tz::America::New_York,
+                date(16),
+                hours,
+                (22, 6)
+            ),
+            Duration::hours(3)
+        );
+        assert_eq!(work_length((22, 6)), 8);
+        assert_eq!(work_length((0, 24)), 24);
+    }
+
+    #[test]
+    fn test_resolve_zone() {
+        let zones = [TimeZone::with_custom_label(
+            chrono_tz::Asia::Kolkata,
+            "Mumbai".to_string(),
+            Some("Platform".to_string()),
+        )];
+        assert_eq!(
+            resolve_zone("platform", &zones),
+            Some(chrono_tz::Asia::Kolkata)
+        );
+        assert_eq!(resolve_zone("Tokyo", &zones), Some(chrono_tz::Asia::Tokyo));
+        assert_eq!(
+            resolve_zone("europe/madrid", &zones),
+            Some(chrono_tz::Europe::Madrid)
+        );
+        assert_eq!(resolve_zone("Atlantis", &zones), None);
+    }
+
+    #[test]
+    fn test_clock_times() {
+        let time = |h, m| NaiveTime::from_hms_opt(h, m, 0);
+        assert_eq!(parse_clock_time("14:30"), time(14, 30));
+        assert_eq!(parse_clock_time("1430"), time(14, 30));
+        assert_eq!(parse_clock_time("930"), time(9, 30));
+        assert_eq!(parse_clock_time("9"), time(9, 0));
+        assert_eq!(parse_clock_time("2:30pm"), time(14, 30));
+        assert_eq!(parse_clock_time("12 AM"), time(0, 0));
+        assert_eq!(parse_clock_time("12p"), time(12, 0));
+        assert_eq!(parse_clock_time("25:00"), None);
+        assert_eq!(parse_clock_time("14:3"), None);
+        assert_eq!(parse_clock_time("13pm"), None);
+        assert_eq!(parse_clock_time("lunch"), None);
+        // Pasted text with multibyte characters where the digits would be
+        assert_eq!(parse_clock_time("aam-\u{feff}"), None);
+
+        // 10:00 UTC is 19:00 in Tokyo: 14:30 there is tomorrow, 20:00 today
+        let now = DateTime::parse_from_rfc3339("2024-06-20T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let tokyo = chrono_tz::Asia::Tokyo;
+        let next = |h, m| next_local_time(tokyo, now, time(h, m).unwrap()).unwrap();
+        assert_eq!(next(14, 30).to_rfc3339(), "2024-06-21T05:30:00+00:00");
+        assert_eq!(next(20, 0).to_rfc3339(), "2024-06-20T11:00:00+00:00");
+
+        // 02:30 doesn't happen in New York on 10 March 2024
+        let night = DateTime::parse_from_rfc3339("2024-03-10T05:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let new_york = chrono_tz::America::New_York;
+        let skipped = next_local_time(new_york, night, time(2, 30).unwrap()).unwrap();
+        assert_eq!(skipped.to_rfc3339(), "2024-03-11T06:30:00+00:00");
+    }
+
+    #[test]
+    fn test_next_work_hours() {
+        let at = |text: &str| {
+            DateTime::parse_from_rfc3339(text)
+                .unwrap()
+                .with_timezone(&Utc)
+        };
+        let hours = (9, 17);
+        // Wednesday noon in London, evening in Tokyo, early morning in New York
+        let now = at("2024-01-17T12:00:00Z");
+        let london = chrono_tz::Europe::London;
+        assert_eq!(
+            next_work_start(london, now, hours),
+            Some(at("2024-01-18T09:00:00Z"))
+        );
+        assert_eq!(
+            next_work_end(london, now, hours),
+            Some(at("2024-01-17T17:00:00Z"))
+        );
+        assert_eq!(
+            next_work_start(chrono_tz::Asia::Tokyo, now, hours),
+            Some(at("2024-01-18T00:00:00Z"))
+        );
+        assert_eq!(
+            next_work_start(chrono_tz::America::New_York, now, hours),
+            Some(at("2024-01-17T14:00:00Z"))
+        );
+
+        // Friday evening's next start is after the weekend
+        assert_eq!(
+            next_work_start(london, at("2024-01-19T18:00:00Z"), hours),
+            Some(at("2024-01-22T09:00:00Z"))
+        );
+    }
+
     #[test]
     fn test_timezone_creation() {
         let tz = TimeZone::from_tz(chrono_tz::UTC);
         assert_eq!(tz.name(), "UTC");
-        assert_eq!(tz.display_name, "UTC");
+        assert_eq!(tz.display.code, "UTC");
         assert_eq!(tz.utc_offset_hours(), 0);
     }
 
@@ -584,6 +1275,14 @@ mod tests {
             offset == "UTC-8" || offset == "UTC-7",
             "Expected UTC-8 or UTC-7, got {offset}"
         );
+
+        // Minutes are never rounded away, even west of UTC
+        let kathmandu = TimeZone::from_tz(chrono_tz::Asia::Kathmandu);
+        assert_eq!(kathmandu.offset_string(), "UTC+5:45");
+        let marquesas = TimeZone::from_tz(chrono_tz::Pacific::Marquesas);
+        assert_eq!(marquesas.offset_string(), "UTC-9:30");
+        assert_eq!(tokyo.offset_label(true), "UTC+9:00");
+        assert_eq!(utc.offset_label(true), "UTC+0:00");
     }
 
     #[test]
@@ -631,9 +1330,9 @@ mod tests {
             Some("Alice (Engineering)".to_string()),
         );
 
-        assert_eq!(timezone.display_name, "TYO");
+        assert_eq!(timezone.display.code, "TYO");
         assert_eq!(
-            timezone.custom_label.as_deref(),
+            timezone.display.label.as_deref(),
             Some("Alice (Engineering)")
         );
         assert_eq!(timezone.effective_display_name(), "Alice (Engineering)");
@@ -644,8 +1343,8 @@ mod tests {
         let tz = chrono_tz::Asia::Tokyo;
         let timezone = TimeZone::with_custom_label(tz, "TYO".to_string(), None);
 
-        assert_eq!(timezone.display_name, "TYO");
-        assert_eq!(timezone.custom_label, None);
+        assert_eq!(timezone.display.code, "TYO");
+        assert_eq!(timezone.display.label, None);
         assert_eq!(timezone.effective_display_name(), "TYO");
     }
 
@@ -655,18 +1354,32 @@ mod tests {
         manager.add_timezone_by_name("Tokyo");
 
         // Initially no custom label
-        assert_eq!(manager.zones()[0].custom_label, None);
+        assert_eq!(manager.zones()[0].display.label, None);
 
         // Update with custom label
         manager.update_zone_label(0, Some("Team Lead".to_string()));
         assert_eq!(
-            manager.zones()[0].custom_label.as_deref(),
+            manager.zones()[0].display.label.as_deref(),
             Some("Team Lead")
         );
 
         // Clear custom label
         manager.update_zone_label(0, None);
-        assert_eq!(manager.zones()[0].custom_label, None);
+        assert_eq!(manager.zones()[0].display.label, None);
+    }
+
+    #[test]
+    fn test_apply_zone_config() {
+        let entry = ZoneConfigCompat::new("Lisbon".to_string(), Some("Ana".to_string()))
+            .with_accent(Some("magenta".to_string()))
+            .with_calls(vec!["mornings".to_string()]);
+        let mut manager = TimeZoneManager::new();
+        manager.add_timezone_by_name("Lisbon");
+        assert!(manager.apply(0, &entry));
+        assert!(!manager.apply(1, &entry));
+
+        // The zone reads back as the entry it was given
+        assert_eq!(ZoneConfigCompat::from(&manager.zones()[0]), entry);
     }
 
     #[test]
@@ -678,7 +1391,7 @@ mod tests {
 
         assert_eq!(manager.zone_count(), 1);
         assert_eq!(
-            manager.zones()[0].custom_label.as_deref(),
+            manager.zones()[0].display.label.as_deref(),
             Some("NYC Office")
         );
     }
@@ -0,0 +1,263 @@
+use crate::terminal::GraphicsProtocol;
+
+/// Pixel size used when the terminal doesn't report its cell size
+const FALLBACK_CELL_PIXELS: (u16, u16) = (10, 20);
+
+/// Kitty image id, so each redraw replaces the previous clock
+const KITTY_IMAGE_ID: u32 = 1;
+
+const FACE_COLOR: [u8; 3] = [160, 160, 160];
+const HAND_COLOR: [u8; 3] = [0, 205, 205];
+
+/// An RGBA image of an analog clock face showing `hour:minute`
+pub struct ClockImage {
+    pub size: u32,
+    pub pixels: Vec<u8>,
+}
+
+/// Distance from `p` to the segment from `a` to `b`
+fn segment_distance(p: (f64, f64), a: (f64, f64), b: (f64, f64)) -> f64 {
+    let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+    let length_sq = dx * dx + dy * dy;
+    let t = if length_sq == 0.0 {
+        0.0
+    } else {
+        (((p.0 - a.0) * dx + (p.1 - a.1) * dy) / length_sq).clamp(0.0, 1.0)
+    };
+    let (x, y) = (a.0 + t * dx, a.1 + t * dy);
+    ((p.0 - x).powi(2) + (p.1 - y).powi(2)).sqrt()
+}
+
+impl ClockImage {
+    pub fn new(hour: u32, minute: u32, size: u32) -> Self {
+        let size = size.max(8);
+        let center = size as f64 / 2.0;
+        let radius = center - 1.5;
+        let stroke = (size as f64 / 32.0).max(1.0);
+
+        // Angles run clockwise from 12 o'clock
+        let point = |angle: f64, length: f64| {
+            (center + length * angle.sin(), center - length * angle.cos())
+        };
+        let minute_angle = minute as f64 / 60.0 * std::f64::consts::TAU;
+        let hour_angle = ((hour % 12) as f64 + minute as f64 / 60.0) / 12.0 * std::f64::consts::TAU;
+        let hands = [
+            (point(hour_angle, radius * 0.5), stroke * 1.6),
+            (point(minute_angle, radius * 0.8), stroke),
+        ];
+        let ticks: Vec<((f64, f64), (f64, f64))> = (0..12)
+            .map(|i| {
+                let angle = i as f64 / 12.0 * std::f64::consts::TAU;
+                (point(angle, radius * 0.82), point(angle, radius * 0.95))
+            })
+            .collect();
+
+        let mut pixels = vec![0u8; (size * size * 4) as usize];
+        for y in 0..size {
+            for x in 0..size {
+                let p = (x as f64 + 0.5, y as f64 + 0.5);
+                let from_center = ((p.0 - center).powi(2) + (p.1 - center).powi(2)).sqrt();
+
+                // Coverage of each shape, antialiased over about a pixel
+                let coverage =
+                    |distance: f64, width: f64| (width / 2.0 - distance + 0.5).clamp(0.0, 1.0);
+                let face = ticks
+                    .iter()
+                    .map(|&(a, b)| coverage(segment_distance(p, a, b), stroke))
+                    .fold(coverage((from_center - radius).abs(), stroke), f64::max);
+                let hand = hands
+                    .iter()
+                    .map(|&(tip, width)| {
+                        coverage(segment_distance(p, (center, center), tip), width)
+                    })
+                    .fold(0.0, f64::max);
+
+                let (color, alpha) = if hand > 0.0 {
+                    (HAND_COLOR, hand.max(face))
+                } else {
+                    (FACE_COLOR, face)
+                };
+                let offset = ((y * size + x) * 4) as usize;
+                pixels[offset..offset + 3].copy_from_slice(&color);
+                pixels[offset + 3] = (alpha * 255.0).round() as u8;
+            }
+        }
+
+        Self { size, pixels }
+    }
+
+    /// Escape sequence drawing the clock over `columns` x `rows` cells at the cursor
+    pub fn encode(&self, protocol: GraphicsProtocol, columns: u16, rows: u16) -> String {
+        match protocol {
+            GraphicsProtocol::Kitty => self.encode_kitty(columns, rows),
+            GraphicsProtocol::Sixel => self.encode_sixel(),
+        }
+    }
+
+    fn encode_kitty(&self, columns: u16, rows: u16) -> String {
+        let data = base64(&self.pixels);
+        let chunks: Vec<&str> = data
+            .as_bytes()
+            .chunks(4096)
+            .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+            .collect();
+
+        let mut out = kitty_delete();
+        for (i, chunk) in chunks.iter().enumerate() {
+            let more = u8::from(i + 1 < chunks.len());
+            if i == 0 {
+                out.push_str(&format!(
+                    "\x1b_Ga=T,f=32,s={size},v={size},i={KITTY_IMAGE_ID},c={columns},r={rows},C=1,q=2,m={more};{chunk}\x1b\\",
+                    size = self.size
+                ));
+            } else {
+                out.push_str(&format!("\x1b_Gm={more};{chunk}\x1b\\"));
+            }
+        }
+        out
+    }
+
+    /// Sixel with a transparent background: each pixel is the face color, the
+    /// hand color, or left alone
+    fn encode_sixel(&self) -> String {
+        let size = self.size as usize;
+        let color_at = |x: usize, y: usize| {
+            let offset = (y * size + x) * 4;
+            let pixel = &self.pixels[offset..offset + 4];
+            if pixel[3] < 128 {
+                None
+            } else if pixel[..3] == HAND_COLOR {
+                Some(1)
+            } else {
+                Some(0)
+            }
+        };
+        let percent = |c: [u8; 3]| c.map(|v| v as u32 * 100 / 255);
+        let [fr, fg, fb] = percent(FACE_COLOR);
+        let [hr, hg, hb] = percent(HAND_COLOR);
+
+        let mut out =
+            format!("\x1bP0;1;0q\"1;1;{size};{size}#0;2;{fr};{fg};{fb}#1;2;{hr};{hg};{hb}");
+        for band in (0..size).step_by(6) {
+            for color in 0..2 {
+                out.push_str(&format!("#{color}"));
+                let sixels: Vec<u8> = (0..size)
+                    .map(|x| {
+                        (0..6)
+                            .filter(|dy| band + dy < size && color_at(x, band + dy) == Some(color))
+                            .fold(0u8, |bits, dy| bits | (1 << dy))
+                    })
+                    .collect();
+                push_run_length(&mut out, &sixels);
+                out.push('$');
+            }
+            out.push('-');
+        }
+        out.push_str("\x1b\\");
+        out
+    }
+}
+
+/// Removes the kitty clock image, e.g. when its area is covered
+pub fn kitty_delete() -> String {
+    format!("\x1b_Ga=d,d=i,i={KITTY_IMAGE_ID},q=2\x1b\\")
+}
+
+/// Square image size in pixels that fills `columns` x `rows` cells
+pub fn image_size(columns: u16, rows: u16) -> u32 {
+    let (cell_width, cell_height) = crossterm::terminal::window_size()
+        .ok()
+        .filter(|size| size.width > 0 && size.height > 0 && size.columns > 0 && size.rows > 0)
+        .map(|size| (size.width / size.columns, size.height / size.rows))
+        .unwrap_or(FALLBACK_CELL_PIXELS);
+    (columns as u32 * cell_width as u32).min(rows as u32 * cell_height as u32)
+}
+
+fn push_run_length(out: &mut String, sixels: &[u8]) {
+    let mut i = 0;
+    while i < sixels.len() {
+        let run = sixels[i..].iter().take_while(|&&s| s == sixels[i]).count();
+        let ch = (63 + sixels[i]) as char;
+        if run > 3 {
+            out.push_str(&format!("!{run}{ch}"));
+        } else {
+            out.extend(std::iter::repeat_n(ch, run));
+        }
+        i += run;
+    }
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alpha(image: &ClockImage, x: u32, y: u32) -> u8 {
+        image.pixels[((y * image.size + x) * 4 + 3) as usize]
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_clock_hands() {
+        // At 3:00 the minute hand points up and the hour hand right
+        let image = ClockImage::new(3, 0, 64);
+        assert_eq!(image.pixels.len(), 64 * 64 * 4);
+        assert!(alpha(&image, 32, 16) > 200);
+        assert!(alpha(&image, 44, 32) > 200);
+        assert_eq!(alpha(&image, 20, 32), 0);
+        assert_eq!(alpha(&image, 0, 0), 0);
+    }
+
+    #[test]
+    fn test_kitty_encoding() {
+        let image = ClockImage::new(10, 10, 48);
+        let encoded = image.encode(GraphicsProtocol::Kitty, 8, 4);
+        assert!(encoded.starts_with(&kitty_delete()));
+        assert!(encoded.contains("a=T,f=32,s=48,v=48"));
+        assert!(encoded.contains("c=8,r=4"));
+        // Every chunk but the last is marked as continued
+        assert!(encoded.ends_with("\x1b\\"));
+        assert_eq!(encoded.matches("m=0;").count(), 1);
+    }
+
+    #[test]
+    fn test_sixel_encoding() {
+        let image = ClockImage::new(10, 10, 24);
+        let encoded = image.encode(GraphicsProtocol::Sixel, 8, 4);
+        assert!(encoded.starts_with("\x1bP0;1;0q\"1;1;24;24"));
+        assert!(encoded.ends_with("\x1b\\"));
+        // Four bands of six pixel rows
+        assert_eq!(encoded.matches('-').count(), 4);
+
+        let mut out = String::new();
+        push_run_length(&mut out, &[0, 0, 0, 0, 0, 1, 1]);
+        assert_eq!(out, "!5?@@");
+    }
+}
@@ -0,0 +1,340 @@
+use chrono::{
+    DateTime, Days, Duration, NaiveDate, NaiveDateTime, Offset, TimeZone as ChronoTimeZone,
+    Timelike, Utc,
+};
+use chrono_tz::Tz;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use crate::config::TimeDisplayConfig;
+use crate::time::TimeZone;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DstTransition {
+    SpringForward, // Clock jumps forward (2 AM -> 3 AM)
+    FallBack,      // Clock falls back (2 AM -> 1 AM)
+}
+
+/// A zone's UTC offset across a window. Offsets only change at transitions,
+/// so the window splits into a few spans of constant offset and converting
+/// an instant is a lookup rather than a time zone calculation.
+#[derive(Debug)]
+pub struct Offsets {
+    /// Each span's first instant and offset in seconds, in order
+    spans: Vec<(DateTime<Utc>, i32)>,
+}
+
+impl Offsets {
+    pub fn new(tz: Tz, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        let offset = |time: DateTime<Utc>| time.with_timezone(&tz).offset().fix().local_minus_utc();
+        let mut spans = vec![(start, offset(start))];
+
+        // Transitions are months apart, so probe hourly and bisect any hour
+        // whose offset changed down to the second it happened
+        let mut current = start;
+        while current < end {
+            let later = (current + Duration::hours(1)).min(end);
+            let before = spans[spans.len() - 1].1;
+            let after = offset(later);
+            if after != before {
+                let (mut low, mut high) = (current, later);
+                while high - low > Duration::seconds(1) {
+                    let middle = low + (high - low) / 2;
+                    if offset(middle) == before {
+                        low = middle;
+                    } else {
+                        high = middle;
+                    }
+                }
+                spans.push((high, after));
+            }
+            current = later;
+        }
+
+        Self { spans }
+    }
+
+    /// The offset in seconds at an instant within the window
+    pub fn offset_at(&self, time: DateTime<Utc>) -> i32 {
+        let index = self.spans.partition_point(|&(start, _)| start <= time);
+        self.spans[index.saturating_sub(1)].1
+    }
+
+    /// Wall-clock time at an instant within the window
+    pub fn local(&self, time: DateTime<Utc>) -> NaiveDateTime {
+        time.naive_utc() + Duration::seconds(i64::from(self.offset_at(time)))
+    }
+
+    /// Each instant the offset changes, with the offset before and after
+    pub fn transitions(&self) -> impl Iterator<Item = (DateTime<Utc>, i32, i32)> + '_ {
+        self.spans
+            .windows(2)
+            .map(|pair| (pair[1].0, pair[0].1, pair[1].1))
+    }
+}
+
+/// Where one zone's days fall within a timeline window, as UTC instants
+#[derive(Debug)]
+pub struct DayLayout {
+    pub start: DateTime<Utc>,
+    /// Each local hour overlapping the window, from the instant it starts
+    pub hours: Vec<(DateTime<Utc>, u32)>,
+    pub midnights: Vec<DateTime<Utc>>,
+    /// The middle of each local day's work hours, where its date is labelled
+    pub work_middles: Vec<(NaiveDate, DateTime<Utc>)>,
+    pub dst_transitions: Vec<(DateTime<Utc>, DstTransition)>,
+    /// Sunrise and sunset on the zone's current local day
+    pub sun_times: Option<(DateTime<Tz>, DateTime<Tz>)>,
+}
+
+impl DayLayout {
+    pub fn new(
+        zone: &TimeZone,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        time_config: &TimeDisplayConfig,
+        current_time: DateTime<Utc>,
+    ) -> Self {
+        let tz = zone.tz;
+        let offsets = Offsets::new(tz, start, end);
+        let local_start = offsets.local(start);
+        let local_end = offsets.local(end);
+
+        // Hours start at local :00, which is not always UTC :00
+        let mut hours = vec![(start, local_start.hour())];
+        let into_hour = Duration::seconds(
+            i64::from(local_start.minute()) * 60 + i64::from(local_start.second()),
+        );
+        let mut boundary = start - into_hour + Duration::hours(1);
+        while boundary < end {
+            hours.push((boundary, offsets.local(boundary).hour()));
+            boundary += Duration::hours(1);
+        }
+
+        let dst_transitions = offsets
+            .transitions()
+            .map(|(time, before, after)| {
+                if after > before {
+                    // Offset increased = clocks fell back (e.g., DST ended)
+                    (time, DstTransition::FallBack)
+                } else {
+                    // Offset decreased = clocks sprang forward (e.g., DST started)
+                    (time, DstTransition::SpringForward)
+                }
+            })
+            .collect();
+
+        // Walk each local day visible in the window
+        let work_hours = (time_config.work_hours_start, time_config.work_hours_end);
+        let work_middle_hour = (work_hours.0 + crate::time::work_length(work_hours) / 2) % 24;
+        let at_local = |date: NaiveDate, hour: u32| {
+            let local = date.and_hms_opt(hour, 0, 0)?;
+            let instant = tz.from_local_datetime(&local).single()?;
+            Some(instant.with_timezone(&Utc))
+        };
+        let mut midnights = Vec::new();
+        let mut work_middles = Vec::new();
+        let mut date = local_start.date();
+        while date <= local_end.date() {
+            if let Some(midnight) = at_local(date, 0).filter(|m| *m >= start && *m <= end) {
+                midnights.push(midnight);
+            }
+            if let Some(middle) = at_local(date, work_middle_hour) {
+                work_middles.push((date, middle));
+            }
+            date = date + Days::new(1);
+        }
+
+        Self {
+            start,
+            hours,
+            midnights,
+            work_middles,
+            dst_transitions,
+            sun_times: zone.get_sunrise_sunset(current_time),
+        }
+    }
+
+    /// The local hour at an instant within the window
+    pub fn hour_at(&self, time: DateTime<Utc>) -> u32 {
+        let index = self.hours.partition_point(|&(start, _)| start <= time);
+        self.hours[index.saturating_sub(1)].1
+    }
+}
+
+/// Inputs that invalidate every cached layout when they change
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Window {
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    // Local days turn over on quarter hours in every zone
+    quarter_hour: i64,
+}
+
+#[derive(Debug, Default)]
+struct CachedLayouts {
+    window: Option<Window>,
+    layouts: HashMap<(Tz, String, (u32, u32)), Arc<DayLayout>>,
+}
+
+/// Day layouts for the zones in one pane, shared by every widget drawing
+/// them (from any thread) and rebuilt only when the timeline window moves.
+/// Panes of different widths see different windows, so each keeps its own.
+#[derive(Debug, Default)]
+pub struct DayLayoutCache {
+    cached: Mutex<CachedLayouts>,
+}
+
+impl DayLayoutCache {
+    pub fn get(
+        &self,
+        zone: &TimeZone,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+        time_config: &TimeDisplayConfig,
+        current_time: DateTime<Utc>,
+    ) -> Arc<DayLayout> {
+        let window = Window {
+            start,
+            end,
+            quarter_hour: current_time.timestamp().div_euclid(15 * 60),
+        };
+        // The city matters too: zones sharing a tz have different sun times.
+        // So do the hours, which each zone can set for itself.
+        let key = (
+            zone.tz,
+            zone.display.code.clone(),
+            (time_config.work_hours_start, time_config.work_hours_end),
+        );
+
+        {
+            let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+            if cached.window != Some(window) {
+                cached.window = Some(window);
+                cached.layouts.clear();
+            }
+            if let Some(layout) = cached.layouts.get(&key) {
+                return layout.clone();
+            }
+        }
+
+        // Built outside the lock so rows rendering in parallel don't queue
+        let layout = Arc::new(DayLayout::new(zone, start, end, time_config, current_time));
+        let mut cached = self.cached.lock().unwrap_or_else(PoisonError::into_inner);
+        if cached.window == Some(window) {
+            cached.layouts.insert(key, layout.clone());
+        }
+        layout
+    }
+}
+
+/// A clock change: when, and the offsets in seconds before and after
+pub type ClockChange = (DateTime<Utc>, i32, i32);
+
+/// `tz`'s first clock change after `now` and up to `window` ahead. Probes
+/// every hour of the window, so see [`ClockChanges`].
+pub fn next_clock_change(tz: Tz, now: DateTime<Utc>, window: Duration) -> Option<ClockChange> {
+    Offsets::new(tz, now, now + window).transitions().next()
+}
+
+/// A zone's next clock change as last looked for, and the span looked in
+#[derive(Debug, Clone, Copy)]
+struct FoundChange {
+    change: Option<ClockChange>,
+    from: DateTime<Utc>,
+    until: DateTime<Utc>,
+}
+
+/// Each zone's next clock change, shared by every widget counting down to
+/// one and only looked for again once it has passed or the countdown
+/// reaches past where it was looked for
+#[derive(Debug, Default)]
+pub struct ClockChanges {
+    found: Mutex<HashMap<Tz, FoundChange>>,
+}
+
+impl ClockChanges {
+    /// `tz`'s first clock change after `now` and up to `window` ahead
+    pub fn next(&self, tz: Tz, now: DateTime<Utc>, window: Duration) -> Option<ClockChange> {
+        let mut found = self.found.lock().unwrap_or_else(PoisonError::into_inner);
+        let fresh = found.get(&tz).is_some_and(|found| {
+            found.from <= now
+                && match found.change {
+                    Some((at, _, _)) => now < at,
+                    None => now + window <= found.until,
+                }
+        });
+        if !fresh {
+            // A day past the window, so the next few thousand ticks can
+            // reuse what was found
+            let span = window + Duration::days(1);
+            let change = next_clock_change(tz, now, span);
+            let until = now + span;
+            found.insert(
+                tz,
+                FoundChange {
+                    change,
+                    from: now,
+                    until,
+                },
+            );
+        }
+        found[&tz].change.filter(|&(at, _, _)| at - now <= window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_day_layout() {
+        let zone = TimeZone::from_tz(chrono_tz::Asia::Kolkata);
+        let config = TimeDisplayConfig::default();
+        let start = DateTime::parse_from_rfc3339("2024-01-15T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = start + Duration::hours(48);
+        let layout = DayLayout::new(&zone, start, end, &config, start);
+
+        // UTC+5:30, so local hours start at UTC :30
+        assert_eq!(layout.hours[0], (start, 5));
+        assert_eq!(layout.hours[1].0, start + Duration::minutes(30));
+        assert_eq!(layout.hour_at(start + Duration::minutes(29)), 5);
+        assert_eq!(layout.hour_at(start + Duration::minutes(30)), 6);
+        assert_eq!(layout.midnights[0], start + Duration::minutes(18 * 60 + 30));
+        assert!(layout.dst_transitions.is_empty());
+
+        // Offsets split at the exact transition, here London's spring forward
+        let march = DateTime::parse_from_rfc3339("2024-03-30T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let offsets = Offsets::new(
+            chrono_tz::Europe::London,
+            march,
+            march + Duration::hours(48),
+        );
+        let transition = DateTime::parse_from_rfc3339("2024-03-31T01:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            offsets.transitions().collect::<Vec<_>>(),
+            vec![(transition, 0, 3600)]
+        );
+        assert_eq!(offsets.local(transition).hour(), 2);
+        assert_eq!(offsets.offset_at(transition - Duration::seconds(1)), 0);
+
+        // Layouts are shared until the window moves
+        let cache = DayLayoutCache::default();
+        let first = cache.get(&zone, start, end, &config, start);
+        assert!(Arc::ptr_eq(
+            &first,
+            &cache.get(&zone, start, end, &config, start)
+        ));
+        let later = start + Duration::hours(1);
+        assert!(!Arc::ptr_eq(
+            &first,
+            &cache.get(&zone, later, end, &config, start)
+        ));
+    }
+}
@@ -0,0 +1,175 @@
+use crate::config::{GlyphMode, TimeActivity};
+use ratatui::symbols::border;
+
+/// Box borders drawn with `+`, `-` and `|`
+const ASCII_BORDER: border::Set = border::Set {
+    top_left: "+",
+    top_right: "+",
+    bottom_left: "+",
+    bottom_right: "+",
+    vertical_left: "|",
+    vertical_right: "|",
+    horizontal_top: "-",
+    horizontal_bottom: "-",
+};
+
+/// Characters used to draw timelines and indicators. Terminals that can't
+/// render the Unicode set, like the legacy Windows console, get plain ASCII.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Glyphs {
+    pub night: char,
+    pub awake: char,
+    pub work: char,
+    pub now: char,
+    pub scrub: char,
+    // Teammates' scrub positions on a shared board
+    pub ghost: char,
+    pub midnight: char,
+    pub spring_forward: char,
+    pub fall_back: char,
+    // Between the names either side of a clock change
+    pub change: char,
+    pub sunrise: char,
+    pub sunset: char,
+    pub separator: char,
+    pub recording: char,
+    pub minus: char,
+    pub milestone: char,
+    // Task due dates on the home zone's bar
+    pub due: char,
+    // Hour marks on the selected zone's ruler
+    pub tick: char,
+    // Lines between groups of zones
+    pub rule: char,
+    // Zones marked for a bulk action
+    pub mark: char,
+    // Upper half block for drawing two rows of QR modules per cell
+    pub half_block: Option<char>,
+    // Rising levels for sparklines
+    pub sparks: [char; 8],
+    pub help_icon: &'static str,
+    // Borders around rows and dialogs
+    pub border: border::Set,
+}
+
+impl Glyphs {
+    pub const UNICODE: Glyphs = Glyphs {
+        night: '░', // Light shade - low activity
+        awake: '▒', // Medium shade - moderate activity
+        work: '▓',  // Dark shade - high activity (less intense than █)
+        now: '│',
+        scrub: '┃',
+        ghost: '╏',
+        midnight: '┊',
+        spring_forward: '⇈',
+        fall_back: '⇊',
+        change: '→',
+        sunrise: '☀',
+        sunset: '☽',
+        separator: '│',
+        recording: '●',
+        minus: '−',
+        milestone: '◆',
+        due: '⚑',
+        tick: '╵',
+        rule: '─',
+        mark: '●',
+        half_block: Some('▀'),
+        sparks: ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'],
+        help_icon: "🕐 ",
+        border: border::PLAIN,
+    };
+
+    pub const ASCII: Glyphs = Glyphs {
+        night: '.',
+        awake: '-',
+        work: '=',
+        now: '|',
+        scrub: '#',
+        ghost: '%',
+        midnight: ':',
+        spring_forward: '^',
+        fall_back: 'v',
+        change: '>',
+        sunrise: '^',
+        sunset: 'v',
+        separator: '|',
+        recording: '*',
+        minus: '-',
+        milestone: '*',
+        due: '!',
+        tick: '\'',
+        rule: '-',
+        mark: '+',
+        half_block: None,
+        sparks: ['_', '.', ',', ':', '-', '=', '+', '#'],
+        help_icon: "",
+        border: ASCII_BORDER,
+    };
+
+    /// Picks the glyph set for a config mode; `Auto` uses Unicode when the
+    /// terminal is known to draw it correctly
+    pub fn for_mode(mode: GlyphMode, unicode_supported: bool) -> Self {
+        match mode {
+            GlyphMode::Unicode => Self::UNICODE,
+            GlyphMode::Ascii => Self::ASCII,
+            GlyphMode::Auto if unicode_supported => Self::UNICODE,
+            GlyphMode::Auto => Self::ASCII,
+        }
+    }
+
+    pub fn activity(&self, activity: TimeActivity) -> char {
+        match activity {
+            TimeActivity::Night => self.night,
+            TimeActivity::Awake => self.awake,
+            TimeActivity::Work => self.work,
+        }
+    }
+}
+
+impl Default for Glyphs {
+    fn default() -> Self {
+        Self::UNICODE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_glyphs() {
+        assert_eq!(Glyphs::UNICODE.activity(TimeActivity::Night), '░');
+        assert_eq!(Glyphs::UNICODE.activity(TimeActivity::Awake), '▒');
+        assert_eq!(Glyphs::UNICODE.activity(TimeActivity::Work), '▓');
+        assert_eq!(Glyphs::for_mode(GlyphMode::Ascii, true), Glyphs::ASCII);
+        assert_eq!(Glyphs::for_mode(GlyphMode::Unicode, false), Glyphs::UNICODE);
+        assert_eq!(Glyphs::for_mode(GlyphMode::Auto, true), Glyphs::UNICODE);
+        assert_eq!(Glyphs::for_mode(GlyphMode::Auto, false), Glyphs::ASCII);
+    }
+
+    #[test]
+    fn test_ascii_glyphs_are_ascii() {
+        let g = Glyphs::ASCII;
+        let chars = [
+            g.night,
+            g.awake,
+            g.work,
+            g.now,
+            g.scrub,
+            g.midnight,
+            g.spring_forward,
+            g.fall_back,
+            g.sunrise,
+            g.sunset,
+            g.separator,
+            g.recording,
+            g.tick,
+            g.due,
+            g.rule,
+        ];
+        assert!(chars.iter().all(char::is_ascii));
+        assert!(g.help_icon.is_ascii());
+        assert!(g.border.top_left.is_ascii() && g.border.horizontal_top.is_ascii());
+    }
+}
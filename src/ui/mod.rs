@@ -1,3 +1,13 @@
+#[cfg(feature = "clock-graphics")]
+pub mod clock;
+pub mod day_layout;
+pub mod glyphs;
+pub mod qr;
+pub mod row_layout;
 pub mod timeline;
 
-pub use timeline::TimelineWidget;
+pub use day_layout::{ClockChanges, DayLayoutCache};
+pub use glyphs::Glyphs;
+pub use qr::QrWidget;
+pub use row_layout::RowLayout;
+pub use timeline::{render_rows, Ghost, TimelineWidget};
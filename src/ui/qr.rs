@@ -0,0 +1,113 @@
+use qrcodegen::{QrCode, QrCodeEcc};
+use ratatui::{
+    buffer::Buffer,
+    layout::Rect,
+    style::{Color, Style},
+    widgets::Widget,
+};
+
+/// Modules of light margin around the code. The spec asks for 4, but phone
+/// cameras manage with less and terminal space is tight.
+const QUIET_ZONE: i32 = 2;
+
+/// A QR code drawn dark on light whatever the theme, since that is what
+/// phone cameras expect. With a half-block glyph each cell holds two modules
+/// stacked; without one, each module is two background-coloured spaces.
+pub struct QrWidget {
+    code: QrCode,
+    half_block: Option<char>,
+}
+
+impl QrWidget {
+    /// `None` if the text is too long to encode
+    pub fn new(text: &str, half_block: Option<char>) -> Option<Self> {
+        let code = QrCode::encode_text(text, QrCodeEcc::Medium).ok()?;
+        Some(Self { code, half_block })
+    }
+
+    /// Width and height in cells, margin included
+    pub fn size(&self) -> (u16, u16) {
+        let modules = (self.code.size() + 2 * QUIET_ZONE) as u16;
+        match self.half_block {
+            Some(_) => (modules, modules.div_ceil(2)),
+            None => (modules * 2, modules),
+        }
+    }
+
+    /// Whether the module at (x, y), counted from the margin's corner, is dark
+    fn dark(&self, x: i32, y: i32) -> bool {
+        // get_module is light outside the code, which covers the margin
+        self.code.get_module(x - QUIET_ZONE, y - QUIET_ZONE)
+    }
+}
+
+fn color(dark: bool) -> Color {
+    if dark {
+        Color::Black
+    } else {
+        Color::White
+    }
+}
+
+impl Widget for QrWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (width, height) = self.size();
+        if width > area.width || height > area.height {
+            return;
+        }
+
+        for row in 0..height {
+            for col in 0..width {
+                let Some(cell) = buf.cell_mut((area.x + col, area.y + row)) else {
+                    continue;
+                };
+                match self.half_block {
+                    Some(glyph) => {
+                        let (x, y) = (col as i32, row as i32 * 2);
+                        cell.set_char(glyph).set_style(
+                            Style::default()
+                                .fg(color(self.dark(x, y)))
+                                .bg(color(self.dark(x, y + 1))),
+                        );
+                    }
+                    None => {
+                        let dark = self.dark(col as i32 / 2, row as i32);
+                        cell.set_char(' ')
+                            .set_style(Style::default().bg(color(dark)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_widget() {
+        let text = "2024-06-20T16:00:00+09:00 Tokyo";
+        let widget = QrWidget::new(text, Some('▀')).unwrap();
+        let (width, height) = widget.size();
+        let area = Rect::new(0, 0, width, height);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        // Margin, then the top-left finder pattern's dark edge
+        assert_eq!(buf[(0, 0)].fg, Color::White);
+        assert_eq!(buf[(2, 1)].fg, Color::Black);
+        assert_eq!(buf[(2, 1)].bg, Color::Black);
+
+        // Without half blocks the code is twice as wide and tall
+        let ascii = QrWidget::new(text, None).unwrap();
+        assert_eq!(ascii.size(), (width * 2, width));
+
+        // Too little room draws nothing rather than an unreadable code
+        let mut small = Buffer::empty(Rect::new(0, 0, 10, 10));
+        QrWidget::new(text, Some('▀'))
+            .unwrap()
+            .render(small.area, &mut small);
+        assert_eq!(small[(2, 1)].bg, Color::Reset);
+    }
+}
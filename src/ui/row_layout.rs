@@ -0,0 +1,102 @@
+use ratatui::{
+    buffer::Buffer,
+    layout::{Alignment, Margin, Rect},
+    style::Style,
+    symbols::border,
+    text::{Line, Span},
+    widgets::{Block, Borders, Widget},
+};
+
+/// One cell of a zone's bar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Cell {
+    pub ch: char,
+    pub style: Style,
+}
+
+/// Text on a row, from column `x` of line `y` inside the frame, cut off at
+/// the row's edge
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowText {
+    pub x: u16,
+    pub y: u16,
+    pub text: String,
+    pub style: Style,
+}
+
+/// The border drawn around a full row, with its title and sun times
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowFrame {
+    pub title: String,
+    pub style: Style,
+    pub sun_times: Option<(String, Style)>,
+    pub border: border::Set,
+}
+
+/// Everything on one zone's row, positioned but not drawn, so the terminal,
+/// exports and tests all place it the same way. Built by
+/// [`TimelineWidget::layout`](crate::ui::TimelineWidget::layout).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RowLayout {
+    pub frame: Option<RowFrame>,
+    /// Where the bar starts on the first line, after any compact prefix
+    pub bar_x: u16,
+    /// The bar's cells with its shading, markers and labels in place
+    pub bar: Vec<Cell>,
+    /// Everything else, drawn in order after the bar
+    pub texts: Vec<RowText>,
+}
+
+impl RowLayout {
+    /// The part of a row's area inside its frame
+    pub fn inner(&self, area: Rect) -> Rect {
+        match self.frame {
+            Some(_) => area.inner(Margin {
+                horizontal: 1,
+                vertical: 1,
+            }),
+            None => area,
+        }
+    }
+
+    /// The bar as plain text, for tests and plain-text exports
+    pub fn bar_text(&self) -> String {
+        self.bar.iter().map(|cell| cell.ch).collect()
+    }
+
+    pub fn render(&self, area: Rect, buf: &mut Buffer) {
+        if let Some(frame) = &self.frame {
+            let mut block = Block::default()
+                .borders(Borders::ALL)
+                .border_set(frame.border)
+                .title(frame.title.as_str())
+                .style(frame.style);
+            if let Some((sun_times, style)) = &frame.sun_times {
+                let line = Line::from(vec![Span::styled(sun_times.as_str(), *style)])
+                    .alignment(Alignment::Right);
+                block = block.title_top(line);
+            }
+            block.render(area, buf);
+        }
+
+        let inner = self.inner(area);
+        for (i, cell) in self.bar.iter().enumerate() {
+            let x = inner.x + self.bar_x + i as u16;
+            if x < inner.right() && inner.height > 0 {
+                buf[(x, inner.y)].set_char(cell.ch).set_style(cell.style);
+            }
+        }
+        for text in &self.texts {
+            if text.x >= inner.width || text.y >= inner.height {
+                continue;
+            }
+            buf.set_stringn(
+                inner.x + text.x,
+                inner.y + text.y,
+                &text.text,
+                (inner.width - text.x) as usize,
+                text.style,
+            );
+        }
+    }
+}
@@ -1,15 +1,29 @@
-use chrono::{DateTime, Days, Duration, Offset, TimeZone as ChronoTimeZone, Timelike, Utc};
+use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
+use chrono_tz::Tz;
 use ratatui::{
     buffer::Buffer,
-    layout::{Alignment, Margin, Rect},
-    style::{Color, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Widget},
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    widgets::Widget,
 };
+use std::sync::Arc;
 
 use crate::app::{TimeFormat, TimezoneDisplayMode};
-use crate::config::{ColorTheme, TimeDisplayConfig};
-use crate::time::TimeZone;
+use crate::config::{BlockedDates, Marker, Milestone, NowLineStyle, Palette, TimeDisplayConfig};
+use crate::handoff;
+use crate::time::{utc_offset_label, FiscalQuarter, TimeZone};
+use crate::ui::day_layout::{
+    next_clock_change, ClockChanges, DayLayout, DayLayoutCache, DstTransition,
+};
+use crate::ui::row_layout::{Cell, RowFrame, RowLayout, RowText};
+use crate::ui::Glyphs;
+
+/// Where a teammate sharing their cursor is scrubbed to
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ghost {
+    pub name: String,
+    pub position: DateTime<Utc>,
+}
 
 pub struct TimelineWidget<'a> {
     pub timeline_position: DateTime<Utc>,
@@ -19,16 +33,148 @@ pub struct TimelineWidget<'a> {
     pub display_format: TimeFormat,
     pub timezone_display_mode: TimezoneDisplayMode,
     pub time_config: &'a TimeDisplayConfig,
-    pub color_theme: ColorTheme,
+    pub palette: Palette,
     pub show_date: bool,
     pub show_dst: bool,
     pub show_sun_times: bool,
+    pub compact: bool,
+    pub borderless: bool,
+    pub glyphs: Glyphs,
+    pub private: bool,
+    pub layout_cache: Option<&'a DayLayoutCache>,
+    pub decorations: bool,
+    pub marker_priority: &'a [Marker],
+    pub now_line: &'a NowLineStyle,
+    pub home_date: Option<NaiveDate>,
+    pub fiscal_year_start: Option<u32>,
+    pub milestones: &'a [Milestone],
+    pub ghosts: &'a [Ghost],
+    pub tasks: &'a [Milestone],
+    pub imminent: Option<Duration>,
+    pub pulse: bool,
+    pub overlap_week: Option<[f32; 7]>,
+    pub blocked: &'a [BlockedDates],
+    pub ruler: bool,
+    pub marked: bool,
+    pub offset_minutes: bool,
+    pub align_to: Option<Tz>,
+    pub dst_countdown: Option<Duration>,
+    pub clock_changes: Option<&'a ClockChanges>,
+}
+
+/// Markers placed on a bar before it is drawn, so where several land on one
+/// cell the highest priority shows rather than whichever was drawn last
+struct MarkerLayer<'p> {
+    priority: &'p [Marker],
+    cells: Vec<Option<(usize, Marker, char, Style)>>,
+}
+
+impl<'p> MarkerLayer<'p> {
+    fn new(priority: &'p [Marker], width: u16) -> Self {
+        Self {
+            priority,
+            cells: vec![None; width as usize],
+        }
+    }
+
+    /// Lower is more important; markers missing from the list come last
+    fn rank(&self, marker: Marker) -> usize {
+        self.priority
+            .iter()
+            .position(|&m| m == marker)
+            .unwrap_or(self.priority.len())
+    }
+
+    fn place(&mut self, position: u16, marker: Marker, ch: char, style: Style) {
+        let rank = self.rank(marker);
+        if let Some(cell) = self.cells.get_mut(position as usize) {
+            if cell.is_none_or(|(existing, ..)| rank < existing) {
+                *cell = Some((rank, marker, ch, style));
+            }
+        }
+    }
+
+    /// Which cells hold a marker that outranks `marker`
+    fn outranking(&self, marker: Marker) -> Vec<bool> {
+        let rank = self.rank(marker);
+        self.cells
+            .iter()
+            .map(|cell| cell.is_some_and(|(existing, ..)| existing < rank))
+            .collect()
+    }
+
+    /// Which cells show one of `markers`
+    fn holding(&self, markers: &[Marker]) -> Vec<bool> {
+        self.cells
+            .iter()
+            .map(|cell| cell.is_some_and(|(_, marker, ..)| markers.contains(&marker)))
+            .collect()
+    }
+
+    /// Puts the markers on the bar, over its shading
+    fn apply(self, bar: &mut [Cell]) {
+        for (cell, marker) in bar.iter_mut().zip(self.cells) {
+            if let Some((_, _, ch, style)) = marker {
+                cell.ch = ch;
+                cell.style = cell.style.patch(style);
+            }
+        }
+    }
+}
+
+/// Time left as "2d 14h", or hours and minutes under a day
+fn format_countdown(left: Duration) -> String {
+    match (left.num_days(), left.num_hours() % 24) {
+        (0, _) => handoff::format_duration(left),
+        (days, 0) => format!("{days}d"),
+        (days, hours) => format!("{days}d {hours}h"),
+    }
+}
+
+/// A label to lay out along a bar, with a shorter form for tight spots
+struct BarLabel {
+    anchor: u16,
+    full: String,
+    short: String,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum DstTransition {
-    SpringForward, // Clock jumps forward (2 AM -> 3 AM)
-    FallBack,      // Clock falls back (2 AM -> 1 AM)
+/// Lays labels out along a bar `width` cells wide, in order, so they never
+/// overlap each other or the `blocked` cells. Each label covers its anchor,
+/// centred on it where possible and otherwise shifted as little as needed;
+/// one that won't fit is shortened, then dropped. Returns each placed
+/// label's first cell and text.
+fn place_labels(labels: Vec<BarLabel>, width: u16, blocked: &[bool]) -> Vec<(u16, String)> {
+    let mut placed = Vec::new();
+    // First cell a label may use, keeping a gap after the previous one
+    let mut free_from = 0u16;
+
+    for label in labels {
+        for text in [label.full, label.short] {
+            let len = text.chars().count() as u16;
+            if len == 0 || len > width {
+                continue;
+            }
+            let lowest = free_from.max((label.anchor + 1).saturating_sub(len));
+            let highest = label.anchor.min(width - len);
+            if lowest > highest {
+                continue;
+            }
+            let centred = label.anchor.saturating_sub(len / 2).clamp(lowest, highest);
+            let clear = |start: u16| {
+                !(start..start + len).any(|x| blocked.get(x as usize).copied().unwrap_or(true))
+            };
+            let start = (lowest..=highest)
+                .filter(|&start| clear(start))
+                .min_by_key(|&start| start.abs_diff(centred));
+            if let Some(start) = start {
+                free_from = start + len + 1;
+                placed.push((start, text));
+                break;
+            }
+        }
+    }
+
+    placed
 }
 
 impl<'a> TimelineWidget<'a> {
@@ -41,7 +187,7 @@ impl<'a> TimelineWidget<'a> {
         display_format: TimeFormat,
         timezone_display_mode: TimezoneDisplayMode,
         time_config: &'a TimeDisplayConfig,
-        color_theme: ColorTheme,
+        palette: Palette,
         show_date: bool,
         show_dst: bool,
         show_sun_times: bool,
@@ -54,10 +200,209 @@ impl<'a> TimelineWidget<'a> {
             display_format,
             timezone_display_mode,
             time_config,
-            color_theme,
+            palette,
             show_date,
             show_dst,
             show_sun_times,
+            compact: false,
+            borderless: false,
+            glyphs: Glyphs::UNICODE,
+            private: false,
+            layout_cache: None,
+            decorations: true,
+            marker_priority: &Marker::DEFAULT_PRIORITY,
+            now_line: &NowLineStyle::DEFAULT,
+            home_date: None,
+            fiscal_year_start: None,
+            milestones: &[],
+            ghosts: &[],
+            tasks: &[],
+            imminent: None,
+            pulse: false,
+            overlap_week: None,
+            blocked: &[],
+            ruler: false,
+            marked: false,
+            offset_minutes: false,
+            align_to: None,
+            dst_countdown: None,
+            clock_changes: None,
+        }
+    }
+
+    /// Renders the zone as a single borderless row: label, scrub time, then the bar
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = compact;
+        self
+    }
+
+    /// Drops the border, title and sun times, keeping just the bar and time line
+    pub fn borderless(mut self, borderless: bool) -> Self {
+        self.borderless = borderless;
+        self
+    }
+
+    /// Sets the characters used to draw the bar and its markers
+    pub fn glyphs(mut self, glyphs: Glyphs) -> Self {
+        self.glyphs = glyphs;
+        self
+    }
+
+    /// Hides the zone's custom label, which often names a person or customer
+    pub fn private(mut self, private: bool) -> Self {
+        self.private = private;
+        self
+    }
+
+    /// Shares day layouts with the other widgets drawing the same window
+    pub fn layout_cache(mut self, cache: &'a DayLayoutCache) -> Self {
+        self.layout_cache = Some(cache);
+        self
+    }
+
+    /// Turns off the DST, midnight and date markers and sun times, for frames
+    /// that have to be drawn quickly
+    pub fn decorations(mut self, decorations: bool) -> Self {
+        self.decorations = decorations;
+        self
+    }
+
+    /// Which marker shows where several share a cell, highest first
+    pub fn marker_priority(mut self, priority: &'a [Marker]) -> Self {
+        self.marker_priority = priority;
+        self
+    }
+
+    /// Overrides the now line's glyph and color, and can tint the current hour
+    pub fn now_line(mut self, style: &'a NowLineStyle) -> Self {
+        self.now_line = style;
+        self
+    }
+
+    /// The home zone's date at the scrub line, so zones on another day get a
+    /// "+1d"/"−1d" badge beside their time
+    pub fn home_date(mut self, date: NaiveDate) -> Self {
+        self.home_date = Some(date);
+        self
+    }
+
+    /// Marks and labels fiscal quarter starts for a fiscal year starting in
+    /// this month
+    pub fn fiscal_year_start(mut self, month: Option<u32>) -> Self {
+        self.fiscal_year_start = month;
+        self
+    }
+
+    /// Marks these milestones on the bar, naming them on the selected zone
+    pub fn milestones(mut self, milestones: &'a [Milestone]) -> Self {
+        self.milestones = milestones;
+        self
+    }
+
+    /// Draws teammates' scrub positions from the shared board as faint scrub
+    /// lines, naming them on the selected zone
+    pub fn ghosts(mut self, ghosts: &'a [Ghost]) -> Self {
+        self.ghosts = ghosts;
+        self
+    }
+
+    /// Marks task due dates on the bar like milestones, naming them on the
+    /// selected zone
+    pub fn tasks(mut self, tasks: &'a [Milestone]) -> Self {
+        self.tasks = tasks;
+        self
+    }
+
+    /// Milestones less than `window` away are drawn in inverse video, from
+    /// yellow to red as they near. With `pulse` the inverse flashes each second.
+    pub fn imminent(mut self, window: Option<Duration>, pulse: bool) -> Self {
+        self.imminent = window;
+        self.pulse = pulse;
+        self
+    }
+
+    /// Days people are away; those for this zone are shaded as night
+    pub fn blocked(mut self, blocked: &'a [BlockedDates]) -> Self {
+        self.blocked = blocked;
+        self
+    }
+
+    /// Draws an hour ruler in the zone's local time on the row's third line
+    pub fn ruler(mut self, ruler: bool) -> Self {
+        self.ruler = ruler;
+        self
+    }
+
+    /// Flags the row as marked for a bulk action
+    pub fn marked(mut self, marked: bool) -> Self {
+        self.marked = marked;
+        self
+    }
+
+    /// Shows minutes in the title's UTC offset for whole-hour zones too
+    pub fn offset_minutes(mut self, offset_minutes: bool) -> Self {
+        self.offset_minutes = offset_minutes;
+        self
+    }
+
+    /// Counts down in the title to a clock change less than `window` away
+    pub fn dst_countdown(mut self, window: Option<Duration>) -> Self {
+        self.dst_countdown = window;
+        self
+    }
+
+    /// Shares the clock changes found for the countdown with other widgets
+    /// and later frames
+    pub fn clock_changes(mut self, changes: &'a ClockChanges) -> Self {
+        self.clock_changes = Some(changes);
+        self
+    }
+
+    /// Starts the window at midnight in `zone` on the scrubbed day, so the
+    /// board reads as that zone's today and tomorrow rather than centring
+    /// on the scrub line
+    pub fn align_to(mut self, zone: Option<Tz>) -> Self {
+        self.align_to = zone;
+        self
+    }
+
+    /// Shows a sparkline of the next seven days' work-hour overlap with home
+    /// on compact rows, each day as a fraction of a full working day
+    pub fn overlap_week(mut self, week: [f32; 7]) -> Self {
+        self.overlap_week = Some(week);
+        self
+    }
+
+    /// The fiscal quarter `date` starts, if it starts one
+    fn quarter_starting(&self, date: NaiveDate) -> Option<FiscalQuarter> {
+        let start_month = self.fiscal_year_start?;
+        FiscalQuarter::starts_on(date, start_month).then(|| FiscalQuarter::of(date, start_month))
+    }
+
+    /// The zone's custom label, unless hidden for privacy
+    fn custom_label(&self) -> Option<&str> {
+        self.timezone
+            .display
+            .label
+            .as_deref()
+            .filter(|_| !self.private)
+    }
+
+    /// Custom label if shown, otherwise the short display name
+    fn display_name(&self) -> &str {
+        self.custom_label().unwrap_or(&self.timezone.display.code)
+    }
+
+    /// The zone's own color, used for its name and border when unselected
+    fn accent(&self) -> Option<Color> {
+        self.timezone.display.accent.as_deref()?.parse().ok()
+    }
+
+    /// The display name, flagged when the row is marked
+    fn marked_name(&self) -> String {
+        match self.marked {
+            true => format!("{} {}", self.glyphs.mark, self.display_name()),
+            false => self.display_name().to_string(),
         }
     }
 
@@ -75,7 +420,23 @@ impl<'a> TimelineWidget<'a> {
         optimal_hours.clamp(MIN_HOURS, MAX_HOURS)
     }
 
+    /// Midnight starting the scrubbed day in the aligned zone, if aligned
+    /// and that midnight exists
+    fn aligned_start(&self) -> Option<DateTime<Utc>> {
+        let zone = self.align_to?;
+        let midnight = self
+            .timeline_position
+            .with_timezone(&zone)
+            .date_naive()
+            .and_hms_opt(0, 0, 0)?;
+        let midnight = chrono::TimeZone::from_local_datetime(&zone, &midnight).earliest()?;
+        Some(midnight.with_timezone(&Utc))
+    }
+
     fn get_timeline_start(&self, width: u16) -> DateTime<Utc> {
+        if let Some(start) = self.aligned_start() {
+            return start;
+        }
         let total_hours = self.get_timeline_hours(width);
         let hours_before = total_hours / 2.0;
         self.timeline_position - Duration::minutes((hours_before * 60.0) as i64)
@@ -83,6 +444,9 @@ impl<'a> TimelineWidget<'a> {
 
     fn get_timeline_end(&self, width: u16) -> DateTime<Utc> {
         let total_hours = self.get_timeline_hours(width);
+        if let Some(start) = self.aligned_start() {
+            return start + Duration::minutes((total_hours * 60.0) as i64);
+        }
         let hours_after = total_hours / 2.0;
         self.timeline_position + Duration::minutes((hours_after * 60.0) as i64)
     }
@@ -102,145 +466,454 @@ impl<'a> TimelineWidget<'a> {
         position.min(width.saturating_sub(1))
     }
 
-    fn get_hour_display(&self, hour: u32) -> (char, Color) {
-        let activity = self.time_config.get_time_activity(hour);
-        let char = self.time_config.get_activity_char(activity);
-        let color = self
-            .time_config
-            .get_activity_color(activity, self.color_theme);
+    /// The shading for `hour` when `hours` are kept, which seasons can change
+    fn get_hour_display(&self, hour: u32, hours: &TimeDisplayConfig) -> (char, Color) {
+        let activity = hours.get_time_activity(hour);
+        let char = self.glyphs.activity(activity);
+        let color = hours.get_activity_color(activity, self.palette);
         (char, color)
     }
 
-    fn detect_dst_transition(&self, utc_time: DateTime<Utc>) -> Option<DstTransition> {
-        // Check for DST transitions by examining offset changes
-        let local_time = utc_time.with_timezone(&self.timezone.tz);
-        let offset_before = local_time.offset().fix().local_minus_utc();
+    /// Where this zone's days fall in the window drawn at `width`
+    fn day_layout(&self, width: u16) -> Arc<DayLayout> {
+        let start = self.get_timeline_start(width);
+        let end = self.get_timeline_end(width);
+        match self.layout_cache {
+            Some(cache) => cache.get(
+                self.timezone,
+                start,
+                end,
+                self.timezone.hours(self.time_config),
+                self.current_time,
+            ),
+            None => Arc::new(DayLayout::new(
+                self.timezone,
+                start,
+                end,
+                self.timezone.hours(self.time_config),
+                self.current_time,
+            )),
+        }
+    }
+
+    fn get_timeline_display(&self, layout: &DayLayout, width: u16) -> Vec<(char, Color)> {
+        let mut display = vec![(self.glyphs.night, Color::DarkGray); width as usize];
+        let total_hours = self.get_timeline_hours(width);
+        let away: Vec<_> = self
+            .blocked
+            .iter()
+            .filter(|entry| self.timezone.answers_to(&entry.who))
+            .collect();
+        let seasonal = !self.timezone.schedule.seasons.is_empty();
+
+        for i in 0..width {
+            // Calculate what time this position represents in the local timezone
+            let hours_offset = (i as f64 / width as f64) * total_hours;
+            let time_at_position = layout.start + Duration::minutes((hours_offset * 60.0) as i64);
+
+            // The local date is only needed for days away and seasonal hours
+            let date = (!away.is_empty() || seasonal)
+                .then(|| self.timezone.convert_time(time_at_position).date_naive());
+            let is_away = date.is_some_and(|date| {
+                away.iter()
+                    .any(|entry| (entry.from..=entry.to).contains(&date))
+            });
+            display[i as usize] = if is_away {
+                (self.glyphs.night, Color::DarkGray)
+            } else {
+                let hours = match date {
+                    Some(date) => self.timezone.hours_on(self.time_config, date),
+                    None => self.timezone.hours(self.time_config),
+                };
+                self.get_hour_display(layout.hour_at(time_at_position), hours)
+            };
+        }
 
-        // Check one hour ahead
-        let one_hour_later = utc_time + Duration::hours(1);
-        let local_time_later = one_hour_later.with_timezone(&self.timezone.tz);
-        let offset_after = local_time_later.offset().fix().local_minus_utc();
+        display
+    }
 
-        if offset_after > offset_before {
-            // Offset increased = clocks fell back (e.g., DST ended)
-            Some(DstTransition::FallBack)
-        } else if offset_after < offset_before {
-            // Offset decreased = clocks sprang forward (e.g., DST started)
-            Some(DstTransition::SpringForward)
-        } else {
-            None
+    /// An hour label in the display format: "09" or "9a"
+    fn hour_label(&self, hour: u32) -> String {
+        match self.display_format {
+            TimeFormat::TwentyFourHour => format!("{hour:02}"),
+            TimeFormat::TwelveHour => {
+                let suffix = if hour < 12 { 'a' } else { 'p' };
+                format!("{}{suffix}", (hour + 11) % 12 + 1)
+            }
         }
     }
 
-    fn get_dst_transitions_in_range(&self, width: u16) -> Vec<(DateTime<Utc>, DstTransition)> {
-        let mut transitions = Vec::new();
+    /// Ticks at each local hour the width has room for, each labelled with
+    /// its hour, so times on the bar can be read to the hour
+    fn ruler_texts(&self, layout: &DayLayout, y: u16, width: u16) -> Vec<RowText> {
+        let cells_per_hour = width as f64 / self.get_timeline_hours(width);
+        let label_width = self.hour_label(12).len() as f64;
+        // Label every hour, or every few, keeping labels a cell apart
+        let Some(step) = [1, 2, 3, 6, 12]
+            .into_iter()
+            .find(|&step| step as f64 * cells_per_hour >= label_width + 2.0)
+        else {
+            return Vec::new();
+        };
+
+        let tick_style = Style::default().fg(Color::DarkGray);
+        let label_style = Style::default().fg(if self.selected {
+            self.palette.selected_border
+        } else {
+            Color::Gray
+        });
         let start = self.get_timeline_start(width);
-        let end = self.get_timeline_end(width);
+        let mut texts = Vec::new();
+        for &(time, hour) in &layout.hours {
+            if time < start || hour % step != 0 {
+                continue;
+            }
+            let position = self.time_to_position(time, width);
+            texts.push(RowText {
+                x: position,
+                y,
+                text: self.glyphs.tick.to_string(),
+                style: tick_style,
+            });
+            let label = self.hour_label(hour);
+            if position + 1 + label.len() as u16 <= width {
+                texts.push(RowText {
+                    x: position + 1,
+                    y,
+                    text: label,
+                    style: label_style,
+                });
+            }
+        }
+        texts
+    }
+
+    /// Tints the bar cells of the zone's current local hour, if enabled
+    fn highlight_hour(&self, bar: &mut [Cell], width: u16) {
+        if !self.now_line.highlight_hour {
+            return;
+        }
+        let local = self.timezone.convert_time(self.current_time);
+        let into_hour =
+            Duration::minutes(local.minute() as i64) + Duration::seconds(local.second() as i64);
+        let hour_start = self.current_time - into_hour;
+        let hour_end = hour_start + Duration::hours(1);
+
+        let start = self.get_timeline_start(width).max(hour_start);
+        let end = self.get_timeline_end(width).min(hour_end);
+        if start >= end {
+            return;
+        }
+        let first = self.time_to_position(start, width);
+        let last = self.time_to_position(end, width);
+        for i in first..last.max(first + 1).min(width) {
+            bar[i as usize].style = bar[i as usize].style.bg(self.palette.current_hour);
+        }
+    }
+
+    /// The bar's shading for the window drawn at `width`, with the current
+    /// hour tinted
+    fn bar(&self, layout: &DayLayout, width: u16) -> Vec<Cell> {
+        let mut bar: Vec<Cell> = self
+            .get_timeline_display(layout, width)
+            .into_iter()
+            .map(|(ch, color)| Cell {
+                ch,
+                style: Style::default().fg(color),
+            })
+            .collect();
+        self.highlight_hour(&mut bar, width);
+        bar
+    }
+
+    fn layout_compact(&self, width: u16, height: u16) -> RowLayout {
+        let mut row = RowLayout::default();
+        if width < 2 || height == 0 {
+            return row;
+        }
+
+        let (_, time_str) = self.scrub_label();
+        let label: String = self.marked_name().chars().take(8).collect();
+        let prefix = format!("{label:<8} {time_str} ");
+
+        let label_style = if self.selected {
+            Style::default()
+                .fg(self.palette.selected_border)
+                .add_modifier(Modifier::BOLD)
+        } else if let Some(accent) = self.accent() {
+            Style::default().fg(accent)
+        } else {
+            Style::default()
+        };
+        let prefix_width = (prefix.chars().count() as u16).min(width);
+        row.texts.push(RowText {
+            x: 0,
+            y: 0,
+            text: prefix,
+            style: label_style,
+        });
 
-        // Check every hour for DST transitions
-        let mut current = start;
-        while current < end {
-            if let Some(transition) = self.detect_dst_transition(current) {
-                transitions.push((current, transition));
+        let mut bar_width = width - prefix_width;
+        row.bar_x = prefix_width;
+        // The sparkline only takes room from a bar that can spare it
+        if let Some(week) = self.overlap_week.filter(|_| bar_width >= 24) {
+            for (i, &overlap) in week.iter().enumerate() {
+                let level = ((overlap.clamp(0.0, 1.0) * 8.0).ceil() as usize).clamp(1, 8);
+                let color = if overlap > 0.0 {
+                    self.palette.work
+                } else {
+                    Color::DarkGray
+                };
+                row.texts.push(RowText {
+                    x: row.bar_x + i as u16,
+                    y: 0,
+                    text: self.glyphs.sparks[level - 1].to_string(),
+                    style: Style::default().fg(color),
+                });
             }
-            current += Duration::hours(1);
+            row.bar_x += 8;
+            bar_width -= 8;
+        }
+        if bar_width < 2 {
+            return row;
+        }
+        let layout = self.day_layout(bar_width);
+        row.bar = self.bar(&layout, bar_width);
+
+        let mut markers = MarkerLayer::new(self.marker_priority, bar_width);
+        self.place_lines(&mut markers, bar_width);
+        self.place_ghosts(&mut markers, bar_width, false);
+        self.place_milestones(&mut markers, bar_width, false);
+        if self.decorations {
+            self.place_midnights(&mut markers, &layout, bar_width, false);
         }
+        markers.apply(&mut row.bar);
+        row
+    }
 
-        transitions
+    /// The zone's time at the scrub line, in full and shortened
+    fn scrub_label(&self) -> (String, String) {
+        let zone_time = self.timezone.convert_time(self.timeline_position);
+        let (full, short) = match self.display_format {
+            TimeFormat::TwentyFourHour => (
+                zone_time.format("%H:%M %a").to_string(),
+                zone_time.format("%H:%M").to_string(),
+            ),
+            TimeFormat::TwelveHour => (
+                zone_time.format("%I:%M %p %a").to_string(),
+                zone_time.format("%I:%M%P").to_string(),
+            ),
+        };
+        match self.day_badge(zone_time.date_naive()) {
+            Some(badge) => (format!("{full} {badge}"), format!("{short}{badge}")),
+            None => (full, short),
+        }
     }
 
-    fn get_midnight_markers_in_range(&self, width: u16) -> Vec<DateTime<Utc>> {
-        let mut midnight_markers = Vec::new();
-        let start = self.get_timeline_start(width);
-        let end = self.get_timeline_end(width);
+    /// "+1d" or "−1d" when the zone's date differs from the home zone's
+    fn day_badge(&self, zone_date: NaiveDate) -> Option<String> {
+        let days = (zone_date - self.home_date?).num_days();
+        match days {
+            0 => None,
+            1.. => Some(format!("+{days}d")),
+            _ => Some(format!("{}{}d", self.glyphs.minus, -days)),
+        }
+    }
 
-        // Convert to this timezone to find local midnights
-        let local_start = start.with_timezone(&self.timezone.tz);
-        let local_end = end.with_timezone(&self.timezone.tz);
-
-        // Find the first midnight after start
-        let mut current_date = local_start.date_naive();
-
-        // If we're not at the start of the day, move to next day
-        if local_start.time() != chrono::NaiveTime::from_hms_opt(0, 0, 0).unwrap() {
-            current_date = current_date + Days::new(1);
-        }
-
-        // Iterate through each midnight in the range
-        while current_date <= local_end.date_naive() {
-            if let Some(midnight_local) = current_date.and_hms_opt(0, 0, 0) {
-                // Convert midnight in this timezone to UTC
-                if let Some(midnight_tz) = self
-                    .timezone
-                    .tz
-                    .from_local_datetime(&midnight_local)
-                    .single()
-                {
-                    let midnight_utc = midnight_tz.with_timezone(&chrono::Utc);
-
-                    // Only include if it's within our timeline range
-                    if midnight_utc >= start && midnight_utc <= end {
-                        midnight_markers.push(midnight_utc);
-                    }
+    /// Places the scrub time on the bar right of the scrub line, or left of
+    /// it, wherever it covers no other line or DST marker; shortened if it
+    /// doesn't fit either side, and left out if it still doesn't
+    fn place_scrub_label(&self, markers: &mut MarkerLayer, width: u16) {
+        let scrub_pos = self.time_to_position(self.timeline_position, width);
+        let blocked = markers.holding(&[Marker::Now, Marker::Scrub, Marker::Dst]);
+        let style = Style::default()
+            .fg(Color::Black)
+            .bg(self.palette.timeline_position);
+
+        let (full, short) = self.scrub_label();
+        for text in [full, short] {
+            let len = text.chars().count() as u16;
+            let sides = [Some(scrub_pos + 1), scrub_pos.checked_sub(len)];
+            let start = sides.into_iter().flatten().find(|&start| {
+                start + len <= width && !(start..start + len).any(|x| blocked[x as usize])
+            });
+            if let Some(start) = start {
+                for (i, ch) in text.chars().enumerate() {
+                    markers.place(start + i as u16, Marker::Scrub, ch, style);
                 }
+                return;
             }
-            current_date = current_date + Days::new(1);
         }
+    }
 
-        midnight_markers
+    /// Marks each local midnight, in a colour that stands out from the night
+    /// shade around it. With `weekdays`, the new day's name follows the mark
+    /// so the date change reads without date labels.
+    fn place_midnights(
+        &self,
+        markers: &mut MarkerLayer,
+        layout: &DayLayout,
+        width: u16,
+        weekdays: bool,
+    ) {
+        let style = Style::default().fg(self.palette.midnight);
+        for &midnight in &layout.midnights {
+            let position = self.time_to_position(midnight, width);
+            let date = self.timezone.convert_time(midnight).date_naive();
+
+            // Fiscal quarters start at a bold mark named for the quarter
+            let quarter = self.quarter_starting(date);
+            let style = match quarter {
+                Some(_) => style.add_modifier(Modifier::BOLD),
+                None => style,
+            };
+            markers.place(position, Marker::Midnight, self.glyphs.midnight, style);
+            if weekdays {
+                let name = match quarter {
+                    Some(quarter) => format!("Q{}", quarter.quarter),
+                    None => date.format("%a").to_string(),
+                };
+                for (i, ch) in name.chars().take(2).enumerate() {
+                    markers.place(position + 1 + i as u16, Marker::Midnight, ch, style);
+                }
+            }
+        }
     }
 
-    fn get_timeline_display(&self, width: u16) -> Vec<(char, Color)> {
-        let mut display = vec![('░', Color::DarkGray); width as usize];
-        let start_time = self.get_timeline_start(width);
-        let total_hours = self.get_timeline_hours(width);
+    /// Marks each milestone and task due date in the window; with `names`,
+    /// its name follows
+    fn place_milestones(&self, markers: &mut MarkerLayer, width: u16, names: bool) {
+        let start = self.get_timeline_start(width);
+        let end = self.get_timeline_end(width);
+        let style = Style::default()
+            .fg(self.palette.selected_border)
+            .add_modifier(Modifier::BOLD);
+        let milestones = self.milestones.iter().map(|m| (m, self.glyphs.milestone));
+        let tasks = self.tasks.iter().map(|task| (task, self.glyphs.due));
+        for (milestone, glyph) in milestones.chain(tasks) {
+            if milestone.time < start || milestone.time >= end {
+                continue;
+            }
+            let style = self.imminent_style(milestone.time).unwrap_or(style);
+            let position = self.time_to_position(milestone.time, width);
+            markers.place(position, Marker::Milestone, glyph, style);
+            if names {
+                for (i, ch) in milestone.name.chars().enumerate() {
+                    markers.place(position + 1 + i as u16, Marker::Milestone, ch, style);
+                }
+            }
+        }
+    }
 
-        // Convert timeline to local timezone for this zone
-        let local_start = start_time.with_timezone(&self.timezone.tz);
+    /// The zone's next clock change within the countdown window, as "CET→CEST
+    /// in 2d 14h". Zones whose abbreviation stays the same are named by
+    /// their offsets instead.
+    fn dst_countdown_label(&self) -> Option<String> {
+        let window = self.dst_countdown?;
+        let now = self.current_time;
+        let (at, before, after) = match self.clock_changes {
+            Some(changes) => changes.next(self.timezone.tz, now, window),
+            None => next_clock_change(self.timezone.tz, now, window),
+        }?;
+        let (from, to) = match (
+            self.timezone.abbreviation_at(at - Duration::seconds(1)),
+            self.timezone.abbreviation_at(at),
+        ) {
+            (from, to) if from != to => (from, to),
+            _ => (
+                utc_offset_label(before / 60, self.offset_minutes),
+                utc_offset_label(after / 60, self.offset_minutes),
+            ),
+        };
+        Some(format!(
+            "{from}{}{to} in {}",
+            self.glyphs.change,
+            format_countdown(at - now)
+        ))
+    }
 
-        for i in 0..width {
-            // Calculate what time this position represents in the local timezone
-            let hours_offset = (i as f64 / width as f64) * total_hours;
-            let time_at_position = local_start + Duration::minutes((hours_offset * 60.0) as i64);
-            let hour = time_at_position.hour();
+    /// The emphasis for a milestone coming up within the imminent window
+    fn imminent_style(&self, time: DateTime<Utc>) -> Option<Style> {
+        let window = self.imminent?;
+        let remaining = time - self.current_time;
+        if remaining < Duration::zero() || remaining > window {
+            return None;
+        }
 
-            display[i as usize] = self.get_hour_display(hour);
+        let color = match remaining.num_seconds() * 3 / window.num_seconds().max(1) {
+            2.. => Color::Yellow,
+            1 => Color::LightRed,
+            _ => Color::Red,
+        };
+        let style = Style::default().fg(color).add_modifier(Modifier::BOLD);
+        if self.pulse && self.current_time.second() % 2 == 1 {
+            Some(style)
+        } else {
+            Some(style.add_modifier(Modifier::REVERSED))
         }
+    }
 
-        display
+    /// Places the now line and the scrub line
+    fn place_lines(&self, markers: &mut MarkerLayer, width: u16) {
+        markers.place(
+            self.time_to_position(self.current_time, width),
+            Marker::Now,
+            self.now_line.glyph.unwrap_or(self.glyphs.now),
+            Style::default().fg(self.now_line.color(self.palette)),
+        );
+        markers.place(
+            self.time_to_position(self.timeline_position, width),
+            Marker::Scrub,
+            self.glyphs.scrub,
+            Style::default().fg(self.palette.timeline_position),
+        );
     }
-}
 
-impl<'a> Widget for TimelineWidget<'a> {
-    fn render(self, area: Rect, buf: &mut Buffer) {
-        let inner = area.inner(Margin {
-            horizontal: 1,
-            vertical: 1,
-        });
-        if inner.width < 2 {
-            return;
+    /// Places teammates' scrub lines behind this board's own; with `names`,
+    /// each is followed by whose it is
+    fn place_ghosts(&self, markers: &mut MarkerLayer, width: u16, names: bool) {
+        let start = self.get_timeline_start(width);
+        let end = self.get_timeline_end(width);
+        let style = Style::default()
+            .fg(self.palette.timeline_position)
+            .add_modifier(Modifier::DIM);
+        for ghost in self.ghosts {
+            if ghost.position < start || ghost.position >= end {
+                continue;
+            }
+            let position = self.time_to_position(ghost.position, width);
+            markers.place(position, Marker::Scrub, self.glyphs.ghost, style);
+            if names {
+                for (i, ch) in ghost.name.chars().enumerate() {
+                    markers.place(position + 1 + i as u16, Marker::Scrub, ch, style);
+                }
+            }
         }
+    }
 
-        // Render border
-        let border_style = if self.selected {
-            Style::default().fg(self.color_theme.get_selected_border_color())
+    fn frame(&self, layout: &DayLayout) -> RowFrame {
+        let style = if self.selected {
+            Style::default().fg(self.palette.selected_border)
+        } else if let Some(accent) = self.accent() {
+            Style::default().fg(accent)
         } else {
             Style::default()
         };
 
+        let offset = self.timezone.offset_label(self.offset_minutes);
         let title = match self.timezone_display_mode {
             TimezoneDisplayMode::Short => {
                 // Use custom label if available, otherwise default display name
                 format!(
                     "{} {} ({})",
-                    self.timezone.effective_display_name(),
+                    self.display_name(),
                     self.timezone.get_timezone_abbreviation(),
-                    self.timezone.offset_string()
+                    offset
                 )
             }
             TimezoneDisplayMode::Full => {
                 // For full mode, show custom label with city name, or just city name
-                match &self.timezone.custom_label {
+                match self.custom_label() {
                     Some(label) => {
                         let city_name = self.timezone.get_city_name();
                         format!(
@@ -248,7 +921,7 @@ impl<'a> Widget for TimelineWidget<'a> {
                             label,
                             city_name,
                             self.timezone.get_timezone_abbreviation(),
-                            self.timezone.offset_string()
+                            offset
                         )
                     }
                     None => {
@@ -257,205 +930,227 @@ impl<'a> Widget for TimelineWidget<'a> {
                             "{} {} ({})",
                             city_name,
                             self.timezone.get_timezone_abbreviation(),
-                            self.timezone.offset_string()
+                            offset
                         )
                     }
                 }
             }
         };
 
-        let mut block = Block::default()
-            .borders(Borders::ALL)
-            .title(title)
-            .style(border_style);
-
-        // Add sunrise/sunset times to bottom right if enabled
-        if self.show_sun_times {
-            let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
-            if let Some(sun_times) = self
-                .timezone
-                .format_sun_times(self.current_time, use_12_hour)
-            {
+        let title = match self.dst_countdown_label() {
+            Some(countdown) => format!("{title} {countdown}"),
+            None => title,
+        };
+
+        let title = match self.marked {
+            true => format!("{} {title}", self.glyphs.mark),
+            false => title,
+        };
+
+        // Sunrise/sunset times go at the top right if enabled
+        let sun_times = layout
+            .sun_times
+            .filter(|_| self.show_sun_times && self.decorations)
+            .map(|sun_times| {
+                let use_12_hour = matches!(self.display_format, TimeFormat::TwelveHour);
+                let sun_times = TimeZone::format_sun_times(
+                    sun_times,
+                    use_12_hour,
+                    (self.glyphs.sunrise, self.glyphs.sunset),
+                );
                 let sun_color = if self.selected {
-                    self.color_theme.get_selected_border_color()
+                    self.palette.selected_border
                 } else {
                     Color::Gray
                 };
-                let sun_line = Line::from(vec![Span::styled(
-                    sun_times,
-                    Style::default().fg(sun_color),
-                )])
-                .alignment(Alignment::Right);
-                block = block.title_top(sun_line);
-            }
+                (sun_times, Style::default().fg(sun_color))
+            });
+
+        RowFrame {
+            title,
+            style,
+            sun_times,
+            border: self.glyphs.border,
         }
+    }
 
-        block.render(area, buf);
+    /// Lays out the row for an area `width` by `height` cells: the frame,
+    /// the bar with everything marked on it, and the lines below
+    pub fn layout(&self, width: u16, height: u16) -> RowLayout {
+        if self.compact {
+            return self.layout_compact(width, height);
+        }
 
-        // Generate timeline display
-        let timeline_display = self.get_timeline_display(inner.width);
+        let (inner_width, inner_height) = if self.borderless {
+            (width, height)
+        } else {
+            (width.saturating_sub(2), height.saturating_sub(2))
+        };
+        let mut row = RowLayout::default();
+        if inner_width < 2 {
+            return row;
+        }
 
-        // Render timeline bar
-        let timeline_y = inner.y;
-        for (i, &(ch, color)) in timeline_display.iter().enumerate() {
-            if i >= inner.width as usize {
-                break;
+        let layout = self.day_layout(inner_width);
+        if !self.borderless {
+            row.frame = Some(self.frame(&layout));
+        }
+        row.bar = self.bar(&layout, inner_width);
+
+        // Now line, scrub line and decorations claim bar cells by priority
+        let mut markers = MarkerLayer::new(self.marker_priority, inner_width);
+        self.place_lines(&mut markers, inner_width);
+        self.place_ghosts(&mut markers, inner_width, self.selected && !self.private);
+
+        // DST transition indicators if enabled
+        if self.show_dst && self.decorations {
+            for &(transition_time, transition_type) in &layout.dst_transitions {
+                let dst_pos = self.time_to_position(transition_time, inner_width);
+                let (symbol, color) = match transition_type {
+                    DstTransition::SpringForward => (self.glyphs.spring_forward, Color::Green), // Double up arrow for spring forward
+                    DstTransition::FallBack => (self.glyphs.fall_back, Color::Yellow), // Double down arrow for fall back
+                };
+                markers.place(dst_pos, Marker::Dst, symbol, Style::default().fg(color));
             }
+        }
 
-            let x = inner.x + i as u16;
-            let style = Style::default().fg(color);
-
-            buf[(x, timeline_y)].set_char(ch).set_style(style);
-        }
-
-        // Render current time indicator (now line)
-        let now_pos = self.time_to_position(self.current_time, inner.width);
-        if now_pos < inner.width {
-            let x = inner.x + now_pos;
-            buf[(x, timeline_y)]
-                .set_char('│')
-                .set_style(Style::default().fg(self.color_theme.get_current_time_color()));
-        }
-
-        // Render timeline position indicator (scrub line)
-        let timeline_pos = self.time_to_position(self.timeline_position, inner.width);
-        if timeline_pos < inner.width && timeline_pos != now_pos {
-            let x = inner.x + timeline_pos;
-            buf[(x, timeline_y)]
-                .set_char('┃')
-                .set_style(Style::default().fg(self.color_theme.get_timeline_position_color()));
-        }
-
-        // Render DST transition indicators if enabled
-        if self.show_dst {
-            let dst_transitions = self.get_dst_transitions_in_range(inner.width);
-            for (transition_time, transition_type) in dst_transitions {
-                let dst_pos = self.time_to_position(transition_time, inner.width);
-                if dst_pos < inner.width {
-                    let x = inner.x + dst_pos;
-                    let (symbol, color) = match transition_type {
-                        DstTransition::SpringForward => ('⇈', Color::Green), // Double up arrow for spring forward
-                        DstTransition::FallBack => ('⇊', Color::Yellow), // Double down arrow for fall back
-                    };
-                    buf[(x, timeline_y)]
-                        .set_char(symbol)
-                        .set_style(Style::default().fg(color));
-                }
-            }
+        self.place_milestones(&mut markers, inner_width, self.selected && !self.private);
+
+        // Midnight markers (subtle day change indicators)
+        if self.decorations {
+            self.place_midnights(&mut markers, &layout, inner_width, !self.show_date);
         }
 
-        // Render midnight markers (subtle day change indicators)
-        let midnight_markers = self.get_midnight_markers_in_range(inner.width);
-        for midnight_time in midnight_markers {
-            let midnight_pos = self.time_to_position(midnight_time, inner.width);
-            if midnight_pos < inner.width && midnight_pos != now_pos && midnight_pos != timeline_pos
-            {
-                let x = inner.x + midnight_pos;
-                // Use a subtle vertical line character with night color
-                buf[(x, timeline_y)]
-                    .set_char('┊')
-                    .set_style(Style::default().fg(self.color_theme.get_night_color()));
-            }
+        // With no line below the bar, the scrub time goes beside the scrub line
+        if inner_height == 1 {
+            self.place_scrub_label(&mut markers, inner_width);
         }
 
-        // Render dates in middle of each day's work hours if enabled
-        if self.show_date {
-            let start_time = self.get_timeline_start(inner.width);
-            let end_time = self.get_timeline_end(inner.width);
-
-            // Find the middle of work hours (default 8 AM to 6 PM, so middle is 1 PM)
-            let work_middle_hour =
-                (self.time_config.work_hours_start + self.time_config.work_hours_end) / 2;
-
-            // Convert timeline to local timezone for this specific timezone
-            let local_start = start_time.with_timezone(&self.timezone.tz);
-            let local_end = end_time.with_timezone(&self.timezone.tz);
-            let mut current_date = local_start.date_naive();
-
-            // Iterate through each day visible in this timezone's local time
-            while current_date <= local_end.date_naive() {
-                // Create a time for the middle of work hours on this day IN THIS TIMEZONE
-                if let Some(work_middle_local) = current_date.and_hms_opt(work_middle_hour, 0, 0) {
-                    // Create the datetime in this timezone, then convert to UTC for position calculation
-                    if let Some(work_middle_tz) = self
-                        .timezone
-                        .tz
-                        .from_local_datetime(&work_middle_local)
-                        .single()
-                    {
-                        let work_middle_utc = work_middle_tz.with_timezone(&chrono::Utc);
-                        let date_pos = self.time_to_position(work_middle_utc, inner.width);
-
-                        // Only render if this position is within the visible timeline
-                        if date_pos < inner.width {
-                            let date_str = current_date.format("%d %b").to_string(); // Format as "15 Jul"
-                            let date_y = timeline_y; // Place date directly on timeline bar
-
-                            // Center the date string around the calculated position
-                            let date_start_x = if date_pos >= (date_str.chars().count() as u16 / 2)
-                            {
-                                date_pos - (date_str.chars().count() as u16 / 2)
-                            } else {
-                                0
-                            };
-
-                            // Ensure we don't go beyond the right edge
-                            let date_start_x = date_start_x
-                                .min(inner.width.saturating_sub(date_str.chars().count() as u16));
-
-                            // Render the date
-                            for (i, ch) in date_str.chars().enumerate() {
-                                let x = inner.x + date_start_x + i as u16;
-                                if x < inner.x + inner.width {
-                                    buf[(x, date_y)].set_char(ch).set_style(
-                                        Style::default().fg(Color::White).bg(Color::DarkGray),
-                                    );
-                                }
-                            }
-                        }
+        // Dates in the middle of each day's work hours if enabled
+        if self.show_date && self.decorations {
+            // Each local day's date sits in the middle of its work hours,
+            // skipping days whose middle is off the bar
+            let start = self.get_timeline_start(inner_width);
+            let end = self.get_timeline_end(inner_width);
+            let labels = layout
+                .work_middles
+                .iter()
+                .filter(|(_, middle)| *middle >= start && *middle < end)
+                .map(|&(date, middle)| {
+                    let anchor = self.time_to_position(middle, inner_width);
+                    let full = date.format("%d %b").to_string(); // Format as "15 Jul"
+                    let short = date.format("%d").to_string();
+                    match self.quarter_starting(date) {
+                        Some(quarter) => BarLabel {
+                            anchor,
+                            full: format!("{full} {quarter}"),
+                            short: format!("{short} Q{}", quarter.quarter),
+                        },
+                        None => BarLabel {
+                            anchor,
+                            full,
+                            short,
+                        },
                     }
+                })
+                .collect();
+
+            // Dates move aside for markers that outrank them
+            let blocked = markers.outranking(Marker::Date);
+            for (start, text) in place_labels(labels, inner_width, &blocked) {
+                // Place the date directly on the timeline bar
+                for (i, ch) in text.chars().enumerate() {
+                    markers.place(
+                        start + i as u16,
+                        Marker::Date,
+                        ch,
+                        Style::default().fg(Color::White).bg(Color::DarkGray),
+                    );
                 }
-
-                // Move to next day
-                current_date = current_date + Days::new(1);
             }
         }
+        markers.apply(&mut row.bar);
 
-        // Render time display under the scrubber position
-        if inner.height > 1 {
-            let zone_time = self.timezone.convert_time(self.timeline_position);
-            let time_str = match self.display_format {
-                TimeFormat::TwentyFourHour => zone_time.format("%H:%M %a").to_string(),
-                TimeFormat::TwelveHour => zone_time.format("%I:%M %p %a").to_string(),
+        // Without a title, label the zone at the start of the time line
+        if self.borderless && inner_height > 1 {
+            let label_style = if self.selected {
+                Style::default().fg(self.palette.selected_border)
+            } else {
+                Style::default().fg(self.accent().unwrap_or(Color::DarkGray))
             };
+            row.texts.push(RowText {
+                x: 0,
+                y: 1,
+                text: self.marked_name(),
+                style: label_style,
+            });
+        }
 
-            let time_y = inner.y + 1;
+        if self.ruler && inner_height > 2 {
+            row.texts.extend(self.ruler_texts(&layout, 2, inner_width));
+        }
+
+        // Time display under the scrubber position
+        if inner_height > 1 {
+            let (time_str, _) = self.scrub_label();
+            let label_style = Style::default()
+                .fg(self.palette.timeline_position)
+                .add_modifier(Modifier::UNDERLINED);
 
             // Position the time display under the timeline position indicator
-            let timeline_pos = self.time_to_position(self.timeline_position, inner.width);
-            let time_start_x = if timeline_pos >= (time_str.chars().count() as u16 / 2) {
-                timeline_pos - (time_str.chars().count() as u16 / 2)
-            } else {
-                0
-            };
+            let len = time_str.chars().count() as u16;
+            let timeline_pos = self.time_to_position(self.timeline_position, inner_width);
+            let time_start_x = timeline_pos.saturating_sub(len / 2);
 
             // Ensure we don't go beyond the right edge
-            let time_start_x =
-                time_start_x.min(inner.width.saturating_sub(time_str.chars().count() as u16));
+            let time_start_x = time_start_x.min(inner_width.saturating_sub(len));
+            row.texts.push(RowText {
+                x: time_start_x,
+                y: 1,
+                text: time_str,
+                style: label_style,
+            });
+        }
+        row
+    }
+}
 
-            for (i, ch) in time_str.chars().enumerate() {
-                let x = inner.x + time_start_x + i as u16;
-                if x < inner.x + inner.width {
-                    buf[(x, time_y)].set_char(ch);
-                }
-            }
+impl<'a> Widget for TimelineWidget<'a> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        self.layout(area.width, area.height).render(area, buf);
+    }
+}
+
+/// Boards with at least this many rows render them in parallel
+#[cfg(feature = "parallel")]
+const PARALLEL_ROWS: usize = 32;
+
+/// Renders zone rows into the buffer. Large boards lay the rows out on all
+/// cores, then draw each into its own area of the buffer.
+pub fn render_rows(buf: &mut Buffer, rows: Vec<(Rect, TimelineWidget<'_>)>) {
+    #[cfg(feature = "parallel")]
+    if rows.len() >= PARALLEL_ROWS {
+        use rayon::prelude::*;
+
+        let laid_out: Vec<(Rect, RowLayout)> = rows
+            .into_par_iter()
+            .map(|(area, widget)| (area, widget.layout(area.width, area.height)))
+            .collect();
+        for (area, row) in laid_out {
+            row.render(area, buf);
         }
+        return;
+    }
+
+    for (area, widget) in rows {
+        widget.render(area, buf);
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::ColorTheme;
     use chrono_tz;
 
     #[test]
@@ -472,7 +1167,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -496,7 +1191,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -520,22 +1215,22 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
         );
 
         // Test work hours get dark shade block
-        let (char, _) = widget.get_hour_display(14); // 2 PM
+        let (char, _) = widget.get_hour_display(14, &config); // 2 PM
         assert_eq!(char, '▓'); // Work hours = dark shade block
 
         // Test awake hours get medium shade
-        let (char, _) = widget.get_hour_display(7); // 7 AM
+        let (char, _) = widget.get_hour_display(7, &config); // 7 AM
         assert_eq!(char, '▒'); // Awake hours = medium shade
 
         // Test night hours get light shade
-        let (char, _) = widget.get_hour_display(2); // 2 AM
+        let (char, _) = widget.get_hour_display(2, &config); // 2 AM
         assert_eq!(char, '░'); // Night hours = light shade
     }
 
@@ -554,7 +1249,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -570,7 +1265,7 @@ mod tests {
             TimeFormat::TwelveHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -594,7 +1289,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             true,
             false,
@@ -602,7 +1297,7 @@ mod tests {
 
         // Test that DST transitions can be detected - function should execute without panic
         const TEST_WIDTH: u16 = 120; // Use standard width for testing
-        let transitions = widget.get_dst_transitions_in_range(TEST_WIDTH);
+        let transitions = widget.day_layout(TEST_WIDTH).dst_transitions.clone();
 
         // Verify the function returns a valid vector and each transition has valid data
         for (time, transition_type) in transitions {
@@ -632,7 +1327,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             true,
             false,
@@ -654,7 +1349,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -683,6 +1378,46 @@ mod tests {
         assert_eq!(actual_duration, expected_duration);
     }
 
+    #[test]
+    fn test_window_aligned_to_midnight() {
+        const WIDTH: u16 = 96; // 48 hours
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Europe::London);
+        let config = crate::config::TimeDisplayConfig::default();
+        // 23:30 in Tokyo on Monday, 14:30 in London
+        let scrub = DateTime::parse_from_rfc3339("2024-01-15T14:30:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = TimelineWidget::new(
+            scrub,
+            scrub,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            false,
+            false,
+            false,
+        )
+        .align_to(Some(chrono_tz::Asia::Tokyo));
+
+        // Tokyo's Monday started at 15:00 UTC on Sunday
+        let start = widget.get_timeline_start(WIDTH);
+        assert_eq!(start.to_rfc3339(), "2024-01-14T15:00:00+00:00");
+        assert_eq!(widget.get_timeline_end(WIDTH) - start, Duration::hours(48));
+        assert_eq!(widget.time_to_position(start, WIDTH), 0);
+        // The scrub line sits near the end of Tokyo's first day
+        assert_eq!(widget.time_to_position(scrub, WIDTH), 47);
+
+        // Unaligned, the window centres on the scrub line
+        let centred = widget.align_to(None);
+        assert_eq!(
+            centred.get_timeline_start(WIDTH),
+            scrub - Duration::hours(24)
+        );
+    }
+
     #[test]
     fn test_midnight_markers() {
         let tz = crate::time::TimeZone::from_tz(chrono_tz::US::Eastern);
@@ -701,7 +1436,7 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -709,7 +1444,7 @@ mod tests {
 
         // Get midnight markers - should find at least one midnight in 48-hour span
         const TEST_WIDTH: u16 = 120; // Use standard width for testing
-        let midnight_markers = widget.get_midnight_markers_in_range(TEST_WIDTH);
+        let midnight_markers = widget.day_layout(TEST_WIDTH).midnights.clone();
 
         // Should have some midnight markers (48 hour span should contain multiple midnights)
         assert!(!midnight_markers.is_empty());
@@ -722,24 +1457,849 @@ mod tests {
     }
 
     #[test]
-    fn test_custom_label_display_short_mode() {
+    fn test_compact_row_rendering() {
         let tz = crate::time::TimeZone::with_custom_label(
-            chrono_tz::Asia::Tokyo,
-            "TYO".to_string(),
-            Some("Alice (Engineering)".to_string()),
+            chrono_tz::UTC,
+            "UTC".to_string(),
+            Some("Alice".to_string()),
         );
         let config = crate::config::TimeDisplayConfig::default();
-        let now = Utc::now();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
 
-        let _widget = TimelineWidget::new(
-            now,
-            now,
-            &tz,
-            false,
+        let widget = TimelineWidget::new(
+            base_time,
+            base_time,
+            &tz,
+            true,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            false,
+            false,
+            false,
+        )
+        .compact(true);
+
+        let area = Rect::new(0, 0, 40, 1);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        let row: String = (0..area.width)
+            .map(|x| buf[(x, 0)].symbol().to_string())
+            .collect();
+        assert!(row.starts_with("Alice    12:34 "), "unexpected row: {row}");
+        // No borders in compact mode, and the now line is on the bar
+        assert!(!row.contains('┌'));
+        assert!(row.contains('│'));
+    }
+
+    #[test]
+    fn test_overlap_sparkline() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let render = |width| {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .compact(true)
+            .overlap_week([1.0, 0.5, 0.1, 0.0, 1.0, 0.0, 0.0]);
+            let area = Rect::new(0, 0, width, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..width)
+                .map(|x| buf[(x, 0)].symbol().to_string())
+                .collect::<String>()
+        };
+
+        assert!(render(60).contains("UTC      12:34 █▄▁▁█▁▁ "));
+        // Narrow rows keep their bar instead
+        assert!(!render(30).contains('█'));
+    }
+
+    #[test]
+    fn test_hour_ruler() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let render = |format: TimeFormat, width| {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                true,
+                format,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .ruler(true);
+            let area = Rect::new(0, 0, width, 5);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..width)
+                .map(|x| buf[(x, 3)].symbol().to_string())
+                .collect::<String>()
+        };
+
+        // About two cells an hour: every other hour is labelled
+        let ruler = render(TimeFormat::TwentyFourHour, 100);
+        assert!(ruler.contains("╵12"), "unexpected ruler: {ruler}");
+        assert!(ruler.contains("╵14"));
+        assert!(!ruler.contains("╵13"));
+        // Twelve-hour labels are a cell wider, so fewer fit
+        let ruler = render(TimeFormat::TwelveHour, 100);
+        assert!(ruler.contains("╵12p"), "unexpected ruler: {ruler}");
+        assert!(ruler.contains("╵3p"));
+        assert!(!ruler.contains("╵2p"));
+    }
+
+    #[test]
+    fn test_task_due_dates() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let tasks = [Milestone {
+            name: "Ship it".to_string(),
+            time: base_time + Duration::hours(3),
+            group: None,
+        }];
+        let render = |selected: bool| {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                selected,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .tasks(&tasks);
+            let area = Rect::new(0, 0, 80, 4);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            buf.content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+
+        assert!(render(false).contains('⚑'));
+        assert!(!render(false).contains("Ship it"));
+        assert!(render(true).contains("⚑Ship it"));
+    }
+
+    #[test]
+    fn test_blocked_days() {
+        let tz = crate::time::TimeZone::with_custom_label(
+            chrono_tz::UTC,
+            "London".to_string(),
+            Some("Alice".to_string()),
+        );
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let day = |d| NaiveDate::from_ymd_opt(2024, 1, d).unwrap();
+        let away = |who: &str, from, to| BlockedDates {
+            who: who.to_string(),
+            from: day(from),
+            to: day(to),
+            note: None,
+        };
+        let render = |blocked: &[BlockedDates]| {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .compact(true)
+            .blocked(blocked);
+            let area = Rect::new(0, 0, 60, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..area.width)
+                .map(|x| buf[(x, 0)].symbol().to_string())
+                .collect::<String>()
+        };
+
+        let work = |row: String| row.matches('▓').count();
+        let open = work(render(&[]));
+        assert!(open > 0);
+        // Other people's days off leave the row alone
+        assert_eq!(work(render(&[away("Bob", 14, 16)])), open);
+        // Blocking the 15th shades its work hours, leaving the 14th's
+        let partly = work(render(&[away("alice", 15, 15)]));
+        assert!(partly > 0 && partly < open);
+        assert_eq!(work(render(&[away("Alice", 14, 16)])), 0);
+    }
+
+    #[test]
+    fn test_borderless_rendering() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let widget = TimelineWidget::new(
+            base_time,
+            base_time,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            false,
+            false,
+            true,
+        )
+        .borderless(true);
+
+        let area = Rect::new(0, 0, 100, 2);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        let row = |y: u16| -> String {
+            (0..area.width)
+                .map(|x| buf[(x, y)].symbol().to_string())
+                .collect()
+        };
+        // The bar starts on the first line with no border corner
+        assert_ne!(row(0).chars().next(), Some('┌'));
+        assert!(row(0).contains('│'));
+        // Label and scrub time share the second line
+        assert!(row(1).starts_with("UTC"));
+        assert!(row(1).contains("12:34 Mon"));
+    }
+
+    #[test]
+    fn test_row_layout() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:34:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = TimelineWidget::new(
+            base_time,
+            base_time,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            true,
+            false,
+            false,
+        );
+
+        // The layout holds what's drawn, without a buffer
+        let layout = widget.layout(100, 4);
+        assert_eq!(layout.frame.as_ref().unwrap().title, "UTC UTC (UTC+0)");
+        assert_eq!(layout.bar.len(), 98);
+        let time = layout
+            .texts
+            .iter()
+            .find(|text| text.text == "12:34 Mon")
+            .unwrap();
+        assert_eq!((time.x, time.y), (45, 1));
+
+        // Drawing it puts the same bar inside the border
+        let area = Rect::new(0, 0, 100, 4);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+        let bar: String = (1..99).map(|x| buf[(x, 1)].symbol().to_string()).collect();
+        assert_eq!(bar, layout.bar_text());
+    }
+
+    #[test]
+    fn test_ghost_cursors() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let ghosts = [Ghost {
+            name: "Ana".to_string(),
+            position: base_time + Duration::hours(6),
+        }];
+        let widget = |selected: bool| {
+            TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                selected,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .ghosts(&ghosts)
+        };
+
+        // Named on the selected zone only
+        let selected = widget(true).layout(100, 4).bar_text();
+        assert!(selected.contains("╏Ana"));
+        let other = widget(false).layout(100, 4).bar_text();
+        assert!(other.contains('╏') && !other.contains("Ana"));
+        assert!(!widget(true)
+            .private(true)
+            .layout(100, 4)
+            .bar_text()
+            .contains("Ana"));
+    }
+
+    #[test]
+    fn test_marker_priority() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        // 13:00 is the middle of the work day, so the date label covers the now line
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T13:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let render = |priority: &[Marker]| {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                true,
+                true,
+                false,
+            )
+            .borderless(true)
+            .marker_priority(priority);
+            let area = Rect::new(0, 0, 100, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            buf[(50, 0)].symbol().to_string()
+        };
+
+        let now = Glyphs::UNICODE.now.to_string();
+        assert_eq!(render(&Marker::DEFAULT_PRIORITY), now);
+        assert_ne!(render(&[Marker::Date, Marker::Now]), now);
+    }
+
+    #[test]
+    fn test_now_line_style() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Asia::Kolkata);
+        let config = crate::config::TimeDisplayConfig::default();
+        // 20:15 in Kolkata, so its hour started 15 minutes ago
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-15T14:45:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let style = NowLineStyle {
+            glyph: Some('┃'),
+            color: Some("yellow".to_string()),
+            highlight_hour: true,
+        };
+        let widget = TimelineWidget::new(
+            now,
+            now,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            false,
+            false,
+            false,
+        )
+        .borderless(true)
+        .now_line(&style);
+        let now_x = widget.time_to_position(now, 96);
+        let hour_start = widget.time_to_position(now - Duration::minutes(15), 96);
+        let hour_end = widget.time_to_position(now + Duration::minutes(45), 96);
+        let area = Rect::new(0, 0, 96, 2);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        assert_eq!(buf[(now_x, 0)].symbol(), "┃");
+        assert_eq!(buf[(now_x, 0)].fg, Color::Yellow);
+        let highlight = ColorTheme::default().palette().current_hour;
+        assert!((hour_start..hour_end).all(|x| buf[(x, 0)].bg == highlight));
+        assert_ne!(buf[(hour_start - 1, 0)].bg, highlight);
+        assert_ne!(buf[(hour_end, 0)].bg, highlight);
+    }
+
+    #[test]
+    fn test_imminent_milestones() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let now = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:00:01Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let milestone = |minutes| Milestone {
+            name: "Standup".to_string(),
+            time: now + Duration::minutes(minutes),
+            group: None,
+        };
+        let milestones = [milestone(3), milestone(12), milestone(90)];
+        let widget = |pulse| {
+            TimelineWidget::new(
+                now,
+                now,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .milestones(&milestones)
+            .imminent(Some(Duration::minutes(15)), pulse)
+        };
+
+        let still = widget(false);
+        let soon = still.imminent_style(milestones[0].time).unwrap();
+        assert_eq!(soon.fg, Some(Color::Red));
+        assert!(soon.add_modifier.contains(Modifier::REVERSED));
+        assert_eq!(
+            still.imminent_style(milestones[1].time).unwrap().fg,
+            Some(Color::Yellow)
+        );
+        assert_eq!(still.imminent_style(milestones[2].time), None);
+        assert_eq!(still.imminent_style(now - Duration::minutes(1)), None);
+
+        // On odd seconds a pulsing marker drops the inverse
+        let pulsing = widget(true).imminent_style(milestones[0].time).unwrap();
+        assert!(!pulsing.add_modifier.contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_overnight_hours_on_bar() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let night_shift = crate::config::TimeDisplayConfig {
+            work_hours_start: 22,
+            work_hours_end: 6,
+            awake_hours_start: 20,
+            awake_hours_end: 10,
+        };
+        // The bar around 02:00 is a shift in full swing
+        let at = chrono::DateTime::parse_from_rfc3339("2024-01-16T02:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = TimelineWidget::new(
+            at,
+            at,
+            &tz,
+            false,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &night_shift,
+            ColorTheme::default().palette(),
+            false,
+            false,
+            false,
+        );
+        let bar = widget.layout(50, 4).bar_text();
+        assert!(bar.contains('▓'), "unexpected bar: {bar}");
+        assert!(bar.contains('░'), "unexpected bar: {bar}");
+    }
+
+    #[test]
+    fn test_milestone_names_hidden_in_privacy() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let now = Utc::now();
+        let milestones = [Milestone {
+            name: "Launch".to_string(),
+            time: now + Duration::hours(2),
+            group: None,
+        }];
+        let render = |private| {
+            let widget = TimelineWidget::new(
+                now,
+                now,
+                &tz,
+                true,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .milestones(&milestones)
+            .private(private);
+            let area = Rect::new(0, 0, 100, 4);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            buf.content()
+                .iter()
+                .map(|cell| cell.symbol())
+                .collect::<String>()
+        };
+
+        assert!(render(false).contains("Launch"));
+        assert!(!render(true).contains("Launch"));
+    }
+
+    #[test]
+    fn test_dst_countdown() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Europe::Berlin);
+        let config = crate::config::TimeDisplayConfig::default();
+        // Berlin's clocks go forward at 01:00 UTC on 31 March 2024
+        let now = chrono::DateTime::parse_from_rfc3339("2024-03-28T20:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = |days: Option<i64>| {
+            TimelineWidget::new(
+                now,
+                now,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .dst_countdown(days.map(Duration::days))
+        };
+
+        assert_eq!(
+            widget(Some(3)).dst_countdown_label().as_deref(),
+            Some("CET→CEST in 2d 5h")
+        );
+        assert_eq!(widget(Some(2)).dst_countdown_label(), None);
+        assert_eq!(widget(None).dst_countdown_label(), None);
+
+        // Found once, and still found the same way on later ticks
+        let changes = ClockChanges::default();
+        let next = |window: i64, now| {
+            let berlin = chrono_tz::Europe::Berlin;
+            changes.next(berlin, now, Duration::days(window))
+        };
+        let change = next(3, now).unwrap();
+        assert_eq!(next(3, now + Duration::hours(1)), Some(change));
+        assert_eq!(next(2, now), None);
+        assert_eq!(next(3, change.0), None);
+
+        assert_eq!(format_countdown(Duration::hours(48)), "2d");
+        assert_eq!(format_countdown(Duration::minutes(5 * 60 + 30)), "5h 30m");
+    }
+
+    #[test]
+    fn test_scrub_label_on_one_line_rows() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Europe::London);
+        let config = crate::config::TimeDisplayConfig::default();
+        // An hour before the clocks change, so the DST marker is just right
+        // of the scrub line
+        let scrub = chrono::DateTime::parse_from_rfc3339("2024-03-31T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let widget = TimelineWidget::new(
+            scrub,
+            scrub - Duration::hours(12),
+            &tz,
+            true,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            false,
+            true,
+            false,
+        )
+        .borderless(true);
+        let area = Rect::new(0, 0, 100, 1);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+        let row: String = (0..area.width).map(|x| buf[(x, 0)].symbol()).collect();
+
+        // The label goes left of the scrub line rather than over the marker
+        let scrub_x = row
+            .chars()
+            .position(|ch| ch == Glyphs::UNICODE.scrub)
+            .unwrap();
+        let label_x = row
+            .find("00:00 Sun")
+            .map(|i| row[..i].chars().count())
+            .unwrap();
+        assert_eq!(label_x + "00:00 Sun".len(), scrub_x);
+        assert!(row
+            .chars()
+            .any(|ch| ch == Glyphs::UNICODE.spring_forward || ch == Glyphs::UNICODE.fall_back));
+    }
+
+    #[test]
+    fn test_day_badge() {
+        let config = crate::config::TimeDisplayConfig::default();
+        let instant = chrono::DateTime::parse_from_rfc3339("2024-01-15T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let row = |tz: chrono_tz::Tz, home_day: u32| -> String {
+            let zone = crate::time::TimeZone::from_tz(tz);
+            let widget = TimelineWidget::new(
+                instant,
+                instant,
+                &zone,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                false,
+                false,
+            )
+            .compact(true)
+            .home_date(NaiveDate::from_ymd_opt(2024, 1, home_day).unwrap());
+            let area = Rect::new(0, 0, 60, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+
+        assert!(row(chrono_tz::Asia::Tokyo, 15).contains("17:00 "));
+        assert!(row(chrono_tz::Pacific::Honolulu, 15).contains("22:00−1d"));
+        assert!(row(chrono_tz::Asia::Tokyo, 14).contains("17:00+1d"));
+    }
+
+    #[test]
+    fn test_midnight_marks() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let row = |compact: bool| -> String {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                false,
+                true,
+                false,
+            )
+            .compact(compact)
+            .borderless(true);
+            let area = Rect::new(0, 0, 100, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+
+        // Without date labels the new day is named beside its midnight
+        assert!(row(false).contains("┊Mo"));
+        assert!(row(false).contains("┊Tu"));
+        assert!(row(true).contains('┊'));
+    }
+
+    #[test]
+    fn test_fiscal_quarter_labels() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::UTC);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-07-01T06:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let row = |show_date: bool| -> String {
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &tz,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                show_date,
+                true,
+                false,
+            )
+            .borderless(true)
+            .fiscal_year_start(Some(4));
+            let area = Rect::new(0, 0, 100, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+            (0..area.width).map(|x| buf[(x, 0)].symbol()).collect()
+        };
+
+        assert!(row(true).contains("01 Jul Q2 FY25"));
+        assert!(row(true).contains("30 Jun"));
+        assert!(row(false).contains("┊Q2"));
+    }
+
+    #[test]
+    fn test_place_labels() {
+        let label = |anchor: u16| BarLabel {
+            anchor,
+            full: "15 Jan".to_string(),
+            short: "15".to_string(),
+        };
+        let open = [false; 20];
+
+        // Centred where there's room, shifted in at the edges
+        assert_eq!(
+            place_labels(vec![label(10)], 20, &open),
+            [(7, "15 Jan".into())]
+        );
+        assert_eq!(
+            place_labels(vec![label(0)], 20, &open),
+            [(0, "15 Jan".into())]
+        );
+        assert_eq!(
+            place_labels(vec![label(19)], 20, &open),
+            [(14, "15 Jan".into())]
+        );
+
+        // Shifted off a blocked cell while still covering the anchor
+        let mut blocked = open;
+        blocked[9] = true;
+        assert_eq!(
+            place_labels(vec![label(10)], 20, &blocked),
+            [(10, "15 Jan".into())]
+        );
+
+        // Crowded labels are shortened, then dropped
+        blocked[12] = true;
+        blocked[7] = true;
+        assert_eq!(
+            place_labels(vec![label(10)], 20, &blocked),
+            [(10, "15".into())]
+        );
+        assert_eq!(
+            place_labels(vec![label(3), label(5)], 20, &open),
+            [(0, "15 Jan".into())]
+        );
+        assert!(place_labels(vec![label(2)], 1, &open).is_empty());
+    }
+
+    #[test]
+    fn test_date_labels() {
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-01-15T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let labels = |tz: chrono_tz::Tz, width: u16| -> Vec<String> {
+            let zone = crate::time::TimeZone::from_tz(tz);
+            let widget = TimelineWidget::new(
+                base_time,
+                base_time,
+                &zone,
+                false,
+                TimeFormat::TwentyFourHour,
+                TimezoneDisplayMode::Short,
+                &config,
+                ColorTheme::default().palette(),
+                true,
+                true,
+                false,
+            )
+            .borderless(true);
+            let area = Rect::new(0, 0, width, 1);
+            let mut buf = Buffer::empty(area);
+            widget.render(area, &mut buf);
+
+            let mut labels: Vec<String> = Vec::new();
+            let mut in_label = false;
+            for x in 0..width {
+                let cell = &buf[(x, 0)];
+                if cell.bg == Color::DarkGray {
+                    if !in_label {
+                        labels.push(String::new());
+                    }
+                    labels.last_mut().unwrap().push_str(cell.symbol());
+                }
+                in_label = cell.bg == Color::DarkGray;
+            }
+            labels
+        };
+
+        // Either side of the antimeridian the same window shows different
+        // days, and days whose middle is off the bar aren't pinned to its edge
+        assert_eq!(
+            labels(chrono_tz::Pacific::Kiritimati, 100),
+            ["15 Jan", "16 Jan"]
+        );
+        assert_eq!(
+            labels(chrono_tz::Pacific::Pago_Pago, 100),
+            ["14 Jan", "15 Jan"]
+        );
+
+        // Narrow bars keep whole labels, shortening or dropping them instead
+        for width in 2..40 {
+            for label in labels(chrono_tz::Pacific::Kiritimati, width) {
+                assert!(["15 Jan", "16 Jan", "15", "16"].contains(&label.as_str()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_custom_label_display_short_mode() {
+        let tz = crate::time::TimeZone::with_custom_label(
+            chrono_tz::Asia::Tokyo,
+            "TYO".to_string(),
+            Some("Alice (Engineering)".to_string()),
+        );
+        let config = crate::config::TimeDisplayConfig::default();
+        let now = Utc::now();
+
+        let _widget = TimelineWidget::new(
+            now,
+            now,
+            &tz,
+            false,
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
@@ -767,14 +2327,14 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Full,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
         );
 
         // In full mode, custom label should be used with original info in parentheses
-        assert_eq!(tz.custom_label.as_deref(), Some("Bob (Sales)"));
+        assert_eq!(tz.display.label.as_deref(), Some("Bob (Sales)"));
         assert!(!tz.get_full_display_name().is_empty());
     }
 
@@ -792,14 +2352,102 @@ mod tests {
             TimeFormat::TwentyFourHour,
             TimezoneDisplayMode::Short,
             &config,
-            ColorTheme::default(),
+            ColorTheme::default().palette(),
             false,
             false,
             false,
         );
 
         // Without custom label, should use default display name
-        assert_eq!(tz.custom_label, None);
-        assert_eq!(tz.effective_display_name(), &tz.display_name);
+        assert_eq!(tz.display.label, None);
+        assert_eq!(tz.effective_display_name(), &tz.display.code);
+    }
+
+    #[test]
+    fn test_ascii_glyph_rendering() {
+        let tz = crate::time::TimeZone::from_tz(chrono_tz::Europe::London);
+        let config = crate::config::TimeDisplayConfig::default();
+        let base_time = chrono::DateTime::parse_from_rfc3339("2024-03-31T00:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let widget = TimelineWidget::new(
+            base_time + Duration::hours(3),
+            base_time,
+            &tz,
+            true,
+            TimeFormat::TwentyFourHour,
+            TimezoneDisplayMode::Short,
+            &config,
+            ColorTheme::default().palette(),
+            true,
+            true,
+            true,
+        )
+        .glyphs(Glyphs::ASCII);
+
+        let area = Rect::new(0, 0, 100, 4);
+        let mut buf = Buffer::empty(area);
+        widget.render(area, &mut buf);
+
+        // The bar row holds only ASCII, including the now and scrub markers
+        let bar: String = (1..area.width - 1)
+            .map(|x| buf[(x, 1)].symbol().to_string())
+            .collect();
+        assert!(bar.is_ascii(), "unexpected bar: {bar}");
+        assert!(
+            bar.contains('|') && bar.contains('#'),
+            "unexpected bar: {bar}"
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_rows_match_sequential() {
+        let zones: Vec<_> = (0..PARALLEL_ROWS as u16)
+            .map(|i| {
+                let tz = if i % 2 == 0 {
+                    chrono_tz::Asia::Kolkata
+                } else {
+                    chrono_tz::America::New_York
+                };
+                crate::time::TimeZone::from_tz(tz)
+            })
+            .collect();
+        let config = crate::config::TimeDisplayConfig::default();
+        let cache = DayLayoutCache::default();
+        let now = Utc::now();
+        let rows = || -> Vec<(Rect, TimelineWidget<'_>)> {
+            zones
+                .iter()
+                .enumerate()
+                .map(|(i, tz)| {
+                    let widget = TimelineWidget::new(
+                        now,
+                        now,
+                        tz,
+                        i == 3,
+                        TimeFormat::TwentyFourHour,
+                        TimezoneDisplayMode::Short,
+                        &config,
+                        ColorTheme::default().palette(),
+                        true,
+                        true,
+                        true,
+                    )
+                    .layout_cache(&cache);
+                    (Rect::new(0, i as u16 * 4, 120, 4), widget)
+                })
+                .collect()
+        };
+
+        let area = Rect::new(0, 0, 120, PARALLEL_ROWS as u16 * 4);
+        let mut parallel = Buffer::empty(area);
+        render_rows(&mut parallel, rows());
+        let mut sequential = Buffer::empty(area);
+        for (row_area, widget) in rows() {
+            widget.render(row_area, &mut sequential);
+        }
+        assert_eq!(parallel, sequential);
     }
 }
@@ -0,0 +1,402 @@
+use crate::platform::{self, Platform};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::mpsc;
+use std::thread;
+
+const REPOSITORY: &str = "abradburne/alltz";
+
+/// The version running now
+pub const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// How long a check is trusted before GitHub is asked again
+pub const CHECK_EVERY: Duration = Duration::days(1);
+
+/// The last answer from GitHub, kept so the check runs at most once a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LastCheck {
+    pub at: DateTime<Utc>,
+    pub latest: String,
+}
+
+pub fn state_path() -> Option<PathBuf> {
+    Platform::current()
+        .config_dir()
+        .map(|dir| dir.join("update.json"))
+}
+
+/// Reads the last check, treating a missing or unreadable file as none
+pub fn load(path: &Path) -> Option<LastCheck> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+pub fn save(path: &Path, check: &LastCheck) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, serde_json::to_string_pretty(check)?)
+}
+
+/// A version's numbers, with or without the tag's leading `v`
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| part.parse().ok())
+        .collect()
+}
+
+/// Whether `latest` is a later release than `current`; tags that aren't
+/// plain version numbers never are
+pub fn is_newer(latest: &str, current: &str) -> bool {
+    match (parse_version(latest), parse_version(current)) {
+        (Some(latest), Some(current)) => latest > current,
+        _ => false,
+    }
+}
+
+/// The one-line notice for a newer release, if `latest` is one
+pub fn notice(latest: &str) -> Option<String> {
+    is_newer(latest, CURRENT_VERSION)
+        .then(|| format!("alltz {latest} is out (you have {CURRENT_VERSION}): alltz self-update"))
+}
+
+/// The tag of the latest release in a GitHub releases API response
+pub fn parse_latest(body: &str) -> io::Result<String> {
+    let release: serde_json::Value = serde_json::from_str(body)?;
+    release["tag_name"]
+        .as_str()
+        .map(str::to_string)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "no tag_name in release"))
+}
+
+/// Asks GitHub for the latest release's tag. Nothing about this machine or
+/// its config is sent, only the request itself.
+pub fn fetch_latest() -> io::Result<String> {
+    let output = platform::run(Command::new("curl").args([
+        "--silent",
+        "--show-error",
+        "--fail",
+        "--location",
+        "--max-time",
+        "10",
+        "--header",
+        "Accept: application/vnd.github+json",
+        &format!("https://api.github.com/repos/{REPOSITORY}/releases/latest"),
+    ]))?;
+    parse_latest(&output)
+}
+
+/// An update check running on a background thread
+#[derive(Debug)]
+pub struct UpdateCheck {
+    receiver: mpsc::Receiver<io::Result<String>>,
+}
+
+impl UpdateCheck {
+    pub fn spawn() -> Self {
+        let (sender, receiver) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = sender.send(fetch_latest());
+        });
+        Self { receiver }
+    }
+
+    /// The latest release's tag, once the background thread has finished
+    pub fn try_result(&self) -> Option<io::Result<String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(mpsc::TryRecvError::Empty) => None,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                Some(Err(io::Error::other("update check stopped")))
+            }
+        }
+    }
+}
+
+/// How this copy of alltz was installed, which decides how it's updated
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum InstallMethod {
+    Homebrew,
+    Cargo,
+    /// A binary from a GitHub release, which can replace itself
+    Release,
+    /// Anywhere else, such as a system package manager's directories, which
+    /// alltz leaves to whatever put it there
+    Other,
+}
+
+impl InstallMethod {
+    /// Guesses from where the executable lives. Only the places the README
+    /// suggests putting a release binary count as releases.
+    pub fn detect(exe: &Path, cargo_home: Option<&Path>, home: Option<&Path>) -> Self {
+        let path = exe.to_string_lossy();
+        let dir = exe.parent();
+        let release_dirs = [
+            Some(Path::new("/usr/local/bin").to_path_buf()),
+            home.map(|home| home.join(".local/bin")),
+            home.map(|home| home.join("bin")),
+        ];
+        if ["/Cellar/", "/homebrew/", "/linuxbrew/"]
+            .iter()
+            .any(|dir| path.contains(dir))
+        {
+            InstallMethod::Homebrew
+        } else if cargo_home.is_some_and(|home| exe.starts_with(home.join("bin"))) {
+            InstallMethod::Cargo
+        } else if release_dirs
+            .iter()
+            .flatten()
+            .any(|release| Some(release.as_path()) == dir)
+        {
+            InstallMethod::Release
+        } else {
+            InstallMethod::Other
+        }
+    }
+}
+
+/// The release asset built for this platform, e.g.
+/// `alltz-x86_64-unknown-linux-gnu.tar.gz`
+pub fn asset_name() -> Option<&'static str> {
+    match (std::env::consts::ARCH, std::env::consts::OS) {
+        ("x86_64", "linux") => Some("alltz-x86_64-unknown-linux-gnu.tar.gz"),
+        ("x86_64", "macos") => Some("alltz-x86_64-apple-darwin.tar.gz"),
+        ("aarch64", "macos") => Some("alltz-aarch64-apple-darwin.tar.gz"),
+        _ => None,
+    }
+}
+
+/// The SHA-256 of `data`, in lowercase hex
+pub fn sha256_hex(data: &[u8]) -> String {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut state: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    // Pad to a whole number of 64-byte blocks, ending with the length in bits
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 64];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = state;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = h
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            h = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (word, add) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+            *word = word.wrapping_add(add);
+        }
+    }
+    state.iter().map(|word| format!("{word:08x}")).collect()
+}
+
+/// The published hash for `asset` in a release's `checksums.txt`, whose
+/// lines are `sha256sum` output such as `<hash>  <dir>/<asset>`
+pub fn expected_checksum(checksums: &str, asset: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (hash, file) = line.split_once(char::is_whitespace)?;
+        let file = file.trim().trim_start_matches('*');
+        (file == asset || file.ends_with(&format!("/{asset}"))).then(|| hash.to_ascii_lowercase())
+    })
+}
+
+/// Downloads `url` to `path` with curl
+fn download(url: &str, path: &Path) -> io::Result<()> {
+    let status = Command::new("curl")
+        .args([
+            "--silent",
+            "--show-error",
+            "--fail",
+            "--location",
+            "--output",
+        ])
+        .arg(path)
+        .arg(url)
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("couldn't download {url}")))
+    }
+}
+
+/// A new directory only this user can read, which fails rather than reuse
+/// anything already at `path`
+fn create_private_dir(path: &Path) -> io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    #[cfg(unix)]
+    std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+    builder.create(path)
+}
+
+/// Downloads `tag`'s release for this platform, checks it against the
+/// release's published SHA-256 and puts it in place of `exe`. Everything is
+/// staged in a fresh private directory beside the old binary, so a failed
+/// or tampered download leaves the installed copy alone.
+pub fn replace_binary(exe: &Path, tag: &str, asset: &str) -> io::Result<()> {
+    let dir = exe
+        .parent()
+        .ok_or_else(|| io::Error::other("the executable has no directory"))?;
+    let staging = dir.join(format!(".alltz-update-{}", std::process::id()));
+    create_private_dir(&staging)?;
+    let archive = staging.join(asset);
+    let checksums = staging.join("checksums.txt");
+    let release = format!("https://github.com/{REPOSITORY}/releases/download/{tag}");
+    let result = (|| {
+        download(&format!("{release}/{asset}"), &archive)?;
+        download(&format!("{release}/checksums.txt"), &checksums)?;
+        let expected = expected_checksum(&fs::read_to_string(&checksums)?, asset)
+            .ok_or_else(|| io::Error::other(format!("no checksum published for {asset}")))?;
+        if sha256_hex(&fs::read(&archive)?) != expected {
+            return Err(io::Error::other(format!(
+                "{asset} doesn't match its published checksum"
+            )));
+        }
+        let status = Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(&staging)
+            .status()?;
+        if !status.success() {
+            return Err(io::Error::other(format!("couldn't unpack {asset}")));
+        }
+        fs::rename(staging.join("alltz"), exe)
+    })();
+    let _ = fs::remove_dir_all(&staging);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versions() {
+        assert!(is_newer("v0.2.0", "0.1.4"));
+        assert!(is_newer("v0.1.10", "0.1.9"));
+        assert!(!is_newer("v0.1.4", "0.1.4"));
+        assert!(!is_newer("v0.1.3", "0.1.4"));
+        assert!(!is_newer("nightly", "0.1.4"));
+        assert_eq!(notice("v0.0.1"), None);
+        assert!(notice("v99.0.0")
+            .unwrap()
+            .starts_with("alltz v99.0.0 is out"));
+
+        assert_eq!(
+            parse_latest(r#"{"tag_name": "v0.2.0", "name": "alltz 0.2.0"}"#).unwrap(),
+            "v0.2.0"
+        );
+        assert!(parse_latest(r#"{"message": "Not Found"}"#).is_err());
+    }
+
+    #[test]
+    fn test_install_method() {
+        let cargo_home = Path::new("/home/maria/.cargo");
+        let home = Path::new("/home/maria");
+        let detect =
+            |exe: &str| InstallMethod::detect(Path::new(exe), Some(cargo_home), Some(home));
+        assert_eq!(
+            detect("/opt/homebrew/Cellar/alltz/0.1.4/bin/alltz"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(
+            detect("/home/linuxbrew/.linuxbrew/bin/alltz"),
+            InstallMethod::Homebrew
+        );
+        assert_eq!(detect("/home/maria/.cargo/bin/alltz"), InstallMethod::Cargo);
+        assert_eq!(detect("/usr/local/bin/alltz"), InstallMethod::Release);
+        assert_eq!(
+            detect("/home/maria/.local/bin/alltz"),
+            InstallMethod::Release
+        );
+        // Package managers' copies are theirs to update
+        assert_eq!(detect("/usr/bin/alltz"), InstallMethod::Other);
+        assert_eq!(
+            detect("/nix/store/abc-alltz-0.1.4/bin/alltz"),
+            InstallMethod::Other
+        );
+    }
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(
+            sha256_hex(b""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            sha256_hex(b"abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two blocks once padded
+        assert_eq!(
+            sha256_hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+
+        let checksums = "\
+1111  alltz-x86_64-apple-darwin/alltz-x86_64-apple-darwin.tar.gz
+ABCD  alltz-x86_64-unknown-linux-gnu/alltz-x86_64-unknown-linux-gnu.tar.gz
+";
+        assert_eq!(
+            expected_checksum(checksums, "alltz-x86_64-unknown-linux-gnu.tar.gz").as_deref(),
+            Some("abcd")
+        );
+        assert_eq!(
+            expected_checksum(checksums, "alltz-aarch64-apple-darwin.tar.gz"),
+            None
+        );
+    }
+}
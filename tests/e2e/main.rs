@@ -0,0 +1,45 @@
+//! End-to-end tests of the board as it appears in a terminal, driving the
+//! built binary with keystrokes. Unix only; run with
+//! `cargo test --features e2e --test e2e`.
+
+#![cfg(unix)]
+
+mod tui;
+
+use tui::Tui;
+
+#[test]
+fn test_demo_board_opens_and_quits() {
+    let mut tui = Tui::start("quit", &["--demo"]);
+    tui.wait_for("Design");
+    tui.wait_for("NYC");
+    tui.send("q");
+    assert!(tui.wait_exit());
+}
+
+#[test]
+fn test_help_popup() {
+    let mut tui = Tui::start("help", &["--demo"]);
+    tui.wait_for("Design");
+    tui.send("?");
+    tui.wait_for("Press any key to close");
+    // Any key closes help
+    tui.send("x");
+    let screen = tui.wait_for_gone("Press any key to close");
+    assert!(screen.contains("Design"));
+}
+
+#[test]
+fn test_add_zone() {
+    let mut tui = Tui::start("add-zone", &["--demo"]);
+    tui.wait_for("Design");
+    assert!(!tui.screen().contains("REK"));
+    tui.send("a");
+    tui.wait_for("Add Timezone");
+    tui.send("Reykjavik");
+    tui.wait_for("Search: Reykjavik");
+    tui.send("\r");
+    tui.wait_for_gone("Add Timezone");
+    // The board shows the city by its code
+    tui.wait_for("REK GMT");
+}
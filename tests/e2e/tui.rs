@@ -0,0 +1,183 @@
+//! Runs the real `alltz` binary in a pseudo-terminal, typing at it the way
+//! someone at the keyboard would and reading back what the screen shows.
+
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long to wait for the screen to show something before failing
+const TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Queries alltz sends at startup, which a real terminal answers and the
+/// screen model doesn't
+const CURSOR_POSITION: &[u8] = b"\x1b[6n";
+const BACKGROUND: &[u8] = b"\x1b]11;?";
+const DEVICE_ATTRIBUTES: &[u8] = b"\x1b[c";
+const QUERIES: [&[u8]; 3] = [CURSOR_POSITION, BACKGROUND, DEVICE_ATTRIBUTES];
+
+pub struct Tui {
+    parser: vt100::Parser,
+    output: Receiver<Vec<u8>>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+    // Kept open for as long as the program runs
+    _master: Box<dyn MasterPty + Send>,
+    home: PathBuf,
+}
+
+impl Tui {
+    pub const ROWS: u16 = 48;
+    pub const COLUMNS: u16 = 120;
+
+    /// Starts alltz with `args` in a terminal of its own. It gets an empty
+    /// home directory named after `name`, so no real config is read or
+    /// written and tests running at once don't share one.
+    pub fn start(name: &str, args: &[&str]) -> Self {
+        let home = std::env::temp_dir().join(format!("alltz-e2e-{}-{name}", std::process::id()));
+        let _ = fs::remove_dir_all(&home);
+        fs::create_dir_all(&home).expect("create home directory");
+
+        let pair = native_pty_system()
+            .openpty(PtySize {
+                rows: Self::ROWS,
+                cols: Self::COLUMNS,
+                pixel_width: 0,
+                pixel_height: 0,
+            })
+            .expect("open a pseudo-terminal");
+        let mut command = CommandBuilder::new(env!("CARGO_BIN_EXE_alltz"));
+        command.args(args);
+        command.cwd(&home);
+        command.env("HOME", &home);
+        command.env("XDG_CONFIG_HOME", home.join(".config"));
+        command.env("TERM", "xterm-256color");
+        command.env_remove("NO_COLOR");
+        let child = pair.slave.spawn_command(command).expect("start alltz");
+        drop(pair.slave);
+
+        let mut reader = pair.master.try_clone_reader().expect("read the terminal");
+        let (sender, output) = mpsc::channel();
+        thread::spawn(move || {
+            let mut buffer = [0; 4096];
+            while let Ok(read) = reader.read(&mut buffer) {
+                if read == 0 || sender.send(buffer[..read].to_vec()).is_err() {
+                    break;
+                }
+            }
+        });
+        let writer = pair.master.take_writer().expect("write to the terminal");
+
+        Tui {
+            parser: vt100::Parser::new(Self::ROWS, Self::COLUMNS, 0),
+            output,
+            writer,
+            child,
+            _master: pair.master,
+            home,
+        }
+    }
+
+    /// Types `keys` as the terminal would send them, e.g. "q", "\r" for
+    /// Enter or "\x1b[C" for Right
+    pub fn send(&mut self, keys: &str) {
+        self.writer.write_all(keys.as_bytes()).expect("type keys");
+        self.writer.flush().expect("type keys");
+    }
+
+    /// The screen as text, one line per row
+    pub fn screen(&self) -> String {
+        self.parser.screen().contents()
+    }
+
+    /// Waits until the screen shows `text`, returning the screen
+    pub fn wait_for(&mut self, text: &str) -> String {
+        self.wait_until(&format!("`{text}` to appear"), |screen| {
+            screen.contains(text)
+        })
+    }
+
+    /// Waits until the screen no longer shows `text`, returning the screen
+    pub fn wait_for_gone(&mut self, text: &str) -> String {
+        self.wait_until(&format!("`{text}` to go"), |screen| !screen.contains(text))
+    }
+
+    /// Waits for alltz to exit, returning whether it exited cleanly
+    pub fn wait_exit(&mut self) -> bool {
+        let deadline = Instant::now() + TIMEOUT;
+        while Instant::now() < deadline {
+            self.pump(Duration::from_millis(50));
+            if let Some(status) = self.child.try_wait().expect("check on alltz") {
+                return status.success();
+            }
+        }
+        panic!("alltz didn't exit; the screen was:\n{}", self.screen());
+    }
+
+    /// Keeps reading the screen until `done` holds for it, failing with the
+    /// screen as it was after the timeout
+    fn wait_until(&mut self, what: &str, done: impl Fn(&str) -> bool) -> String {
+        let deadline = Instant::now() + TIMEOUT;
+        loop {
+            let screen = self.screen();
+            if done(&screen) {
+                return screen;
+            }
+            if Instant::now() >= deadline {
+                panic!("timed out waiting for {what}; the screen was:\n{screen}");
+            }
+            self.pump(Duration::from_millis(50));
+        }
+    }
+
+    /// Reads whatever alltz has drawn, waiting up to `timeout` for some
+    fn pump(&mut self, timeout: Duration) {
+        let Ok(first) = self.output.recv_timeout(timeout) else {
+            return;
+        };
+        self.process(&first);
+        while let Ok(more) = self.output.try_recv() {
+            self.process(&more);
+        }
+    }
+
+    /// Feeds output to the screen, answering queries as they come up so
+    /// cursor positions are reported from the screen at that point
+    fn process(&mut self, mut bytes: &[u8]) {
+        while let Some((at, query)) = find_query(bytes) {
+            let (before, rest) = bytes.split_at(at + query.len());
+            self.parser.process(before);
+            let reply = if query == CURSOR_POSITION {
+                let (row, column) = self.parser.screen().cursor_position();
+                format!("\x1b[{};{}R", row + 1, column + 1)
+            } else if query == BACKGROUND {
+                "\x1b]11;rgb:0000/0000/0000\x1b\\".to_string()
+            } else {
+                "\x1b[?1;2c".to_string()
+            };
+            self.send(&reply);
+            bytes = rest;
+        }
+        self.parser.process(bytes);
+    }
+}
+
+impl Drop for Tui {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = fs::remove_dir_all(&self.home);
+    }
+}
+
+/// The first terminal query in `bytes` and where it starts
+fn find_query(bytes: &[u8]) -> Option<(usize, &'static [u8])> {
+    (0..bytes.len()).find_map(|at| {
+        QUERIES
+            .into_iter()
+            .find(|query| bytes[at..].starts_with(query))
+            .map(|query| (at, query))
+    })
+}